@@ -1,7 +1,12 @@
 use incubar_tauri_lib::providers::{
-    load_cost_snapshot, ProviderId, ProviderRegistry, StatusIndicator,
+    forecast_usage, load_cost_snapshot, run_refresh_loop, AccountKey, Forecast, ProviderId,
+    ProviderRegistry, RefreshEmitter, RefreshEvent, StatusIndicator, StatusSource, UsageSnapshot,
+    FORECAST_LOOKBACK_HOURS, PROVIDER_DESCRIPTORS,
 };
+use incubar_tauri_lib::storage::history::{self, HistoryQuery, HistoryRecord, HistorySink};
 use serde::Serialize;
+use std::io::Write;
+use std::sync::Arc;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum OutputFormat {
@@ -13,7 +18,13 @@ enum OutputFormat {
 struct CliArgs {
     command: String,
     format: OutputFormat,
+    /// Whether the format was asked for rather than the default
+    format_given: bool,
     provider: Option<String>,
+    account: Option<String>,
+    window: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
     pretty: bool,
 }
 
@@ -21,6 +32,9 @@ struct CliArgs {
 #[serde(rename_all = "camelCase")]
 struct StatusPayload {
     provider: String,
+    /// Set for plugins, which each report their own status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
     indicator: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
@@ -30,6 +44,14 @@ struct StatusPayload {
     url: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountPayload {
+    provider: String,
+    account: String,
+    label: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct CostPayload {
@@ -49,6 +71,64 @@ struct CostPayload {
     error: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UsagePayload {
+    provider: String,
+    account: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<UsageSnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl UsagePayload {
+    fn new(key: &AccountKey, result: Result<UsageSnapshot, String>) -> Self {
+        let (usage, error) = match result {
+            Ok(usage) => (Some(usage), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            provider: provider_id_string(key.provider_id).to_string(),
+            account: key.account_id.clone(),
+            usage,
+            error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ForecastPayload {
+    provider: String,
+    account: String,
+    recorded_at: String,
+    forecasts: Vec<Forecast>,
+}
+
+/// Prints every refresh loop event to stdout as one line of JSON
+struct WatchEmitter;
+
+impl RefreshEmitter for WatchEmitter {
+    fn emit_event(&self, event: RefreshEvent) {
+        let line = match serde_json::to_string(&event) {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("Failed to encode {} event: {err}", event.event);
+                return;
+            }
+        };
+        let mut stdout = std::io::stdout().lock();
+        // The reader went away, e.g. `incubar watch | head`
+        if writeln!(stdout, "{line}")
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            std::process::exit(0);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let args = parse_args(std::env::args().skip(1).collect());
@@ -64,7 +144,11 @@ async fn main() {
     let result = match args.command.as_str() {
         "status" => run_status(args).await,
         "cost" => run_cost(args).await,
-        "usage" => Err("usage is not supported in the bundled CLI".to_string()),
+        "accounts" => run_accounts(args).await,
+        "history" => run_history(args),
+        "forecast" => run_forecast(args),
+        "usage" => run_usage(args).await,
+        "watch" => run_watch(args).await,
         _ => Err(format!(
             "Unknown command: {}. Use --help for usage.",
             args.command
@@ -79,8 +163,13 @@ async fn main() {
 
 fn parse_args(mut argv: Vec<String>) -> CliArgs {
     let mut format = OutputFormat::Text;
+    let mut format_given = false;
     let mut pretty = false;
     let mut provider = None;
+    let mut account = None;
+    let mut window = None;
+    let mut since = None;
+    let mut until = None;
     let mut command = String::new();
     let mut json_output = false;
 
@@ -101,21 +190,29 @@ fn parse_args(mut argv: Vec<String>) -> CliArgs {
                 if let Some(value) = iter.next() {
                     if value == "json" {
                         format = OutputFormat::Json;
+                        format_given = true;
                     } else if value == "text" {
                         format = OutputFormat::Text;
+                        format_given = true;
                     }
                 }
             }
             "--json" => {
                 format = OutputFormat::Json;
+                format_given = true;
                 json_output = true;
             }
             "--json-output" => {
                 format = OutputFormat::Json;
+                format_given = true;
                 json_output = true;
             }
             "--pretty" => pretty = true,
             "--provider" => provider = iter.next(),
+            "--account" => account = iter.next(),
+            "--window" => window = iter.next(),
+            "--since" => since = iter.next(),
+            "--until" => until = iter.next(),
             "--help" | "-h" | "--version" | "-V" => {
                 command = arg;
                 break;
@@ -127,7 +224,12 @@ fn parse_args(mut argv: Vec<String>) -> CliArgs {
     CliArgs {
         command,
         format,
+        format_given,
         provider,
+        account,
+        window,
+        since,
+        until,
         pretty: pretty || json_output,
     }
 }
@@ -138,9 +240,26 @@ async fn run_status(args: CliArgs) -> Result<(), String> {
     let mut payloads = Vec::new();
     let mut sections = Vec::new();
 
+    // Plugins report one status each, every other provider one for all its accounts
+    let accounts = registry.list_accounts().await;
+    let mut keys = Vec::new();
     for provider_id in providers {
+        if provider_id.descriptor().status_source == StatusSource::Fetcher {
+            keys.extend(
+                accounts
+                    .iter()
+                    .filter(|account| account.provider_id == provider_id)
+                    .map(|account| AccountKey::new(provider_id, account.account_id.clone())),
+            );
+        } else {
+            keys.push(AccountKey::default_for(provider_id));
+        }
+    }
+
+    for key in keys {
+        let provider_id = key.provider_id;
         let status = registry
-            .fetch_status(&provider_id)
+            .fetch_status(&key)
             .await
             .map_err(|err| err.to_string())?;
 
@@ -148,6 +267,7 @@ async fn run_status(args: CliArgs) -> Result<(), String> {
         let url = status_page_url(provider_id);
         let payload = StatusPayload {
             provider: provider_name.to_string(),
+            account: (!key.is_default()).then(|| key.account_id.clone()),
             indicator: status_indicator_string(status.indicator).to_string(),
             description: status.description.clone(),
             updated_at: status.updated_at.clone(),
@@ -155,7 +275,7 @@ async fn run_status(args: CliArgs) -> Result<(), String> {
         };
 
         match args.format {
-            OutputFormat::Text => sections.push(render_status_text(provider_name, &payload)),
+            OutputFormat::Text => sections.push(render_status_text(&key.to_string(), &payload)),
             OutputFormat::Json => payloads.push(payload),
         }
     }
@@ -172,16 +292,119 @@ async fn run_status(args: CliArgs) -> Result<(), String> {
     Ok(())
 }
 
+async fn run_usage(args: CliArgs) -> Result<(), String> {
+    let explicit =
+        args.account.is_some() || args.provider.as_deref().is_some_and(|value| value != "all");
+    let providers = select_providers(args.provider.as_deref(), ProviderSelectionKind::All)?;
+    let registry = ProviderRegistry::new();
+    registry.add_sink(Arc::new(HistorySink::default()));
+    let keys: Vec<AccountKey> = registry
+        .list_accounts()
+        .await
+        .into_iter()
+        .filter(|account| providers.contains(&account.provider_id))
+        .filter(|account| {
+            args.account
+                .as_deref()
+                .is_none_or(|id| id == account.account_id)
+        })
+        .map(|account| AccountKey::new(account.provider_id, account.account_id))
+        .collect();
+    if keys.is_empty() {
+        if let Some(account) = args.account.as_deref() {
+            return Err(format!("Unknown account: {account}"));
+        }
+    }
+    let mut payloads = Vec::new();
+
+    for key in keys {
+        let auth_status = registry.auth_status(&key).await;
+        if !auth_status.authenticated {
+            // Across all providers, only the ones set up on this machine are worth listing
+            if !explicit {
+                continue;
+            }
+            let reason = auth_status
+                .error
+                .unwrap_or_else(|| "Not authenticated".to_string());
+            payloads.push(UsagePayload::new(
+                &key,
+                Err(format!("Not authenticated: {reason}")),
+            ));
+            continue;
+        }
+
+        let result = registry.fetch_usage(&key).await;
+        if let Ok(usage) = result.as_ref() {
+            registry.publish_usage(&key, usage);
+        }
+        payloads.push(UsagePayload::new(
+            &key,
+            result.map_err(|err| err.to_string()),
+        ));
+    }
+    // History is written on the sink thread, which exiting would cut short
+    registry.flush_sinks().await;
+
+    match args.format {
+        OutputFormat::Text => {
+            let sections: Vec<String> = payloads.iter().map(render_usage_text).collect();
+            if sections.is_empty() {
+                println!("No authenticated providers");
+            } else {
+                println!("{}", sections.join("\n\n"));
+            }
+        }
+        OutputFormat::Json => print_json(&payloads, args.pretty)?,
+    }
+
+    Ok(())
+}
+
+async fn run_watch(args: CliArgs) -> Result<(), String> {
+    if args.format_given && args.format == OutputFormat::Text {
+        return Err("watch only prints JSON lines".to_string());
+    }
+    if args.account.is_some() {
+        return Err(
+            "watch refreshes every account of the selected providers; drop --account".to_string(),
+        );
+    }
+    let providers = select_providers(args.provider.as_deref(), ProviderSelectionKind::All)?;
+    let registry = ProviderRegistry::new();
+    // The refresh loop covers every account of these providers, skipping signed-out
+    // ones until they sign in
+    registry.set_enabled_providers(&providers).await;
+    registry.add_sink(Arc::new(HistorySink::default()));
+
+    run_refresh_loop(&registry, &WatchEmitter).await;
+    Ok(())
+}
+
 async fn run_cost(args: CliArgs) -> Result<(), String> {
     let providers = select_providers(args.provider.as_deref(), ProviderSelectionKind::CostOnly)?;
     if providers.is_empty() {
-        return Err("cost is only supported for codex and claude".to_string());
+        let supported: Vec<&str> = PROVIDER_DESCRIPTORS
+            .iter()
+            .filter(|descriptor| descriptor.supports_cost_scan)
+            .map(|descriptor| descriptor.slug)
+            .collect();
+        return Err(format!(
+            "cost is only supported for {}",
+            supported.join(" and ")
+        ));
     }
     let mut payloads = Vec::new();
     let mut sections = Vec::new();
     for provider_id in providers {
         let provider_name = provider_id_string(provider_id).to_string();
         let snapshot = load_cost_snapshot(provider_id).await;
+        if let Some(snapshot) = snapshot.as_ref() {
+            let key = AccountKey::default_for(provider_id);
+            if let Err(err) = history::record_cost(&key, snapshot) {
+                eprintln!("Failed to record cost history: {err}");
+            }
+        }
         let payload = match snapshot {
             Some(snapshot) => CostPayload {
                 provider: provider_name.clone(),
@@ -223,6 +446,269 @@ async fn run_cost(args: CliArgs) -> Result<(), String> {
     Ok(())
 }
 
+async fn run_accounts(args: CliArgs) -> Result<(), String> {
+    let providers = select_providers(args.provider.as_deref(), ProviderSelectionKind::All)?;
+    let registry = ProviderRegistry::new();
+    let payloads: Vec<AccountPayload> = registry
+        .list_accounts()
+        .await
+        .into_iter()
+        .filter(|account| providers.contains(&account.provider_id))
+        .filter(|account| {
+            args.account
+                .as_deref()
+                .is_none_or(|id| id == account.account_id)
+        })
+        .map(|account| AccountPayload {
+            provider: provider_id_string(account.provider_id).to_string(),
+            account: account.account_id,
+            label: account.label,
+        })
+        .collect();
+
+    if payloads.is_empty() {
+        if let Some(account) = args.account.as_deref() {
+            return Err(format!("Unknown account: {account}"));
+        }
+    }
+
+    match args.format {
+        OutputFormat::Text => {
+            let lines: Vec<String> = payloads
+                .iter()
+                .map(|payload| format!("{}/{}  {}", payload.provider, payload.account, payload.label))
+                .collect();
+            if !lines.is_empty() {
+                println!("{}", lines.join("\n"));
+            }
+        }
+        OutputFormat::Json => print_json(&payloads, args.pretty)?,
+    }
+
+    Ok(())
+}
+
+fn run_history(args: CliArgs) -> Result<(), String> {
+    let provider_id = match args.provider.as_deref() {
+        Some("all") | None => None,
+        Some(value) => Some(parse_provider(value)?),
+    };
+    let query = HistoryQuery {
+        provider_id,
+        account_id: args.account.clone(),
+        window_id: args.window.clone(),
+        since: args.since.as_deref().map(parse_time).transpose()?,
+        until: args.until.as_deref().map(parse_time).transpose()?,
+    };
+    let records = history::query_history(&query).map_err(|err| err.to_string())?;
+
+    match args.format {
+        OutputFormat::Text => {
+            let lines: Vec<String> = records.iter().map(render_history_line).collect();
+            if !lines.is_empty() {
+                println!("{}", lines.join("\n"));
+            }
+        }
+        OutputFormat::Json => print_json(&records, args.pretty)?,
+    }
+
+    Ok(())
+}
+
+fn run_forecast(args: CliArgs) -> Result<(), String> {
+    let provider_id = match args.provider.as_deref() {
+        Some("all") | None => None,
+        Some(value) => Some(parse_provider(value)?),
+    };
+    let query = HistoryQuery {
+        provider_id,
+        account_id: args.account.clone(),
+        since: Some(chrono::Utc::now() - chrono::Duration::hours(FORECAST_LOOKBACK_HOURS)),
+        ..HistoryQuery::default()
+    };
+    let records = history::query_history(&query).map_err(|err| err.to_string())?;
+
+    // The latest snapshot of each account, forecast from the records before it
+    let mut accounts: Vec<AccountKey> = Vec::new();
+    for record in &records {
+        let key = AccountKey::new(record.provider_id, record.account_id.clone());
+        if record.usage.is_some() && !accounts.contains(&key) {
+            accounts.push(key);
+        }
+    }
+    let mut payloads = Vec::new();
+    for key in accounts {
+        let account_records: Vec<HistoryRecord> = records
+            .iter()
+            .filter(|record| {
+                record.provider_id == key.provider_id && record.account_id == key.account_id
+            })
+            .cloned()
+            .collect();
+        let Some(latest) = account_records.iter().rposition(|record| record.usage.is_some())
+        else {
+            continue;
+        };
+        let (earlier, rest) = account_records.split_at(latest);
+        let record = &rest[0];
+        let (Some(usage), Some(recorded_at)) = (record.usage.as_ref(), record.recorded_at())
+        else {
+            continue;
+        };
+        let forecasts = forecast_usage(earlier, usage, recorded_at)
+            .into_iter()
+            .filter(|forecast| args.window.as_deref().is_none_or(|id| id == forecast.id))
+            .collect();
+        payloads.push(ForecastPayload {
+            provider: provider_id_string(key.provider_id).to_string(),
+            account: key.account_id,
+            recorded_at: record.recorded_at.clone(),
+            forecasts,
+        });
+    }
+
+    match args.format {
+        OutputFormat::Text => {
+            let sections: Vec<String> = payloads.iter().map(render_forecast_text).collect();
+            if !sections.is_empty() {
+                println!("{}", sections.join("\n\n"));
+            }
+        }
+        OutputFormat::Json => print_json(&payloads, args.pretty)?,
+    }
+
+    Ok(())
+}
+
+fn render_usage_text(payload: &UsagePayload) -> String {
+    let mut lines = vec![format!("== {}/{} Usage ==", payload.provider, payload.account)];
+    let Some(usage) = payload.usage.as_ref() else {
+        let error = payload.error.as_deref().unwrap_or("No usage data");
+        lines.push(format!("Error: {error}"));
+        return lines.join("\n");
+    };
+
+    for window in usage.rate_windows() {
+        let name = window
+            .label
+            .as_deref()
+            .or(window.id.as_deref())
+            .unwrap_or("Window");
+        let mut line = format!("{name}: {:.0}% used", window.used_percent);
+        if let Some(counts) = window.count_text() {
+            line.push_str(&format!(" ({counts})"));
+        }
+        if let Some(reset) = window.reset_description.as_deref() {
+            line.push_str(&format!(" · {reset}"));
+        } else if let Some(resets_at) = window.resets_at.as_deref() {
+            line.push_str(&format!(" · resets {resets_at}"));
+        }
+        lines.push(line);
+    }
+    if let Some(credits) = usage.credits.as_ref() {
+        let mut line = format!(
+            "Credits: {} {} remaining",
+            format_amount(credits.remaining),
+            credits.unit
+        );
+        if let Some(total) = credits.total {
+            line.push_str(&format!(" of {}", format_amount(total)));
+        }
+        lines.push(line);
+    }
+    if let Some(identity) = usage.identity.as_ref() {
+        let parts: Vec<&str> = [
+            identity.email.as_deref(),
+            identity.name.as_deref(),
+            identity.plan.as_deref(),
+            identity.organization.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !parts.is_empty() {
+            lines.push(format!("Account: {}", parts.join(" · ")));
+        }
+    }
+    if let Some(cost) = usage.cost.as_ref() {
+        lines.push(format!(
+            "Cost: {} today · {} tokens; {} last 30 days · {} tokens",
+            format_usd(cost.today_amount),
+            format_tokens(cost.today_tokens),
+            format_usd(cost.month_amount),
+            format_tokens(cost.month_tokens)
+        ));
+    }
+    if let Some(error) = usage.error.as_deref() {
+        lines.push(format!("Error: {error}"));
+    }
+    lines.push(format!("Updated: {}", usage.updated_at));
+    lines.join("\n")
+}
+
+fn render_forecast_text(payload: &ForecastPayload) -> String {
+    let mut lines = vec![format!("== {}/{} Forecast ==", payload.provider, payload.account)];
+    for forecast in &payload.forecasts {
+        lines.push(format!(
+            "{} (confidence {:.0}%)",
+            forecast.description,
+            forecast.confidence * 100.0
+        ));
+    }
+    if payload.forecasts.is_empty() {
+        lines.push("No forecast".to_string());
+    }
+    lines.push(format!("As of: {}", payload.recorded_at));
+    lines.join("\n")
+}
+
+/// An RFC 3339 timestamp, or a duration before now such as `30m`, `24h` or `7d`
+fn parse_time(value: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&chrono::Utc));
+    }
+    let invalid = || format!("Invalid time: {value}. Use RFC 3339 or e.g. 30m, 24h, 7d");
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let duration = match unit {
+        'm' => chrono::Duration::minutes(amount),
+        'h' => chrono::Duration::hours(amount),
+        'd' => chrono::Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(chrono::Utc::now() - duration)
+}
+
+fn render_history_line(record: &HistoryRecord) -> String {
+    let mut parts = Vec::new();
+    if let Some(usage) = record.usage.as_ref() {
+        for window in usage.rate_windows() {
+            let name = window.id.as_deref().unwrap_or("window");
+            let mut part = format!("{name} {:.0}%", window.used_percent);
+            if let Some(counts) = window.count_text() {
+                part.push_str(&format!(" ({counts})"));
+            }
+            parts.push(part);
+        }
+    }
+    if let Some(cost) = record.cost.as_ref() {
+        parts.push(format!(
+            "cost {} today · {} last 30 days",
+            format_usd(cost.today_amount),
+            format_usd(cost.month_amount)
+        ));
+    }
+    format!(
+        "{}  {}/{}  {}",
+        record.recorded_at,
+        provider_id_string(record.provider_id),
+        record.account_id,
+        parts.join(", ")
+    )
+}
+
 fn render_status_text(provider: &str, payload: &StatusPayload) -> String {
     let mut lines = Vec::new();
     lines.push(format!("== {provider} Status =="));
@@ -276,6 +762,12 @@ fn format_usd(amount: f64) -> String {
     format!("${:.2}", amount)
 }
 
+/// Up to two decimals, without trailing zeros
+fn format_amount(amount: f64) -> String {
+    let text = format!("{:.2}", amount);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
 fn format_tokens(tokens: u64) -> String {
     let text = tokens.to_string();
     let mut out = String::new();
@@ -319,56 +811,17 @@ fn select_providers(
         ProviderSelectionKind::All => selected,
         ProviderSelectionKind::CostOnly => selected
             .into_iter()
-            .filter(|id| matches!(id, ProviderId::Codex | ProviderId::Claude))
+            .filter(|id| id.descriptor().supports_cost_scan)
             .collect(),
     })
 }
 
 fn parse_provider(value: &str) -> Result<ProviderId, String> {
-    match value {
-        "claude" => Ok(ProviderId::Claude),
-        "codex" => Ok(ProviderId::Codex),
-        "cursor" => Ok(ProviderId::Cursor),
-        "copilot" => Ok(ProviderId::Copilot),
-        "gemini" => Ok(ProviderId::Gemini),
-        "antigravity" => Ok(ProviderId::Antigravity),
-        "factory" => Ok(ProviderId::Factory),
-        "zai" => Ok(ProviderId::Zai),
-        "minimax" => Ok(ProviderId::Minimax),
-        "kimi" => Ok(ProviderId::Kimi),
-        "kimi_k2" => Ok(ProviderId::KimiK2),
-        "kiro" => Ok(ProviderId::Kiro),
-        "vertexai" => Ok(ProviderId::Vertex),
-        "augment" => Ok(ProviderId::Augment),
-        "amp" => Ok(ProviderId::Amp),
-        "jetbrains" => Ok(ProviderId::Jetbrains),
-        "opencode" => Ok(ProviderId::Opencode),
-        "synthetic" => Ok(ProviderId::Synthetic),
-        _ => Err(format!("Unknown provider: {value}")),
-    }
+    ProviderId::from_slug(value).ok_or_else(|| format!("Unknown provider: {value}"))
 }
 
 fn provider_id_string(provider: ProviderId) -> &'static str {
-    match provider {
-        ProviderId::Claude => "claude",
-        ProviderId::Codex => "codex",
-        ProviderId::Cursor => "cursor",
-        ProviderId::Copilot => "copilot",
-        ProviderId::Gemini => "gemini",
-        ProviderId::Antigravity => "antigravity",
-        ProviderId::Factory => "factory",
-        ProviderId::Zai => "zai",
-        ProviderId::Minimax => "minimax",
-        ProviderId::Kimi => "kimi",
-        ProviderId::KimiK2 => "kimi_k2",
-        ProviderId::Kiro => "kiro",
-        ProviderId::Vertex => "vertexai",
-        ProviderId::Augment => "augment",
-        ProviderId::Amp => "amp",
-        ProviderId::Jetbrains => "jetbrains",
-        ProviderId::Opencode => "opencode",
-        ProviderId::Synthetic => "synthetic",
-    }
+    provider.slug()
 }
 
 fn status_indicator_string(indicator: StatusIndicator) -> &'static str {
@@ -394,22 +847,12 @@ fn status_label(indicator: &str) -> &'static str {
 }
 
 fn status_page_url(provider: ProviderId) -> Option<String> {
-    match provider {
-        ProviderId::Claude => Some("https://status.anthropic.com".to_string()),
-        ProviderId::Codex => Some("https://status.openai.com".to_string()),
-        ProviderId::Cursor => Some("https://status.cursor.sh".to_string()),
-        ProviderId::Copilot => Some("https://www.githubstatus.com".to_string()),
-        ProviderId::Antigravity => Some(
-            "https://www.google.com/appsstatus/dashboard/products/npdyhgECDJ6tB66MxXyo".to_string(),
-        ),
-        ProviderId::Kiro => Some("https://status.aws.amazon.com/rss/all.rss".to_string()),
-        _ => None,
-    }
+    provider.descriptor().dashboard_url.map(str::to_string)
 }
 
 fn print_help() {
     println!(
-        "incubar {}\n\nUsage:\n  incubar status [--format text|json] [--provider <id|all>] [--pretty]\n  incubar cost [--format text|json] [--provider <id|all>] [--pretty]\n\nCommands:\n  status  Print provider status indicators\n  cost    Print local cost usage for Claude/Codex\n\nFlags:\n  --format <text|json>  Output format\n  --json               Shortcut for --format json\n  --pretty             Pretty-print JSON output\n  --provider <id|all>  Provider to query\n  --json-output        Use JSON output\n  -h, --help           Show help\n  -V, --version        Show version",
+        "incubar {}\n\nUsage:\n  incubar status [--format text|json] [--provider <id|all>] [--pretty]\n  incubar usage [--format text|json] [--provider <id|all>] [--account <id>] [--pretty]\n  incubar cost [--format text|json] [--provider <id|all>] [--pretty]\n  incubar accounts [--format text|json] [--provider <id|all>] [--account <id>] [--pretty]\n  incubar history [--format text|json] [--provider <id|all>] [--account <id>] [--window <id>] [--since <time>] [--until <time>] [--pretty]\n  incubar forecast [--format text|json] [--provider <id|all>] [--account <id>] [--window <id>] [--pretty]\n  incubar watch [--provider <id|all>]\n\nCommands:\n  status    Print provider status indicators\n  usage     Fetch and print rate windows, credits, identity and cost\n  cost      Print local cost usage for Claude/Codex\n  accounts  List configured provider accounts\n  history   Print recorded usage history\n  forecast  Project when each window and credit balance runs out\n  watch     Keep refreshing usage and print each event as a line of JSON\n\nFlags:\n  --format <text|json>  Output format\n  --json               Shortcut for --format json\n  --pretty             Pretty-print JSON output\n  --provider <id|all>  Provider to query\n  --account <id>       Account to query (defaults to every account)\n  --window <id>        Rate window to query, e.g. session or weekly\n  --since <time>       Start of the history range (RFC 3339, or e.g. 24h, 7d)\n  --until <time>       End of the history range\n  --json-output        Use JSON output\n  -h, --help           Show help\n  -V, --version        Show version",
         env!("CARGO_PKG_VERSION")
     );
 }
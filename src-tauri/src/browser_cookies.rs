@@ -17,6 +17,7 @@
 //! - Full Disk Access: Required to read Safari's Cookies.binarycookies file
 //! - Grant in System Settings > Privacy & Security > Full Disk Access
 
+use crate::providers::ProviderId;
 use anyhow::Result;
 use decrypt_cookies::chromium::{ChromiumCookie, GetCookies};
 use decrypt_cookies::prelude::*;
@@ -30,15 +31,10 @@ use decrypt_cookies::firefox::{
 #[cfg(target_os = "macos")]
 use decrypt_cookies::safari::SafariBuilder;
 
-/// Domains to extract cookies for Cursor
-const CURSOR_DOMAINS: &[&str] = &["cursor.com", "cursor.sh", "workos.com"];
-const CODEX_DOMAINS: &[&str] = &["chatgpt.com", "openai.com"];
-const FACTORY_DOMAINS: &[&str] = &["factory.ai", "app.factory.ai"];
-const AUGMENT_DOMAINS: &[&str] = &["augmentcode.com", "app.augmentcode.com"];
-const KIMI_DOMAINS: &[&str] = &["kimi.moonshot.cn", "kimi.com"];
-const MINIMAX_DOMAINS: &[&str] = &["minimax.chat", "platform.minimax.io"];
-const AMP_DOMAINS: &[&str] = &["ampcode.com", "www.ampcode.com"];
-const OPENCODE_DOMAINS: &[&str] = &["opencode.ai", "app.opencode.ai"];
+/// Domains to extract cookies for, as declared in the provider descriptor table
+fn cookie_domains(provider: ProviderId) -> &'static [&'static str] {
+    provider.descriptor().cookie_domains
+}
 
 /// Result of a browser cookie import
 #[derive(Debug)]
@@ -53,90 +49,90 @@ pub struct BrowserCookieResult {
 /// Tries Chrome first (most common), then other Chromium browsers.
 /// Returns the first successful result.
 pub async fn import_cursor_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(CURSOR_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Cursor)).await
 }
 
 pub async fn import_cursor_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(CURSOR_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Cursor), source).await
 }
 
 /// Import Codex (ChatGPT) cookies from system browsers
 pub async fn import_codex_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(CODEX_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Codex)).await
 }
 
 pub async fn import_codex_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(CODEX_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Codex), source).await
 }
 
 /// Import Factory (Droid) cookies from system browsers
 pub async fn import_factory_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(FACTORY_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Factory)).await
 }
 
 pub async fn import_factory_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(FACTORY_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Factory), source).await
 }
 
 /// Import Augment cookies from system browsers
 pub async fn import_augment_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(AUGMENT_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Augment)).await
 }
 
 pub async fn import_augment_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(AUGMENT_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Augment), source).await
 }
 
 /// Import Kimi cookies from system browsers
 pub async fn import_kimi_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(KIMI_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Kimi)).await
 }
 
 pub async fn import_kimi_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(KIMI_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Kimi), source).await
 }
 
 /// Import MiniMax cookies from system browsers
 pub async fn import_minimax_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(MINIMAX_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Minimax)).await
 }
 
 pub async fn import_minimax_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(MINIMAX_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Minimax), source).await
 }
 
 /// Import Amp cookies from system browsers
 pub async fn import_amp_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(AMP_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Amp)).await
 }
 
 pub async fn import_amp_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(AMP_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Amp), source).await
 }
 
 /// Import OpenCode cookies from system browsers
 pub async fn import_opencode_cookies_from_browser() -> Result<BrowserCookieResult> {
-    import_cookies_for_domains(OPENCODE_DOMAINS).await
+    import_cookies_for_domains(cookie_domains(ProviderId::Opencode)).await
 }
 
 pub async fn import_opencode_cookies_from_browser_source(
     source: BrowserCookieSource,
 ) -> Result<BrowserCookieResult> {
-    import_cookies_for_domains_from_source(OPENCODE_DOMAINS, source).await
+    import_cookies_for_domains_from_source(cookie_domains(ProviderId::Opencode), source).await
 }
 
 /// Import cookies for specified domains from system browsers
@@ -2,16 +2,23 @@
 
 use serde::{Deserialize, Serialize};
 use tauri::{command, AppHandle, Emitter, Manager, Runtime, State};
-use tauri_plugin_store::StoreExt;
 use tauri_plugin_autostart::AutoLaunchManager;
 use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
 
 use crate::browser_cookies::BrowserCookieSource;
 use crate::debug_settings;
 use crate::login::{self, AuthStatus, LoginResult};
-use crate::providers::{ProviderId, ProviderRegistry, ProviderStatus, UsageSnapshot};
+use crate::providers::{
+    self, AccountInfo, AccountKey, CustomProviderAuth, CustomProviderConfig,
+    NotificationPreferences, NotificationSnoozes, PluginInfo, ProviderAccount, ProviderError,
+    ProviderErrorKind, ProviderId, ProviderRegistry, ProviderStatus, RefreshIntervals,
+    UsageSnapshot,
+};
+use crate::server::{api, metrics};
+use crate::storage::history::{self, HistoryQuery, HistoryRecord};
 use crate::storage::install_origin;
-use crate::storage::widget_snapshot;
+use crate::storage::SecureStorage;
 use crate::tray;
 
 struct LoadingGuard {
@@ -46,11 +53,12 @@ impl Drop for LoadingGuard {
     }
 }
 
-fn emit_refreshing(app: &AppHandle, provider_id: ProviderId, is_refreshing: bool) {
+fn emit_refreshing(app: &AppHandle, key: &AccountKey, is_refreshing: bool) {
     let _ = app.emit(
         "refreshing-provider",
         serde_json::json!({
-            "providerId": provider_id,
+            "providerId": key.provider_id,
+            "accountId": key.account_id,
             "isRefreshing": is_refreshing,
         }),
     );
@@ -76,7 +84,20 @@ pub struct AppSettings {
     pub debug_file_logging: bool,
     pub debug_keep_cli_sessions_alive: bool,
     pub debug_random_blink: bool,
+    #[serde(default)]
+    pub debug_record_fixtures: bool,
     pub redact_personal_info: bool,
+    /// Per-provider base URL overrides, e.g. an internal reverse proxy
+    #[serde(default)]
+    pub provider_base_urls: std::collections::HashMap<ProviderId, String>,
+    /// Per-host base URL overrides of providers that talk to several hosts, keyed by
+    /// the host they replace
+    #[serde(default)]
+    pub provider_host_base_urls:
+        std::collections::HashMap<ProviderId, std::collections::HashMap<String, String>>,
+    /// Per-provider refresh intervals in seconds, overriding `refresh_interval_seconds`
+    #[serde(default)]
+    pub provider_refresh_intervals: std::collections::HashMap<ProviderId, u32>,
 }
 
 /// Open (or focus) the settings window
@@ -114,7 +135,29 @@ impl Default for AppSettings {
             debug_file_logging: false,
             debug_keep_cli_sessions_alive: false,
             debug_random_blink: false,
+            debug_record_fixtures: false,
             redact_personal_info: false,
+            provider_base_urls: std::collections::HashMap::new(),
+            provider_host_base_urls: std::collections::HashMap::new(),
+            provider_refresh_intervals: std::collections::HashMap::new(),
+        }
+    }
+}
+
+/// Why `refresh_provider` failed, so callers can tell a cancelled fetch from a failed one
+/// without matching on the message
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshError {
+    pub message: String,
+    pub error_kind: ProviderErrorKind,
+}
+
+impl From<&ProviderError> for RefreshError {
+    fn from(error: &ProviderError) -> Self {
+        Self {
+            message: error.to_string(),
+            error_kind: error.kind(),
         }
     }
 }
@@ -123,63 +166,82 @@ impl Default for AppSettings {
 #[command]
 pub async fn refresh_provider(
     provider_id: ProviderId,
+    account_id: Option<String>,
+    force: Option<bool>,
     registry: State<'_, ProviderRegistry>,
     app: AppHandle,
-) -> Result<UsageSnapshot, String> {
-    tracing::info!("refresh_provider: starting for {:?}", provider_id);
+) -> Result<UsageSnapshot, RefreshError> {
+    let key = AccountKey::from_optional(provider_id, account_id);
+    tracing::info!("refresh_provider: starting for {:?}", key);
     let start = std::time::Instant::now();
 
     // Check if provider is authenticated before attempting refresh
-    // Convert ProviderId to string using serde serialization (snake_case)
-    let provider_id_str = serde_json::to_string(&provider_id)
-        .unwrap_or_default()
-        .trim_matches('"')
-        .to_string();
-    let auth_status = login::check_auth_status(&provider_id_str).await;
-    tracing::info!("refresh_provider: auth check for {:?} took {:?}, authenticated: {}", 
-        provider_id, start.elapsed(), auth_status.authenticated);
-    
+    let auth_status = registry.auth_status(&key).await;
+    tracing::info!(
+        "refresh_provider: auth check for {:?} took {:?}, authenticated: {}",
+        key,
+        start.elapsed(),
+        auth_status.authenticated
+    );
+
     if !auth_status.authenticated {
         let error_msg = auth_status
             .error
             .unwrap_or_else(|| "Not authenticated".to_string());
-        tracing::info!(
-            "refresh_provider: skipping {:?}: {}",
-            provider_id,
-            error_msg
-        );
+        tracing::info!("refresh_provider: skipping {:?}: {}", key, error_msg);
         let usage = UsageSnapshot::error(format!("Not authenticated: {}", error_msg));
-        
+
         let _ = app.emit(
             "usage-updated",
             serde_json::json!({
                 "providerId": provider_id,
+                "accountId": key.account_id,
                 "usage": usage.clone(),
             }),
         );
-        
-        return Err(format!("Not authenticated: {}", error_msg));
+
+        return Err(RefreshError {
+            message: format!("Not authenticated: {}", error_msg),
+            error_kind: ProviderErrorKind::NotAuthenticated,
+        });
     }
 
-    tracing::info!("refresh_provider: preparing loading guard for {:?}", provider_id);
+    tracing::info!("refresh_provider: preparing loading guard for {:?}", key);
     let mut loading_guard = LoadingGuard::new(&app);
-    tracing::info!("refresh_provider: loading guard acquired for {:?}", provider_id);
-    emit_refreshing(&app, provider_id, true);
+    tracing::info!("refresh_provider: loading guard acquired for {:?}", key);
+    emit_refreshing(&app, &key, true);
 
     tracing::info!("refresh_provider: fetching status for {:?}", provider_id);
     let status_start = std::time::Instant::now();
-    let status = registry.fetch_status(&provider_id).await.ok();
-    tracing::info!("refresh_provider: status fetch for {:?} took {:?}", provider_id, status_start.elapsed());
-    
-    tracing::info!("refresh_provider: fetching usage for {:?}", provider_id);
+    let status = registry.fetch_status(&key).await.ok();
+    tracing::info!(
+        "refresh_provider: status fetch for {:?} took {:?}",
+        provider_id,
+        status_start.elapsed()
+    );
+
+    tracing::info!("refresh_provider: fetching usage for {:?}", key);
     let usage_start = std::time::Instant::now();
-    let usage_result = registry.fetch_usage(&provider_id).await;
-    tracing::info!("refresh_provider: usage fetch for {:?} took {:?}", provider_id, usage_start.elapsed());
+    // A forced refresh replaces a fetch already in flight, e.g. one using stale credentials
+    let usage_result = if force.unwrap_or(false) {
+        registry.fetch_usage_forced(&key).await
+    } else {
+        registry.fetch_usage(&key).await
+    };
+    tracing::info!(
+        "refresh_provider: usage fetch for {:?} took {:?}",
+        key,
+        usage_start.elapsed()
+    );
 
     loading_guard.finish();
-    emit_refreshing(&app, provider_id, false);
-    
-    tracing::info!("refresh_provider: total time for {:?}: {:?}", provider_id, start.elapsed());
+    emit_refreshing(&app, &key, false);
+
+    tracing::info!(
+        "refresh_provider: total time for {:?}: {:?}",
+        key,
+        start.elapsed()
+    );
 
     match usage_result {
         Ok(usage) => {
@@ -187,35 +249,35 @@ pub async fn refresh_provider(
                 "usage-updated",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.account_id,
                     "usage": usage,
                 }),
             );
+            registry.publish_usage(&key, &usage);
 
             let _ = app.emit(
                 "status-updated",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.status_key().account_id,
                     "status": status,
                 }),
             );
 
-            if let Err(err) = widget_snapshot::write_widget_snapshot(provider_id, &usage) {
-                tracing::warn!("Failed to write widget snapshot: {}", err);
-            }
-            tray::handle_usage_update(&app, provider_id, usage.clone())
-                .map_err(|e| e.to_string())?;
-
             Ok(usage)
         }
+        // Cancelled or replaced by a forced refresh, which reports its own result
+        Err(e @ ProviderError::Cancelled) => Err(RefreshError::from(&e)),
         Err(e) => {
-            let message = e.to_string();
-            let usage = UsageSnapshot::error(message.clone());
+            let usage = UsageSnapshot::from_error(&e);
 
             let _ = app.emit(
                 "refresh-failed",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.account_id,
                     "usage": usage.clone(),
+                    "errorKind": e.kind(),
                 }),
             );
 
@@ -223,6 +285,7 @@ pub async fn refresh_provider(
                 "status-updated",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.status_key().account_id,
                     "status": status,
                 }),
             );
@@ -231,22 +294,30 @@ pub async fn refresh_provider(
                 "usage-updated",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.account_id,
                     "usage": usage.clone(),
                 }),
             );
+            registry.publish_failure(&key, &e, &usage);
 
-            if let Err(err) = widget_snapshot::write_widget_snapshot(provider_id, &usage) {
-                tracing::warn!("Failed to write widget snapshot: {}", err);
-            }
-            if let Err(e) = tray::handle_usage_update(&app, provider_id, usage) {
-                tracing::warn!("Failed to update tray icon: {}", e);
-            }
-
-            Err(message)
+            Err(RefreshError::from(&e))
         }
     }
 }
 
+/// Cancel a usage fetch in flight. Everyone waiting on it gets a cancellation error.
+#[command]
+pub async fn cancel_refresh(
+    provider_id: ProviderId,
+    account_id: Option<String>,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<bool, String> {
+    let key = AccountKey::from_optional(provider_id, account_id);
+    let cancelled = registry.cancel_fetch(&key);
+    tracing::info!("cancel_refresh: {:?} cancelled: {}", key, cancelled);
+    Ok(cancelled)
+}
+
 /// Refresh all enabled providers
 #[command]
 pub async fn refresh_all_providers(
@@ -255,29 +326,25 @@ pub async fn refresh_all_providers(
 ) -> Result<(), String> {
     tracing::debug!("Refreshing all providers");
 
-    let providers = registry.get_enabled_providers().await;
+    let accounts = registry.get_enabled_accounts().await;
 
     let mut loading_guard = LoadingGuard::new(&app);
 
-    for provider_id in &providers {
-        emit_refreshing(&app, *provider_id, true);
-    }
-
-    fn provider_id_to_string(provider_id: &ProviderId) -> Result<String, String> {
-        serde_json::to_string(provider_id)
-            .map_err(|e| format!("Failed to serialize provider id {:?}: {}", provider_id, e))
-            .map(|s| s.trim_matches('"').to_owned())
+    for key in &accounts {
+        emit_refreshing(&app, key, true);
     }
 
-    for provider_id in providers {
+    let mut statuses: std::collections::HashMap<AccountKey, Option<ProviderStatus>> =
+        std::collections::HashMap::new();
+    for key in accounts {
+        let provider_id = key.provider_id;
         // Check if provider is authenticated before attempting refresh
-        let provider_id_str = provider_id_to_string(&provider_id)?;
-        let auth_status = login::check_auth_status(&provider_id_str).await;
-        
+        let auth_status = registry.auth_status(&key).await;
+
         if !auth_status.authenticated {
             tracing::debug!(
                 "refresh_all_providers: skipping {:?} - not authenticated",
-                provider_id
+                key
             );
             let error_msg = auth_status
                 .error
@@ -287,44 +354,55 @@ pub async fn refresh_all_providers(
                 "usage-updated",
                 serde_json::json!({
                     "providerId": provider_id,
+                    "accountId": key.account_id,
                     "usage": usage.clone(),
                 }),
             );
-            emit_refreshing(&app, provider_id, false);
+            emit_refreshing(&app, &key, false);
             continue;
         }
 
-        let status = registry.fetch_status(&provider_id).await.ok();
-        match registry.fetch_usage(&provider_id).await {
+        // Status is provider-wide except for plugins, so fetch it once per status key
+        let status_key = key.status_key();
+        let status = match statuses.get(&status_key) {
+            Some(status) => status.clone(),
+            None => {
+                let status = registry.fetch_status(&status_key).await.ok();
+                statuses.insert(status_key, status.clone());
+                status
+            }
+        };
+        match registry.fetch_usage(&key).await {
             Ok(usage) => {
                 let _ = app.emit(
                     "usage-updated",
                     serde_json::json!({
                         "providerId": provider_id,
+                        "accountId": key.account_id,
                         "usage": usage,
                     }),
                 );
+                registry.publish_usage(&key, &usage);
                 let _ = app.emit(
                     "status-updated",
                     serde_json::json!({
                         "providerId": provider_id,
+                        "accountId": key.status_key().account_id,
                         "status": status,
                     }),
                 );
-                if let Err(err) = widget_snapshot::write_widget_snapshot(provider_id, &usage) {
-                    tracing::warn!("Failed to write widget snapshot: {}", err);
-                }
-                if let Err(e) = tray::handle_usage_update(&app, provider_id, usage.clone()) {
-                    tracing::warn!("Failed to update tray icon: {}", e);
-                }
+            }
+            Err(ProviderError::Cancelled) => {
+                tracing::debug!("refresh_all_providers: fetch for {:?} was cancelled", key);
             }
             Err(e) => {
-                tracing::warn!("Failed to refresh {:?}: {}", provider_id, e);
-                let usage = UsageSnapshot::error(e.to_string());
+                tracing::warn!("Failed to refresh {:?}: {}", key, e);
+                let usage = UsageSnapshot::from_error(&e);
                 let _ = app.emit(
                     "usage-updated",
                     serde_json::json!({
                         "providerId": provider_id,
+                        "accountId": key.account_id,
                         "usage": usage.clone(),
                     }),
                 );
@@ -332,25 +410,23 @@ pub async fn refresh_all_providers(
                     "refresh-failed",
                     serde_json::json!({
                         "providerId": provider_id,
+                        "accountId": key.account_id,
                         "usage": usage.clone(),
+                        "errorKind": e.kind(),
                     }),
                 );
                 let _ = app.emit(
                     "status-updated",
                     serde_json::json!({
                         "providerId": provider_id,
+                        "accountId": key.status_key().account_id,
                         "status": status,
                     }),
                 );
-                if let Err(err) = widget_snapshot::write_widget_snapshot(provider_id, &usage) {
-                    tracing::warn!("Failed to write widget snapshot: {}", err);
-                }
-                if let Err(e) = tray::handle_usage_update(&app, provider_id, usage) {
-                    tracing::warn!("Failed to update tray icon: {}", e);
-                }
+                registry.publish_failure(&key, &e, &usage);
             }
         }
-        emit_refreshing(&app, provider_id, false);
+        emit_refreshing(&app, &key, false);
     }
 
     loading_guard.finish();
@@ -362,17 +438,34 @@ pub async fn refresh_all_providers(
 #[command]
 pub async fn get_provider_usage(
     provider_id: ProviderId,
+    account_id: Option<String>,
     registry: State<'_, ProviderRegistry>,
 ) -> Result<Option<UsageSnapshot>, String> {
-    Ok(registry.get_cached_usage(&provider_id).await)
+    let key = AccountKey::from_optional(provider_id, account_id);
+    Ok(registry.get_cached_usage(&key).await)
 }
 
-/// Get all cached usage data
+/// Get all cached usage data, keyed by provider slug for default accounts and
+/// `slug:account` for the others
 #[command]
 pub async fn get_all_usage(
     registry: State<'_, ProviderRegistry>,
-) -> Result<std::collections::HashMap<ProviderId, UsageSnapshot>, String> {
-    Ok(registry.get_all_cached_usage().await)
+) -> Result<std::collections::HashMap<String, UsageSnapshot>, String> {
+    Ok(registry
+        .get_all_cached_usage()
+        .await
+        .into_iter()
+        .map(|(key, usage)| (key.to_string(), usage))
+        .collect())
+}
+
+/// Recorded usage history matching a provider, account, window and time range
+#[command]
+pub async fn get_usage_history(query: HistoryQuery) -> Result<Vec<HistoryRecord>, String> {
+    tokio::task::spawn_blocking(move || history::query_history(&query))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
 }
 
 /// Poll provider status/incident data (only for enabled providers)
@@ -383,21 +476,18 @@ pub async fn poll_provider_statuses(
     // Only poll enabled providers to avoid wasting resources on disabled ones
     let enabled_providers = registry.get_enabled_providers().await;
     let mut statuses = std::collections::HashMap::new();
-    
+
     for provider_id in enabled_providers {
         // Check if provider is authenticated before polling status
-        let provider_id_str = serde_json::to_string(&provider_id)
-            .unwrap_or_default()
-            .trim_matches('"')
-            .to_string();
-        let auth_status = login::check_auth_status(&provider_id_str).await;
-        
+        let key = AccountKey::default_for(provider_id);
+        let auth_status = registry.auth_status(&key).await;
+
         if !auth_status.authenticated {
             statuses.insert(provider_id, None);
             continue;
         }
-        
-        let status = registry.fetch_status(&provider_id).await.ok();
+
+        let status = registry.fetch_status(&key).await.ok();
         statuses.insert(provider_id, status);
     }
     Ok(statuses)
@@ -417,13 +507,40 @@ pub async fn set_provider_enabled(
     Ok(())
 }
 
+/// Apply refresh intervals from the frontend when it sent them
+async fn sync_refresh_intervals(
+    registry: &ProviderRegistry,
+    refresh_interval_seconds: Option<u32>,
+    provider_refresh_intervals: Option<std::collections::HashMap<ProviderId, u32>>,
+) {
+    if refresh_interval_seconds.is_none() && provider_refresh_intervals.is_none() {
+        return;
+    }
+    let current = registry.refresh_intervals().await;
+    registry
+        .set_refresh_intervals(RefreshIntervals {
+            default_seconds: refresh_interval_seconds.unwrap_or(current.default_seconds),
+            per_provider: provider_refresh_intervals.unwrap_or(current.per_provider),
+        })
+        .await;
+}
+
 /// Sync enabled providers list with registry
 #[command]
 pub async fn set_enabled_providers(
     provider_ids: Vec<ProviderId>,
+    refresh_interval_seconds: Option<u32>,
+    provider_refresh_intervals: Option<std::collections::HashMap<ProviderId, u32>>,
     registry: State<'_, ProviderRegistry>,
     app: AppHandle,
 ) -> Result<(), String> {
+    // Intervals go first so the refresh loop starts with them once providers are synced
+    sync_refresh_intervals(
+        &registry,
+        refresh_interval_seconds,
+        provider_refresh_intervals,
+    )
+    .await;
     registry.set_enabled_providers(&provider_ids).await;
     for provider_id in ProviderId::all() {
         let enabled = provider_ids.contains(&provider_id);
@@ -437,6 +554,10 @@ pub async fn set_enabled_providers(
 struct SettingsUpdatedPayload {
     enabled_providers: Vec<ProviderId>,
     provider_order: Vec<ProviderId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_interval_seconds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_refresh_intervals: Option<std::collections::HashMap<ProviderId, u32>>,
 }
 
 /// Broadcast settings updates to all windows and sync registry
@@ -444,9 +565,17 @@ struct SettingsUpdatedPayload {
 pub async fn broadcast_settings_updated(
     enabled_providers: Vec<ProviderId>,
     provider_order: Vec<ProviderId>,
+    refresh_interval_seconds: Option<u32>,
+    provider_refresh_intervals: Option<std::collections::HashMap<ProviderId, u32>>,
     registry: State<'_, ProviderRegistry>,
     app: AppHandle,
 ) -> Result<(), String> {
+    sync_refresh_intervals(
+        &registry,
+        refresh_interval_seconds,
+        provider_refresh_intervals.clone(),
+    )
+    .await;
     registry.set_enabled_providers(&enabled_providers).await;
     for provider_id in ProviderId::all() {
         let enabled = enabled_providers.contains(&provider_id);
@@ -455,6 +584,8 @@ pub async fn broadcast_settings_updated(
     let payload = SettingsUpdatedPayload {
         enabled_providers,
         provider_order,
+        refresh_interval_seconds,
+        provider_refresh_intervals,
     };
     app.emit("settings-updated", payload)
         .map_err(|e| e.to_string())?;
@@ -468,9 +599,7 @@ pub async fn get_settings(app: AppHandle) -> Result<AppSettings, String> {
 }
 
 async fn load_settings<R: Runtime>(app: AppHandle<R>) -> Result<AppSettings, String> {
-    let store = app
-        .store(SETTINGS_STORE_PATH)
-        .map_err(|e| e.to_string())?;
+    let store = app.store(SETTINGS_STORE_PATH).map_err(|e| e.to_string())?;
     if let Some(value) = store.get(SETTINGS_STORE_KEY) {
         serde_json::from_value(value).map_err(|e| e.to_string())
     } else {
@@ -484,10 +613,11 @@ pub async fn save_settings(settings: AppSettings, app: AppHandle) -> Result<(),
     save_settings_inner(settings, app).await
 }
 
-async fn save_settings_inner<R: Runtime>(settings: AppSettings, app: AppHandle<R>) -> Result<(), String> {
-    let store = app
-        .store(SETTINGS_STORE_PATH)
-        .map_err(|e| e.to_string())?;
+async fn save_settings_inner<R: Runtime>(
+    settings: AppSettings,
+    app: AppHandle<R>,
+) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE_PATH).map_err(|e| e.to_string())?;
     let value = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
     store.set(SETTINGS_STORE_KEY, value);
     store.save().map_err(|e| e.to_string())?;
@@ -535,10 +665,16 @@ mod tests {
         store.clear();
         store.save().expect("store save");
 
-        let settings = tauri::async_runtime::block_on(load_settings(handle.clone()))
-            .expect("get settings");
-        assert_eq!(settings.refresh_interval_seconds, AppSettings::default().refresh_interval_seconds);
-        assert_eq!(settings.enabled_providers, AppSettings::default().enabled_providers);
+        let settings =
+            tauri::async_runtime::block_on(load_settings(handle.clone())).expect("get settings");
+        assert_eq!(
+            settings.refresh_interval_seconds,
+            AppSettings::default().refresh_interval_seconds
+        );
+        assert_eq!(
+            settings.enabled_providers,
+            AppSettings::default().enabled_providers
+        );
     }
 
     #[test]
@@ -554,10 +690,13 @@ mod tests {
         tauri::async_runtime::block_on(save_settings_inner(settings.clone(), handle.clone()))
             .expect("save settings");
 
-        let loaded = tauri::async_runtime::block_on(load_settings(handle.clone()))
-            .expect("get settings");
+        let loaded =
+            tauri::async_runtime::block_on(load_settings(handle.clone())).expect("get settings");
 
-        assert_eq!(loaded.refresh_interval_seconds, settings.refresh_interval_seconds);
+        assert_eq!(
+            loaded.refresh_interval_seconds,
+            settings.refresh_interval_seconds
+        );
         assert_eq!(loaded.display_mode, settings.display_mode);
         assert_eq!(loaded.enabled_providers, settings.enabled_providers);
     }
@@ -600,12 +739,67 @@ pub async fn set_debug_random_blink(enabled: bool) -> Result<(), String> {
     Ok(())
 }
 
+#[command]
+pub async fn set_debug_record_fixtures(enabled: bool) -> Result<(), String> {
+    debug_settings::set_record_fixtures(enabled);
+    Ok(())
+}
+
 #[command]
 pub async fn set_redact_personal_info(enabled: bool) -> Result<(), String> {
     debug_settings::set_redact_personal_info(enabled);
     Ok(())
 }
 
+#[command]
+pub async fn set_provider_base_urls(
+    base_urls: std::collections::HashMap<ProviderId, String>,
+    host_base_urls: Option<
+        std::collections::HashMap<ProviderId, std::collections::HashMap<String, String>>,
+    >,
+) -> Result<(), String> {
+    providers::set_base_url_overrides(base_urls, host_base_urls.unwrap_or_default());
+    Ok(())
+}
+
+/// Sync the alert preferences the background notifier uses
+#[command]
+pub async fn set_notification_preferences(
+    preferences: NotificationPreferences,
+) -> Result<(), String> {
+    providers::set_notification_preferences(preferences).map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_notification_snoozes() -> Result<NotificationSnoozes, String> {
+    providers::notification_snoozes().map_err(|e| e.to_string())
+}
+
+/// Snooze alerts for one provider, or all of them, for `minutes`; zero lifts the snooze
+#[command]
+pub async fn snooze_notifications(
+    provider_id: Option<ProviderId>,
+    minutes: u32,
+) -> Result<NotificationSnoozes, String> {
+    providers::snooze_notifications(provider_id, minutes).map_err(|e| e.to_string())
+}
+
+/// Start, move or stop the localhost OpenMetrics endpoint
+#[command]
+pub async fn set_metrics_exporter(app: AppHandle, enabled: bool, port: u16) -> Result<(), String> {
+    metrics::set_metrics_exporter(app, enabled, port)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Start, move or stop the localhost JSON API
+#[command]
+pub async fn set_api_server(app: AppHandle, enabled: bool, port: u16) -> Result<(), String> {
+    api::set_api_server(app, enabled, port)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Send a test notification
 #[command]
 pub async fn send_test_notification(app: AppHandle) -> Result<(), String> {
@@ -682,52 +876,6 @@ pub async fn start_login(provider_id: String, app: AppHandle) -> Result<LoginRes
                 provider_id: "cursor".to_string(),
             });
         }
-        "factory" => {
-            return Ok(LoginResult {
-                success: true,
-                message: "Factory uses browser cookies. Use Import from Browser or paste cookies manually.".to_string(),
-                provider_id: "factory".to_string(),
-            });
-        }
-        "augment" => {
-            return Ok(LoginResult {
-                success: true,
-                message: "Augment uses browser cookies. Use Import from Browser or paste cookies manually.".to_string(),
-                provider_id: "augment".to_string(),
-            });
-        }
-        "amp" => {
-            return Ok(LoginResult {
-                success: true,
-                message:
-                    "Amp uses browser cookies. Use Import from Browser or paste cookies manually."
-                        .to_string(),
-                provider_id: "amp".to_string(),
-            });
-        }
-        "kimi" => {
-            return Ok(LoginResult {
-                success: true,
-                message:
-                    "Kimi uses browser cookies. Use Import from Browser or paste cookies manually."
-                        .to_string(),
-                provider_id: "kimi".to_string(),
-            });
-        }
-        "minimax" => {
-            return Ok(LoginResult {
-                success: true,
-                message: "MiniMax uses browser cookies. Use Import from Browser or paste cookies manually.".to_string(),
-                provider_id: "minimax".to_string(),
-            });
-        }
-        "opencode" => {
-            return Ok(LoginResult {
-                success: true,
-                message: "OpenCode uses browser cookies. Use Import from Browser or paste cookies manually.".to_string(),
-                provider_id: "opencode".to_string(),
-            });
-        }
         "antigravity" => {
             return Ok(LoginResult {
                 success: true,
@@ -738,7 +886,8 @@ pub async fn start_login(provider_id: String, app: AppHandle) -> Result<LoginRes
         "jetbrains" => {
             return Ok(LoginResult {
                 success: true,
-                message: "Open a JetBrains IDE with AI Assistant enabled to connect automatically.".to_string(),
+                message: "Open a JetBrains IDE with AI Assistant enabled to connect automatically."
+                    .to_string(),
                 provider_id: "jetbrains".to_string(),
             });
         }
@@ -746,13 +895,25 @@ pub async fn start_login(provider_id: String, app: AppHandle) -> Result<LoginRes
             .await
             .map_err(|e| e.to_string())?,
         "gemini" => login::run_gemini_login().await.map_err(|e| e.to_string())?,
-        _ => {
-            return Ok(LoginResult {
-                success: false,
-                message: format!("Login not supported for provider: {}", provider_id),
-                provider_id,
-            });
-        }
+        slug => match ProviderId::from_slug(slug).map(ProviderId::descriptor) {
+            Some(descriptor) if descriptor.uses_cookies_only() => {
+                return Ok(LoginResult {
+                    success: true,
+                    message: format!(
+                        "{} uses browser cookies. Use Import from Browser or paste cookies manually.",
+                        descriptor.display_name
+                    ),
+                    provider_id: descriptor.slug.to_string(),
+                });
+            }
+            _ => {
+                return Ok(LoginResult {
+                    success: false,
+                    message: format!("Login not supported for provider: {}", provider_id),
+                    provider_id,
+                });
+            }
+        },
     };
 
     // Emit login completed event
@@ -770,41 +931,226 @@ pub async fn start_login(provider_id: String, app: AppHandle) -> Result<LoginRes
 
 /// Check authentication status for a provider
 #[command]
-pub async fn check_auth(provider_id: String) -> Result<AuthStatus, String> {
-    Ok(login::check_auth_status(&provider_id).await)
+pub async fn check_auth(
+    provider_id: ProviderId,
+    account_id: Option<String>,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<AuthStatus, String> {
+    let key = AccountKey::from_optional(provider_id, account_id);
+    Ok(registry.auth_status(&key).await)
 }
 
 /// Check authentication status for all providers
 #[command]
-pub async fn check_all_auth() -> Result<std::collections::HashMap<String, AuthStatus>, String> {
-    let providers = vec![
-        "claude",
-        "codex",
-        "cursor",
-        "factory",
-        "augment",
-        "amp",
-        "copilot",
-        "gemini",
-        "zai",
-        "kimi",
-        "kimi_k2",
-        "minimax",
-        "opencode",
-        "synthetic",
-        "antigravity",
-        "kiro",
-    ];
+pub async fn check_all_auth(
+    registry: State<'_, ProviderRegistry>,
+) -> Result<std::collections::HashMap<String, AuthStatus>, String> {
     let mut results = std::collections::HashMap::new();
 
-    for provider_id in providers {
-        let status = login::check_auth_status(provider_id).await;
-        results.insert(provider_id.to_string(), status);
+    for provider_id in ProviderId::all() {
+        if !provider_id.descriptor().has_default_account() {
+            continue;
+        }
+        let status = registry
+            .auth_status(&AccountKey::default_for(provider_id))
+            .await;
+        results.insert(provider_id.slug().to_string(), status);
     }
 
     Ok(results)
 }
 
+/// List every registered account, including each provider's default account
+#[command]
+pub async fn list_accounts(
+    registry: State<'_, ProviderRegistry>,
+) -> Result<Vec<AccountInfo>, String> {
+    Ok(registry.list_accounts().await)
+}
+
+/// Let every window know the registered accounts changed
+async fn emit_accounts_updated(app: &AppHandle, registry: &ProviderRegistry) {
+    if let Err(err) = app.emit("accounts-updated", registry.list_accounts().await) {
+        tracing::warn!("Failed to emit accounts-updated: {}", err);
+    }
+}
+
+/// Register an additional account and persist it to `accounts.json`
+#[command]
+pub async fn add_account(
+    app: AppHandle,
+    account: ProviderAccount,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<(), String> {
+    registry
+        .add_account(account)
+        .await
+        .map_err(|e| e.to_string())?;
+    emit_accounts_updated(&app, &registry).await;
+    registry.save_accounts().await.map_err(|e| e.to_string())
+}
+
+/// Remove an additional account. Stored credentials are left untouched.
+#[command]
+pub async fn remove_account(
+    app: AppHandle,
+    provider_id: ProviderId,
+    account_id: String,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<(), String> {
+    let key = AccountKey::new(provider_id, account_id);
+    if !registry.remove_account(&key).await {
+        return Err(format!("Account {} cannot be removed", key.account_id));
+    }
+    emit_accounts_updated(&app, &registry).await;
+    registry.save_accounts().await.map_err(|e| e.to_string())
+}
+
+/// Store session cookies for an additional account of a cookie-based provider
+#[command]
+pub async fn store_account_cookies(
+    provider_id: ProviderId,
+    account_id: String,
+    cookie_header: String,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<LoginResult, String> {
+    if !provider_id.descriptor().uses_cookies_only() {
+        return Err(format!(
+            "{} does not use browser cookies",
+            provider_id.display_name()
+        ));
+    }
+    let account = registry
+        .declared_accounts()
+        .await
+        .into_iter()
+        .find(|account| account.provider_id == provider_id && account.account_id == account_id)
+        .ok_or_else(|| format!("Account {} not found", account_id))?;
+    let session_path = account
+        .resolve_credentials_path()
+        .map_err(|e| e.to_string())?;
+
+    match login::store_session_file(&session_path, cookie_header).await {
+        Ok(()) => Ok(LoginResult {
+            success: true,
+            message: format!(
+                "{} cookies saved successfully for {}",
+                provider_id.display_name(),
+                account.label
+            ),
+            provider_id: provider_id.slug().to_string(),
+        }),
+        Err(e) => Ok(LoginResult {
+            success: false,
+            message: format!(
+                "Failed to save {} cookies: {}",
+                provider_id.display_name(),
+                e
+            ),
+            provider_id: provider_id.slug().to_string(),
+        }),
+    }
+}
+
+/// List the user-defined JSON HTTP providers
+#[command]
+pub async fn list_custom_providers(
+    registry: State<'_, ProviderRegistry>,
+) -> Result<Vec<CustomProviderConfig>, String> {
+    Ok(registry.custom_providers().await)
+}
+
+/// Add a custom provider, or update an existing one when `replace` is set, and persist
+/// it to `custom_providers.json`. A `secret` is stored in the keyring rather than in the
+/// definition.
+#[command]
+pub async fn save_custom_provider(
+    app: AppHandle,
+    mut config: CustomProviderConfig,
+    secret: Option<String>,
+    replace: Option<bool>,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<(), String> {
+    let replace = replace.unwrap_or(false);
+    config.id = config.id.trim().to_string();
+    config.validate().map_err(|e| e.to_string())?;
+    // Checked before the secret is stored, so a clashing id keeps its secret
+    let exists = registry
+        .custom_providers()
+        .await
+        .iter()
+        .any(|existing| existing.id == config.id);
+    if exists && !replace {
+        return Err(format!("Custom provider {} already exists", config.id));
+    }
+    if let Some(secret) = secret.filter(|secret| !secret.trim().is_empty()) {
+        let keyring_key = config.default_keyring_key();
+        SecureStorage::new()
+            .store(&keyring_key, secret.trim())
+            .map_err(|e| e.to_string())?;
+        let auth = config.auth.get_or_insert_with(|| CustomProviderAuth {
+            header: "Authorization".to_string(),
+            scheme: Some("Bearer".to_string()),
+            value: None,
+            keyring_key: None,
+        });
+        auth.value = None;
+        auth.keyring_key = Some(keyring_key);
+    }
+
+    let saved = if replace {
+        registry.update_custom_provider(config).await
+    } else {
+        registry.add_custom_provider(config).await
+    };
+    saved.map_err(|e| e.to_string())?;
+    emit_accounts_updated(&app, &registry).await;
+    registry
+        .save_custom_providers()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a custom provider and the secret saved for it
+#[command]
+pub async fn remove_custom_provider(
+    app: AppHandle,
+    id: String,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<(), String> {
+    let Some(config) = registry
+        .custom_providers()
+        .await
+        .into_iter()
+        .find(|config| config.id == id)
+    else {
+        return Err(format!("Custom provider {} not found", id));
+    };
+    registry
+        .remove_account(&AccountKey::new(ProviderId::Custom, id))
+        .await;
+    emit_accounts_updated(&app, &registry).await;
+    let default_keyring_key = config.default_keyring_key();
+    if config.auth.and_then(|auth| auth.keyring_key) == Some(default_keyring_key.clone()) {
+        let _ = SecureStorage::new().delete(&default_keyring_key);
+    }
+    registry
+        .save_custom_providers()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rescan the plugins directory for `incubar-provider-*` executables
+#[command]
+pub async fn rescan_plugins(
+    app: AppHandle,
+    registry: State<'_, ProviderRegistry>,
+) -> Result<Vec<PluginInfo>, String> {
+    let plugins = registry.rescan_plugins().await;
+    emit_accounts_updated(&app, &registry).await;
+    Ok(plugins)
+}
+
 /// Store Cursor session cookies (for manual cookie paste)
 #[command]
 pub async fn store_cursor_cookies(cookie_header: String) -> Result<LoginResult, String> {
@@ -11,6 +11,7 @@ static FILE_LOGGING_ENABLED: AtomicBool = AtomicBool::new(false);
 static KEEP_CLI_SESSIONS_ALIVE: AtomicBool = AtomicBool::new(false);
 static RANDOM_BLINK_ENABLED: AtomicBool = AtomicBool::new(false);
 static REDACT_PERSONAL_INFO: AtomicBool = AtomicBool::new(false);
+static RECORD_FIXTURES: AtomicBool = AtomicBool::new(false);
 
 static DEBUG_LOG_FILE: Lazy<Arc<Mutex<Box<dyn Write + Send>>>> = Lazy::new(|| {
     let file = open_debug_log_file().unwrap_or_else(|_| open_fallback_log_file());
@@ -49,6 +50,14 @@ pub fn redact_personal_info_enabled() -> bool {
     REDACT_PERSONAL_INFO.load(Ordering::Relaxed)
 }
 
+pub fn set_record_fixtures(enabled: bool) {
+    RECORD_FIXTURES.store(enabled, Ordering::Relaxed);
+}
+
+pub fn record_fixtures_enabled() -> bool {
+    RECORD_FIXTURES.load(Ordering::Relaxed)
+}
+
 pub fn redact_value(value: &str) -> String {
     if redact_personal_info_enabled() {
         "[redacted]".to_string()
@@ -8,9 +8,11 @@ pub mod commands;
 pub mod debug_settings;
 pub mod login;
 pub mod providers;
+pub mod server;
 pub mod storage;
 pub mod tray;
 
+use std::sync::Arc;
 use tauri::{Emitter, Manager};
 #[cfg(target_os = "macos")]
 use tauri::ActivationPolicy;
@@ -73,8 +75,16 @@ pub fn run() {
             });
             eprintln!("Tray setup scheduled");
 
-            // Initialize the provider registry
+            // Initialize the provider registry, with the sinks every fetch result passes through
             let registry = providers::ProviderRegistry::new();
+            registry.add_sink(Arc::new(tray::TraySink::new(app.handle().clone())));
+            registry.add_sink(Arc::new(storage::widget_snapshot::WidgetSnapshotSink));
+            registry.add_sink(Arc::new(storage::history::HistorySink::default()));
+            registry.add_sink(Arc::new(providers::NotificationSink::new(
+                app.handle().clone(),
+            )));
+            registry.add_sink(Arc::new(server::metrics::MetricsSink));
+            registry.add_sink(Arc::new(server::api::ApiEventSink));
             app.manage(registry);
 
             // Start the refresh timer
@@ -83,6 +93,12 @@ pub fn run() {
                 providers::start_refresh_loop(handle).await;
             });
 
+            // Report window resets found by any refresh
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                providers::start_reset_listener(handle).await;
+            });
+
             app.global_shortcut()
                 .on_shortcut("CmdOrCtrl+R", move |app, _, _| {
                     let _ = app.emit("refresh-requested", ());
@@ -94,8 +110,10 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::refresh_provider,
             commands::refresh_all_providers,
+            commands::cancel_refresh,
             commands::get_provider_usage,
             commands::get_all_usage,
+            commands::get_usage_history,
             commands::poll_provider_statuses,
             commands::set_provider_enabled,
             commands::set_enabled_providers,
@@ -104,16 +122,31 @@ pub fn run() {
             commands::save_settings,
             commands::save_menu_bar_display_settings,
             commands::send_test_notification,
+            commands::set_notification_preferences,
+            commands::get_notification_snoozes,
+            commands::snooze_notifications,
             commands::get_install_origin,
             commands::set_debug_file_logging,
             commands::set_debug_keep_cli_sessions_alive,
             commands::set_debug_random_blink,
+            commands::set_debug_record_fixtures,
             commands::set_redact_personal_info,
+            commands::set_provider_base_urls,
+            commands::set_metrics_exporter,
+            commands::set_api_server,
             commands::export_support_bundle,
             commands::open_settings_window,
             commands::start_login,
             commands::check_auth,
             commands::check_all_auth,
+            commands::list_accounts,
+            commands::add_account,
+            commands::remove_account,
+            commands::store_account_cookies,
+            commands::list_custom_providers,
+            commands::save_custom_provider,
+            commands::remove_custom_provider,
+            commands::rescan_plugins,
             commands::store_cursor_cookies,
             commands::store_factory_cookies,
             commands::store_augment_cookies,
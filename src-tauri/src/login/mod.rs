@@ -9,6 +9,7 @@
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::{Deserialize, Serialize};
 use std::io::Read;
+use std::path::PathBuf;
 use tokio::process::Command;
 
 /// Login result returned to frontend
@@ -216,32 +217,6 @@ pub async fn run_codex_login() -> Result<LoginResult, anyhow::Error> {
     }
 }
 
-/// Check authentication status for a provider
-pub async fn check_auth_status(provider_id: &str) -> AuthStatus {
-    match provider_id {
-        "claude" => check_claude_auth().await,
-        "codex" => check_codex_auth().await,
-        "cursor" => check_cursor_auth().await,
-        "factory" => check_factory_auth().await,
-        "augment" => check_augment_auth().await,
-        "copilot" => check_copilot_auth().await,
-        "gemini" => check_gemini_auth().await,
-        "zai" => check_zai_auth().await,
-        "kimi" => check_kimi_auth().await,
-        "kimi_k2" => check_kimi_k2_auth().await,
-        "minimax" => check_minimax_auth().await,
-        "synthetic" => check_synthetic_auth().await,
-        "antigravity" => check_antigravity_auth().await,
-        "kiro" => check_kiro_auth().await,
-        _ => AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: Some("Unknown provider".to_string()),
-        },
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthStatus {
@@ -251,568 +226,35 @@ pub struct AuthStatus {
     pub error: Option<String>,
 }
 
-async fn check_claude_auth() -> AuthStatus {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find home directory".to_string()),
-            }
-        }
-    };
-
-    let creds_path = home.join(".claude").join(".credentials.json");
-
-    if creds_path.exists() {
-        // Try to parse and validate
-        match tokio::fs::read_to_string(&creds_path).await {
-            Ok(content) => {
-                if content.contains("claudeAiOauth") && content.contains("accessToken") {
-                    AuthStatus {
-                        authenticated: true,
-                        method: Some("oauth".to_string()),
-                        email: None,
-                        error: None,
-                    }
-                } else {
-                    AuthStatus {
-                        authenticated: false,
-                        method: None,
-                        email: None,
-                        error: Some("Credentials file exists but missing OAuth data".to_string()),
-                    }
-                }
-            }
-            Err(e) => AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some(format!("Could not read credentials: {}", e)),
-            },
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_codex_auth() -> AuthStatus {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find home directory".to_string()),
-            }
-        }
-    };
-
-    let auth_path = home.join(".codex").join("auth.json");
-
-    if auth_path.exists() {
-        match tokio::fs::read_to_string(&auth_path).await {
-            Ok(content) => {
-                if content.contains("access_token") {
-                    AuthStatus {
-                        authenticated: true,
-                        method: Some("oauth".to_string()),
-                        email: None,
-                        error: None,
-                    }
-                } else {
-                    AuthStatus {
-                        authenticated: false,
-                        method: None,
-                        email: None,
-                        error: Some("Auth file exists but missing access token".to_string()),
-                    }
-                }
-            }
-            Err(e) => AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some(format!("Could not read auth file: {}", e)),
-            },
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_cursor_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let session_path = data_dir.join("IncuBar").join("cursor-session.json");
-
-    if session_path.exists() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("cookies".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_factory_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let session_path = data_dir.join("IncuBar").join("factory-session.json");
-
-    if session_path.exists() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("cookies".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_augment_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let session_path = data_dir.join("IncuBar").join("augment-session.json");
-
-    if session_path.exists() {
-        AuthStatus {
+impl AuthStatus {
+    pub fn authenticated(method: &str) -> Self {
+        Self {
             authenticated: true,
-            method: Some("cookies".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
+            method: Some(method.to_string()),
             email: None,
             error: None,
         }
     }
-}
-
-async fn check_kimi_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let session_path = data_dir.join("IncuBar").join("kimi-session.json");
 
-    if session_path.exists() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("cookies".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
+    pub fn unauthenticated(error: Option<String>) -> Self {
+        Self {
             authenticated: false,
             method: None,
             email: None,
-            error: None,
+            error,
         }
     }
-}
 
-async fn check_minimax_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let session_path = data_dir.join("IncuBar").join("minimax-session.json");
-
-    if session_path.exists() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("cookies".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
+    /// Cookie-based providers count as connected once a session file has been stored
+    pub fn from_session_file(session_path: Result<PathBuf, anyhow::Error>) -> Self {
+        match session_path {
+            Ok(path) if path.exists() => Self::authenticated("cookies"),
+            Ok(_) => Self::unauthenticated(None),
+            Err(_) => Self::unauthenticated(Some("Could not find data directory".to_string())),
         }
     }
 }
 
-async fn check_copilot_auth() -> AuthStatus {
-    let data_dir = match dirs::data_dir() {
-        Some(d) => d,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find data directory".to_string()),
-            }
-        }
-    };
-
-    let token_path = data_dir.join("IncuBar").join("copilot-token.json");
-
-    if token_path.exists() {
-        match tokio::fs::read_to_string(&token_path).await {
-            Ok(content) => {
-                if content.contains("access_token") {
-                    AuthStatus {
-                        authenticated: true,
-                        method: Some("device_flow".to_string()),
-                        email: None,
-                        error: None,
-                    }
-                } else {
-                    AuthStatus {
-                        authenticated: false,
-                        method: None,
-                        email: None,
-                        error: Some("Token file exists but missing access token".to_string()),
-                    }
-                }
-            }
-            Err(e) => AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some(format!("Could not read token file: {}", e)),
-            },
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_gemini_auth() -> AuthStatus {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("Could not find home directory".to_string()),
-            }
-        }
-    };
-
-    let creds_path = home.join(".gemini").join("oauth_creds.json");
-
-    if creds_path.exists() {
-        match tokio::fs::read_to_string(&creds_path).await {
-            Ok(content) => {
-                if content.contains("access_token") || content.contains("refresh_token") {
-                    AuthStatus {
-                        authenticated: true,
-                        method: Some("oauth".to_string()),
-                        email: None,
-                        error: None,
-                    }
-                } else {
-                    AuthStatus {
-                        authenticated: false,
-                        method: None,
-                        email: None,
-                        error: Some("Credentials file exists but missing tokens".to_string()),
-                    }
-                }
-            }
-            Err(e) => AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some(format!("Could not read credentials: {}", e)),
-            },
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: None,
-        }
-    }
-}
-
-async fn check_zai_auth() -> AuthStatus {
-    // z.ai uses Z_AI_API_KEY environment variable
-    if std::env::var("Z_AI_API_KEY").is_ok() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("api_key".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: Some("Set Z_AI_API_KEY environment variable".to_string()),
-        }
-    }
-}
-
-async fn check_kimi_k2_auth() -> AuthStatus {
-    // Kimi K2 uses KIMI_K2_API_KEY, KIMI_API_KEY, or KIMI_KEY environment variable
-    if std::env::var("KIMI_K2_API_KEY").is_ok()
-        || std::env::var("KIMI_API_KEY").is_ok()
-        || std::env::var("KIMI_KEY").is_ok()
-    {
-        AuthStatus {
-            authenticated: true,
-            method: Some("api_key".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: Some("Set KIMI_K2_API_KEY environment variable".to_string()),
-        }
-    }
-}
-
-async fn check_synthetic_auth() -> AuthStatus {
-    // Synthetic uses SYNTHETIC_API_KEY environment variable
-    if std::env::var("SYNTHETIC_API_KEY").is_ok() {
-        AuthStatus {
-            authenticated: true,
-            method: Some("api_key".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: Some("Set SYNTHETIC_API_KEY environment variable".to_string()),
-        }
-    }
-}
-
-async fn check_antigravity_auth() -> AuthStatus {
-    let output = tokio::process::Command::new("/bin/ps")
-        .args(["-ax", "-o", "command="])
-        .output()
-        .await;
-
-    let stdout = match output {
-        Ok(result) => String::from_utf8_lossy(&result.stdout).to_string(),
-        Err(_) => String::new(),
-    };
-
-    if stdout
-        .lines()
-        .any(|line| line.to_lowercase().contains("language_server_macos"))
-    {
-        AuthStatus {
-            authenticated: true,
-            method: Some("local_probe".to_string()),
-            email: None,
-            error: None,
-        }
-    } else {
-        AuthStatus {
-            authenticated: false,
-            method: None,
-            email: None,
-            error: Some("Antigravity not running".to_string()),
-        }
-    }
-}
-
-async fn check_kiro_auth() -> AuthStatus {
-    let cli_path = match find_kiro_cli().await {
-        Some(path) => path,
-        None => {
-            return AuthStatus {
-                authenticated: false,
-                method: None,
-                email: None,
-                error: Some("kiro-cli not found. Install it from https://kiro.dev".to_string()),
-            };
-        }
-    };
-
-    let command = tokio::process::Command::new(cli_path)
-        .arg("whoami")
-        .output();
-    let output = match tokio::time::timeout(std::time::Duration::from_secs(5), command).await {
-        Ok(result) => match result {
-            Ok(result) => result,
-            Err(err) => {
-                return AuthStatus {
-                    authenticated: false,
-                    method: Some("cli".to_string()),
-                    email: None,
-                    error: Some(format!("Failed to run kiro-cli: {}", err)),
-                };
-            }
-        },
-        Err(_) => {
-            return AuthStatus {
-                authenticated: false,
-                method: Some("cli".to_string()),
-                email: None,
-                error: Some("kiro-cli timed out".to_string()),
-            };
-        }
-    };
-
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-    let combined = if stderr.is_empty() { stdout } else { stderr };
-    let lowered = combined.to_lowercase();
-
-    if lowered.contains("not logged in") || lowered.contains("login required") {
-        return AuthStatus {
-            authenticated: false,
-            method: Some("cli".to_string()),
-            email: None,
-            error: Some("Run `kiro-cli login` in Terminal".to_string()),
-        };
-    }
-
-    if !output.status.success() {
-        return AuthStatus {
-            authenticated: false,
-            method: Some("cli".to_string()),
-            email: None,
-            error: Some(if combined.is_empty() {
-                format!("kiro-cli failed with status {}", output.status)
-            } else {
-                combined
-            }),
-        };
-    }
-
-    let email = if combined.contains('@') {
-        Some(combined)
-    } else {
-        None
-    };
-
-    AuthStatus {
-        authenticated: true,
-        method: Some("cli".to_string()),
-        email,
-        error: None,
-    }
-}
-
-async fn find_kiro_cli() -> Option<String> {
-    let output = tokio::process::Command::new("/usr/bin/which")
-        .arg("kiro-cli")
-        .output()
-        .await
-        .ok()?;
-    if output.status.success() {
-        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !path.is_empty() {
-            return Some(path);
-        }
-    }
-
-    let candidates = [
-        "/usr/local/bin/kiro-cli",
-        "/opt/homebrew/bin/kiro-cli",
-        &format!(
-            "{}/.local/bin/kiro-cli",
-            std::env::var("HOME").unwrap_or_default()
-        ),
-    ];
-    candidates
-        .iter()
-        .map(|path| path.to_string())
-        .find(|path| std::path::Path::new(path).exists())
-}
-
 /// Find a binary in PATH or common locations
 async fn find_binary(name: &str) -> Result<String, anyhow::Error> {
     // Try which first
@@ -892,6 +334,26 @@ pub async fn store_cursor_session(cookie_header: String) -> Result<(), anyhow::E
     Ok(())
 }
 
+/// Store session cookies at an explicit path (used for additional accounts)
+pub async fn store_session_file(
+    session_path: &std::path::Path,
+    cookie_header: String,
+) -> Result<(), anyhow::Error> {
+    if let Some(parent) = session_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let content = serde_json::json!({
+        "cookieHeader": cookie_header,
+        "savedAt": chrono::Utc::now().to_rfc3339(),
+    });
+
+    tokio::fs::write(session_path, serde_json::to_string_pretty(&content)?).await?;
+
+    tracing::info!("Saved session to {:?}", session_path);
+    Ok(())
+}
+
 /// Store Factory session cookies
 pub async fn store_factory_session(cookie_header: String) -> Result<(), anyhow::Error> {
     let data_dir =
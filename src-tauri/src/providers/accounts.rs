@@ -0,0 +1,230 @@
+//! Provider accounts
+//!
+//! Every provider has an implicit `default` account that reads the provider's usual
+//! credential location. Additional accounts are declared in `accounts.json` next to the
+//! stored sessions and point at their own credential file (or directory, for Codex).
+
+use super::{ProviderId, StatusSource};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Identifier of the implicit account every provider starts with
+pub const DEFAULT_ACCOUNT_ID: &str = "default";
+
+const ACCOUNTS_FILE_NAME: &str = "accounts.json";
+
+/// Addresses a single account of a provider
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountKey {
+    pub provider_id: ProviderId,
+    pub account_id: String,
+}
+
+impl AccountKey {
+    pub fn new(provider_id: ProviderId, account_id: impl Into<String>) -> Self {
+        Self {
+            provider_id,
+            account_id: account_id.into(),
+        }
+    }
+
+    pub fn default_for(provider_id: ProviderId) -> Self {
+        Self::new(provider_id, DEFAULT_ACCOUNT_ID)
+    }
+
+    /// Resolve an optional account id coming from the frontend or CLI
+    pub fn from_optional(provider_id: ProviderId, account_id: Option<String>) -> Self {
+        match account_id {
+            Some(account_id) if !account_id.trim().is_empty() => {
+                Self::new(provider_id, account_id.trim())
+            }
+            _ => Self::default_for(provider_id),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.account_id == DEFAULT_ACCOUNT_ID
+    }
+
+    /// The key this account's status is kept under. Status is provider-wide and kept under
+    /// the default account, except where each account's fetcher reports its own, as plugins do.
+    pub fn status_key(&self) -> AccountKey {
+        if self.provider_id.descriptor().status_source == StatusSource::Fetcher {
+            self.clone()
+        } else {
+            Self::default_for(self.provider_id)
+        }
+    }
+}
+
+/// The provider slug for a default account, `slug:account` otherwise. Used where
+/// accounts are keyed by a single string, such as the local API.
+impl fmt::Display for AccountKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_default() {
+            f.write_str(self.provider_id.slug())
+        } else {
+            write!(f, "{}:{}", self.provider_id.slug(), self.account_id)
+        }
+    }
+}
+
+/// An additional account declared by the user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderAccount {
+    pub provider_id: ProviderId,
+    pub account_id: String,
+    pub label: String,
+    /// Credential file for this account. Cookie-based providers fall back to a
+    /// per-account session file when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_path: Option<PathBuf>,
+}
+
+impl ProviderAccount {
+    pub fn key(&self) -> AccountKey {
+        AccountKey::new(self.provider_id, self.account_id.clone())
+    }
+
+    /// Check the account can be registered and resolve where its credentials live
+    pub fn resolve_credentials_path(&self) -> Result<PathBuf, anyhow::Error> {
+        let account_id = self.account_id.trim();
+        if account_id.is_empty() {
+            return Err(anyhow::anyhow!("Account id must not be empty"));
+        }
+        if account_id == DEFAULT_ACCOUNT_ID {
+            return Err(anyhow::anyhow!(
+                "The {} account id is reserved",
+                DEFAULT_ACCOUNT_ID
+            ));
+        }
+        if !account_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(anyhow::anyhow!(
+                "Account id may only contain letters, digits, '-' and '_'"
+            ));
+        }
+
+        let descriptor = self.provider_id.descriptor();
+        if descriptor.create_account_fetcher.is_none() {
+            return Err(anyhow::anyhow!(
+                "{} does not support multiple accounts",
+                descriptor.display_name
+            ));
+        }
+
+        if let Some(path) = &self.credentials_path {
+            return Ok(path.clone());
+        }
+        if descriptor.uses_cookies_only() {
+            let data_dir = dirs::data_dir()
+                .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+            return Ok(data_dir
+                .join("IncuBar")
+                .join(format!("{}-{}-session.json", descriptor.slug, account_id)));
+        }
+        Err(anyhow::anyhow!(
+            "A credentials path is required for {} accounts",
+            descriptor.display_name
+        ))
+    }
+}
+
+/// Load the additional accounts declared in `data_dir`. A missing file means no extra
+/// accounts.
+pub fn load_accounts_in(data_dir: &Path) -> Result<Vec<ProviderAccount>, anyhow::Error> {
+    let path = data_dir.join(ACCOUNTS_FILE_NAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub fn save_accounts_in(
+    data_dir: &Path,
+    accounts: &[ProviderAccount],
+) -> Result<(), anyhow::Error> {
+    let path = data_dir.join(ACCOUNTS_FILE_NAME);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(accounts)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(provider_id: ProviderId, account_id: &str) -> ProviderAccount {
+        ProviderAccount {
+            provider_id,
+            account_id: account_id.to_string(),
+            label: "Work".to_string(),
+            credentials_path: None,
+        }
+    }
+
+    #[test]
+    fn from_optional_falls_back_to_default_account() {
+        assert!(AccountKey::from_optional(ProviderId::Claude, None).is_default());
+        assert!(AccountKey::from_optional(ProviderId::Claude, Some("  ".to_string())).is_default());
+        assert_eq!(
+            AccountKey::from_optional(ProviderId::Claude, Some("work".to_string())).account_id,
+            "work"
+        );
+    }
+
+    #[test]
+    fn displays_default_accounts_as_the_provider_slug() {
+        assert_eq!(AccountKey::default_for(ProviderId::Claude).to_string(), "claude");
+        assert_eq!(
+            AccountKey::new(ProviderId::Custom, "gateway").to_string(),
+            "custom:gateway"
+        );
+    }
+
+    #[test]
+    fn cookie_accounts_get_their_own_session_file() {
+        let path = account(ProviderId::Cursor, "work")
+            .resolve_credentials_path()
+            .expect("cursor account");
+        assert!(path.ends_with("IncuBar/cursor-work-session.json"));
+    }
+
+    #[test]
+    fn file_based_accounts_require_a_credentials_path() {
+        assert!(account(ProviderId::Claude, "work")
+            .resolve_credentials_path()
+            .is_err());
+        assert!(account(ProviderId::Codex, "work")
+            .resolve_credentials_path()
+            .is_err());
+
+        let mut with_path = account(ProviderId::Claude, "work");
+        with_path.credentials_path = Some(PathBuf::from("/tmp/work/.credentials.json"));
+        assert_eq!(
+            with_path.resolve_credentials_path().unwrap(),
+            PathBuf::from("/tmp/work/.credentials.json")
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_and_unsupported_accounts() {
+        assert!(account(ProviderId::Cursor, DEFAULT_ACCOUNT_ID)
+            .resolve_credentials_path()
+            .is_err());
+        assert!(account(ProviderId::Cursor, "../work")
+            .resolve_credentials_path()
+            .is_err());
+        assert!(account(ProviderId::Zai, "work")
+            .resolve_credentials_path()
+            .is_err());
+    }
+}
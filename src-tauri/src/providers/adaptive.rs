@@ -0,0 +1,209 @@
+//! Adaptive polling
+//!
+//! The background loop refreshes each account on its configured interval, adjusted by
+//! what the last snapshot showed: faster while a window is nearly used up or about to
+//! reset, slower while nothing changes, and right after a known reset so the fresh
+//! window shows up without waiting a full interval.
+
+use super::{RateWindow, UsageSnapshot};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Windows at or above this percentage are polled faster
+const HIGH_USAGE_PERCENT: f64 = 80.0;
+/// Windows resetting within this long are polled faster
+const RESET_SOON: Duration = Duration::from_secs(15 * 60);
+/// Divisor applied to the interval while polling faster
+const FAST_DIVISOR: u32 = 4;
+/// Identical snapshots in a row before polling slows down
+const UNCHANGED_BEFORE_SLOWDOWN: u32 = 3;
+/// Slowed-down delay as a fraction of the interval. Stays below the 2x interval after
+/// which the frontend flags usage as stale (`STALE_USAGE_MULTIPLIER`).
+const SLOWDOWN_NUMERATOR: u32 = 3;
+const SLOWDOWN_DENOMINATOR: u32 = 2;
+/// Polling never gets faster than this, unless the configured interval already is
+const MIN_DELAY: Duration = Duration::from_secs(30);
+/// Wait after a reset before fetching, so the provider has rolled the window over
+const RESET_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AdaptivePolling {
+    last_fingerprint: Option<String>,
+    unchanged_streak: u32,
+}
+
+impl AdaptivePolling {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay until the next fetch after `usage` was fetched at `now`
+    pub(crate) fn next_delay(
+        &mut self,
+        interval: Duration,
+        usage: &UsageSnapshot,
+        now: DateTime<Utc>,
+    ) -> Duration {
+        self.observe(usage);
+        if usage.error.is_some() {
+            return interval;
+        }
+
+        let windows = usage.rate_windows();
+        let resets: Vec<DateTime<Utc>> = windows
+            .iter()
+            .filter_map(|window| reset_time(window))
+            .filter(|reset| *reset > now)
+            .collect();
+
+        let is_hot = windows
+            .iter()
+            .any(|window| window.used_percent >= HIGH_USAGE_PERCENT)
+            || resets
+                .iter()
+                .any(|reset| until(now, *reset) <= RESET_SOON);
+
+        let delay = if is_hot {
+            (interval / FAST_DIVISOR).max(MIN_DELAY).min(interval)
+        } else if self.unchanged_streak >= UNCHANGED_BEFORE_SLOWDOWN {
+            interval * SLOWDOWN_NUMERATOR / SLOWDOWN_DENOMINATOR
+        } else {
+            interval
+        };
+
+        // Fetch right after the earliest reset instead of sleeping past it
+        match resets.iter().min() {
+            Some(reset) => delay.min(until(now, *reset) + RESET_GRACE),
+            None => delay,
+        }
+    }
+
+    fn observe(&mut self, usage: &UsageSnapshot) {
+        let fingerprint = fingerprint(usage);
+        if self.last_fingerprint.as_ref() == Some(&fingerprint) {
+            self.unchanged_streak = self.unchanged_streak.saturating_add(1);
+        } else {
+            self.unchanged_streak = 0;
+            self.last_fingerprint = Some(fingerprint);
+        }
+    }
+}
+
+fn reset_time(window: &RateWindow) -> Option<DateTime<Utc>> {
+    let resets_at = window.resets_at.as_deref()?;
+    DateTime::parse_from_rfc3339(resets_at)
+        .ok()
+        .map(|reset| reset.with_timezone(&Utc))
+}
+
+fn until(now: DateTime<Utc>, later: DateTime<Utc>) -> Duration {
+    (later - now).to_std().unwrap_or_default()
+}
+
+/// What counts as a change in usage; timestamps like `updated_at` are left out
+fn fingerprint(usage: &UsageSnapshot) -> String {
+    let windows: Vec<String> = usage
+        .rate_windows()
+        .into_iter()
+        .map(|window| {
+            format!(
+                "{:.2}@{}",
+                window.used_percent,
+                window.resets_at.as_deref().unwrap_or_default()
+            )
+        })
+        .collect();
+    let credits = usage
+        .credits
+        .as_ref()
+        .map(|credits| format!("{:.4}", credits.remaining))
+        .unwrap_or_default();
+    format!("{}|{}|{}", windows.join(","), credits, usage.error.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(300);
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn usage(used_percent: f64, resets_at: Option<DateTime<Utc>>) -> UsageSnapshot {
+        let mut usage = UsageSnapshot::error(String::new());
+        usage.error = None;
+        usage.primary = Some(RateWindow {
+            id: None,
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(300),
+            resets_at: resets_at.map(|reset| reset.to_rfc3339()),
+            reset_description: None,
+            label: None,
+        });
+        usage
+    }
+
+    #[test]
+    fn polls_faster_near_the_limit_or_a_reset() {
+        let mut polling = AdaptivePolling::new();
+        assert_eq!(polling.next_delay(INTERVAL, &usage(40.0, None), now()), INTERVAL);
+        assert_eq!(
+            polling.next_delay(INTERVAL, &usage(85.0, None), now()),
+            Duration::from_secs(75)
+        );
+
+        let reset = now() + chrono::Duration::minutes(10);
+        assert_eq!(
+            polling.next_delay(INTERVAL, &usage(10.0, Some(reset)), now()),
+            Duration::from_secs(75)
+        );
+        assert_eq!(
+            polling.next_delay(Duration::from_secs(60), &usage(90.0, None), now()),
+            MIN_DELAY
+        );
+        assert_eq!(
+            polling.next_delay(Duration::from_secs(10), &usage(90.0, None), now()),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn polls_slower_while_usage_is_unchanged() {
+        let mut polling = AdaptivePolling::new();
+        let snapshot = usage(20.0, None);
+        let delays: Vec<Duration> = (0..8)
+            .map(|_| polling.next_delay(INTERVAL, &snapshot, now()))
+            .collect();
+
+        assert_eq!(delays[2], INTERVAL);
+        assert_eq!(delays[3], Duration::from_secs(450));
+        assert_eq!(delays[7], Duration::from_secs(450));
+
+        assert_eq!(polling.next_delay(INTERVAL, &usage(21.0, None), now()), INTERVAL);
+    }
+
+    #[test]
+    fn fetches_right_after_a_known_reset() {
+        let mut polling = AdaptivePolling::new();
+        let interval = Duration::from_secs(1_800);
+        let reset = now() + chrono::Duration::minutes(20);
+        assert_eq!(
+            polling.next_delay(interval, &usage(50.0, Some(reset)), now()),
+            Duration::from_secs(1_205)
+        );
+
+        let past_reset = now() - chrono::Duration::seconds(100);
+        assert_eq!(
+            polling.next_delay(interval, &usage(50.0, Some(past_reset)), now()),
+            interval
+        );
+    }
+}
@@ -4,13 +4,21 @@
 //! Uses cookie-based authentication via browser cookie import.
 //! Endpoint: https://ampcode.com/settings
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 
 const SETTINGS_URL: &str = "https://ampcode.com/settings";
 
 pub struct AmpProvider {
     client: reqwest::Client,
+    /// Account-specific Amp session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl AmpProvider {
@@ -20,13 +28,24 @@ impl AmpProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Amp session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(&self, cookie_header: &str) -> Result<UsageSnapshot, AmpError> {
         let response = self
             .client
-            .get(SETTINGS_URL)
+            .get(endpoint(ProviderId::Amp, SETTINGS_URL))
             .header("Cookie", cookie_header)
             .header(
                 "Accept",
@@ -36,7 +55,7 @@ impl AmpProvider {
             .header("Origin", "https://ampcode.com")
             .header("Referer", SETTINGS_URL)
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Amp)
             .await
             .map_err(|err| AmpError::Api(err.to_string()))?;
 
@@ -90,7 +109,12 @@ impl AmpProvider {
 
         UsageSnapshot {
             primary: Some(RateWindow {
+                id: Some("free".to_string()),
+                category: None,
                 used_percent: used_percent.clamp(0.0, 100.0),
+                used: Some(used),
+                limit: Some(quota),
+                unit: Some("credits".to_string()),
                 window_minutes,
                 resets_at,
                 reset_description: None,
@@ -108,6 +132,10 @@ impl AmpProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -282,6 +310,10 @@ impl AmpProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("amp-session.json"))
@@ -320,7 +352,7 @@ impl ProviderFetcher for AmpProvider {
         "Amp"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Amp usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -341,13 +373,15 @@ impl ProviderFetcher for AmpProvider {
                 if let Err(err) = self.store_session(&session_cookie).await {
                     tracing::debug!("Failed to store Amp session: {}", err);
                 }
-                self.fetch_with_cookies(&session_cookie)
-                    .await
-                    .map_err(|err| anyhow::anyhow!(err.to_string()))
+                Ok(self.fetch_with_cookies(&session_cookie).await?)
             }
-            Err(err) => Err(anyhow::anyhow!("Not authenticated: {}", err)),
+            Err(err) => Err(ProviderError::not_authenticated(err)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -378,9 +412,44 @@ enum AmpError {
     NoSessionCookie,
 }
 
+impl From<AmpError> for ProviderError {
+    fn from(err: AmpError) -> Self {
+        let message = err.to_string();
+        match err {
+            AmpError::SessionExpired => ProviderError::TokenExpired(message),
+            AmpError::NotLoggedIn | AmpError::NoSessionCookie => {
+                ProviderError::NotAuthenticated(message)
+            }
+            AmpError::Parse(_) => ProviderError::Parse(message),
+            AmpError::Api(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    #[tokio::test]
+    async fn replays_cookie_usage() {
+        let server = ReplayServer::from_fixture("amp-cookies")
+            .await
+            .route(ProviderId::Amp);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("amp-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "session=abc123"}"#).unwrap();
+
+        let usage = AmpProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert!((primary.used_percent - 25.0).abs() < 0.01);
+        assert_eq!(primary.window_minutes, Some(1440));
+        assert_eq!(server.received()[0].headers["cookie"], "session=abc123");
+    }
 
     #[test]
     fn parses_usage_from_html() {
@@ -3,8 +3,10 @@
 //! Uses local Antigravity language server status probe to read usage data.
 
 use super::{
-    ProviderFetcher, ProviderIdentity, ProviderStatus, RateWindow, StatusIndicator, UsageSnapshot,
+    ProviderError, ProviderFetcher, ProviderIdentity, ProviderStatus, RateWindow, StatusIndicator,
+    UsageSnapshot, WindowCategory, USAGE_SNAPSHOT_VERSION,
 };
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use regex::Regex;
 use std::time::Instant;
@@ -72,6 +74,7 @@ impl AntigravityProvider {
         let primary = ordered.get(0).map(to_rate_window);
         let secondary = ordered.get(1).map(to_rate_window);
         let tertiary = ordered.get(2).map(to_rate_window);
+        let windows = model_windows(&ordered, &snapshot.model_quotas);
 
         if primary.is_none() {
             return Err(AntigravityError::Parse(
@@ -102,6 +105,10 @@ impl AntigravityProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows,
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         })
     }
 
@@ -416,31 +423,31 @@ impl ProviderFetcher for AntigravityProvider {
         "Antigravity status probe"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Antigravity usage");
         let snapshot = self.fetch_snapshot().await?;
         Ok(self.build_usage_snapshot(snapshot)?)
     }
 
-    async fn fetch_status(&self) -> Result<ProviderStatus, anyhow::Error> {
-        let response = self
-            .client
-            .get("https://www.google.com/appsstatus/json/en")
-            .header("Accept", "application/json")
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
-        let status = response.status();
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
-        if !status.is_success() {
-            return Err(anyhow::anyhow!(format!("HTTP {}", status)));
+    async fn auth_status(&self) -> AuthStatus {
+        let output = tokio::process::Command::new("/bin/ps")
+            .args(["-ax", "-o", "command="])
+            .output()
+            .await;
+
+        let stdout = match output {
+            Ok(result) => String::from_utf8_lossy(&result.stdout).to_string(),
+            Err(_) => String::new(),
+        };
+
+        if stdout
+            .lines()
+            .any(|line| line.to_lowercase().contains(PROCESS_NAME))
+        {
+            AuthStatus::authenticated("local_probe")
+        } else {
+            AuthStatus::unauthenticated(Some("Antigravity not running".to_string()))
         }
-        parse_google_workspace_status(&bytes, "npdyhgECDJ6tB66MxXyo")
-            .map_err(|err| anyhow::anyhow!(err.to_string()))
     }
 }
 
@@ -474,14 +481,13 @@ struct AntigravityStatusSnapshot {
 #[derive(Debug, Clone)]
 struct AntigravityModelQuota {
     label: String,
-    #[allow(dead_code)]
     model_id: String,
     remaining_fraction: Option<f64>,
     reset_time: Option<String>,
 }
 
 #[derive(thiserror::Error, Debug)]
-enum AntigravityError {
+pub(super) enum AntigravityError {
     #[error("Antigravity language server not detected. Launch Antigravity and retry.")]
     NotRunning,
     #[error("Antigravity CSRF token not found. Restart Antigravity and retry.")]
@@ -496,6 +502,20 @@ enum AntigravityError {
     Process(String),
 }
 
+impl From<AntigravityError> for ProviderError {
+    fn from(err: AntigravityError) -> Self {
+        let message = err.to_string();
+        match err {
+            AntigravityError::NotRunning
+            | AntigravityError::MissingCsrfToken
+            | AntigravityError::PortDetection(_)
+            | AntigravityError::Process(_) => ProviderError::Unavailable(message),
+            AntigravityError::Parse(_) => ProviderError::Parse(message),
+            AntigravityError::Api(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 fn default_request_body() -> serde_json::Value {
     serde_json::json!({
         "metadata": {
@@ -555,13 +575,29 @@ fn select_models(models: &[AntigravityModelQuota]) -> Vec<AntigravityModelQuota>
     ordered
 }
 
+/// One window per model: the selected ones first, then every other model in API order
+fn model_windows(
+    selected: &[AntigravityModelQuota],
+    models: &[AntigravityModelQuota],
+) -> Vec<RateWindow> {
+    let others = models
+        .iter()
+        .filter(|model| !selected.iter().any(|picked| picked.label == model.label));
+    selected.iter().chain(others).map(to_rate_window).collect()
+}
+
 fn remaining_percent(quota: &AntigravityModelQuota) -> f64 {
     quota.remaining_fraction.unwrap_or(0.0).clamp(0.0, 1.0) * 100.0
 }
 
 fn to_rate_window(quota: &AntigravityModelQuota) -> RateWindow {
     RateWindow {
+        id: Some(quota.model_id.clone()),
+        category: Some(WindowCategory::Model),
         used_percent: (100.0 - remaining_percent(quota)).clamp(0.0, 100.0),
+        used: None,
+        limit: None,
+        unit: None,
         window_minutes: None,
         resets_at: quota.reset_time.clone(),
         reset_description: None,
@@ -622,7 +658,7 @@ fn quota_from_config(config: ModelConfig) -> Option<AntigravityModelQuota> {
     })
 }
 
-fn parse_google_workspace_status(
+pub(super) fn parse_google_workspace_status(
     data: &[u8],
     product_id: &str,
 ) -> Result<ProviderStatus, AntigravityError> {
@@ -680,6 +716,7 @@ fn parse_google_workspace_status(
         indicator,
         description,
         updated_at,
+        url: None,
     })
 }
 
@@ -1032,6 +1069,11 @@ mod tests {
         let ordered = select_models(&models);
         assert_eq!(ordered.len(), 1);
         assert_eq!(ordered[0].label, "Claude Opus");
+
+        let windows = model_windows(&ordered, &models);
+        let ids: Vec<_> = windows.iter().map(|window| window.id.as_deref()).collect();
+        assert_eq!(ids, [Some("claude-opus"), Some("other")]);
+        assert_eq!(windows[1].used_percent, 80.0);
     }
 
     #[test]
@@ -5,7 +5,13 @@
 //! - /api/credits - usage credits
 //! - /api/subscription - subscription details
 
-use super::{Credits, ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::{self, endpoint};
+use super::fixtures::RecordingSend;
+use super::{
+    Credits, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow,
+    UsageSnapshot, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 
@@ -21,6 +27,8 @@ const SESSION_ENDPOINTS: &[&str] = &[
 
 pub struct AugmentProvider {
     client: reqwest::Client,
+    /// Account-specific Augment session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl AugmentProvider {
@@ -30,7 +38,18 @@ impl AugmentProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Augment session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(&self, cookie_header: &str) -> Result<UsageSnapshot, AugmentError> {
@@ -61,11 +80,11 @@ impl AugmentProvider {
     ) -> Result<AugmentCreditsResponse, AugmentError> {
         let response = self
             .client
-            .get(CREDITS_URL)
+            .get(endpoint(ProviderId::Augment, CREDITS_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Augment)
             .await
             .map_err(|e| AugmentError::Api(e.to_string()))?;
 
@@ -83,11 +102,11 @@ impl AugmentProvider {
     ) -> Result<AugmentSubscriptionResponse, AugmentError> {
         let response = self
             .client
-            .get(SUBSCRIPTION_URL)
+            .get(endpoint(ProviderId::Augment, SUBSCRIPTION_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Augment)
             .await
             .map_err(|e| AugmentError::Api(e.to_string()))?;
 
@@ -137,7 +156,12 @@ impl AugmentProvider {
             .and_then(|date| self.normalize_reset_time(date));
 
         let primary = Some(RateWindow {
+            id: Some("credits".to_string()),
+            category: None,
             used_percent,
+            used: Some(consumed),
+            limit: total,
+            unit: Some("credits".to_string()),
             window_minutes: None,
             resets_at: resets_at.clone(),
             reset_description: resets_at
@@ -166,6 +190,10 @@ impl AugmentProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -204,13 +232,13 @@ impl AugmentProvider {
         for endpoint in SESSION_ENDPOINTS {
             let response = self
                 .client
-                .get(*endpoint)
+                .get(endpoints::endpoint(ProviderId::Augment, endpoint))
                 .header("Cookie", cookie_header)
                 .header("Accept", "application/json")
                 .header("User-Agent", "IncuBar/1.0")
                 .header("Origin", BASE_URL)
                 .header("Referer", BASE_URL)
-                .send()
+                .send_recorded(ProviderId::Augment)
                 .await
                 .map_err(|e| AugmentError::Api(e.to_string()))?;
 
@@ -262,6 +290,10 @@ impl AugmentProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("augment-session.json"))
@@ -278,7 +310,7 @@ impl ProviderFetcher for AugmentProvider {
         "Augment AI"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Augment usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -301,13 +333,15 @@ impl ProviderFetcher for AugmentProvider {
                 if let Err(err) = self.store_session(&result.cookie_header).await {
                     tracing::debug!("Failed to store Augment session: {}", err);
                 }
-                self.fetch_with_cookies(&result.cookie_header)
-                    .await
-                    .map_err(|err| anyhow::anyhow!(err.to_string()))
+                Ok(self.fetch_with_cookies(&result.cookie_header).await?)
             }
-            Err(err) => Err(anyhow::anyhow!("Not authenticated: {}", err)),
+            Err(err) => Err(ProviderError::not_authenticated(err)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -347,9 +381,48 @@ enum AugmentError {
     Parse(String),
 }
 
+impl From<AugmentError> for ProviderError {
+    fn from(err: AugmentError) -> Self {
+        let message = err.to_string();
+        match err {
+            AugmentError::SessionExpired => ProviderError::TokenExpired(message),
+            AugmentError::NotLoggedIn => ProviderError::NotAuthenticated(message),
+            AugmentError::Parse(_) => ProviderError::Parse(message),
+            AugmentError::Api(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    #[tokio::test]
+    async fn replays_cookie_usage() {
+        let server = ReplayServer::from_fixture("augment-cookies")
+            .await
+            .route(ProviderId::Augment);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("augment-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "_session=abc"}"#).unwrap();
+
+        let usage = AugmentProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert!((primary.used_percent - 25.0).abs() < 0.01);
+        assert_eq!(primary.limit, Some(4000.0));
+        assert_eq!(usage.credits.expect("credits").remaining, 3000.0);
+        assert_eq!(
+            usage.identity.expect("identity").plan.as_deref(),
+            Some("Developer")
+        );
+        assert_eq!(server.received().len(), 3);
+        assert!(server.pending().is_empty());
+    }
 
     #[test]
     fn builds_snapshot_with_credits_and_subscription() {
@@ -5,7 +5,13 @@
 //! 2. Web API (claude.ai) - requires session cookie from browser
 //! 3. CLI fallback (not yet implemented)
 
-use super::{cost_usage, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    cost_usage, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow,
+    UsageSnapshot, WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::PathBuf;
@@ -19,6 +25,8 @@ const WEB_BASE_URL: &str = "https://claude.ai/api";
 
 pub struct ClaudeProvider {
     client: reqwest::Client,
+    /// Account-specific Claude credentials file; `None` uses the default location
+    credentials_path: Option<PathBuf>,
 }
 
 impl ClaudeProvider {
@@ -28,7 +36,18 @@ impl ClaudeProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            credentials_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Claude credentials file lives at `path`
+    pub fn with_credentials_path(path: PathBuf) -> Self {
+        Self {
+            credentials_path: Some(path),
+            ..Self::new()
+        }
     }
 
     /// Try to fetch via OAuth API first
@@ -37,20 +56,22 @@ impl ClaudeProvider {
 
         let response = self
             .client
-            .get(format!("{}{}", OAUTH_BASE_URL, OAUTH_USAGE_PATH))
+            .get(endpoint(
+                ProviderId::Claude,
+                &format!("{}{}", OAUTH_BASE_URL, OAUTH_USAGE_PATH),
+            ))
             .header("Authorization", format!("Bearer {}", creds.access_token))
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
             .header("anthropic-beta", OAUTH_BETA_HEADER)
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Claude)
             .await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "OAuth API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("OAuth API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let usage_response: OAuthUsageResponse = response.json().await?;
@@ -85,6 +106,10 @@ impl ClaudeProvider {
 
     /// Get the path to Claude's credentials file
     fn get_credentials_path(&self) -> Result<PathBuf, anyhow::Error> {
+        if let Some(path) = &self.credentials_path {
+            return Ok(path.clone());
+        }
+
         let home = dirs::home_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
         Ok(home.join(".claude").join(".credentials.json"))
@@ -96,32 +121,45 @@ impl ClaudeProvider {
         response: OAuthUsageResponse,
         tier: Option<String>,
     ) -> UsageSnapshot {
-        let primary = response.five_hour.map(|w| RateWindow {
+        let window = |w: &OAuthUsageWindow, id: &str, category, minutes, label: &str| RateWindow {
+            id: Some(id.to_string()),
+            category: Some(category),
             used_percent: w.utilization.unwrap_or(0.0),
-            window_minutes: Some(300), // 5 hours
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(minutes),
             resets_at: w.resets_at.clone(),
             reset_description: w.resets_at.as_ref().and_then(|r| self.format_reset_time(r)),
-            label: Some("Session".to_string()),
-        });
-
-        let secondary = response.seven_day.map(|w| RateWindow {
-            used_percent: w.utilization.unwrap_or(0.0),
-            window_minutes: Some(10080), // 7 days
-            resets_at: w.resets_at.clone(),
-            reset_description: w.resets_at.as_ref().and_then(|r| self.format_reset_time(r)),
-            label: Some("Weekly".to_string()),
-        });
-
-        let tertiary = response
+            label: Some(label.to_string()),
+        };
+
+        let primary = response
+            .five_hour
+            .as_ref()
+            .map(|w| window(w, "session", WindowCategory::Session, 300, "Session"));
+        let secondary = response
+            .seven_day
+            .as_ref()
+            .map(|w| window(w, "weekly", WindowCategory::Weekly, 10080, "Weekly"));
+        let opus = response
             .seven_day_opus
-            .or(response.seven_day_sonnet)
-            .map(|w| RateWindow {
-                used_percent: w.utilization.unwrap_or(0.0),
-                window_minutes: Some(10080),
-                resets_at: w.resets_at.clone(),
-                reset_description: w.resets_at.as_ref().and_then(|r| self.format_reset_time(r)),
-                label: Some("Weekly (Model)".to_string()),
-            });
+            .as_ref()
+            .map(|w| window(w, "weekly-opus", WindowCategory::Model, 10080, "Weekly (Opus)"));
+        let sonnet = response
+            .seven_day_sonnet
+            .as_ref()
+            .map(|w| window(w, "weekly-sonnet", WindowCategory::Model, 10080, "Weekly (Sonnet)"));
+
+        // Older readers only see one model window, as before
+        let tertiary = opus.clone().or_else(|| sonnet.clone()).map(|w| RateWindow {
+            label: Some("Weekly (Model)".to_string()),
+            ..w
+        });
+        let windows = [primary.clone(), secondary.clone(), opus, sonnet]
+            .into_iter()
+            .flatten()
+            .collect();
 
         let plan = tier.map(|t| {
             // Convert tier codes to display names
@@ -150,6 +188,10 @@ impl ClaudeProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows,
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -179,7 +221,7 @@ impl ProviderFetcher for ClaudeProvider {
         "Anthropic Claude AI Assistant"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Claude usage");
 
         // Try OAuth first
@@ -192,8 +234,33 @@ impl ProviderFetcher for ClaudeProvider {
             Err(e) => {
                 tracing::debug!("Claude OAuth fetch failed: {}", e);
                 // Return error snapshot - no mock data
-                Err(anyhow::anyhow!("Not authenticated: {}", e))
+                Err(ProviderError::not_authenticated(e))
+            }
+        }
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let creds_path = match self.get_credentials_path() {
+            Ok(path) => path,
+            Err(_) => {
+                return AuthStatus::unauthenticated(Some(
+                    "Could not find home directory".to_string(),
+                ))
             }
+        };
+
+        if !creds_path.exists() {
+            return AuthStatus::unauthenticated(None);
+        }
+
+        match tokio::fs::read_to_string(&creds_path).await {
+            Ok(content) if content.contains("claudeAiOauth") && content.contains("accessToken") => {
+                AuthStatus::authenticated("oauth")
+            }
+            Ok(_) => AuthStatus::unauthenticated(Some(
+                "Credentials file exists but missing OAuth data".to_string(),
+            )),
+            Err(e) => AuthStatus::unauthenticated(Some(format!("Could not read credentials: {}", e))),
         }
     }
 }
@@ -257,3 +324,58 @@ struct OAuthExtraUsage {
     utilization: Option<f64>,
     currency: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    fn provider_with_credentials(dir: &std::path::Path) -> ClaudeProvider {
+        let path = dir.join(".credentials.json");
+        std::fs::write(
+            &path,
+            r#"{"claudeAiOauth": {"accessToken": "oauth-token", "scopes": ["user:inference", "user:profile"], "rateLimitTier": "default_claude_max_5x"}}"#,
+        )
+        .unwrap();
+        ClaudeProvider::with_credentials_path(path)
+    }
+
+    #[tokio::test]
+    async fn replays_oauth_usage() {
+        let server = ReplayServer::from_fixture("claude-oauth")
+            .await
+            .route(ProviderId::Claude);
+        let dir = tempfile::tempdir().unwrap();
+
+        let usage = provider_with_credentials(dir.path())
+            .fetch()
+            .await
+            .expect("fetch");
+
+        assert_eq!(usage.primary.expect("primary").used_percent, 35.0);
+        assert_eq!(usage.secondary.expect("secondary").used_percent, 60.0);
+        let tertiary = usage.tertiary.expect("tertiary");
+        assert_eq!(tertiary.used_percent, 12.0);
+        assert_eq!(tertiary.label.as_deref(), Some("Weekly (Model)"));
+        assert_eq!(usage.identity.and_then(|i| i.plan), Some("Max".to_string()));
+
+        let request = &server.received()[0];
+        assert_eq!(request.headers["authorization"], "Bearer oauth-token");
+        assert_eq!(request.headers["anthropic-beta"], OAUTH_BETA_HEADER);
+    }
+
+    #[tokio::test]
+    async fn reports_rejected_oauth_tokens() {
+        let _server = ReplayServer::from_fixture("claude-oauth-expired")
+            .await
+            .route(ProviderId::Claude);
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = provider_with_credentials(dir.path())
+            .fetch()
+            .await
+            .expect_err("expired token");
+
+        assert!(err.to_string().contains("401"));
+    }
+}
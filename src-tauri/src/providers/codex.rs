@@ -4,9 +4,13 @@
 //! 1. OAuth API (chatgpt.com) - uses credentials from ~/.codex/auth.json
 //! 2. OpenAI web cookies (chatgpt.com) - optional extras via browser cookies
 
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
 use super::{
-    cost_usage, Credits, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    cost_usage, Credits, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow,
+    UsageSnapshot, WindowCategory, USAGE_SNAPSHOT_VERSION,
 };
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::PathBuf;
@@ -15,6 +19,10 @@ const DEFAULT_USAGE_URL: &str = "https://chatgpt.com/backend-api/wham/usage";
 
 pub struct CodexProvider {
     client: reqwest::Client,
+    /// Account-specific Codex home directory; `None` uses the default location
+    codex_home: Option<PathBuf>,
+    /// Cookie session file; `None` uses the app data directory
+    session_path: Option<PathBuf>,
 }
 
 impl CodexProvider {
@@ -24,7 +32,25 @@ impl CodexProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            codex_home: None,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Codex home directory lives at `path`
+    pub fn with_credentials_path(path: PathBuf) -> Self {
+        Self {
+            codex_home: Some(path),
+            ..Self::new()
+        }
+    }
+
+    #[cfg(test)]
+    fn with_session_path(mut self, path: PathBuf) -> Self {
+        self.session_path = Some(path);
+        self
     }
 
     /// Fetch usage via OAuth API
@@ -33,7 +59,7 @@ impl CodexProvider {
 
         let mut request = self
             .client
-            .get(DEFAULT_USAGE_URL)
+            .get(endpoint(ProviderId::Codex, DEFAULT_USAGE_URL))
             .header("Authorization", format!("Bearer {}", auth.access_token))
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0");
@@ -42,13 +68,12 @@ impl CodexProvider {
             request = request.header("ChatGPT-Account-Id", account_id);
         }
 
-        let response = request.send().await?;
+        let response = request.send_recorded(ProviderId::Codex).await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Codex API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("Codex API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let usage_response: CodexUsageResponse = response.json().await?;
@@ -60,11 +85,11 @@ impl CodexProvider {
 
         let response = self
             .client
-            .get(DEFAULT_USAGE_URL)
+            .get(endpoint(ProviderId::Codex, DEFAULT_USAGE_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Codex)
             .await?;
 
         match response.status().as_u16() {
@@ -115,6 +140,10 @@ impl CodexProvider {
 
     /// Get the path to Codex auth file
     fn get_auth_path(&self) -> Result<PathBuf, anyhow::Error> {
+        if let Some(codex_home) = &self.codex_home {
+            return Ok(codex_home.join("auth.json"));
+        }
+
         // Check CODEX_HOME first, then default to ~/.codex
         if let Ok(codex_home) = std::env::var("CODEX_HOME") {
             return Ok(PathBuf::from(codex_home).join("auth.json"));
@@ -126,6 +155,15 @@ impl CodexProvider {
     }
 
     fn get_session_path(&self) -> Result<PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+        if self.codex_home.is_some() {
+            return Err(anyhow::anyhow!(
+                "Cookie sessions are only stored for the default Codex account"
+            ));
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("codex-session.json"))
@@ -137,7 +175,12 @@ impl CodexProvider {
             rl.primary_window.as_ref().map(|w| {
                 let used_percent = w.used_percent.unwrap_or(0) as f64;
                 RateWindow {
+                    id: Some("session".to_string()),
+                    category: Some(WindowCategory::Session),
                     used_percent,
+                    used: None,
+                    limit: None,
+                    unit: None,
                     window_minutes: w.limit_window_seconds.map(|s| s / 60),
                     resets_at: w.reset_at.map(|ts| {
                         chrono::DateTime::from_timestamp(ts as i64, 0)
@@ -154,7 +197,12 @@ impl CodexProvider {
             rl.secondary_window.as_ref().map(|w| {
                 let used_percent = w.used_percent.unwrap_or(0) as f64;
                 RateWindow {
+                    id: Some("weekly".to_string()),
+                    category: Some(WindowCategory::Weekly),
                     used_percent,
+                    used: None,
+                    limit: None,
+                    unit: None,
                     window_minutes: w.limit_window_seconds.map(|s| s / 60),
                     resets_at: w.reset_at.map(|ts| {
                         chrono::DateTime::from_timestamp(ts as i64, 0)
@@ -208,6 +256,10 @@ impl CodexProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -236,7 +288,7 @@ impl ProviderFetcher for CodexProvider {
         "OpenAI Codex CLI"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Codex usage");
 
         match self.fetch_via_oauth().await {
@@ -253,10 +305,33 @@ impl ProviderFetcher for CodexProvider {
                     usage.cost = cost_usage::load_cost_snapshot(ProviderId::Codex).await;
                     return Ok(usage);
                 }
-                Err(anyhow::anyhow!("Not authenticated: {}", e))
+                Err(ProviderError::not_authenticated(e))
             }
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let auth_path = match self.get_auth_path() {
+            Ok(path) => path,
+            Err(_) => {
+                return AuthStatus::unauthenticated(Some(
+                    "Could not find home directory".to_string(),
+                ))
+            }
+        };
+
+        if !auth_path.exists() {
+            return AuthStatus::unauthenticated(None);
+        }
+
+        match tokio::fs::read_to_string(&auth_path).await {
+            Ok(content) if content.contains("access_token") => AuthStatus::authenticated("oauth"),
+            Ok(_) => AuthStatus::unauthenticated(Some(
+                "Auth file exists but missing access token".to_string(),
+            )),
+            Err(e) => AuthStatus::unauthenticated(Some(format!("Could not read auth file: {}", e))),
+        }
+    }
 }
 
 // ---- Response Types ----
@@ -363,3 +438,64 @@ where
 
     deserializer.deserialize_any(BalanceVisitor)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    fn write_credentials(dir: &std::path::Path) -> CodexProvider {
+        std::fs::write(
+            dir.join("auth.json"),
+            r#"{"tokens": {"access_token": "oauth-token", "account_id": "acct-1"}}"#,
+        )
+        .unwrap();
+        let session_path = dir.join("codex-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "__Secure-next-auth.session-token=abc"}"#)
+            .unwrap();
+        CodexProvider::with_credentials_path(dir.to_path_buf()).with_session_path(session_path)
+    }
+
+    #[tokio::test]
+    async fn replays_oauth_usage() {
+        let server = ReplayServer::from_fixture("codex-oauth")
+            .await
+            .route(ProviderId::Codex);
+        let dir = tempfile::tempdir().unwrap();
+
+        let usage = write_credentials(dir.path()).fetch().await.expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert_eq!(primary.used_percent, 42.0);
+        assert_eq!(primary.window_minutes, Some(300));
+        assert_eq!(usage.secondary.expect("secondary").used_percent, 17.0);
+        assert_eq!(usage.credits.expect("credits").remaining, 12.5);
+        assert_eq!(usage.identity.and_then(|i| i.plan), Some("Plus".to_string()));
+
+        let received = server.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].headers["authorization"], "Bearer oauth-token");
+        assert_eq!(received[0].headers["chatgpt-account-id"], "acct-1");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_cookies_when_oauth_is_rejected() {
+        let server = ReplayServer::from_fixture("codex-oauth-fallback")
+            .await
+            .route(ProviderId::Codex);
+        let dir = tempfile::tempdir().unwrap();
+
+        let usage = write_credentials(dir.path()).fetch().await.expect("fetch");
+
+        assert_eq!(usage.primary.expect("primary").used_percent, 42.0);
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].headers["authorization"], "Bearer oauth-token");
+        assert!(!received[1].headers.contains_key("authorization"));
+        assert_eq!(
+            received[1].headers["cookie"],
+            "__Secure-next-auth.session-token=abc"
+        );
+        assert!(server.pending().is_empty());
+    }
+}
@@ -6,7 +6,13 @@
 //! - /login/oauth/access_token - Poll for access token
 //! - api.github.com/copilot_internal/user - Usage data
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +26,8 @@ const COPILOT_USER_URL: &str = "https://api.github.com/copilot_internal/user";
 
 pub struct CopilotProvider {
     client: reqwest::Client,
+    /// Account-specific Copilot token file; `None` uses the default location
+    token_path: Option<std::path::PathBuf>,
 }
 
 impl CopilotProvider {
@@ -29,34 +37,42 @@ impl CopilotProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            token_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Copilot token file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            token_path: Some(path),
+            ..Self::new()
+        }
     }
 
     /// Fetch usage with stored token
     async fn fetch_with_token(&self, token: &str) -> Result<UsageSnapshot, anyhow::Error> {
         let response = self
             .client
-            .get(COPILOT_USER_URL)
+            .get(endpoint(ProviderId::Copilot, COPILOT_USER_URL))
             .header("Authorization", format!("token {}", token))
             .header("Accept", "application/json")
             .header("Editor-Version", "vscode/1.96.2")
             .header("Editor-Plugin-Version", "copilot-chat/0.26.7")
             .header("User-Agent", "GitHubCopilotChat/0.26.7")
             .header("X-Github-Api-Version", "2025-04-01")
-            .send()
+            .send_recorded(ProviderId::Copilot)
             .await?;
 
-        if response.status() == 401 || response.status() == 403 {
-            return Err(anyhow::anyhow!(
-                "Authentication required - please login again"
-            ));
-        }
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Copilot API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = match status.as_u16() {
+                401 => "Authentication required - please login again".to_string(),
+                403 => "GitHub denied access to Copilot usage - check your plan".to_string(),
+                _ => format!("Copilot API returned status: {}", status),
+            };
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let raw_json = response.text().await?;
@@ -77,7 +93,12 @@ impl CopilotProvider {
             .map(|q| {
                 let used_percent = 100.0 - q.percent_remaining;
                 RateWindow {
+                    id: Some("premium".to_string()),
+                    category: Some(WindowCategory::Monthly),
                     used_percent,
+                    used: Some(q.used()),
+                    limit: Some(q.entitlement),
+                    unit: Some("requests".to_string()),
                     window_minutes: None,
                     resets_at: Some(usage.quota_reset_date.clone()),
                     reset_description: Some(self.format_reset_description(&usage.quota_reset_date)),
@@ -89,7 +110,12 @@ impl CopilotProvider {
         let secondary = usage.quota_snapshots.chat.as_ref().map(|q| {
             let used_percent = 100.0 - q.percent_remaining;
             RateWindow {
+                id: Some("chat".to_string()),
+                category: Some(WindowCategory::Monthly),
                 used_percent,
+                used: Some(q.used()),
+                limit: Some(q.entitlement),
+                unit: Some("messages".to_string()),
                 window_minutes: None,
                 resets_at: Some(usage.quota_reset_date.clone()),
                 reset_description: None,
@@ -114,6 +140,10 @@ impl CopilotProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -147,6 +177,10 @@ impl CopilotProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.token_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("copilot-token.json"))
@@ -163,7 +197,7 @@ impl ProviderFetcher for CopilotProvider {
         "GitHub Copilot"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Copilot usage");
 
         // Try to load stored token
@@ -198,8 +232,37 @@ impl ProviderFetcher for CopilotProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: Some("Login required".to_string()),
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         })
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let token_path = match self.get_session_path() {
+            Ok(path) => path,
+            Err(_) => {
+                return AuthStatus::unauthenticated(Some(
+                    "Could not find data directory".to_string(),
+                ))
+            }
+        };
+
+        if !token_path.exists() {
+            return AuthStatus::unauthenticated(None);
+        }
+
+        match tokio::fs::read_to_string(&token_path).await {
+            Ok(content) if content.contains("access_token") => {
+                AuthStatus::authenticated("device_flow")
+            }
+            Ok(_) => AuthStatus::unauthenticated(Some(
+                "Token file exists but missing access token".to_string(),
+            )),
+            Err(e) => AuthStatus::unauthenticated(Some(format!("Could not read token file: {}", e))),
+        }
+    }
 }
 
 // ============== Device Flow ==============
@@ -239,10 +302,10 @@ pub async fn request_device_code() -> Result<DeviceCodeResponse, anyhow::Error>
     let params = [("client_id", GITHUB_CLIENT_ID), ("scope", GITHUB_SCOPES)];
 
     let response = client
-        .post(DEVICE_CODE_URL)
+        .post(endpoint(ProviderId::Copilot, DEVICE_CODE_URL))
         .header("Accept", "application/json")
         .form(&params)
-        .send()
+        .send_recorded(ProviderId::Copilot)
         .await?;
 
     if !response.status().is_success() {
@@ -287,10 +350,10 @@ pub async fn poll_for_token(device_code: &str, interval: i32) -> Result<String,
         tokio::time::sleep(std::time::Duration::from_secs(current_interval)).await;
 
         let response = client
-            .post(ACCESS_TOKEN_URL)
+            .post(endpoint(ProviderId::Copilot, ACCESS_TOKEN_URL))
             .header("Accept", "application/json")
             .form(&params)
-            .send()
+            .send_recorded(ProviderId::Copilot)
             .await?;
 
         let body = response.text().await?;
@@ -376,15 +439,19 @@ struct QuotaSnapshots {
 
 #[derive(Debug, Deserialize)]
 struct QuotaSnapshot {
-    #[allow(dead_code)]
     entitlement: f64,
-    #[allow(dead_code)]
     remaining: f64,
     percent_remaining: f64,
     #[allow(dead_code)]
     quota_id: String,
 }
 
+impl QuotaSnapshot {
+    fn used(&self) -> f64 {
+        self.entitlement - self.remaining
+    }
+}
+
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
@@ -396,6 +463,39 @@ fn capitalize_first(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    #[tokio::test]
+    async fn replays_token_usage() {
+        let server = ReplayServer::from_fixture("copilot-token")
+            .await
+            .route(ProviderId::Copilot);
+        let dir = tempfile::tempdir().unwrap();
+        let token_path = dir.path().join("copilot-token.json");
+        std::fs::write(
+            &token_path,
+            r#"{"access_token": "gho_test", "saved_at": null}"#,
+        )
+        .unwrap();
+
+        let usage = CopilotProvider::with_credentials_path(token_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert_eq!(primary.used_percent, 25.0);
+        assert_eq!(primary.used, Some(75.0));
+        assert_eq!(usage.secondary.expect("secondary").used_percent, 10.0);
+        assert_eq!(
+            usage.identity.and_then(|i| i.plan),
+            Some("Individual".to_string())
+        );
+        assert_eq!(
+            server.received()[0].headers["authorization"],
+            "token gho_test"
+        );
+    }
 
     /// Test that the timeout constant is exactly 600 seconds (10 minutes)
     /// and the error message is as expected.
@@ -5,8 +5,14 @@
 //! - /api/usage-summary - Token-based usage
 //! - /api/auth/me - User info
 
-use super::{Credits, ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    Credits, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow,
+    UsageSnapshot, WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
 use crate::debug_settings;
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::Path;
@@ -16,6 +22,8 @@ const AUTH_ME_URL: &str = "https://cursor.com/api/auth/me";
 
 pub struct CursorProvider {
     client: reqwest::Client,
+    /// Account-specific Cursor session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl CursorProvider {
@@ -25,7 +33,18 @@ impl CursorProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Cursor session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     /// Fetch usage with a cookie header
@@ -36,18 +55,17 @@ impl CursorProvider {
         // Fetch usage summary
         let usage_response = self
             .client
-            .get(USAGE_SUMMARY_URL)
+            .get(endpoint(ProviderId::Cursor, USAGE_SUMMARY_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Cursor)
             .await?;
 
-        if !usage_response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Cursor API returned status: {}",
-                usage_response.status()
-            ));
+        let status = usage_response.status();
+        if !status.is_success() {
+            let message = format!("Cursor API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         // Get the raw JSON for debugging
@@ -77,18 +95,17 @@ impl CursorProvider {
     async fn fetch_user_info(&self, cookie_header: &str) -> Result<CursorUserInfo, anyhow::Error> {
         let response = self
             .client
-            .get(AUTH_ME_URL)
+            .get(endpoint(ProviderId::Cursor, AUTH_ME_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Cursor)
             .await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Cursor auth API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("Cursor auth API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         Ok(response.json().await?)
@@ -136,7 +153,12 @@ impl CursorProvider {
                 let limit_usd = limit_cents / 100.0;
 
                 let primary = Some(RateWindow {
+                    id: Some("plan".to_string()),
+                    category: Some(WindowCategory::Monthly),
                     used_percent,
+                    used: Some(used_usd),
+                    limit: Some(limit_usd),
+                    unit: Some("USD".to_string()),
                     window_minutes: None,
                     resets_at: usage.billing_cycle_end.clone(),
                     reset_description: billing_cycle_end.map(|d| self.format_reset_time(&d)),
@@ -178,7 +200,12 @@ impl CursorProvider {
                     // Only show if there's actual usage or it's enabled
                     if used_usd > 0.0 || on_demand.enabled.unwrap_or(false) {
                         Some(RateWindow {
+                            id: Some("on-demand".to_string()),
+                            category: Some(WindowCategory::Monthly),
                             used_percent,
+                            used: Some(used_usd),
+                            limit: limit_cents.map(|limit| limit / 100.0),
+                            unit: Some("USD".to_string()),
                             window_minutes: None,
                             resets_at: usage.billing_cycle_end.clone(),
                             reset_description: Some(format!("${:.2} on-demand", used_usd)),
@@ -213,7 +240,12 @@ impl CursorProvider {
 
                     if used_usd > 0.0 {
                         Some(RateWindow {
+                            id: Some("team-on-demand".to_string()),
+                            category: Some(WindowCategory::Monthly),
                             used_percent,
+                            used: Some(used_usd),
+                            limit: limit_cents.map(|limit| limit / 100.0),
+                            unit: Some("USD".to_string()),
                             window_minutes: None,
                             resets_at: usage.billing_cycle_end.clone(),
                             reset_description: Some(format!("${:.2} team on-demand", used_usd)),
@@ -255,6 +287,10 @@ impl CursorProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -344,6 +380,10 @@ impl CursorProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("cursor-session.json"))
@@ -360,7 +400,7 @@ impl ProviderFetcher for CursorProvider {
         "Cursor AI Code Editor"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Cursor usage");
 
         // Try to load stored cookies
@@ -396,7 +436,12 @@ impl ProviderFetcher for CursorProvider {
         // Fallback to mock data
         Ok(UsageSnapshot {
             primary: Some(RateWindow {
+                id: Some("monthly".to_string()),
+                category: Some(WindowCategory::Monthly),
                 used_percent: 62.0,
+                used: None,
+                limit: None,
+                unit: None,
                 window_minutes: None,
                 resets_at: Some((chrono::Utc::now() + chrono::Duration::days(12)).to_rfc3339()),
                 reset_description: Some("Resets in 12 days".to_string()),
@@ -414,8 +459,16 @@ impl ProviderFetcher for CursorProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         })
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 // ---- Response Types ----
@@ -429,6 +482,7 @@ struct CursorSession {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
 
     #[tokio::test]
     async fn stores_and_loads_cursor_session_cookie() {
@@ -452,6 +506,39 @@ mod tests {
 
         assert_eq!(loaded, cookie_header);
     }
+
+    #[tokio::test]
+    async fn replays_usage_summary_and_user_info() {
+        let server = ReplayServer::from_fixture("cursor-cookies")
+            .await
+            .route(ProviderId::Cursor);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("cursor-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "WorkosCursorSessionToken=abc"}"#)
+            .unwrap();
+
+        let usage = CursorProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert_eq!(primary.used_percent, 75.0);
+        assert_eq!(primary.label.as_deref(), Some("Plan Usage"));
+        let credits = usage.credits.expect("credits");
+        assert_eq!(credits.remaining, 5.0);
+        assert_eq!(credits.total, Some(20.0));
+        assert_eq!(
+            usage.identity.and_then(|i| i.name),
+            Some("Test User".to_string())
+        );
+
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert!(received
+            .iter()
+            .all(|request| request.headers["cookie"] == "WorkosCursorSessionToken=abc"));
+    }
 }
 
 // Note: Some fields below are unused but required for serde deserialization
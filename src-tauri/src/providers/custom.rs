@@ -0,0 +1,512 @@
+//! User-defined JSON HTTP providers
+//!
+//! Each definition in `custom_providers.json` points at a quota endpoint and maps
+//! fields of its JSON response onto usage windows. Field paths are dot-separated,
+//! may start with `$` and use `[n]` for array indices (`$.data.quotas[0].used`).
+//! Without explicit `used`/`limit` paths the response is parsed with the same
+//! heuristics as the Synthetic and Factory providers.
+
+use super::fixtures::RecordingSend;
+use super::quota_json::{self, QuotaEntry};
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
+use crate::storage::keyring::KeyringError;
+use crate::storage::SecureStorage;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+const CUSTOM_PROVIDERS_FILE_NAME: &str = "custom_providers.json";
+const DEFAULT_AUTH_HEADER: &str = "Authorization";
+
+/// A user-defined provider as stored in `custom_providers.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomProviderConfig {
+    /// Stable identifier, also used as the account id of the provider
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<CustomProviderAuth>,
+    /// Extra request headers sent with every fetch
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub fields: CustomProviderFields,
+}
+
+/// How the secret is attached to requests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomProviderAuth {
+    /// Header carrying the secret, `Authorization` by default
+    #[serde(default = "default_auth_header")]
+    pub header: String,
+    /// Prefix placed before the secret, e.g. `Bearer`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    /// Secret stored inline in the definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Keyring entry holding the secret, used when `value` is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyring_key: Option<String>,
+}
+
+fn default_auth_header() -> String {
+    DEFAULT_AUTH_HEADER.to_string()
+}
+
+/// Paths into the response JSON. `used`, `limit`, `reset` and `label` are resolved
+/// relative to each quota entry; `quotas` and `plan` relative to the response root.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomProviderFields {
+    /// Array (or single object) of quota entries; the root when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quotas: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+}
+
+impl CustomProviderConfig {
+    /// Check the definition can be registered
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let id = self.id.trim();
+        if id.is_empty() {
+            return Err(anyhow::anyhow!("Custom provider id must not be empty"));
+        }
+        if !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(anyhow::anyhow!(
+                "Custom provider id may only contain letters, digits, '-' and '_'"
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Custom provider name must not be empty"));
+        }
+        let url = url::Url::parse(self.url.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid URL for {}: {}", self.name, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(anyhow::anyhow!(
+                "Custom provider URL must use http or https"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Keyring entry used when a secret is saved through the app
+    pub fn default_keyring_key(&self) -> String {
+        format!("custom_provider_{}", self.id)
+    }
+}
+
+/// Load the custom provider definitions stored in `data_dir`. A missing file means none
+/// are configured.
+pub fn load_custom_providers_in(
+    data_dir: &Path,
+) -> Result<Vec<CustomProviderConfig>, anyhow::Error> {
+    let path = data_dir.join(CUSTOM_PROVIDERS_FILE_NAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub fn save_custom_providers_in(
+    data_dir: &Path,
+    providers: &[CustomProviderConfig],
+) -> Result<(), anyhow::Error> {
+    let path = data_dir.join(CUSTOM_PROVIDERS_FILE_NAME);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(providers)?)?;
+    Ok(())
+}
+
+pub struct CustomHttpProvider {
+    client: reqwest::Client,
+    config: CustomProviderConfig,
+}
+
+impl CustomHttpProvider {
+    pub fn new(config: CustomProviderConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self { client, config }
+    }
+
+    /// Resolve the secret from the definition or the keyring
+    fn resolve_secret(&self, auth: &CustomProviderAuth) -> Result<String, anyhow::Error> {
+        if let Some(value) = auth.value.as_deref().map(str::trim) {
+            if !value.is_empty() {
+                return Ok(value.to_string());
+            }
+        }
+
+        if let Some(key) = auth.keyring_key.as_deref() {
+            return match SecureStorage::new().get(key) {
+                Ok(value) => Ok(value),
+                Err(KeyringError::NotFound) => Err(anyhow::anyhow!(
+                    "No secret stored in the keyring for {}",
+                    self.config.name
+                )),
+                Err(err) => Err(anyhow::anyhow!(
+                    "Failed to read secret for {}: {}",
+                    self.config.name,
+                    err
+                )),
+            };
+        }
+
+        Err(anyhow::anyhow!("No secret configured for {}", self.config.name))
+    }
+
+    async fn fetch_usage(&self) -> Result<UsageSnapshot, anyhow::Error> {
+        let url = self.config.url.trim();
+        tracing::debug!("Fetching {} usage from: {}", self.config.name, url);
+
+        let mut request = self
+            .client
+            .get(url)
+            .header("Accept", "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(auth) = &self.config.auth {
+            let secret = self.resolve_secret(auth)?;
+            let value = match auth.scheme.as_deref().map(str::trim) {
+                Some(scheme) if !scheme.is_empty() => format!("{} {}", scheme, secret),
+                _ => secret,
+            };
+            request = request.header(auth.header.as_str(), value);
+        }
+
+        let response = request.send_recorded(ProviderId::Custom).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = match status.as_u16() {
+                401 => format!("Invalid {} credentials", self.config.name),
+                403 => format!("{} denied access with these credentials", self.config.name),
+                _ => format!("{} API error: HTTP {}", self.config.name, status),
+            };
+            return Err(ProviderError::from_status(status, message).into());
+        }
+
+        let body = response.text().await?;
+        // The body may carry account details, so only its size is logged
+        tracing::debug!(
+            "{} response: HTTP {}, {} bytes",
+            self.config.name,
+            status,
+            body.len()
+        );
+
+        let json: Value = serde_json::from_str(&body)?;
+        self.parse_response(&json)
+    }
+
+    fn parse_response(&self, json: &Value) -> Result<UsageSnapshot, anyhow::Error> {
+        let fields = &self.config.fields;
+        let root = match fields.quotas.as_deref() {
+            Some(path) => select_path(json, path).ok_or_else(|| {
+                anyhow::anyhow!("{} response has no value at {}", self.config.name, path)
+            })?,
+            None => json,
+        };
+
+        let quotas: Vec<QuotaEntry> = if fields.used.is_some() || fields.limit.is_some() {
+            let entries: Vec<&Value> = match root.as_array() {
+                Some(array) => array.iter().collect(),
+                None => vec![root],
+            };
+            entries
+                .into_iter()
+                .filter_map(|entry| self.parse_mapped_quota(entry))
+                .collect()
+        } else {
+            quota_json::find_quota_objects(root)
+                .into_iter()
+                .filter_map(quota_json::parse_quota)
+                .collect()
+        };
+
+        if quotas.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Could not parse any quota entries from {}",
+                self.config.name
+            ));
+        }
+
+        let plan = match fields.plan.as_deref() {
+            Some(path) => select_path(json, path).and_then(value_to_string),
+            None => quota_json::find_plan_name(json),
+        };
+
+        Ok(UsageSnapshot {
+            primary: quotas.first().map(QuotaEntry::to_rate_window),
+            secondary: quotas.get(1).map(QuotaEntry::to_rate_window),
+            tertiary: quotas.get(2).map(QuotaEntry::to_rate_window),
+            credits: None,
+            cost: None,
+            identity: plan.map(|plan| ProviderIdentity {
+                email: None,
+                name: None,
+                plan: Some(plan),
+                organization: None,
+            }),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            error: None,
+            retry_at: None,
+            windows: quotas.iter().map(QuotaEntry::to_rate_window).collect(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        })
+    }
+
+    /// Build a quota from the configured field paths
+    fn parse_mapped_quota(&self, entry: &Value) -> Option<QuotaEntry> {
+        let fields = &self.config.fields;
+        let used = select_path(entry, fields.used.as_deref()?).and_then(quota_json::to_double)?;
+        let limit =
+            select_path(entry, fields.limit.as_deref()?).and_then(quota_json::to_double)?;
+
+        let used_percent = if limit > 0.0 && limit.is_finite() {
+            (used / limit) * 100.0
+        } else {
+            0.0
+        };
+
+        let resets_at = match fields.reset.as_deref() {
+            Some(path) => select_path(entry, path).and_then(quota_json::parse_reset_value),
+            None => quota_json::find_reset_time(entry),
+        };
+        let reset_description = resets_at
+            .as_ref()
+            .and_then(|reset_at| quota_json::format_reset_time(reset_at));
+
+        let label = match fields.label.as_deref() {
+            Some(path) => select_path(entry, path).and_then(value_to_string),
+            None => quota_json::find_string(entry, &["name", "label", "type", "title"]),
+        };
+
+        Some(QuotaEntry {
+            label,
+            used_percent: used_percent.clamp(0.0, 100.0),
+            used: Some(used),
+            limit: Some(limit),
+            unit: quota_json::find_unit(entry),
+            window_minutes: quota_json::find_window_minutes(entry),
+            resets_at,
+            reset_description,
+        })
+    }
+}
+
+/// Resolve a dot-separated path with optional `$` prefix and `[n]` indices
+fn select_path<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = json;
+
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(start) => segment.split_at(start),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices.split('[').filter(|index| !index.is_empty()) {
+            let index: usize = index.strip_suffix(']')?.trim().parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+
+    Some(current)
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+#[async_trait]
+impl ProviderFetcher for CustomHttpProvider {
+    fn name(&self) -> &'static str {
+        "Custom"
+    }
+
+    fn description(&self) -> &'static str {
+        "User-defined JSON HTTP provider"
+    }
+
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
+        tracing::debug!("Fetching custom provider {} usage", self.config.id);
+        Ok(self.fetch_usage().await?)
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let Some(auth) = &self.config.auth else {
+            return AuthStatus::authenticated("none");
+        };
+        match self.resolve_secret(auth) {
+            Ok(_) => AuthStatus::authenticated("api_key"),
+            Err(err) => AuthStatus::unauthenticated(Some(err.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::fixtures::{Exchange, ReplayServer};
+    use crate::providers::ProviderErrorKind;
+    use serde_json::json;
+
+    fn provider(fields: CustomProviderFields) -> CustomHttpProvider {
+        CustomHttpProvider::new(CustomProviderConfig {
+            id: "gateway".to_string(),
+            name: "Gateway".to_string(),
+            url: "https://gateway.example.com/quota".to_string(),
+            auth: None,
+            headers: HashMap::new(),
+            fields,
+        })
+    }
+
+    #[tokio::test]
+    async fn separates_rejected_from_forbidden_credentials() {
+        let exchange = |status: u16| -> Exchange {
+            serde_json::from_value(json!({
+                "request": { "method": "GET", "path": "/quota" },
+                "response": { "status": status, "body": "{}" }
+            }))
+            .unwrap()
+        };
+        let server = ReplayServer::start(vec![exchange(401), exchange(403)]).await;
+        let gateway = CustomHttpProvider::new(CustomProviderConfig {
+            url: format!("{}/quota", server.base_url()),
+            ..provider(CustomProviderFields::default()).config
+        });
+
+        let rejected = gateway.fetch().await.unwrap_err();
+        assert_eq!(rejected.kind(), ProviderErrorKind::TokenExpired);
+        let forbidden = gateway.fetch().await.unwrap_err();
+        assert_eq!(forbidden.kind(), ProviderErrorKind::Forbidden);
+        assert_eq!(
+            forbidden.to_string(),
+            "Gateway denied access with these credentials"
+        );
+    }
+
+    #[test]
+    fn select_path_supports_root_prefix_and_indices() {
+        let json = json!({ "data": { "quotas": [{ "used": 1 }, { "used": 2 }] } });
+
+        assert_eq!(
+            select_path(&json, "$.data.quotas[1].used"),
+            Some(&json!(2))
+        );
+        assert_eq!(select_path(&json, "data.quotas[0]"), Some(&json!({ "used": 1 })));
+        assert_eq!(select_path(&json, "$"), Some(&json));
+        assert!(select_path(&json, "data.quotas[5]").is_none());
+        assert!(select_path(&json, "data.missing").is_none());
+    }
+
+    #[test]
+    fn parses_mapped_fields() {
+        let provider = provider(CustomProviderFields {
+            quotas: Some("$.result.limits".to_string()),
+            used: Some("consumed.tokens".to_string()),
+            limit: Some("max".to_string()),
+            reset: Some("window.end".to_string()),
+            label: Some("kind".to_string()),
+            plan: Some("$.result.tier".to_string()),
+        });
+        let json = json!({
+            "result": {
+                "tier": "Team",
+                "limits": [
+                    { "kind": "Daily", "consumed": { "tokens": 250 }, "max": 1000, "window": { "end": 1893456000 } },
+                    { "kind": "Monthly", "consumed": { "tokens": "900" }, "max": "1000" }
+                ]
+            }
+        });
+
+        let snapshot = provider.parse_response(&json).expect("snapshot");
+        let primary = snapshot.primary.expect("primary");
+        let secondary = snapshot.secondary.expect("secondary");
+
+        assert_eq!(primary.used_percent, 25.0);
+        assert_eq!(primary.label.as_deref(), Some("Daily"));
+        assert!(primary.resets_at.is_some());
+        assert!(primary.reset_description.is_some());
+        assert_eq!(secondary.used_percent, 90.0);
+        assert_eq!(
+            snapshot.identity.and_then(|identity| identity.plan),
+            Some("Team".to_string())
+        );
+    }
+
+    #[test]
+    fn falls_back_to_heuristic_parsing() {
+        let provider = provider(CustomProviderFields::default());
+        let json = json!({
+            "plan": "Pro",
+            "quotas": [{ "name": "Requests", "limit": 200, "used": 50, "windowHours": 5 }]
+        });
+
+        let snapshot = provider.parse_response(&json).expect("snapshot");
+        let primary = snapshot.primary.expect("primary");
+
+        assert_eq!(primary.used_percent, 25.0);
+        assert_eq!(primary.window_minutes, Some(300));
+        assert_eq!(
+            snapshot.identity.and_then(|identity| identity.plan),
+            Some("Pro".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_ids_and_urls() {
+        let mut config = provider(CustomProviderFields::default()).config;
+        assert!(config.validate().is_ok());
+
+        config.id = "../gateway".to_string();
+        assert!(config.validate().is_err());
+
+        config.id = "gateway".to_string();
+        config.url = "file:///etc/passwd".to_string();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,439 @@
+//! Static per-provider metadata
+//!
+//! Every fact about a provider that is not fetched at runtime (names, slugs, how it
+//! authenticates, where its status comes from) lives in this table so the app, the tray
+//! and the CLI stay in sync.
+
+use super::{
+    amp, antigravity, augment, claude, codex, copilot, cursor, factory, gemini, jetbrains, kimi,
+    kimi_k2, kiro, minimax, opencode, synthetic, vertexai, zai, ProviderFetcher, ProviderId,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// How a provider obtains credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthKind {
+    /// OAuth credentials written by the provider's own CLI
+    Oauth,
+    /// Session cookies imported from a browser or pasted manually
+    Cookies,
+    /// GitHub device authorization flow
+    DeviceFlow,
+    /// API key from the environment or keychain
+    ApiKey,
+    /// Logged-in vendor CLI
+    Cli,
+    /// Locally running language server
+    LocalProbe,
+    /// IDE log files on disk
+    LocalLogs,
+    /// External plugin executable
+    Plugin,
+}
+
+/// Where incident/status information for a provider is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusSource {
+    /// No status feed is polled
+    None,
+    /// Each account's fetcher reports status itself, e.g. one status per plugin
+    Fetcher,
+    /// Google Workspace status dashboard JSON, filtered to one product
+    GoogleWorkspace { product_id: &'static str },
+    /// AWS Health Dashboard RSS feed
+    AwsHealth,
+}
+
+/// Static description of a provider
+#[derive(Debug, Clone, Copy)]
+pub struct ProviderDescriptor {
+    pub id: ProviderId,
+    /// Human-readable name shown in the tray and CLI output
+    pub display_name: &'static str,
+    /// Identifier used by the CLI and frontend (matches the serde name)
+    pub slug: &'static str,
+    pub auth_kinds: &'static [AuthKind],
+    /// Domains to extract browser cookies for (empty when cookies are not used)
+    pub cookie_domains: &'static [&'static str],
+    pub status_source: StatusSource,
+    /// Public status dashboard for the provider
+    pub dashboard_url: Option<&'static str>,
+    /// Whether usage cost can be computed from local session logs
+    pub supports_cost_scan: bool,
+    /// Builds the fetcher of the default account; `None` when the provider only has
+    /// user-defined accounts
+    pub(crate) create_fetcher: Option<fn() -> Arc<dyn ProviderFetcher>>,
+    /// Builds a fetcher for an additional account; `None` when only one account is supported
+    pub(crate) create_account_fetcher: Option<fn(PathBuf) -> Arc<dyn ProviderFetcher>>,
+}
+
+/// All providers, in display order
+pub static PROVIDER_DESCRIPTORS: &[ProviderDescriptor] = &[
+    ProviderDescriptor {
+        id: ProviderId::Claude,
+        display_name: "Claude",
+        slug: "claude",
+        auth_kinds: &[AuthKind::Oauth],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: Some("https://status.anthropic.com"),
+        supports_cost_scan: true,
+        create_fetcher: Some(|| Arc::new(claude::ClaudeProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(claude::ClaudeProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Codex,
+        display_name: "Codex",
+        slug: "codex",
+        auth_kinds: &[AuthKind::Oauth, AuthKind::Cookies],
+        cookie_domains: &["chatgpt.com", "openai.com"],
+        status_source: StatusSource::None,
+        dashboard_url: Some("https://status.openai.com"),
+        supports_cost_scan: true,
+        create_fetcher: Some(|| Arc::new(codex::CodexProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(codex::CodexProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Cursor,
+        display_name: "Cursor",
+        slug: "cursor",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["cursor.com", "cursor.sh", "workos.com"],
+        status_source: StatusSource::None,
+        dashboard_url: Some("https://status.cursor.sh"),
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(cursor::CursorProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(cursor::CursorProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Copilot,
+        display_name: "Copilot",
+        slug: "copilot",
+        auth_kinds: &[AuthKind::DeviceFlow],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: Some("https://www.githubstatus.com"),
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(copilot::CopilotProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(copilot::CopilotProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Gemini,
+        display_name: "Gemini",
+        slug: "gemini",
+        auth_kinds: &[AuthKind::Oauth],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(gemini::GeminiProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(gemini::GeminiProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Antigravity,
+        display_name: "Antigravity",
+        slug: "antigravity",
+        auth_kinds: &[AuthKind::LocalProbe],
+        cookie_domains: &[],
+        status_source: StatusSource::GoogleWorkspace {
+            product_id: "npdyhgECDJ6tB66MxXyo",
+        },
+        dashboard_url: Some(
+            "https://www.google.com/appsstatus/dashboard/products/npdyhgECDJ6tB66MxXyo",
+        ),
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(antigravity::AntigravityProvider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Factory,
+        display_name: "Droid",
+        slug: "factory",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["factory.ai", "app.factory.ai"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(factory::FactoryProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(factory::FactoryProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Zai,
+        display_name: "z.ai",
+        slug: "zai",
+        auth_kinds: &[AuthKind::ApiKey],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(zai::ZaiProvider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Minimax,
+        display_name: "MiniMax",
+        slug: "minimax",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["minimax.chat", "platform.minimax.io"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(minimax::MinimaxProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(minimax::MinimaxProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Kimi,
+        display_name: "Kimi",
+        slug: "kimi",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["kimi.moonshot.cn", "kimi.com"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(kimi::KimiProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(kimi::KimiProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::KimiK2,
+        display_name: "Kimi K2",
+        slug: "kimi_k2",
+        auth_kinds: &[AuthKind::ApiKey],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(kimi_k2::KimiK2Provider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Kiro,
+        display_name: "Kiro",
+        slug: "kiro",
+        auth_kinds: &[AuthKind::Cli],
+        cookie_domains: &[],
+        status_source: StatusSource::AwsHealth,
+        dashboard_url: Some("https://status.aws.amazon.com/rss/all.rss"),
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(kiro::KiroProvider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Vertex,
+        display_name: "Vertex AI",
+        slug: "vertexai",
+        auth_kinds: &[AuthKind::Oauth],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: Some("https://status.cloud.google.com"),
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(vertexai::VertexAiProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(vertexai::VertexAiProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Augment,
+        display_name: "Augment",
+        slug: "augment",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["augmentcode.com", "app.augmentcode.com"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(augment::AugmentProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(augment::AugmentProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Amp,
+        display_name: "Amp",
+        slug: "amp",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["ampcode.com", "www.ampcode.com"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(amp::AmpProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(amp::AmpProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Jetbrains,
+        display_name: "JetBrains AI",
+        slug: "jetbrains",
+        auth_kinds: &[AuthKind::LocalLogs],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(jetbrains::JetbrainsProvider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Opencode,
+        display_name: "OpenCode",
+        slug: "opencode",
+        auth_kinds: &[AuthKind::Cookies],
+        cookie_domains: &["opencode.ai", "app.opencode.ai"],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(opencode::OpencodeProvider::new())),
+        create_account_fetcher: Some(|path| {
+            Arc::new(opencode::OpencodeProvider::with_credentials_path(path))
+        }),
+    },
+    ProviderDescriptor {
+        id: ProviderId::Synthetic,
+        display_name: "Synthetic",
+        slug: "synthetic",
+        auth_kinds: &[AuthKind::ApiKey],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: Some(|| Arc::new(synthetic::SyntheticProvider::new())),
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Custom,
+        display_name: "Custom",
+        slug: "custom",
+        auth_kinds: &[AuthKind::ApiKey],
+        cookie_domains: &[],
+        status_source: StatusSource::None,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: None,
+        create_account_fetcher: None,
+    },
+    ProviderDescriptor {
+        id: ProviderId::Plugin,
+        display_name: "Plugins",
+        slug: "plugin",
+        auth_kinds: &[AuthKind::Plugin],
+        cookie_domains: &[],
+        status_source: StatusSource::Fetcher,
+        dashboard_url: None,
+        supports_cost_scan: false,
+        create_fetcher: None,
+        create_account_fetcher: None,
+    },
+];
+
+impl ProviderDescriptor {
+    /// Look up a provider by its CLI/frontend slug
+    pub fn from_slug(slug: &str) -> Option<&'static ProviderDescriptor> {
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .find(|descriptor| descriptor.slug == slug)
+    }
+
+    /// Whether the provider has an implicit default account
+    pub fn has_default_account(&self) -> bool {
+        self.create_fetcher.is_some()
+    }
+
+    pub fn supports_accounts(&self) -> bool {
+        self.create_account_fetcher.is_some()
+    }
+
+    pub fn uses_cookies(&self) -> bool {
+        self.auth_kinds.contains(&AuthKind::Cookies)
+    }
+
+    /// Browser cookies are the only way to authenticate this provider
+    pub fn uses_cookies_only(&self) -> bool {
+        self.auth_kinds == [AuthKind::Cookies]
+    }
+}
+
+impl ProviderId {
+    pub fn descriptor(self) -> &'static ProviderDescriptor {
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .find(|descriptor| descriptor.id == self)
+            .expect("every ProviderId has a descriptor")
+    }
+
+    pub fn display_name(self) -> &'static str {
+        self.descriptor().display_name
+    }
+
+    pub fn slug(self) -> &'static str {
+        self.descriptor().slug
+    }
+
+    pub fn from_slug(slug: &str) -> Option<ProviderId> {
+        ProviderDescriptor::from_slug(slug).map(|descriptor| descriptor.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptor_ids_are_unique() {
+        let ids = ProviderId::all();
+        for id in &ids {
+            assert_eq!(ids.iter().filter(|other| *other == id).count(), 1, "{:?}", id);
+            assert_eq!(id.descriptor().id, *id);
+        }
+    }
+
+    #[test]
+    fn slugs_match_serde_names() {
+        for descriptor in PROVIDER_DESCRIPTORS {
+            let serialized = serde_json::to_value(descriptor.id).unwrap();
+            assert_eq!(serialized.as_str(), Some(descriptor.slug));
+            assert_eq!(ProviderId::from_slug(descriptor.slug), Some(descriptor.id));
+        }
+    }
+
+    #[test]
+    fn only_plugins_report_status_through_their_fetcher() {
+        for descriptor in PROVIDER_DESCRIPTORS {
+            assert_eq!(
+                descriptor.status_source == StatusSource::Fetcher,
+                descriptor.id == ProviderId::Plugin,
+                "{:?}",
+                descriptor.id
+            );
+        }
+    }
+
+    #[test]
+    fn cookie_domains_are_only_set_for_cookie_providers() {
+        for descriptor in PROVIDER_DESCRIPTORS {
+            assert_eq!(
+                descriptor.uses_cookies(),
+                !descriptor.cookie_domains.is_empty(),
+                "{:?}",
+                descriptor.id
+            );
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Events of the background refresh loop
+//!
+//! The loop reports through a [`RefreshEmitter`], so the same scheduling drives the app,
+//! which forwards each event to the webview, and `incubar watch`, which prints them.
+
+use super::{AccountKey, ProviderError, ProviderStatus, UsageSnapshot};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// An event named as the webview knows it, e.g. `usage-updated`, with its payload
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshEvent {
+    pub event: &'static str,
+    #[serde(flatten)]
+    pub payload: serde_json::Value,
+}
+
+impl RefreshEvent {
+    pub fn usage_updated(key: &AccountKey, usage: &UsageSnapshot) -> Self {
+        Self {
+            event: "usage-updated",
+            payload: serde_json::json!({
+                "providerId": key.provider_id,
+                "accountId": key.account_id,
+                "usage": usage,
+            }),
+        }
+    }
+
+    /// `key` is the status key, the default account unless the account reports its own
+    pub fn status_updated(key: &AccountKey, status: &ProviderStatus) -> Self {
+        Self {
+            event: "status-updated",
+            payload: serde_json::json!({
+                "providerId": key.provider_id,
+                "accountId": key.account_id,
+                "status": status,
+            }),
+        }
+    }
+
+    pub fn refresh_failed(key: &AccountKey, usage: &UsageSnapshot, error: &ProviderError) -> Self {
+        Self {
+            event: "refresh-failed",
+            payload: serde_json::json!({
+                "providerId": key.provider_id,
+                "accountId": key.account_id,
+                "usage": usage,
+                "errorKind": error.kind(),
+            }),
+        }
+    }
+}
+
+/// Where the refresh loop's events go
+pub trait RefreshEmitter: Send + Sync {
+    fn emit_event(&self, event: RefreshEvent);
+}
+
+impl RefreshEmitter for AppHandle {
+    fn emit_event(&self, event: RefreshEvent) {
+        let _ = self.emit(event.event, event.payload);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ProviderId;
+
+    #[test]
+    fn events_serialize_as_one_flat_object() {
+        let key = AccountKey::new(ProviderId::Codex, "work");
+        let usage = UsageSnapshot::error("offline".to_string());
+        let error = ProviderError::Network("offline".to_string());
+        let line =
+            serde_json::to_value(RefreshEvent::refresh_failed(&key, &usage, &error)).unwrap();
+
+        assert_eq!(line["event"], "refresh-failed");
+        assert_eq!(line["providerId"], "codex");
+        assert_eq!(line["accountId"], "work");
+        assert_eq!(line["errorKind"], "network");
+        assert_eq!(line["usage"]["error"], "offline");
+    }
+}
@@ -0,0 +1,301 @@
+//! Per-provider base URL overrides
+//!
+//! Every URL a provider requests goes through [`endpoint`], which moves it onto an
+//! override base when one is configured. The scheme, host and port come from the base;
+//! the default path is appended to the base's own path, so `https://proxy.corp/anthropic`
+//! turns `https://api.anthropic.com/api/oauth/usage` into
+//! `https://proxy.corp/anthropic/api/oauth/usage`.
+//!
+//! Providers that talk to several hosts (Copilot, Gemini, Vertex AI) can override each
+//! host on its own. The base for a URL is the first of:
+//!
+//! 1. `INCUBAR_<SLUG>_<HOST>_BASE_URL`, e.g. `INCUBAR_COPILOT_API_GITHUB_COM_BASE_URL`
+//! 2. `INCUBAR_<SLUG>_BASE_URL`, e.g. `INCUBAR_KIMI_K2_BASE_URL`
+//! 3. the base saved in settings for the URL's host
+//! 4. the base saved in settings for the provider
+
+use super::ProviderId;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use url::Url;
+
+/// Base URLs saved in settings
+#[derive(Debug, Default)]
+struct Overrides {
+    /// Applies to every URL of the provider
+    providers: HashMap<ProviderId, String>,
+    /// Applies to the URLs of one of the provider's hosts, keyed by the default host
+    hosts: HashMap<(ProviderId, String), String>,
+}
+
+static BASE_URL_OVERRIDES: Lazy<RwLock<Overrides>> =
+    Lazy::new(|| RwLock::new(Overrides::default()));
+
+#[cfg(test)]
+thread_local! {
+    // Per-thread overrides so parallel tests can point providers at their own mock servers
+    static TEST_BASE_URLS: std::cell::RefCell<HashMap<ProviderId, String>> =
+        std::cell::RefCell::new(HashMap::new());
+}
+
+/// Route `provider` to `base` on the current thread only, ahead of every other override
+#[cfg(test)]
+pub fn set_test_base_url(provider: ProviderId, base: Option<String>) {
+    TEST_BASE_URLS.with(|urls| match base {
+        Some(base) => urls.borrow_mut().insert(provider, base),
+        None => urls.borrow_mut().remove(&provider),
+    });
+}
+
+fn valid_base(provider: ProviderId, base: &str) -> Option<String> {
+    let base = base.trim();
+    if base.is_empty() {
+        return None;
+    }
+    if Url::parse(base).is_err() {
+        tracing::warn!("Ignoring invalid base URL for {:?}: {}", provider, base);
+        return None;
+    }
+    Some(base.to_string())
+}
+
+impl Overrides {
+    /// Blank and invalid bases are dropped; hosts are matched case-insensitively
+    fn from_settings(
+        providers: HashMap<ProviderId, String>,
+        hosts: HashMap<ProviderId, HashMap<String, String>>,
+    ) -> Self {
+        Self {
+            providers: providers
+                .into_iter()
+                .filter_map(|(provider, base)| Some((provider, valid_base(provider, &base)?)))
+                .collect(),
+            hosts: hosts
+                .into_iter()
+                .flat_map(|(provider, bases)| {
+                    bases.into_iter().filter_map(move |(host, base)| {
+                        let host = host.trim().to_ascii_lowercase();
+                        Some(((provider, host), valid_base(provider, &base)?))
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Replace the base URL overrides configured in settings: one base per provider, and
+/// per-host bases keyed by the host they replace
+pub fn set_base_url_overrides(
+    providers: HashMap<ProviderId, String>,
+    hosts: HashMap<ProviderId, HashMap<String, String>>,
+) {
+    if let Ok(mut current) = BASE_URL_OVERRIDES.write() {
+        *current = Overrides::from_settings(providers, hosts);
+    }
+}
+
+/// Name of the environment variable that overrides a provider's base URL
+pub fn base_url_env_var(provider: ProviderId) -> String {
+    format!("INCUBAR_{}_BASE_URL", provider.slug().to_ascii_uppercase())
+}
+
+/// Name of the environment variable that overrides one host of a provider
+pub fn host_base_url_env_var(provider: ProviderId, host: &str) -> String {
+    let host: String = host
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!(
+        "INCUBAR_{}_{}_BASE_URL",
+        provider.slug().to_ascii_uppercase(),
+        host
+    )
+}
+
+/// The base URL replacing `host` for a provider, or `None` to keep the default
+fn resolve_base(
+    provider: ProviderId,
+    host: &str,
+    env: impl Fn(&str) -> Option<String>,
+    overrides: &Overrides,
+) -> Option<String> {
+    let from_env = |name: String| {
+        env(&name)
+            .map(|base| base.trim().to_string())
+            .filter(|base| !base.is_empty())
+    };
+    from_env(host_base_url_env_var(provider, host))
+        .or_else(|| from_env(base_url_env_var(provider)))
+        .or_else(|| overrides.hosts.get(&(provider, host.to_string())).cloned())
+        .or_else(|| overrides.providers.get(&provider).cloned())
+}
+
+/// Resolve `default_url` for `provider`, applying the base URL override if any. Call it
+/// for every request, so changed settings apply without restarting.
+pub fn endpoint(provider: ProviderId, default_url: &str) -> String {
+    #[cfg(test)]
+    if let Some(base) = TEST_BASE_URLS.with(|urls| urls.borrow().get(&provider).cloned()) {
+        return rebase(default_url, &base).unwrap_or_else(|| default_url.to_string());
+    }
+    let host = Url::parse(default_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_default();
+    let base = match BASE_URL_OVERRIDES.read() {
+        Ok(overrides) => resolve_base(provider, &host, |name| std::env::var(name).ok(), &overrides),
+        Err(_) => None,
+    };
+    let Some(base) = base else {
+        return default_url.to_string();
+    };
+    match rebase(default_url, &base) {
+        Some(url) => url,
+        None => {
+            tracing::warn!("Ignoring invalid base URL for {:?}: {}", provider, base);
+            default_url.to_string()
+        }
+    }
+}
+
+fn rebase(default_url: &str, base: &str) -> Option<String> {
+    let default = Url::parse(default_url).ok()?;
+    let mut rebased = Url::parse(base).ok()?;
+    if rebased.cannot_be_a_base() {
+        return None;
+    }
+
+    let prefix = rebased.path().trim_end_matches('/').to_string();
+    rebased.set_path(&format!("{}{}", prefix, default.path()));
+    rebased.set_query(default.query());
+    rebased.set_fragment(None);
+
+    let mut url = rebased.to_string();
+    // Bare hosts such as "https://opencode.ai" are used as prefixes for further paths
+    if !default_url.ends_with('/') && url.ends_with('/') && rebased.query().is_none() {
+        url.pop();
+    }
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebases_path_and_query_onto_base() {
+        assert_eq!(
+            rebase(
+                "https://api.anthropic.com/api/oauth/usage",
+                "https://proxy.corp/anthropic/"
+            )
+            .as_deref(),
+            Some("https://proxy.corp/anthropic/api/oauth/usage")
+        );
+        assert_eq!(
+            rebase("https://opencode.ai/_server?id=1", "http://127.0.0.1:8080").as_deref(),
+            Some("http://127.0.0.1:8080/_server?id=1")
+        );
+        assert_eq!(
+            rebase("https://opencode.ai", "http://127.0.0.1:8080").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(rebase("https://cursor.com/api/auth/me", "not a url"), None);
+    }
+
+    #[test]
+    fn environment_takes_precedence_over_settings() {
+        let overrides = Overrides {
+            providers: HashMap::from([(
+                ProviderId::Copilot,
+                "https://settings.example".to_string(),
+            )]),
+            hosts: HashMap::from([(
+                (ProviderId::Copilot, "github.com".to_string()),
+                "https://login.settings.example".to_string(),
+            )]),
+        };
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            resolve_base(ProviderId::Copilot, "api.github.com", env(&[]), &overrides).as_deref(),
+            Some("https://settings.example")
+        );
+        assert_eq!(
+            resolve_base(ProviderId::Copilot, "github.com", env(&[]), &overrides).as_deref(),
+            Some("https://login.settings.example")
+        );
+        assert_eq!(
+            resolve_base(
+                ProviderId::Copilot,
+                "github.com",
+                env(&[("INCUBAR_COPILOT_BASE_URL", "http://localhost:9000")]),
+                &overrides
+            )
+            .as_deref(),
+            Some("http://localhost:9000")
+        );
+        assert_eq!(
+            resolve_base(
+                ProviderId::Copilot,
+                "api.github.com",
+                env(&[
+                    ("INCUBAR_COPILOT_BASE_URL", "http://localhost:9000"),
+                    (
+                        "INCUBAR_COPILOT_API_GITHUB_COM_BASE_URL",
+                        "http://localhost:9001"
+                    ),
+                ]),
+                &overrides
+            )
+            .as_deref(),
+            Some("http://localhost:9001")
+        );
+        assert_eq!(
+            resolve_base(
+                ProviderId::KimiK2,
+                "kimi-k2.ai",
+                env(&[("INCUBAR_KIMI_K2_BASE_URL", "   ")]),
+                &overrides
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn drops_blank_and_invalid_settings() {
+        let overrides = Overrides::from_settings(
+            HashMap::from([
+                (ProviderId::Kimi, "   ".to_string()),
+                (ProviderId::Amp, "not a url".to_string()),
+            ]),
+            HashMap::from([(
+                ProviderId::Gemini,
+                HashMap::from([(
+                    " OAuth2.googleapis.com ".to_string(),
+                    "https://token.corp".to_string(),
+                )]),
+            )]),
+        );
+        assert!(overrides.providers.is_empty());
+        assert_eq!(
+            overrides
+                .hosts
+                .get(&(ProviderId::Gemini, "oauth2.googleapis.com".to_string()))
+                .map(String::as_str),
+            Some("https://token.corp")
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Typed provider failures
+//!
+//! Fetchers keep using `anyhow` internally and attach a [`ProviderError`] where they
+//! know what went wrong, e.g. `Err(ProviderError::from_status(status, message).into())`.
+//! At the `ProviderFetcher::fetch` boundary the `anyhow::Error` is turned back into a
+//! `ProviderError`: an attached one is kept, `reqwest` and `serde_json` errors are
+//! classified, and anything else becomes `Other`.
+
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ProviderError {
+    /// No credentials, or the user is not logged in
+    #[error("{0}")]
+    NotAuthenticated(String),
+    /// Credentials exist but were rejected or have expired
+    #[error("{0}")]
+    TokenExpired(String),
+    /// Credentials were accepted but do not grant access, e.g. no plan or a missing scope
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("{message}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// Connection failures, DNS, TLS
+    #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Timeout(String),
+    /// Any other unsuccessful HTTP status
+    #[error("{message}")]
+    Http { status: u16, message: String },
+    /// The response did not have the expected shape
+    #[error("{0}")]
+    Parse(String),
+    /// A CLI, app or local server the provider depends on is missing or not running
+    #[error("{0}")]
+    Unavailable(String),
+    /// Cancelled, or replaced by a forced refresh
+    #[error("Fetch was cancelled")]
+    Cancelled,
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Kind of a [`ProviderError`], sent to the frontend with `refresh-failed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderErrorKind {
+    NotAuthenticated,
+    TokenExpired,
+    Forbidden,
+    RateLimited,
+    Network,
+    Timeout,
+    Http,
+    Parse,
+    Unavailable,
+    Cancelled,
+    Other,
+}
+
+impl ProviderError {
+    /// Classify an unsuccessful HTTP status
+    pub fn from_status(status: reqwest::StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status.as_u16() {
+            401 => Self::TokenExpired(message),
+            403 => Self::Forbidden(message),
+            429 => Self::RateLimited {
+                retry_after: None,
+                message,
+            },
+            408 | 504 => Self::Timeout(message),
+            code => Self::Http {
+                status: code,
+                message,
+            },
+        }
+    }
+
+    pub fn kind(&self) -> ProviderErrorKind {
+        match self {
+            Self::NotAuthenticated(_) => ProviderErrorKind::NotAuthenticated,
+            Self::TokenExpired(_) => ProviderErrorKind::TokenExpired,
+            Self::Forbidden(_) => ProviderErrorKind::Forbidden,
+            Self::RateLimited { .. } => ProviderErrorKind::RateLimited,
+            Self::Network(_) => ProviderErrorKind::Network,
+            Self::Timeout(_) => ProviderErrorKind::Timeout,
+            Self::Http { .. } => ProviderErrorKind::Http,
+            Self::Parse(_) => ProviderErrorKind::Parse,
+            Self::Unavailable(_) => ProviderErrorKind::Unavailable,
+            Self::Cancelled => ProviderErrorKind::Cancelled,
+            Self::Other(_) => ProviderErrorKind::Other,
+        }
+    }
+
+    /// Failures that retrying cannot fix until the user signs in or starts something
+    pub fn needs_user_action(&self) -> bool {
+        matches!(
+            self,
+            Self::NotAuthenticated(_)
+                | Self::TokenExpired(_)
+                | Self::Forbidden(_)
+                | Self::Unavailable(_)
+        )
+    }
+
+    /// Failures worth retrying soon: outages, timeouts and server errors
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout(_) | Self::Other(_) => true,
+            Self::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Error for a fetcher whose sign-in flow failed. Failures that were already
+    /// classified keep their kind; the message gets the usual "Not authenticated" prefix.
+    pub(crate) fn not_authenticated(err: anyhow::Error) -> Self {
+        let message = format!("Not authenticated: {}", err);
+        match Self::from(err) {
+            Self::Other(_) | Self::NotAuthenticated(_) => Self::NotAuthenticated(message),
+            Self::TokenExpired(_) => Self::TokenExpired(message),
+            Self::Forbidden(_) => Self::Forbidden(message),
+            Self::RateLimited { retry_after, .. } => Self::RateLimited {
+                retry_after,
+                message,
+            },
+            Self::Network(_) => Self::Network(message),
+            Self::Timeout(_) => Self::Timeout(message),
+            Self::Http { status, .. } => Self::Http { status, message },
+            Self::Parse(_) => Self::Parse(message),
+            Self::Unavailable(_) => Self::Unavailable(message),
+            Self::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ProviderError {
+    fn from(err: anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(provider_error) = cause.downcast_ref::<ProviderError>() {
+                return provider_error.clone();
+            }
+            if let Some(reqwest_error) = cause.downcast_ref::<reqwest::Error>() {
+                return Self::from_reqwest(reqwest_error, &err.to_string());
+            }
+            if cause.downcast_ref::<serde_json::Error>().is_some() {
+                return Self::Parse(err.to_string());
+            }
+        }
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<reqwest::Error> for ProviderError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::from_reqwest(&err, &err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ProviderError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
+
+impl ProviderError {
+    fn from_reqwest(err: &reqwest::Error, message: &str) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(message.to_string())
+        } else if err.is_decode() {
+            Self::Parse(message.to_string())
+        } else if let Some(status) = err.status() {
+            Self::from_status(status, message)
+        } else if err.is_connect() || err.is_request() {
+            Self::Network(message.to_string())
+        } else {
+            Self::Other(message.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn classifies_http_statuses() {
+        assert_eq!(
+            ProviderError::from_status(StatusCode::UNAUTHORIZED, "Unauthorized").kind(),
+            ProviderErrorKind::TokenExpired
+        );
+        let forbidden = ProviderError::from_status(StatusCode::FORBIDDEN, "No Copilot plan");
+        assert_eq!(forbidden.kind(), ProviderErrorKind::Forbidden);
+        assert!(forbidden.needs_user_action());
+        assert_eq!(
+            ProviderError::from_status(StatusCode::TOO_MANY_REQUESTS, "Slow down").kind(),
+            ProviderErrorKind::RateLimited
+        );
+        let server = ProviderError::from_status(StatusCode::BAD_GATEWAY, "Cursor API error: 502");
+        assert_eq!(server.to_string(), "Cursor API error: 502");
+        assert!(server.is_transient());
+        assert!(!ProviderError::from_status(StatusCode::NOT_FOUND, "Not found").is_transient());
+    }
+
+    #[test]
+    fn keeps_attached_errors_through_anyhow() {
+        let err: anyhow::Error = ProviderError::TokenExpired("expired".to_string()).into();
+        let err = err.context("Claude OAuth failed");
+        assert_eq!(
+            ProviderError::from(err),
+            ProviderError::TokenExpired("expired".to_string())
+        );
+
+        let parse = serde_json::from_str::<serde_json::Value>("{")
+            .context("Failed to parse usage")
+            .unwrap_err();
+        assert_eq!(ProviderError::from(parse).kind(), ProviderErrorKind::Parse);
+
+        let other = ProviderError::from(anyhow::anyhow!("Something else"));
+        assert_eq!(other, ProviderError::Other("Something else".to_string()));
+    }
+
+    #[test]
+    fn sign_in_failures_keep_their_kind() {
+        let missing = ProviderError::not_authenticated(anyhow::anyhow!("No credentials"));
+        assert_eq!(
+            missing,
+            ProviderError::NotAuthenticated("Not authenticated: No credentials".to_string())
+        );
+
+        let limited: anyhow::Error =
+            ProviderError::from_status(StatusCode::TOO_MANY_REQUESTS, "Usage API: 429").into();
+        let limited = ProviderError::not_authenticated(limited);
+        assert_eq!(limited.kind(), ProviderErrorKind::RateLimited);
+        assert_eq!(limited.to_string(), "Not authenticated: Usage API: 429");
+    }
+}
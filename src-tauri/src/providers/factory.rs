@@ -4,7 +4,14 @@
 //! Uses cookie-based authentication via browser cookie import or stored session.
 //! Endpoint: https://app.factory.ai/api/usage
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::quota_json::{self, QuotaEntry};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde_json::Value;
 
@@ -12,6 +19,8 @@ const USAGE_URL: &str = "https://app.factory.ai/api/usage";
 
 pub struct FactoryProvider {
     client: reqwest::Client,
+    /// Account-specific Factory session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl FactoryProvider {
@@ -21,7 +30,18 @@ impl FactoryProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Factory session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(
@@ -30,18 +50,17 @@ impl FactoryProvider {
     ) -> Result<UsageSnapshot, anyhow::Error> {
         let response = self
             .client
-            .get(USAGE_URL)
+            .get(endpoint(ProviderId::Factory, USAGE_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Factory)
             .await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Factory API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("Factory API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let raw_json = response.text().await?;
@@ -53,8 +72,8 @@ impl FactoryProvider {
 
     fn parse_usage_response(&self, json: &Value) -> Result<UsageSnapshot, anyhow::Error> {
         let base = json.get("data").unwrap_or(json);
-        let reset_fallback = self.find_reset_time(base);
-        let window_minutes = self.find_window_minutes(base);
+        let reset_fallback = quota_json::find_reset_time(base);
+        let window_minutes = quota_json::find_window_minutes(base);
 
         let quotas = self.extract_quota_entries(base, reset_fallback.clone(), window_minutes);
         if quotas.is_empty() {
@@ -64,7 +83,7 @@ impl FactoryProvider {
         let primary = quotas.get(0).map(|q| q.to_rate_window());
         let secondary = quotas.get(1).map(|q| q.to_rate_window());
 
-        let plan = self.find_string(
+        let plan = quota_json::find_string(
             base,
             &[
                 "plan",
@@ -76,7 +95,7 @@ impl FactoryProvider {
             ],
         );
 
-        let email = self.find_string(base, &["email", "userEmail", "user_email"]);
+        let email = quota_json::find_string(base, &["email", "userEmail", "user_email"]);
 
         Ok(UsageSnapshot {
             primary,
@@ -92,6 +111,10 @@ impl FactoryProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: quotas.iter().map(QuotaEntry::to_rate_window).collect(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         })
     }
 
@@ -161,7 +184,7 @@ impl FactoryProvider {
         reset_fallback: Option<String>,
         window_minutes: Option<i32>,
     ) -> Option<QuotaEntry> {
-        let used = self.find_double(
+        let used = quota_json::find_double(
             value,
             &[
                 "used",
@@ -176,7 +199,7 @@ impl FactoryProvider {
                 "usedTotal",
             ],
         )?;
-        let limit = self.find_double(
+        let limit = quota_json::find_double(
             value,
             &[
                 "limit",
@@ -199,169 +222,21 @@ impl FactoryProvider {
             0.0
         };
 
-        let resets_at = self.find_reset_time(value).or(reset_fallback.clone());
-        let reset_description = resets_at.as_ref().and_then(|t| self.format_reset_time(t));
+        let resets_at = quota_json::find_reset_time(value).or(reset_fallback.clone());
+        let reset_description = resets_at.as_ref().and_then(|t| quota_json::format_reset_time(t));
 
         Some(QuotaEntry {
             label,
             used_percent: used_percent.clamp(0.0, 100.0),
+            used: Some(used),
+            limit: Some(limit),
+            unit: quota_json::find_unit(value).or_else(|| Some("tokens".to_string())),
             window_minutes,
             resets_at,
             reset_description,
         })
     }
 
-    fn find_string(&self, json: &Value, keys: &[&str]) -> Option<String> {
-        for key in keys {
-            if let Some(value) = json.get(*key) {
-                if let Some(s) = value.as_str() {
-                    let trimmed = s.trim();
-                    if !trimmed.is_empty() {
-                        return Some(trimmed.to_string());
-                    }
-                }
-            }
-        }
-        None
-    }
-
-    fn find_double(&self, json: &Value, keys: &[&str]) -> Option<f64> {
-        for key in keys {
-            if let Some(value) = json.get(*key) {
-                if let Some(number) = self.to_double(value) {
-                    return Some(number);
-                }
-            }
-        }
-        None
-    }
-
-    fn find_window_minutes(&self, json: &Value) -> Option<i32> {
-        if let Some(minutes) = self.find_double(
-            json,
-            &[
-                "windowMinutes",
-                "window_minutes",
-                "periodMinutes",
-                "period_minutes",
-            ],
-        ) {
-            return Some(minutes as i32);
-        }
-        if let Some(hours) = self.find_double(
-            json,
-            &["windowHours", "window_hours", "periodHours", "period_hours"],
-        ) {
-            return Some((hours * 60.0) as i32);
-        }
-        if let Some(days) = self.find_double(
-            json,
-            &["windowDays", "window_days", "periodDays", "period_days"],
-        ) {
-            return Some((days * 24.0 * 60.0) as i32);
-        }
-        if let Some(seconds) = self.find_double(
-            json,
-            &[
-                "windowSeconds",
-                "window_seconds",
-                "periodSeconds",
-                "period_seconds",
-            ],
-        ) {
-            return Some((seconds / 60.0) as i32);
-        }
-        None
-    }
-
-    fn find_reset_time(&self, json: &Value) -> Option<String> {
-        let timestamp_keys = [
-            "resetAt",
-            "reset_at",
-            "resetsAt",
-            "resets_at",
-            "resetTime",
-            "reset_time",
-            "nextReset",
-            "next_reset",
-            "renewAt",
-            "renew_at",
-            "periodEnd",
-            "period_end",
-        ];
-
-        for key in &timestamp_keys {
-            if let Some(value) = json.get(*key) {
-                if let Some(ts) = self.to_double(value) {
-                    return self.timestamp_to_iso(ts);
-                }
-                if let Some(s) = value.as_str() {
-                    if let Ok(ts) = s.trim().parse::<f64>() {
-                        return self.timestamp_to_iso(ts);
-                    }
-                    if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
-                        return Some(s.to_string());
-                    }
-                }
-            }
-        }
-
-        let duration_keys = [
-            "resetInSeconds",
-            "reset_in_seconds",
-            "resetSeconds",
-            "reset_seconds",
-            "resetIn",
-            "reset_in",
-        ];
-
-        for key in &duration_keys {
-            if let Some(seconds) = json.get(*key).and_then(|v| self.to_double(v)) {
-                if seconds > 0.0 {
-                    let reset_at = chrono::Utc::now() + chrono::Duration::seconds(seconds as i64);
-                    return Some(reset_at.to_rfc3339());
-                }
-            }
-        }
-
-        None
-    }
-
-    fn timestamp_to_iso(&self, timestamp: f64) -> Option<String> {
-        let seconds = if timestamp > 1_000_000_000_000.0 {
-            (timestamp / 1000.0) as i64
-        } else {
-            timestamp as i64
-        };
-        chrono::DateTime::from_timestamp(seconds, 0).map(|dt| dt.to_rfc3339())
-    }
-
-    fn to_double(&self, value: &Value) -> Option<f64> {
-        match value {
-            Value::Number(n) => n.as_f64(),
-            Value::String(s) => s.trim().parse().ok(),
-            _ => None,
-        }
-    }
-
-    fn format_reset_time(&self, iso_time: &str) -> Option<String> {
-        let reset_date = chrono::DateTime::parse_from_rfc3339(iso_time).ok()?;
-        let now = chrono::Utc::now();
-        let duration = reset_date.signed_duration_since(now);
-
-        if duration.num_seconds() <= 0 {
-            return Some("Resets soon".to_string());
-        }
-
-        if duration.num_hours() < 1 {
-            Some(format!("Resets in {} min", duration.num_minutes().max(1)))
-        } else if duration.num_hours() < 24 {
-            Some(format!("Resets in {}h", duration.num_hours()))
-        } else {
-            Some(format!("Resets in {} days", duration.num_days()))
-        }
-    }
-
     async fn load_stored_cookies(&self) -> Result<String, anyhow::Error> {
         let session_path = self.get_session_path()?;
         if session_path.exists() {
@@ -386,6 +261,10 @@ impl FactoryProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("factory-session.json"))
@@ -402,7 +281,7 @@ impl ProviderFetcher for FactoryProvider {
         "Factory AI Droid"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Factory usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -416,11 +295,15 @@ impl ProviderFetcher for FactoryProvider {
                 if let Err(e) = self.store_session(&result.cookie_header).await {
                     tracing::debug!("Failed to store Factory session: {}", e);
                 }
-                self.fetch_with_cookies(&result.cookie_header).await
+                Ok(self.fetch_with_cookies(&result.cookie_header).await?)
             }
-            Err(e) => Err(anyhow::anyhow!("Not authenticated: {}", e)),
+            Err(e) => Err(ProviderError::not_authenticated(e)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -429,32 +312,32 @@ struct FactorySession {
     cookie_header: String,
 }
 
-#[derive(Debug)]
-struct QuotaEntry {
-    label: Option<String>,
-    used_percent: f64,
-    window_minutes: Option<i32>,
-    resets_at: Option<String>,
-    reset_description: Option<String>,
-}
-
-impl QuotaEntry {
-    fn to_rate_window(&self) -> RateWindow {
-        RateWindow {
-            used_percent: self.used_percent,
-            window_minutes: self.window_minutes,
-            resets_at: self.resets_at.clone(),
-            reset_description: self.reset_description.clone(),
-            label: self.label.clone(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
     use serde_json::json;
 
+    #[tokio::test]
+    async fn replays_cookie_usage() {
+        let server = ReplayServer::from_fixture("factory-cookies")
+            .await
+            .route(ProviderId::Factory);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("factory-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "session=abc"}"#).unwrap();
+
+        let usage = FactoryProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        assert_eq!(usage.primary.expect("primary").used_percent, 25.0);
+        assert_eq!(usage.secondary.expect("secondary").used_percent, 25.0);
+        assert_eq!(usage.identity.and_then(|i| i.plan), Some("Pro".to_string()));
+        assert_eq!(server.received()[0].headers["cookie"], "session=abc");
+    }
+
     #[test]
     fn parses_usage_with_reset() {
         let provider = FactoryProvider::new();
@@ -0,0 +1,597 @@
+//! HTTP fixture recording and replay
+//!
+//! While fixture recording is enabled in the debug settings, every provider request
+//! sent with [`RecordingSend::send_recorded`] is appended, with credentials and
+//! personal data redacted, to `<slug>.jsonl` in the fixtures directory
+//! (`INCUBAR_FIXTURES_DIR` or `IncuBar/fixtures` in the data dir). Each line is one
+//! request/response exchange.
+//!
+//! Tests copy those lines into `src-tauri/fixtures/providers/` and replay them with
+//! `ReplayServer`, which serves the exchanges from a local server that the provider is
+//! routed to through its base URL override.
+//!
+//! Every provider that reads its credentials from a file has a replay test. The rest
+//! are covered by parser unit tests instead: Gemini also reads the CLI settings in the
+//! home directory, z.ai, Kimi K2 and Synthetic take API keys from the environment or
+//! the keychain, Antigravity, JetBrains, Kiro and OpenCode query local apps and CLIs,
+//! and custom and plugin providers are exercised through the registry.
+
+use super::{rate_limit, ProviderId};
+use crate::debug_settings;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+const FIXTURES_DIR_ENV: &str = "INCUBAR_FIXTURES_DIR";
+const REDACTED: &str = "[redacted]";
+
+/// Headers whose values are always replaced before writing a fixture
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-goog-api-key",
+    "chatgpt-account-id",
+];
+
+/// JSON, form and query keys whose values are replaced, compared lowercase without
+/// separators
+const SENSITIVE_KEYS: &[&str] = &[
+    "accesstoken",
+    "refreshtoken",
+    "idtoken",
+    "token",
+    "apikey",
+    "secret",
+    "clientsecret",
+    "password",
+    "email",
+    "devicecode",
+    "usercode",
+    "cookie",
+    "authorization",
+    "accountid",
+    "userid",
+];
+
+/// Response headers that describe the original transfer rather than the content
+const TRANSFER_HEADERS: &[&str] = &["content-length", "transfer-encoding", "connection"];
+
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+
+/// One recorded request and the response it received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Exchange {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query, without scheme and host
+    pub path: String,
+    /// Header names are lowercase
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Sends a provider request, recording the exchange when fixture recording is enabled and
+/// noting rate-limiting responses for the fetch that sent it
+#[async_trait]
+pub trait RecordingSend {
+    async fn send_recorded(self, provider: ProviderId) -> reqwest::Result<reqwest::Response>;
+}
+
+#[async_trait]
+impl RecordingSend for reqwest::RequestBuilder {
+    async fn send_recorded(self, provider: ProviderId) -> reqwest::Result<reqwest::Response> {
+        let response = if !debug_settings::record_fixtures_enabled() {
+            self.send().await
+        } else {
+            match fixtures_dir() {
+                Ok(dir) => send_and_record(&dir, provider, self).await,
+                Err(err) => {
+                    tracing::warn!("Failed to resolve fixtures directory: {}", err);
+                    self.send().await
+                }
+            }
+        };
+        if let Ok(response) = &response {
+            rate_limit::observe(response);
+        }
+        response
+    }
+}
+
+/// Directory recorded fixtures are written to
+pub fn fixtures_dir() -> Result<PathBuf, anyhow::Error> {
+    if let Ok(dir) = std::env::var(FIXTURES_DIR_ENV) {
+        if !dir.trim().is_empty() {
+            return Ok(PathBuf::from(dir.trim()));
+        }
+    }
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    Ok(data_dir.join("IncuBar").join("fixtures"))
+}
+
+async fn send_and_record(
+    dir: &Path,
+    provider: ProviderId,
+    builder: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = builder.build_split();
+    let request = request?;
+    let recorded_request = record_request(&request);
+
+    let response = client.execute(request).await?;
+    let status = response.status();
+    let version = response.version();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+
+    let exchange = Exchange {
+        request: recorded_request,
+        response: RecordedResponse {
+            status: status.as_u16(),
+            headers: record_headers(&headers, TRANSFER_HEADERS),
+            body: redact_body(&String::from_utf8_lossy(&body)),
+        },
+    };
+    if let Err(err) = append_exchange(dir, provider, &exchange).await {
+        tracing::warn!("Failed to record {} fixture: {}", provider.slug(), err);
+    }
+
+    // The body has been consumed, so hand the provider an equivalent response
+    let mut replayed = http::Response::new(body);
+    *replayed.status_mut() = status;
+    *replayed.version_mut() = version;
+    *replayed.headers_mut() = headers;
+    Ok(reqwest::Response::from(replayed))
+}
+
+async fn append_exchange(
+    dir: &Path,
+    provider: ProviderId,
+    exchange: &Exchange,
+) -> Result<(), anyhow::Error> {
+    tokio::fs::create_dir_all(dir).await?;
+    let mut line = serde_json::to_string(exchange)?;
+    line.push('\n');
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.jsonl", provider.slug())))
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+fn record_request(request: &reqwest::Request) -> RecordedRequest {
+    let url = request.url();
+    let mut path = url.path().to_string();
+    if url.query().is_some() {
+        let query: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if is_sensitive_key(&key) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        let encoded = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query)
+            .finish();
+        path = format!("{}?{}", path, encoded);
+    }
+
+    RecordedRequest {
+        method: request.method().to_string(),
+        path,
+        headers: record_headers(request.headers(), &[]),
+        body: request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| redact_body(&String::from_utf8_lossy(bytes))),
+    }
+}
+
+fn record_headers(headers: &reqwest::header::HeaderMap, skip: &[&str]) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| !skip.contains(&name.as_str()))
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                redact_text(&String::from_utf8_lossy(value.as_bytes()))
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    SENSITIVE_KEYS.contains(&normalized.as_str())
+}
+
+/// Redact a request or response body: JSON and form fields by key, emails anywhere
+fn redact_body(body: &str) -> String {
+    if let Ok(mut json) = serde_json::from_str::<Value>(body) {
+        redact_json(&mut json);
+        return json.to_string();
+    }
+
+    let trimmed = body.trim();
+    if trimmed.contains('=') && !trimmed.contains(char::is_whitespace) && !trimmed.contains('<') {
+        let pairs: Vec<(String, String)> = url::form_urlencoded::parse(trimmed.as_bytes())
+            .map(|(key, value)| {
+                let value = if is_sensitive_key(&key) {
+                    REDACTED.to_string()
+                } else {
+                    redact_text(&value)
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        return url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish();
+    }
+
+    redact_text(body)
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::String(text) => *text = redact_text(text),
+        _ => {}
+    }
+}
+
+fn redact_text(text: &str) -> String {
+    EMAIL_RE.replace_all(text, REDACTED).into_owned()
+}
+
+#[cfg(test)]
+pub(crate) use replay::ReplayServer;
+
+#[cfg(test)]
+mod replay {
+    use super::{Exchange, RecordedRequest};
+    use crate::providers::endpoints::set_test_base_url;
+    use crate::providers::ProviderId;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[derive(Default)]
+    struct ReplayState {
+        pending: Vec<Exchange>,
+        received: Vec<RecordedRequest>,
+    }
+
+    /// Local HTTP server answering requests from recorded exchanges
+    ///
+    /// Each exchange answers one request with the same method and path (falling back
+    /// to the path without its query), in recording order. Requests without a match
+    /// get a 404. Routed providers go back to their real endpoints on drop.
+    pub(crate) struct ReplayServer {
+        base_url: String,
+        state: Arc<Mutex<ReplayState>>,
+        routed: Vec<ProviderId>,
+    }
+
+    impl ReplayServer {
+        pub async fn start(exchanges: Vec<Exchange>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+            let state = Arc::new(Mutex::new(ReplayState {
+                pending: exchanges,
+                received: Vec::new(),
+            }));
+
+            let server_state = state.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let state = server_state.clone();
+                    tokio::spawn(async move {
+                        let _ = serve(stream, state).await;
+                    });
+                }
+            });
+
+            Self {
+                base_url,
+                state,
+                routed: Vec::new(),
+            }
+        }
+
+        /// Serve `src-tauri/fixtures/providers/<name>.jsonl`
+        pub async fn from_fixture(name: &str) -> Self {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("fixtures")
+                .join("providers")
+                .join(format!("{}.jsonl", name));
+            let content = std::fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("read fixture {}: {}", path.display(), err));
+            let exchanges = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).expect("fixture exchange"))
+                .collect();
+            Self::start(exchanges).await
+        }
+
+        /// Send the provider's requests on this thread to the replay server
+        pub fn route(mut self, provider: ProviderId) -> Self {
+            set_test_base_url(provider, Some(self.base_url.clone()));
+            self.routed.push(provider);
+            self
+        }
+
+        /// Address of the server, for providers whose URL is configured rather than
+        /// resolved through `endpoint`
+        pub fn base_url(&self) -> &str {
+            &self.base_url
+        }
+
+        /// Requests received so far, with real (unredacted) header values
+        pub fn received(&self) -> Vec<RecordedRequest> {
+            self.state.lock().unwrap().received.clone()
+        }
+
+        /// Exchanges that no request has consumed yet
+        pub fn pending(&self) -> Vec<Exchange> {
+            self.state.lock().unwrap().pending.clone()
+        }
+    }
+
+    impl Drop for ReplayServer {
+        fn drop(&mut self) {
+            for provider in &self.routed {
+                set_test_base_url(*provider, None);
+            }
+        }
+    }
+
+    async fn serve(mut stream: TcpStream, state: Arc<Mutex<ReplayState>>) -> std::io::Result<()> {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(index) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break index + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let path = request_line.next().unwrap_or_default().to_string();
+        let headers: BTreeMap<String, String> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+
+        let content_length = headers
+            .get("content-length")
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + content_length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[header_end..]).to_string();
+
+        let response = {
+            let mut state = state.lock().unwrap();
+            let without_query = path.split('?').next().unwrap_or_default().to_string();
+            let index = state
+                .pending
+                .iter()
+                .position(|exchange| {
+                    exchange.request.method.eq_ignore_ascii_case(&method)
+                        && exchange.request.path == path
+                })
+                .or_else(|| {
+                    state.pending.iter().position(|exchange| {
+                        exchange.request.method.eq_ignore_ascii_case(&method)
+                            && exchange.request.path.split('?').next() == Some(&without_query)
+                    })
+                });
+            state.received.push(RecordedRequest {
+                method: method.clone(),
+                path: path.clone(),
+                headers,
+                body: (!body.is_empty()).then_some(body),
+            });
+            index.map(|index| state.pending.remove(index).response)
+        };
+
+        let (status, headers, body) = match response {
+            Some(response) => (response.status, response.headers, response.body),
+            None => (
+                404,
+                BTreeMap::new(),
+                format!("No fixture for {} {}", method, path),
+            ),
+        };
+
+        let mut reply = format!("HTTP/1.1 {} Replayed\r\n", status);
+        for (name, value) in headers {
+            reply.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        reply.push_str(&format!(
+            "content-length: {}\r\nconnection: close\r\n\r\n",
+            body.len()
+        ));
+        stream.write_all(reply.as_bytes()).await?;
+        stream.write_all(body.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::endpoints::endpoint;
+
+    fn exchange(method: &str, path: &str, status: u16, body: &str) -> Exchange {
+        Exchange {
+            request: RecordedRequest {
+                method: method.to_string(),
+                path: path.to_string(),
+                headers: BTreeMap::new(),
+                body: None,
+            },
+            response: RecordedResponse {
+                status,
+                headers: BTreeMap::from([(
+                    "content-type".to_string(),
+                    "application/json".to_string(),
+                )]),
+                body: body.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn redacts_credentials_and_personal_data() {
+        let json = redact_body(
+            r#"{"access_token":"abc","user":{"email":"a@b.com","plan":"pro"},"note":"mail me at x.y@corp.io","quota":null}"#,
+        );
+        let json: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["access_token"], REDACTED);
+        assert_eq!(json["user"]["email"], REDACTED);
+        assert_eq!(json["user"]["plan"], "pro");
+        assert_eq!(json["note"], "mail me at [redacted]");
+        assert!(json["quota"].is_null());
+
+        let form = redact_body("grant_type=refresh_token&refresh_token=secret&client_id=app");
+        assert_eq!(
+            form,
+            "grant_type=refresh_token&refresh_token=%5Bredacted%5D&client_id=app"
+        );
+
+        assert_eq!(
+            redact_body("<p>Signed in as dev@example.com</p>"),
+            "<p>Signed in as [redacted]</p>"
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_exchanges_in_order() {
+        let server = ReplayServer::start(vec![
+            exchange("GET", "/usage", 401, "{}"),
+            exchange("GET", "/usage", 200, r#"{"ok":true}"#),
+        ])
+        .await
+        .route(ProviderId::Synthetic);
+        let client = reqwest::Client::new();
+        let url = endpoint(ProviderId::Synthetic, "https://api.synthetic.new/usage?x=1");
+
+        let first = client.get(&url).send().await.unwrap();
+        assert_eq!(first.status(), 401);
+        let second = client.get(&url).send().await.unwrap();
+        assert_eq!(second.status(), 200);
+        assert_eq!(second.text().await.unwrap(), r#"{"ok":true}"#);
+        let unmatched = client.post(&url).send().await.unwrap();
+        assert_eq!(unmatched.status(), 404);
+
+        assert_eq!(server.received().len(), 3);
+        assert_eq!(server.received()[0].path, "/usage?x=1");
+        assert!(server.pending().is_empty());
+
+        drop(server);
+        assert!(endpoint(ProviderId::Synthetic, "https://api.synthetic.new/usage")
+            .starts_with("https://api.synthetic.new"));
+    }
+
+    #[tokio::test]
+    async fn records_redacted_exchanges() {
+        let server = ReplayServer::start(vec![exchange(
+            "GET",
+            "/api/usage",
+            200,
+            r#"{"used":5,"email":"dev@example.com"}"#,
+        )])
+        .await
+        .route(ProviderId::Factory);
+        let dir = tempfile::tempdir().unwrap();
+        let request = reqwest::Client::new()
+            .get(endpoint(ProviderId::Factory, "https://app.factory.ai/api/usage?token=t"))
+            .header("Cookie", "session=secret");
+
+        let response = send_and_record(dir.path(), ProviderId::Factory, request)
+            .await
+            .expect("send");
+        // The provider still sees the unredacted body
+        assert!(response.text().await.unwrap().contains("dev@example.com"));
+
+        let content = std::fs::read_to_string(dir.path().join("factory.jsonl")).unwrap();
+        let recorded: Exchange = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(recorded.request.path, "/api/usage?token=%5Bredacted%5D");
+        assert_eq!(recorded.request.headers["cookie"], REDACTED);
+        assert_eq!(recorded.response.status, 200);
+        assert!(!recorded.response.headers.contains_key("content-length"));
+        assert_eq!(
+            recorded.response.body,
+            r#"{"email":"[redacted]","used":5}"#
+        );
+        assert_eq!(server.received()[0].headers["cookie"], "session=secret");
+    }
+}
@@ -0,0 +1,399 @@
+//! Quota exhaustion forecasting
+//!
+//! Fits a line through recent samples of each rate window and of the credit balance to
+//! project when the limit is hit at the current pace. Only samples since the last reset
+//! or top-up count. Without enough history a window falls back to its average pace since
+//! it started, with low confidence.
+
+use super::reset::RESET_DROP_PERCENT;
+use super::{Credits, RateWindow, UsageSnapshot};
+use crate::storage::history::HistoryRecord;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How far back history is read for a forecast
+pub const FORECAST_LOOKBACK_HOURS: i64 = 12;
+/// Samples and hours covered for a fit to count as fully confident
+const CONFIDENT_SAMPLES: f64 = 6.0;
+const CONFIDENT_SPAN_HOURS: f64 = 2.0;
+/// Confidence of the average-pace fallback for a window that has fully elapsed
+const AVERAGE_PACE_CONFIDENCE: f64 = 0.3;
+/// Average pace is not trusted until this much of the window has passed
+const AVERAGE_PACE_MIN_ELAPSED: f64 = 0.05;
+/// Slower than this, per hour, counts as not being used
+const FLAT_RATE: f64 = 1e-6;
+/// Id of the credit balance forecast
+pub const CREDITS_FORECAST_ID: &str = "credits";
+
+/// Projected time-to-exhaustion of a rate window or credit balance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Forecast {
+    /// The `RateWindow::id` forecast, or "credits" for the credit balance
+    pub id: String,
+    /// Percentage points per hour for windows, credit units per hour for credits
+    pub rate_per_hour: f64,
+    /// When the limit is hit at the current pace; missing while usage is flat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exhausts_at: Option<String>,
+    /// The window resets before `exhausts_at`
+    #[serde(default)]
+    pub resets_first: bool,
+    /// 0-1, from how many samples the pace rests on, the time they cover and how well
+    /// they fit a line
+    pub confidence: f64,
+    /// e.g. "Weekly limit hit Thu 14:00 at current pace" or "Credits last ~6 days"
+    pub description: String,
+}
+
+/// Forecasts for every window and the credit balance of `usage`, as of `now`.
+/// `history` holds earlier records of the same account, oldest first.
+pub fn forecast_usage(
+    history: &[HistoryRecord],
+    usage: &UsageSnapshot,
+    now: DateTime<Utc>,
+) -> Vec<Forecast> {
+    if usage.error.is_some() {
+        return Vec::new();
+    }
+    let earlier: Vec<(DateTime<Utc>, &UsageSnapshot)> = history
+        .iter()
+        .filter_map(|record| Some((record.recorded_at()?, record.usage.as_ref()?)))
+        .filter(|(at, usage)| *at < now && usage.error.is_none())
+        .collect();
+
+    let mut forecasts: Vec<Forecast> = usage
+        .rate_windows()
+        .into_iter()
+        .filter_map(|window| {
+            let id = window.id.as_deref()?;
+            let mut samples: Vec<(DateTime<Utc>, f64)> = earlier
+                .iter()
+                .filter_map(|(at, usage)| {
+                    let earlier = usage
+                        .rate_windows()
+                        .into_iter()
+                        .find(|earlier| earlier.id.as_deref() == Some(id))?;
+                    Some((*at, earlier.used_percent))
+                })
+                .collect();
+            samples.push((now, window.used_percent));
+            forecast_window(window, &since_reset(samples, RESET_DROP_PERCENT), now)
+        })
+        .collect();
+
+    if let Some(credits) = usage.credits.as_ref() {
+        // Consumption is the balance going down, so fit its negation
+        let mut samples: Vec<(DateTime<Utc>, f64)> = earlier
+            .iter()
+            .filter_map(|(at, usage)| Some((*at, -usage.credits.as_ref()?.remaining)))
+            .collect();
+        samples.push((now, -credits.remaining));
+        if let Some(forecast) = forecast_credits(credits, &since_reset(samples, 0.0), now) {
+            forecasts.push(forecast);
+        }
+    }
+    forecasts
+}
+
+fn forecast_window(
+    window: &RateWindow,
+    samples: &[(DateTime<Utc>, f64)],
+    now: DateTime<Utc>,
+) -> Option<Forecast> {
+    let id = window.id.clone()?;
+    let label = window.label.clone().unwrap_or_else(|| id.clone());
+    let resets_at = reset_time(window);
+    let (rate, confidence) = fit(samples).or_else(|| average_pace(window, resets_at, now))?;
+    let remaining = 100.0 - window.used_percent;
+
+    let mut forecast = Forecast {
+        id,
+        rate_per_hour: rate,
+        exhausts_at: None,
+        resets_first: false,
+        confidence,
+        description: format!("{} not running out at current pace", label),
+    };
+    if remaining <= 0.0 {
+        forecast.exhausts_at = Some(now.to_rfc3339());
+        forecast.description = format!("{} limit reached", label);
+        return Some(forecast);
+    }
+    if rate <= FLAT_RATE {
+        return Some(forecast);
+    }
+
+    let exhausts_at = now + hours(remaining / rate);
+    forecast.exhausts_at = Some(exhausts_at.to_rfc3339());
+    if resets_at.is_some_and(|resets_at| resets_at <= exhausts_at) {
+        forecast.resets_first = true;
+        forecast.description = format!("{} resets before the limit at current pace", label);
+    } else {
+        forecast.description = format!(
+            "{} limit hit {} at current pace",
+            label,
+            format_local_time(exhausts_at, now)
+        );
+    }
+    Some(forecast)
+}
+
+fn forecast_credits(
+    credits: &Credits,
+    samples: &[(DateTime<Utc>, f64)],
+    now: DateTime<Utc>,
+) -> Option<Forecast> {
+    let (rate, confidence) = fit(samples)?;
+    let label = capitalize(&credits.unit);
+    let mut forecast = Forecast {
+        id: CREDITS_FORECAST_ID.to_string(),
+        rate_per_hour: rate,
+        exhausts_at: None,
+        resets_first: false,
+        confidence,
+        description: format!("{} not running out at current pace", label),
+    };
+    if credits.remaining <= 0.0 {
+        forecast.exhausts_at = Some(now.to_rfc3339());
+        forecast.description = format!("{} used up", label);
+    } else if rate > FLAT_RATE {
+        let left = credits.remaining / rate;
+        forecast.exhausts_at = Some((now + hours(left)).to_rfc3339());
+        forecast.description = format!("{} last ~{}", label, format_approx_hours(left));
+    }
+    Some(forecast)
+}
+
+/// Samples from the last reset on: anything after a drop of more than `drop`
+fn since_reset(samples: Vec<(DateTime<Utc>, f64)>, drop: f64) -> Vec<(DateTime<Utc>, f64)> {
+    let start = samples
+        .windows(2)
+        .rposition(|pair| pair[1].1 < pair[0].1 - drop)
+        .map_or(0, |index| index + 1);
+    samples[start..].to_vec()
+}
+
+/// Least-squares slope per hour and its confidence
+fn fit(samples: &[(DateTime<Utc>, f64)]) -> Option<(f64, f64)> {
+    let (first, _) = samples.first()?;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|(at, value)| ((*at - *first).num_seconds() as f64 / 3600.0, *value))
+        .collect();
+    let count = points.len() as f64;
+    let span = points.last()?.0;
+    if points.len() < 2 || span <= 0.0 {
+        return None;
+    }
+
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in &points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx <= 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    // A flat series fits perfectly
+    let fit = if syy <= 0.0 { 1.0 } else { (sxy * sxy) / (sxx * syy) };
+    let confidence = (count / CONFIDENT_SAMPLES).min(1.0)
+        * (span / CONFIDENT_SPAN_HOURS).min(1.0)
+        * fit;
+    Some((slope, round_confidence(confidence)))
+}
+
+/// Usage so far divided by the time the window has been running
+fn average_pace(
+    window: &RateWindow,
+    resets_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<(f64, f64)> {
+    let duration = window.window_minutes? as f64 / 60.0;
+    let until_reset = (resets_at? - now).num_seconds() as f64 / 3600.0;
+    if duration <= 0.0 || until_reset < 0.0 || until_reset > duration {
+        return None;
+    }
+    let elapsed = duration - until_reset;
+    if elapsed < duration * AVERAGE_PACE_MIN_ELAPSED {
+        return None;
+    }
+    let confidence = AVERAGE_PACE_CONFIDENCE * elapsed / duration;
+    Some((window.used_percent / elapsed, round_confidence(confidence)))
+}
+
+fn reset_time(window: &RateWindow) -> Option<DateTime<Utc>> {
+    let resets_at = window.resets_at.as_deref()?;
+    DateTime::parse_from_rfc3339(resets_at)
+        .ok()
+        .map(|reset| reset.with_timezone(&Utc))
+}
+
+fn hours(value: f64) -> Duration {
+    // Capped so a near-zero pace cannot overflow the date
+    Duration::seconds((value.min(24.0 * 365.0 * 10.0) * 3600.0).round() as i64)
+}
+
+fn round_confidence(value: f64) -> f64 {
+    (value.clamp(0.0, 1.0) * 100.0).round() / 100.0
+}
+
+/// Weekday and time within the coming week, the date after that
+fn format_local_time(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let local = at.with_timezone(&chrono::Local);
+    if at - now < Duration::days(6) {
+        local.format("%a %H:%M").to_string()
+    } else {
+        local.format("%b %-d %H:%M").to_string()
+    }
+}
+
+fn format_approx_hours(hours: f64) -> String {
+    let plural = |count: f64, unit: &str| {
+        let count = count.round().max(1.0);
+        if count == 1.0 {
+            format!("1 {}", unit)
+        } else {
+            format!("{:.0} {}s", count, unit)
+        }
+    };
+    if hours < 1.0 {
+        plural(hours * 60.0, "minute")
+    } else if hours < 48.0 {
+        plural(hours, "hour")
+    } else {
+        plural(hours / 24.0, "day")
+    }
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ProviderId, USAGE_SNAPSHOT_VERSION};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn window(used_percent: f64, resets_in_hours: i64) -> RateWindow {
+        RateWindow {
+            id: Some("weekly".to_string()),
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(10080),
+            resets_at: Some((now() + Duration::hours(resets_in_hours)).to_rfc3339()),
+            reset_description: None,
+            label: Some("Weekly".to_string()),
+        }
+    }
+
+    fn usage(window: Option<RateWindow>, credits: Option<f64>) -> UsageSnapshot {
+        UsageSnapshot {
+            primary: window.clone(),
+            secondary: None,
+            tertiary: None,
+            credits: credits.map(|remaining| Credits {
+                remaining,
+                total: None,
+                unit: "credits".to_string(),
+            }),
+            cost: None,
+            identity: None,
+            updated_at: now().to_rfc3339(),
+            error: None,
+            retry_at: None,
+            windows: window.into_iter().collect(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        }
+    }
+
+    fn record(hours_ago: i64, usage: UsageSnapshot) -> HistoryRecord {
+        HistoryRecord {
+            recorded_at: (now() - Duration::hours(hours_ago)).to_rfc3339(),
+            provider_id: ProviderId::Claude,
+            account_id: "default".to_string(),
+            usage: Some(usage),
+            cost: None,
+        }
+    }
+
+    #[test]
+    fn projects_window_exhaustion_from_recent_pace() {
+        // 5 points an hour since a reset three hours ago
+        let history = vec![
+            record(4, usage(Some(window(90.0, 100)), None)),
+            record(3, usage(Some(window(45.0, 100)), None)),
+            record(2, usage(Some(window(50.0, 100)), None)),
+            record(1, usage(Some(window(55.0, 100)), None)),
+        ];
+        let forecasts = forecast_usage(&history, &usage(Some(window(60.0, 100)), None), now());
+        assert_eq!(forecasts.len(), 1);
+        let forecast = &forecasts[0];
+        assert_eq!(forecast.id, "weekly");
+        assert!((forecast.rate_per_hour - 5.0).abs() < 1e-9);
+        assert_eq!(
+            forecast.exhausts_at.as_deref(),
+            Some((now() + Duration::hours(8)).to_rfc3339().as_str())
+        );
+        assert!(!forecast.resets_first);
+        assert_eq!(forecast.confidence, 0.67);
+        assert!(forecast.description.starts_with("Weekly limit hit "));
+
+        let forecasts = forecast_usage(&history, &usage(Some(window(60.0, 4)), None), now());
+        assert!(forecasts[0].resets_first);
+    }
+
+    #[test]
+    fn falls_back_to_average_pace_without_history() {
+        // Half of a week gone with 35% used
+        let forecasts = forecast_usage(&[], &usage(Some(window(35.0, 84)), None), now());
+        let forecast = &forecasts[0];
+        assert!((forecast.rate_per_hour - 35.0 / 84.0).abs() < 1e-9);
+        assert_eq!(forecast.confidence, 0.15);
+        assert!(forecast.resets_first);
+    }
+
+    #[test]
+    fn projects_credit_balance_after_top_up() {
+        let history = vec![
+            record(30, usage(None, Some(10.0))),
+            record(24, usage(None, Some(600.0))),
+            record(12, usage(None, Some(594.0))),
+        ];
+        let forecasts = forecast_usage(&history, &usage(None, Some(588.0)), now());
+        let forecast = &forecasts[0];
+        assert_eq!(forecast.id, CREDITS_FORECAST_ID);
+        assert!((forecast.rate_per_hour - 0.5).abs() < 1e-9);
+        assert_eq!(forecast.description, "Credits last ~49 days");
+        assert_eq!(forecast.confidence, 0.5);
+    }
+
+    #[test]
+    fn flat_and_exhausted_windows() {
+        let history = vec![record(2, usage(Some(window(20.0, 100)), None))];
+        let forecast = &forecast_usage(&history, &usage(Some(window(20.0, 100)), None), now())[0];
+        assert!(forecast.exhausts_at.is_none());
+        assert_eq!(forecast.description, "Weekly not running out at current pace");
+
+        let forecast = &forecast_usage(&history, &usage(Some(window(100.0, 100)), None), now())[0];
+        assert_eq!(forecast.description, "Weekly limit reached");
+    }
+}
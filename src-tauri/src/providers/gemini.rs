@@ -3,7 +3,13 @@
 //! Uses OAuth credentials from ~/.gemini/oauth_creds.json
 //! Fetches quota via Google Cloud Code Private API
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::{Deserialize, Serialize};
@@ -24,6 +30,8 @@ const SETTINGS_PATH: &str = ".gemini/settings.json";
 
 pub struct GeminiProvider {
     client: reqwest::Client,
+    /// Account-specific Gemini OAuth credentials file; `None` uses the default location
+    credentials_path: Option<PathBuf>,
 }
 
 impl GeminiProvider {
@@ -33,7 +41,18 @@ impl GeminiProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            credentials_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Gemini OAuth credentials file lives at `path`
+    pub fn with_credentials_path(path: PathBuf) -> Self {
+        Self {
+            credentials_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_usage(&self) -> Result<UsageSnapshot, anyhow::Error> {
@@ -128,6 +147,10 @@ impl GeminiProvider {
     }
 
     fn get_credentials_path(&self) -> Result<PathBuf, anyhow::Error> {
+        if let Some(path) = &self.credentials_path {
+            return Ok(path.clone());
+        }
+
         let home = dirs::home_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
         Ok(home.join(CREDENTIALS_PATH))
@@ -199,10 +222,10 @@ impl GeminiProvider {
 
         let response = self
             .client
-            .post(TOKEN_REFRESH_ENDPOINT)
+            .post(endpoint(ProviderId::Gemini, TOKEN_REFRESH_ENDPOINT))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(body)
-            .send()
+            .send_recorded(ProviderId::Gemini)
             .await?;
 
         if !response.status().is_success() {
@@ -318,11 +341,11 @@ impl GeminiProvider {
 
         let response = match self
             .client
-            .post(LOAD_CODE_ASSIST_ENDPOINT)
+            .post(endpoint(ProviderId::Gemini, LOAD_CODE_ASSIST_ENDPOINT))
             .header("Authorization", format!("Bearer {}", access_token))
             .header("Content-Type", "application/json")
             .body(body)
-            .send()
+            .send_recorded(ProviderId::Gemini)
             .await
         {
             Ok(r) => r,
@@ -392,24 +415,24 @@ impl GeminiProvider {
 
         let response = self
             .client
-            .post(QUOTA_ENDPOINT)
+            .post(endpoint(ProviderId::Gemini, QUOTA_ENDPOINT))
             .header("Authorization", format!("Bearer {}", access_token))
             .header("Content-Type", "application/json")
             .body(body)
-            .send()
+            .send_recorded(ProviderId::Gemini)
             .await?;
 
-        if response.status().as_u16() == 401 {
-            return Err(anyhow::anyhow!(
-                "Gemini token expired. Run 'gemini' to re-authenticate."
-            ));
+        let status = response.status();
+        if status.as_u16() == 401 {
+            return Err(ProviderError::TokenExpired(
+                "Gemini token expired. Run 'gemini' to re-authenticate.".to_string(),
+            )
+            .into());
         }
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Gemini quota API error: HTTP {}",
-                response.status()
-            ));
+        if !status.is_success() {
+            let message = format!("Gemini quota API error: HTTP {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let json: serde_json::Value = response.json().await?;
@@ -502,22 +525,10 @@ impl GeminiProvider {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        // Primary is Pro, secondary is Flash (24h windows)
-        let primary = pro_min.map(|q| RateWindow {
-            used_percent: 100.0 - q.percent_left,
-            window_minutes: Some(1440), // 24 hours
-            resets_at: q.reset_time.clone(),
-            reset_description: q.reset_description.clone(),
-            label: Some("Pro".to_string()),
-        });
-
-        let secondary = flash_min.map(|q| RateWindow {
-            used_percent: 100.0 - q.percent_left,
-            window_minutes: Some(1440),
-            resets_at: q.reset_time.clone(),
-            reset_description: q.reset_description.clone(),
-            label: Some("Flash".to_string()),
-        });
+        // Primary is Pro, secondary is Flash (24h windows); every model is in `windows`
+        let primary = pro_min.map(|q| q.to_rate_window("Pro"));
+        let secondary = flash_min.map(|q| q.to_rate_window("Flash"));
+        let windows = quotas.iter().map(|q| q.to_rate_window(&q.model_id)).collect();
 
         UsageSnapshot {
             primary,
@@ -533,6 +544,10 @@ impl GeminiProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows,
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -587,9 +602,34 @@ impl ProviderFetcher for GeminiProvider {
         "Google Gemini AI"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Gemini usage");
-        self.fetch_usage().await
+        Ok(self.fetch_usage().await?)
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let creds_path = match self.get_credentials_path() {
+            Ok(path) => path,
+            Err(_) => {
+                return AuthStatus::unauthenticated(Some(
+                    "Could not find home directory".to_string(),
+                ))
+            }
+        };
+
+        if !creds_path.exists() {
+            return AuthStatus::unauthenticated(None);
+        }
+
+        match tokio::fs::read_to_string(&creds_path).await {
+            Ok(content) if content.contains("access_token") || content.contains("refresh_token") => {
+                AuthStatus::authenticated("oauth")
+            }
+            Ok(_) => AuthStatus::unauthenticated(Some(
+                "Credentials file exists but missing tokens".to_string(),
+            )),
+            Err(e) => AuthStatus::unauthenticated(Some(format!("Could not read credentials: {}", e))),
+        }
     }
 }
 
@@ -609,6 +649,23 @@ struct ModelQuota {
     reset_description: Option<String>,
 }
 
+impl ModelQuota {
+    fn to_rate_window(&self, label: &str) -> RateWindow {
+        RateWindow {
+            id: Some(self.model_id.clone()),
+            category: Some(WindowCategory::Model),
+            used_percent: 100.0 - self.percent_left,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(1440), // 24 hours
+            resets_at: self.reset_time.clone(),
+            reset_description: self.reset_description.clone(),
+            label: Some(label.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct GeminiCredentials {
     access_token: Option<String>,
@@ -788,6 +845,34 @@ exit 0
         assert_eq!(refreshed.access_token.as_deref(), Some("cli-token"));
     }
 
+    #[test]
+    fn keeps_a_window_for_every_model() {
+        let quota = |model_id: &str, percent_left| ModelQuota {
+            model_id: model_id.to_string(),
+            percent_left,
+            reset_time: None,
+            reset_description: None,
+        };
+        let quotas = vec![
+            quota("gemini-2.5-flash", 90.0),
+            quota("gemini-2.5-flash-lite", 40.0),
+            quota("gemini-2.5-pro", 75.0),
+        ];
+
+        let usage = GeminiProvider::new().build_usage_snapshot(quotas, None, None);
+
+        let secondary = usage.secondary.expect("flash");
+        assert_eq!(secondary.label.as_deref(), Some("Flash"));
+        assert_eq!(secondary.id.as_deref(), Some("gemini-2.5-flash-lite"));
+        assert_eq!(usage.primary.expect("pro").used_percent, 25.0);
+        let ids: Vec<_> = usage.windows.iter().map(|w| w.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, ["gemini-2.5-flash", "gemini-2.5-flash-lite", "gemini-2.5-pro"]);
+        assert!(usage
+            .windows
+            .iter()
+            .all(|w| w.category == Some(WindowCategory::Model)));
+    }
+
     #[test]
     fn exit_code_saturates_on_overflow() {
         let exit_code: u64 = i64::from(i32::MAX) as u64 + 1;
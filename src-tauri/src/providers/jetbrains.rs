@@ -2,12 +2,16 @@
 //!
 //! Reads local IDE log files to extract monthly AI credits usage.
 
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use chrono::{Datelike, TimeZone};
 use glob::glob;
 use std::path::{Path, PathBuf};
 
-use super::{Credits, ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::{
+    Credits, ProviderError, ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot,
+    WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
 
 const LOG_FILENAME_PREFIX: &str = "idea.log";
 
@@ -236,7 +240,12 @@ impl JetbrainsProvider {
 
         UsageSnapshot {
             primary: Some(RateWindow {
+                id: Some("monthly".to_string()),
+                category: Some(WindowCategory::Monthly),
                 used_percent,
+                used: total.map(|total| (total - usage.remaining).max(0.0)),
+                limit: total,
+                unit: Some("credits".to_string()),
                 window_minutes: None,
                 resets_at,
                 reset_description,
@@ -258,6 +267,10 @@ impl JetbrainsProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -345,11 +358,27 @@ impl ProviderFetcher for JetbrainsProvider {
         "JetBrains AI Assistant"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching JetBrains AI usage");
         let usage = self.fetch_from_logs().await?;
         Ok(self.build_snapshot(usage))
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        let log_paths = tokio::task::spawn_blocking(|| {
+            JetbrainsProvider::new().collect_log_paths()
+        })
+        .await;
+
+        match log_paths {
+            Ok(Ok(paths)) if !paths.is_empty() => AuthStatus::authenticated("local_logs"),
+            Ok(Err(err)) => AuthStatus::unauthenticated(Some(err.to_string())),
+            _ => AuthStatus::unauthenticated(Some(
+                "No JetBrains IDE logs found. Open a JetBrains IDE with AI Assistant enabled."
+                    .to_string(),
+            )),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -3,13 +3,21 @@
 //! Uses JWT cookie authentication via browser cookie import.
 //! Endpoint: https://kimi.com/apiv2/grpc/kimi_api.BillingService/GetUsages
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 
 const USAGE_URL: &str = "https://kimi.com/apiv2/grpc/kimi_api.BillingService/GetUsages";
 
 pub struct KimiProvider {
     client: reqwest::Client,
+    /// Account-specific Kimi session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl KimiProvider {
@@ -19,7 +27,18 @@ impl KimiProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose Kimi session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(
@@ -28,20 +47,19 @@ impl KimiProvider {
     ) -> Result<UsageSnapshot, anyhow::Error> {
         let response = self
             .client
-            .post(USAGE_URL)
+            .post(endpoint(ProviderId::Kimi, USAGE_URL))
             .header("Cookie", cookie_header)
             .header("Content-Type", "application/proto")
             .header("Accept", "application/proto")
             .header("User-Agent", "IncuBar/1.0")
             .body(Vec::new())
-            .send()
+            .send_recorded(ProviderId::Kimi)
             .await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Kimi API returned status: {}",
-                response.status()
-            ));
+        let status = response.status();
+        if !status.is_success() {
+            let message = format!("Kimi API returned status: {}", status);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let bytes = response.bytes().await?;
@@ -73,6 +91,10 @@ impl KimiProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -125,7 +147,12 @@ impl KimiProvider {
         };
 
         Some(RateWindow {
+            id: None,
+            category: None,
             used_percent: used_percent.clamp(0.0, 100.0),
+            used: Some(used),
+            limit: Some(limit),
+            unit: None,
             window_minutes: None,
             resets_at: None,
             reset_description,
@@ -185,6 +212,10 @@ impl KimiProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("kimi-session.json"))
@@ -201,7 +232,7 @@ impl ProviderFetcher for KimiProvider {
         "Kimi (Moonshot AI)"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Kimi usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -215,11 +246,15 @@ impl ProviderFetcher for KimiProvider {
                 if let Err(err) = self.store_session(&result.cookie_header).await {
                     tracing::debug!("Failed to store Kimi session: {}", err);
                 }
-                self.fetch_with_cookies(&result.cookie_header).await
+                Ok(self.fetch_with_cookies(&result.cookie_header).await?)
             }
-            Err(err) => Err(anyhow::anyhow!("Not authenticated: {}", err)),
+            Err(err) => Err(ProviderError::not_authenticated(err)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -349,6 +384,27 @@ fn skip_field(bytes: &[u8], start: usize, wire_type: u8) -> Result<usize, anyhow
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    #[tokio::test]
+    async fn replays_cookie_usage() {
+        let server = ReplayServer::from_fixture("kimi-cookies")
+            .await
+            .route(ProviderId::Kimi);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("kimi-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "kimi-auth=jwt"}"#).unwrap();
+
+        let usage = KimiProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert!((primary.used_percent - 25.0).abs() < 0.01);
+        assert_eq!(primary.label.as_deref(), Some("Tokens"));
+        assert_eq!(server.received()[0].headers["cookie"], "kimi-auth=jwt");
+    }
 
     fn encode_varint(mut value: u64) -> Vec<u8> {
         let mut out = Vec::new();
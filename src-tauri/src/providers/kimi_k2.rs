@@ -3,7 +3,13 @@
 //! Uses API key authentication via KIMI_K2_API_KEY, KIMI_API_KEY, or KIMI_KEY
 //! environment variables.
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use crate::storage::keyring::KeyringError;
 use crate::storage::SecureStorage;
@@ -83,19 +89,21 @@ impl KimiK2Provider {
 
     async fn fetch_usage(&self) -> Result<UsageSnapshot, anyhow::Error> {
         let api_key = self.resolve_api_key().ok_or_else(|| {
-            anyhow::anyhow!(
+            ProviderError::NotAuthenticated(
                 "Kimi K2 API key not found. Set KIMI_K2_API_KEY or store a keychain token."
+                    .to_string(),
             )
         })?;
 
-        tracing::debug!("Fetching Kimi K2 usage from: {}", CREDITS_URL);
+        let url = endpoint(ProviderId::KimiK2, CREDITS_URL);
+        tracing::debug!("Fetching Kimi K2 usage from: {}", url);
 
         let response = self
             .client
-            .get(CREDITS_URL)
+            .get(&url)
             .header("Authorization", format!("Bearer {}", api_key))
             .header("Accept", "application/json")
-            .send()
+            .send_recorded(ProviderId::KimiK2)
             .await?;
 
         // Check for remaining credits in headers
@@ -108,11 +116,8 @@ impl KimiK2Provider {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!(
-                "Kimi K2 API error: HTTP {} - {}",
-                status,
-                body
-            ));
+            let message = format!("Kimi K2 API error: HTTP {} - {}", status, body);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let body = response.text().await?;
@@ -186,7 +191,12 @@ impl KimiK2Provider {
 
         UsageSnapshot {
             primary: Some(RateWindow {
+                id: Some("credits".to_string()),
+                category: None,
                 used_percent,
+                used: Some(consumed),
+                limit: Some(total),
+                unit: Some("credits".to_string()),
                 window_minutes: None,
                 resets_at: None,
                 reset_description,
@@ -204,6 +214,10 @@ impl KimiK2Provider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -283,9 +297,19 @@ impl ProviderFetcher for KimiK2Provider {
         "Kimi K2 AI Assistant"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Kimi K2 usage");
-        self.fetch_usage().await
+        Ok(self.fetch_usage().await?)
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        if self.resolve_api_key().is_some() {
+            AuthStatus::authenticated("api_key")
+        } else {
+            AuthStatus::unauthenticated(Some(
+                "Set KIMI_K2_API_KEY environment variable".to_string(),
+            ))
+        }
     }
 }
 
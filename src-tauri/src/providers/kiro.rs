@@ -3,7 +3,8 @@
 //! Uses the Kiro CLI status probe to read usage data.
 
 use super::{
-    ProviderFetcher, ProviderIdentity, ProviderStatus, RateWindow, StatusIndicator, UsageSnapshot,
+    ProviderError, ProviderFetcher, ProviderIdentity, ProviderStatus, RateWindow, StatusIndicator,
+    UsageSnapshot, USAGE_SNAPSHOT_VERSION,
 };
 use async_trait::async_trait;
 use chrono::Datelike;
@@ -12,26 +13,20 @@ use regex::Regex;
 use std::io::Read;
 
 use crate::debug_settings;
+use crate::login::AuthStatus;
 
 const CLI_NAME: &str = "kiro-cli";
-const STATUS_FEED_URL: &str = "https://status.aws.amazon.com/rss/all.rss";
 const DEFAULT_TIMEOUT_SECS: u64 = 20;
 const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 10;
 
 pub struct KiroProvider {
-    client: reqwest::Client,
     timeout: std::time::Duration,
     idle_timeout: std::time::Duration,
 }
 
 impl KiroProvider {
     pub fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .unwrap_or_default();
         Self {
-            client,
             timeout: std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS),
             idle_timeout: std::time::Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS),
         }
@@ -153,24 +148,6 @@ impl KiroProvider {
         Ok(result.stdout)
     }
 
-    async fn fetch_status_snapshot(&self) -> Result<ProviderStatus, KiroError> {
-        let response = self
-            .client
-            .get(STATUS_FEED_URL)
-            .header("Accept", "application/rss+xml, text/xml;q=0.9, */*;q=0.8")
-            .send()
-            .await
-            .map_err(|err| KiroError::Status(err.to_string()))?;
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .map_err(|err| KiroError::Status(err.to_string()))?;
-        if !status.is_success() {
-            return Err(KiroError::Status(format!("HTTP {}", status)));
-        }
-        parse_aws_status_feed(&body)
-    }
 }
 
 #[async_trait]
@@ -183,7 +160,7 @@ impl ProviderFetcher for KiroProvider {
         "Kiro status probe"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching Kiro usage via CLI");
         self.ensure_logged_in()
             .await
@@ -198,10 +175,53 @@ impl ProviderFetcher for KiroProvider {
         Ok(snapshot.to_usage_snapshot())
     }
 
-    async fn fetch_status(&self) -> Result<ProviderStatus, anyhow::Error> {
-        self.fetch_status_snapshot()
-            .await
-            .map_err(|err| anyhow::anyhow!(err.to_string()))
+    async fn auth_status(&self) -> AuthStatus {
+        let cli_failure = |error: String| AuthStatus {
+            authenticated: false,
+            method: Some("cli".to_string()),
+            email: None,
+            error: Some(error),
+        };
+
+        let cli_path = match tokio::task::spawn_blocking(which_cli).await.ok().flatten() {
+            Some(path) => path,
+            None => {
+                return AuthStatus::unauthenticated(Some(
+                    "kiro-cli not found. Install it from https://kiro.dev".to_string(),
+                ))
+            }
+        };
+
+        let command = tokio::process::Command::new(cli_path)
+            .arg("whoami")
+            .output();
+        let output = match tokio::time::timeout(std::time::Duration::from_secs(5), command).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(err)) => return cli_failure(format!("Failed to run kiro-cli: {}", err)),
+            Err(_) => return cli_failure("kiro-cli timed out".to_string()),
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let combined = if stderr.is_empty() { stdout } else { stderr };
+        let lowered = combined.to_lowercase();
+
+        if lowered.contains("not logged in") || lowered.contains("login required") {
+            return cli_failure("Run `kiro-cli login` in Terminal".to_string());
+        }
+
+        if !output.status.success() {
+            return cli_failure(if combined.is_empty() {
+                format!("kiro-cli failed with status {}", output.status)
+            } else {
+                combined
+            });
+        }
+
+        AuthStatus {
+            email: combined.contains('@').then_some(combined),
+            ..AuthStatus::authenticated("cli")
+        }
     }
 }
 
@@ -223,7 +243,12 @@ struct KiroUsageSnapshot {
 impl KiroUsageSnapshot {
     fn to_usage_snapshot(&self) -> UsageSnapshot {
         let primary = RateWindow {
+            id: Some("credits".to_string()),
+            category: None,
             used_percent: self.credits_percent.clamp(0.0, 100.0),
+            used: None,
+            limit: None,
+            unit: None,
             window_minutes: None,
             resets_at: self.resets_at.clone(),
             reset_description: None,
@@ -240,7 +265,12 @@ impl KiroUsageSnapshot {
                     })
                     .map(|value| value.to_rfc3339());
                 Some(RateWindow {
+                    id: Some("bonus".to_string()),
+                    category: None,
                     used_percent: bonus_percent,
+                    used: Some(used),
+                    limit: Some(total),
+                    unit: Some("credits".to_string()),
                     window_minutes: None,
                     resets_at: expires_at,
                     reset_description: self
@@ -266,6 +296,10 @@ impl KiroUsageSnapshot {
             }),
             updated_at: self.updated_at.to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 }
@@ -279,7 +313,7 @@ struct CommandResult {
 }
 
 #[derive(thiserror::Error, Debug)]
-enum KiroError {
+pub(super) enum KiroError {
     #[error("Kiro CLI not found. Install it from https://kiro.dev")]
     CliNotFound,
     #[error("Not logged in to Kiro. Run 'kiro-cli login' first.")]
@@ -294,6 +328,19 @@ enum KiroError {
     Status(String),
 }
 
+impl From<KiroError> for ProviderError {
+    fn from(err: KiroError) -> Self {
+        let message = err.to_string();
+        match err {
+            KiroError::CliNotFound => ProviderError::Unavailable(message),
+            KiroError::NotLoggedIn => ProviderError::NotAuthenticated(message),
+            KiroError::Parse(_) => ProviderError::Parse(message),
+            KiroError::Timeout => ProviderError::Timeout(message),
+            KiroError::CliFailed(_) | KiroError::Status(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 fn is_not_logged_in(lowered: &str) -> bool {
     lowered.contains("not logged in")
         || lowered.contains("login required")
@@ -462,7 +509,7 @@ fn strip_ansi(text: &str) -> String {
     }
 }
 
-fn parse_aws_status_feed(feed: &str) -> Result<ProviderStatus, KiroError> {
+pub(super) fn parse_aws_status_feed(feed: &str) -> Result<ProviderStatus, KiroError> {
     let item_regex =
         Regex::new(r"(?s)<item>(.*?)</item>").map_err(|err| KiroError::Status(err.to_string()))?;
     let item = match item_regex.captures(feed).and_then(|cap| cap.get(1)) {
@@ -481,6 +528,7 @@ fn parse_aws_status_feed(feed: &str) -> Result<ProviderStatus, KiroError> {
         indicator,
         description,
         updated_at: pub_date,
+        url: None,
     })
 }
 
@@ -4,7 +4,13 @@
 //! Uses cookie-based authentication via browser cookie import.
 //! Endpoint: https://platform.minimax.io/platform/api/subscription/coding_plan/remains
 
-use super::{Credits, ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    Credits, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow,
+    UsageSnapshot, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 
@@ -12,6 +18,8 @@ const USAGE_URL: &str = "https://platform.minimax.io/platform/api/subscription/c
 
 pub struct MinimaxProvider {
     client: reqwest::Client,
+    /// Account-specific MiniMax session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl MinimaxProvider {
@@ -21,7 +29,18 @@ impl MinimaxProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose MiniMax session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(
@@ -30,11 +49,11 @@ impl MinimaxProvider {
     ) -> Result<MinimaxUsageResponse, MinimaxError> {
         let response = self
             .client
-            .get(USAGE_URL)
+            .get(endpoint(ProviderId::Minimax, USAGE_URL))
             .header("Cookie", cookie_header)
             .header("Accept", "application/json")
             .header("User-Agent", "IncuBar/1.0")
-            .send()
+            .send_recorded(ProviderId::Minimax)
             .await
             .map_err(|e| MinimaxError::Api(e.to_string()))?;
 
@@ -75,7 +94,12 @@ impl MinimaxProvider {
         };
 
         let primary = Some(RateWindow {
+            id: Some("credits".to_string()),
+            category: None,
             used_percent,
+            used: Some(consumed),
+            limit: Some(total),
+            unit: Some("credits".to_string()),
             window_minutes: None,
             resets_at: None,
             reset_description: if total > 0.0 {
@@ -110,6 +134,10 @@ impl MinimaxProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         })
     }
 
@@ -137,6 +165,10 @@ impl MinimaxProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("minimax-session.json"))
@@ -153,7 +185,7 @@ impl ProviderFetcher for MinimaxProvider {
         "MiniMax"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching MiniMax usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -177,12 +209,15 @@ impl ProviderFetcher for MinimaxProvider {
                     tracing::debug!("Failed to store MiniMax session: {}", err);
                 }
                 let response = self.fetch_with_cookies(&result.cookie_header).await?;
-                self.build_snapshot(&response)
-                    .map_err(|err| anyhow::anyhow!(err.to_string()))
+                Ok(self.build_snapshot(&response)?)
             }
-            Err(err) => Err(anyhow::anyhow!("Not authenticated: {}", err)),
+            Err(err) => Err(ProviderError::not_authenticated(err)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 impl MinimaxProvider {
@@ -229,9 +264,41 @@ enum MinimaxError {
     MissingData,
 }
 
+impl From<MinimaxError> for ProviderError {
+    fn from(err: MinimaxError) -> Self {
+        let message = err.to_string();
+        match err {
+            MinimaxError::SessionExpired => ProviderError::TokenExpired(message),
+            MinimaxError::NotLoggedIn => ProviderError::NotAuthenticated(message),
+            MinimaxError::Parse(_) | MinimaxError::MissingData => ProviderError::Parse(message),
+            MinimaxError::Api(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    #[tokio::test]
+    async fn replays_cookie_usage() {
+        let server = ReplayServer::from_fixture("minimax-cookies")
+            .await
+            .route(ProviderId::Minimax);
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("minimax-session.json");
+        std::fs::write(&session_path, r#"{"cookieHeader": "session=abc"}"#).unwrap();
+
+        let usage = MinimaxProvider::with_credentials_path(session_path)
+            .fetch()
+            .await
+            .expect("fetch");
+
+        assert_eq!(usage.primary.expect("primary").used_percent, 25.0);
+        assert_eq!(usage.credits.expect("credits").remaining, 600.0);
+        assert_eq!(server.received()[0].headers["cookie"], "session=abc");
+    }
 
     #[test]
     fn builds_snapshot_with_credits() {
@@ -1,5 +1,7 @@
 //! Provider system for fetching usage data from various AI services
 
+mod accounts;
+mod adaptive;
 mod amp;
 mod antigravity;
 mod augment;
@@ -8,29 +10,64 @@ mod codex;
 pub mod copilot;
 mod cost_usage;
 mod cursor;
+mod custom;
+mod descriptor;
+mod emitter;
+mod endpoints;
+mod error;
 mod factory;
+mod fixtures;
+mod forecast;
 mod gemini;
 mod jetbrains;
 mod kimi;
 mod kimi_k2;
 mod kiro;
 mod minimax;
+mod notifications;
 pub(crate) mod opencode;
+mod plugin;
+mod quota_json;
+mod rate_limit;
+mod reset;
+mod single_flight;
+mod sink;
+mod status;
 mod synthetic;
 mod traits;
+mod vertexai;
 mod zai;
 
+pub use accounts::{AccountKey, ProviderAccount, DEFAULT_ACCOUNT_ID};
+pub use custom::{CustomProviderAuth, CustomProviderConfig, CustomProviderFields};
+pub use descriptor::{AuthKind, ProviderDescriptor, StatusSource, PROVIDER_DESCRIPTORS};
+pub use emitter::{RefreshEmitter, RefreshEvent};
+pub use endpoints::set_base_url_overrides;
+pub use error::{ProviderError, ProviderErrorKind};
+pub use forecast::{forecast_usage, Forecast, FORECAST_LOOKBACK_HOURS};
+pub(crate) use notifications::notifications_allowed;
+pub use notifications::NotificationSink;
+pub use notifications::{
+    notification_snoozes, set_notification_preferences, snooze_notifications,
+    NotificationPreferences, NotificationSnoozes, ProviderThresholds,
+};
+pub use plugin::PluginInfo;
+pub use reset::{start_reset_listener, WindowReset};
+pub use sink::UsageSink;
 pub use traits::*;
 
+use crate::login::AuthStatus;
 use anyhow::anyhow;
-use async_trait::async_trait;
+use futures_util::{FutureExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter, Manager};
-use tokio::time::timeout;
+use tauri::{AppHandle, Manager};
 use tokio::sync::RwLock;
+use tokio::time::timeout;
 
 /// Provider identifier enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -56,64 +93,65 @@ pub enum ProviderId {
     Jetbrains,
     Opencode,
     Synthetic,
+    /// User-defined JSON HTTP providers, one account per definition
+    Custom,
+    /// External `incubar-provider-*` executables, one account per plugin
+    Plugin,
 }
 
 impl ProviderId {
     pub fn all() -> Vec<ProviderId> {
-        vec![
-            ProviderId::Claude,
-            ProviderId::Codex,
-            ProviderId::Cursor,
-            ProviderId::Copilot,
-            ProviderId::Gemini,
-            ProviderId::Antigravity,
-            ProviderId::Factory,
-            ProviderId::Zai,
-            ProviderId::Minimax,
-            ProviderId::Kimi,
-            ProviderId::KimiK2,
-            ProviderId::Kiro,
-            ProviderId::Vertex,
-            ProviderId::Augment,
-            ProviderId::Amp,
-            ProviderId::Jetbrains,
-            ProviderId::Opencode,
-            ProviderId::Synthetic,
-        ]
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .map(|descriptor| descriptor.id)
+            .collect()
     }
 }
 
-struct PlaceholderProvider {
-    name: &'static str,
-    description: &'static str,
-}
+/// Current shape of [`UsageSnapshot`]. Snapshots without a version predate the
+/// `windows` list and only carry the three fixed windows.
+pub const USAGE_SNAPSHOT_VERSION: u32 = 2;
 
-impl PlaceholderProvider {
-    fn new(name: &'static str, description: &'static str) -> Self {
-        Self { name, description }
-    }
+fn legacy_snapshot_version() -> u32 {
+    1
 }
 
-#[async_trait]
-impl ProviderFetcher for PlaceholderProvider {
-    fn name(&self) -> &'static str {
-        self.name
-    }
-
-    fn description(&self) -> &'static str {
-        self.description
-    }
+fn missing_percent() -> f64 {
+    f64::NAN
+}
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
-        Err(anyhow!("Provider not implemented"))
-    }
+/// What a rate window measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowCategory {
+    /// Short rolling window, e.g. five hours or a day
+    Session,
+    Weekly,
+    Monthly,
+    /// Quota for a single model
+    Model,
 }
 
 /// Rate window (usage period)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RateWindow {
+    /// Stable within a provider, so the UI and tray can tell windows apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<WindowCategory>,
+    /// Derived from `used` and `limit` by `validated` when missing
+    #[serde(default = "missing_percent")]
     pub used_percent: f64,
+    /// Absolute usage, when the provider reports counts rather than just a percentage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+    /// What `used` and `limit` count, e.g. "requests" or "tokens"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_minutes: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -140,6 +178,7 @@ pub struct Credits {
 pub struct CostSnapshot {
     pub today_amount: f64,
     pub today_tokens: u64,
+    /// Spend over the last 30 days, including today
     pub month_amount: f64,
     pub month_tokens: u64,
     pub currency: String,
@@ -175,9 +214,24 @@ pub struct UsageSnapshot {
     pub cost: Option<CostSnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identity: Option<ProviderIdentity>,
+    /// Filled in by `validated` when missing or malformed
+    #[serde(default)]
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// When a rate-limited provider will be tried again, so the error can count down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_at: Option<String>,
+    /// Every window the provider reports. `primary`, `secondary` and `tertiary` hold
+    /// the first three for older readers; `validated` keeps both in sync.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<RateWindow>,
+    /// Projected exhaustion of each window and the credit balance, filled in from the
+    /// account's recent history after a fetch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forecasts: Vec<Forecast>,
+    #[serde(default = "legacy_snapshot_version")]
+    pub version: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -199,6 +253,9 @@ pub struct ProviderStatus {
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
+    /// Public status page, from the provider's descriptor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
 }
 
 impl ProviderStatus {
@@ -207,6 +264,7 @@ impl ProviderStatus {
             indicator: StatusIndicator::None,
             description: None,
             updated_at: None,
+            url: None,
         }
     }
 
@@ -221,6 +279,7 @@ impl ProviderStatus {
             indicator: self.indicator,
             description,
             updated_at,
+            url: self.url,
         }
     }
 }
@@ -234,11 +293,7 @@ pub(crate) struct RefreshSchedule {
 const FAILURE_BACKOFF_BASE_SECONDS: u64 = 30;
 
 impl RefreshSchedule {
-    #[allow(dead_code)]
-    fn new(interval: Duration) -> Self {
-        Self::new_at(SystemTime::now(), interval)
-    }
-
+    #[cfg(test)]
     pub(crate) fn new_at(now: SystemTime, interval: Duration) -> Self {
         Self {
             interval,
@@ -259,6 +314,11 @@ impl RefreshSchedule {
         now >= self.next_refresh_at
     }
 
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[cfg(test)]
     pub(crate) fn mark_refreshed(&mut self, now: SystemTime) {
         self.next_refresh_at = now.checked_add(self.interval).unwrap_or(now);
     }
@@ -266,6 +326,48 @@ impl RefreshSchedule {
     pub(crate) fn schedule_after(&mut self, now: SystemTime, delay: Duration) {
         self.next_refresh_at = now.checked_add(delay).unwrap_or(now);
     }
+
+    /// Change the interval, pulling the next refresh forward if the new interval is shorter
+    pub(crate) fn set_interval(&mut self, now: SystemTime, interval: Duration) {
+        if interval == self.interval {
+            return;
+        }
+        self.interval = interval;
+        let next_at_new_interval = now.checked_add(interval).unwrap_or(now);
+        if next_at_new_interval < self.next_refresh_at {
+            self.next_refresh_at = next_at_new_interval;
+        }
+    }
+}
+
+const DEFAULT_REFRESH_INTERVAL_SECONDS: u32 = 300;
+
+/// How often the background loop refreshes each provider, in seconds. Zero means manual.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshIntervals {
+    pub default_seconds: u32,
+    pub per_provider: HashMap<ProviderId, u32>,
+}
+
+impl Default for RefreshIntervals {
+    fn default() -> Self {
+        Self {
+            default_seconds: DEFAULT_REFRESH_INTERVAL_SECONDS,
+            per_provider: HashMap::new(),
+        }
+    }
+}
+
+impl RefreshIntervals {
+    /// The interval for `provider`, or `None` when it is only refreshed manually
+    pub fn for_provider(&self, provider: ProviderId) -> Option<Duration> {
+        let seconds = self
+            .per_provider
+            .get(&provider)
+            .copied()
+            .unwrap_or(self.default_seconds);
+        (seconds > 0).then(|| Duration::from_secs(u64::from(seconds)))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -282,14 +384,14 @@ impl ConsecutiveFailureGate {
         self.streak = 0;
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn reset(&mut self) {
-        self.streak = 0;
-    }
-
-    pub(crate) fn should_surface_error(&mut self, had_prior_data: bool) -> bool {
+    pub(crate) fn should_surface_error(
+        &mut self,
+        had_prior_data: bool,
+        error: &ProviderError,
+    ) -> bool {
         self.streak = self.streak.saturating_add(1);
-        if had_prior_data && self.streak == 1 {
+        // Signing in again is on the user, so there is no point holding that back
+        if had_prior_data && self.streak == 1 && !error.needs_user_action() {
             return false;
         }
         true
@@ -314,9 +416,24 @@ impl RefreshBackoff {
         self.failure_streak = 0;
     }
 
-    pub(crate) fn register_failure(&mut self) -> Duration {
+    pub(crate) fn set_base_interval(&mut self, base_interval: Duration) {
+        self.base_interval = base_interval;
+    }
+
+    /// Delay before retrying after `error`. Transient failures back off exponentially;
+    /// rate limits wait as long as the provider asked, and anything else that a quick
+    /// retry will not fix waits a full interval.
+    pub(crate) fn register_failure(&mut self, error: &ProviderError) -> Duration {
         self.failure_streak = self.failure_streak.saturating_add(1);
-        self.backoff_delay()
+        match error {
+            ProviderError::RateLimited { retry_after, .. } => retry_after
+                .unwrap_or(self.base_interval)
+                .max(Duration::from_secs(FAILURE_BACKOFF_BASE_SECONDS)),
+            error if error.is_transient() => self.backoff_delay(),
+            _ => self
+                .base_interval
+                .max(Duration::from_secs(FAILURE_BACKOFF_BASE_SECONDS)),
+        }
     }
 
     pub(crate) fn backoff_delay(&self) -> Duration {
@@ -334,15 +451,17 @@ struct ProviderRefreshState {
     schedule: RefreshSchedule,
     backoff: RefreshBackoff,
     failure_gate: ConsecutiveFailureGate,
+    polling: adaptive::AdaptivePolling,
 }
 
 impl ProviderRefreshState {
-    fn new(_now: SystemTime, interval: Duration) -> Self {
+    fn new(interval: Duration) -> Self {
         Self {
             // Use new_due_now so the first refresh happens immediately
             schedule: RefreshSchedule::new_due_now(interval),
             backoff: RefreshBackoff::new(interval),
             failure_gate: ConsecutiveFailureGate::new(),
+            polling: adaptive::AdaptivePolling::new(),
         }
     }
 
@@ -350,15 +469,30 @@ impl ProviderRefreshState {
         self.schedule.is_due(now)
     }
 
-    fn record_success(&mut self, now: SystemTime) {
+    fn set_interval(&mut self, now: SystemTime, interval: Duration) {
+        self.schedule.set_interval(now, interval);
+        self.backoff.set_base_interval(interval);
+    }
+
+    fn record_success(&mut self, now: SystemTime, usage: &UsageSnapshot) {
         self.backoff.reset();
         self.failure_gate.record_success();
-        self.schedule.mark_refreshed(now);
+        let delay = self.polling.next_delay(
+            self.schedule.interval(),
+            usage,
+            chrono::DateTime::<chrono::Utc>::from(now),
+        );
+        self.schedule.schedule_after(now, delay);
     }
 
-    fn record_failure(&mut self, now: SystemTime, had_data: bool) -> bool {
-        let should_surface = self.failure_gate.should_surface_error(had_data);
-        let delay = self.backoff.register_failure();
+    /// The manual refresh that took over counts as this one, so wait a regular interval
+    fn record_cancelled(&mut self, now: SystemTime) {
+        self.schedule.schedule_after(now, self.schedule.interval());
+    }
+
+    fn record_failure(&mut self, now: SystemTime, had_data: bool, error: &ProviderError) -> bool {
+        let should_surface = self.failure_gate.should_surface_error(had_data, error);
+        let delay = self.backoff.register_failure(error);
         self.schedule.schedule_after(now, delay);
         should_surface
     }
@@ -375,17 +509,40 @@ impl UsageSnapshot {
             identity: None,
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: Some(message),
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        }
+    }
+
+    /// Error snapshot for a failed fetch. Rate limits with a known delay say when the
+    /// provider is tried again.
+    pub fn from_error(error: &ProviderError) -> Self {
+        let mut usage = Self::error(error.to_string());
+        if let ProviderError::RateLimited {
+            retry_after: Some(delay),
+            ..
+        } = error
+        {
+            usage.retry_at = chrono::Duration::from_std(*delay)
+                .ok()
+                .map(|delay| (chrono::Utc::now() + delay).to_rfc3339());
         }
+        usage
     }
 
     pub fn validated(mut self) -> Self {
         self.primary = self.primary.and_then(RateWindow::validated);
         self.secondary = self.secondary.and_then(RateWindow::validated);
         self.tertiary = self.tertiary.and_then(RateWindow::validated);
+        self.sync_windows();
         self.credits = self.credits.and_then(Credits::validated);
         self.cost = self.cost.and_then(CostSnapshot::validated);
         self.identity = self.identity.and_then(ProviderIdentity::validated);
         self.error = normalize_text(self.error);
+        self.retry_at = normalize_datetime(self.retry_at);
+        self.version = USAGE_SNAPSHOT_VERSION;
 
         if chrono::DateTime::parse_from_rfc3339(self.updated_at.trim()).is_err() {
             self.updated_at = chrono::Utc::now().to_rfc3339();
@@ -396,12 +553,111 @@ impl UsageSnapshot {
 
         self
     }
+
+    /// Every window, falling back to the legacy fields for snapshots from before `windows`
+    pub fn rate_windows(&self) -> Vec<&RateWindow> {
+        if self.windows.is_empty() {
+            [&self.primary, &self.secondary, &self.tertiary]
+                .into_iter()
+                .flatten()
+                .collect()
+        } else {
+            self.windows.iter().collect()
+        }
+    }
+
+    /// Give every window an id and, where the length tells, a category. Providers that
+    /// only fill the legacy fields get `windows` derived from them, and the other way round.
+    fn sync_windows(&mut self) {
+        let mut ids = Vec::new();
+        let legacy = [
+            ("primary", &mut self.primary),
+            ("secondary", &mut self.secondary),
+            ("tertiary", &mut self.tertiary),
+        ];
+        for (slot, window) in legacy {
+            if let Some(window) = window {
+                window.identify(slot, &mut ids);
+            }
+        }
+
+        let windows = std::mem::take(&mut self.windows);
+        let mut windows: Vec<_> = windows
+            .into_iter()
+            .filter_map(RateWindow::validated)
+            .collect();
+        if windows.is_empty() {
+            let legacy = [&self.primary, &self.secondary, &self.tertiary];
+            self.windows = legacy.into_iter().flatten().cloned().collect();
+            return;
+        }
+
+        let mut ids = Vec::new();
+        for (index, window) in windows.iter_mut().enumerate() {
+            window.identify(&format!("window-{}", index + 1), &mut ids);
+        }
+        if self.primary.is_none() && self.secondary.is_none() && self.tertiary.is_none() {
+            let mut first = windows.iter().cloned();
+            self.primary = first.next();
+            self.secondary = first.next();
+            self.tertiary = first.next();
+        }
+        self.windows = windows;
+    }
 }
 
 impl RateWindow {
+    /// Fill in a missing id from the label, or `fallback`, keeping it unique among `ids`
+    fn identify(&mut self, fallback: &str, ids: &mut Vec<String>) {
+        let base = self
+            .id
+            .as_deref()
+            .map(slug)
+            .or_else(|| self.label.as_deref().map(slug))
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| fallback.to_string());
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while ids.contains(&id) {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        ids.push(id.clone());
+        self.id = Some(id);
+
+        if self.category.is_none() {
+            self.category = self.window_minutes.and_then(|minutes| match minutes {
+                ..=1440 => Some(WindowCategory::Session),
+                1441..=10080 => Some(WindowCategory::Weekly),
+                40320.. => Some(WindowCategory::Monthly),
+                _ => None,
+            });
+        }
+    }
+
+    /// Counts in a readable form, e.g. "412 / 500 requests"
+    pub fn count_text(&self) -> Option<String> {
+        let mut text = format_count(self.used?);
+        if let Some(limit) = self.limit {
+            text = format!("{} / {}", text, format_count(limit));
+        }
+        if let Some(unit) = &self.unit {
+            text = format!("{} {}", text, unit);
+        }
+        Some(text)
+    }
+
     fn validated(mut self) -> Option<Self> {
+        self.used = self.used.filter(|used| used.is_finite() && *used >= 0.0);
+        self.limit = self.limit.filter(|limit| limit.is_finite() && *limit > 0.0);
+        self.unit = normalize_text(self.unit);
+        if self.used.is_none() {
+            self.limit = None;
+            self.unit = None;
+        }
         if !self.used_percent.is_finite() {
-            return None;
+            let (used, limit) = self.used.zip(self.limit)?;
+            self.used_percent = used / limit * 100.0;
         }
         self.used_percent = self.used_percent.clamp(0.0, 100.0);
         if let Some(minutes) = self.window_minutes {
@@ -466,6 +722,28 @@ impl ProviderIdentity {
     }
 }
 
+/// Compact count, e.g. "412", "12.5", "1.5K" or "2.0M"
+pub(crate) fn format_count(value: f64) -> String {
+    if value >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if value >= 1_000.0 {
+        format!("{:.1}K", value / 1_000.0)
+    } else {
+        let text = format!("{:.2}", value);
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Lowercase, dash-separated form of an id or label
+fn slug(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 fn normalize_text(value: Option<String>) -> Option<String> {
     value.and_then(|text| {
         let trimmed = text.trim();
@@ -487,306 +765,497 @@ fn normalize_datetime(value: Option<String>) -> Option<String> {
         .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339())
 }
 
+/// Forecast from the account's recent history. The snapshot itself is appended to the
+/// history by `HistorySink` once reported. Recent history is kept in memory, but the
+/// first lookup reads the file and every lookup shares a lock with history writes, so
+/// it runs on the blocking pool.
+async fn apply_forecasts(key: AccountKey, usage: UsageSnapshot) -> UsageSnapshot {
+    let fallback = usage.clone();
+    let applied = tokio::task::spawn_blocking(move || {
+        let mut usage = usage;
+        let now = chrono::Utc::now();
+        let since = now - chrono::Duration::hours(FORECAST_LOOKBACK_HOURS);
+        match crate::storage::history::recent_usage(&key, since) {
+            Ok(history) => usage.forecasts = forecast_usage(&history, &usage, now),
+            Err(err) => tracing::warn!("Failed to read usage history for {:?}: {}", key, err),
+        }
+        usage
+    })
+    .await;
+    applied.unwrap_or(fallback)
+}
+
 pub async fn load_cost_snapshot(provider: ProviderId) -> Option<CostSnapshot> {
     cost_usage::load_cost_snapshot(provider).await
 }
 
+/// Where an account came from
+enum AccountSource {
+    Default,
+    /// Declared in `accounts.json`
+    Declared(ProviderAccount),
+    /// Defined in `custom_providers.json`
+    Custom(Box<CustomProviderConfig>),
+    /// Discovered in the plugins directory
+    Plugin(PluginInfo),
+}
+
+/// Account state
+struct AccountState {
+    account_id: String,
+    label: String,
+    source: AccountSource,
+    cached_usage: Option<UsageSnapshot>,
+    fetcher: Arc<dyn ProviderFetcher>,
+}
+
 /// Provider state
-#[allow(dead_code)]
 struct ProviderState {
     enabled: bool,
-    cached_usage: Option<UsageSnapshot>,
-    fetcher: Arc<dyn ProviderFetcher>,
+    /// The default account followed by any user-declared accounts
+    accounts: Vec<AccountState>,
+}
+
+impl ProviderState {
+    fn account(&self, account_id: &str) -> Option<&AccountState> {
+        self.accounts
+            .iter()
+            .find(|account| account.account_id == account_id)
+    }
+
+    fn account_mut(&mut self, account_id: &str) -> Option<&mut AccountState> {
+        self.accounts
+            .iter_mut()
+            .find(|account| account.account_id == account_id)
+    }
+}
+
+/// Summary of a registered account returned to the frontend and CLI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    pub provider_id: ProviderId,
+    pub account_id: String,
+    pub label: String,
+    pub enabled: bool,
+    pub usage: Option<UsageSnapshot>,
 }
 
 /// Registry managing all providers
 pub struct ProviderRegistry {
     providers: RwLock<HashMap<ProviderId, ProviderState>>,
+    /// Folder declared accounts, custom providers and plugins are loaded from
+    data_dir: Option<PathBuf>,
     /// Flag indicating whether the frontend has synced enabled providers
     /// The refresh loop waits for this before starting to avoid using stale defaults
     frontend_synced: RwLock<bool>,
+    refresh_intervals: RwLock<RefreshIntervals>,
+    /// Usage fetches in flight, shared between concurrent callers
+    in_flight: single_flight::SingleFlight<AccountKey, UsageSnapshot>,
+    /// Accounts that must not be fetched again until the provider's rate limit passes
+    rate_limits: rate_limit::RateLimits,
+    /// Window resets spotted when a fetched snapshot replaces the cached one
+    resets: tokio::sync::broadcast::Sender<WindowReset>,
+    /// Where reported fetch results go
+    sinks: sink::UsageSinks,
+    /// Last status fetched, under each account's `status_key`
+    statuses: RwLock<HashMap<AccountKey, ProviderStatus>>,
 }
 
 impl ProviderRegistry {
+    /// Registry backed by the `IncuBar` folder in the user's data directory
     pub fn new() -> Self {
-        let mut providers = HashMap::new();
-
-        // Start with all providers disabled - frontend will sync the correct list
-        // This prevents the race condition where refresh loop starts before frontend sync
-
-        // Claude
-        providers.insert(
-            ProviderId::Claude,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(claude::ClaudeProvider::new()),
-            },
-        );
+        match dirs::data_dir() {
+            Some(data_dir) => Self::with_data_dir(data_dir.join("IncuBar")),
+            None => {
+                tracing::warn!("Could not determine data directory, loading default accounts only");
+                Self::from_providers(Self::default_providers(), None)
+            }
+        }
+    }
 
-        // Codex
-        providers.insert(
-            ProviderId::Codex,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(codex::CodexProvider::new()),
-            },
-        );
+    /// Registry loading declared accounts, custom providers and plugins from `data_dir`
+    pub fn with_data_dir(data_dir: PathBuf) -> Self {
+        let mut providers = Self::default_providers();
+
+        match accounts::load_accounts_in(&data_dir) {
+            Ok(declared) => {
+                for account in declared {
+                    if let Err(err) = Self::insert_account(&mut providers, account.clone()) {
+                        tracing::warn!(
+                            "Skipping account {:?}/{}: {}",
+                            account.provider_id,
+                            account.account_id,
+                            err
+                        );
+                    }
+                }
+            }
+            Err(err) => tracing::warn!("Failed to load accounts: {}", err),
+        }
 
-        // Cursor
-        providers.insert(
-            ProviderId::Cursor,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(cursor::CursorProvider::new()),
-            },
-        );
+        match custom::load_custom_providers_in(&data_dir) {
+            Ok(definitions) => {
+                for config in definitions {
+                    let id = config.id.clone();
+                    if let Err(err) = Self::insert_custom_provider(&mut providers, config, false) {
+                        tracing::warn!("Skipping custom provider {}: {}", id, err);
+                    }
+                }
+            }
+            Err(err) => tracing::warn!("Failed to load custom providers: {}", err),
+        }
 
-        // Copilot
-        providers.insert(
-            ProviderId::Copilot,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(copilot::CopilotProvider::new()),
-            },
-        );
+        if let Some(state) = providers.get_mut(&ProviderId::Plugin) {
+            Self::sync_plugins(state, plugin::discover_plugins(&data_dir));
+        }
 
-        // z.ai
-        providers.insert(
-            ProviderId::Zai,
-            ProviderState {
-                enabled: false, // Requires API token, not enabled by default
-                cached_usage: None,
-                fetcher: Arc::new(zai::ZaiProvider::new()),
-            },
-        );
+        Self::from_providers(providers, Some(data_dir))
+    }
 
-        // Kimi K2
-        providers.insert(
-            ProviderId::KimiK2,
-            ProviderState {
-                enabled: false, // Requires API key, not enabled by default
-                cached_usage: None,
-                fetcher: Arc::new(kimi_k2::KimiK2Provider::new()),
-            },
-        );
+    /// Every provider with its default account, if it has one
+    fn default_providers() -> HashMap<ProviderId, ProviderState> {
+        // Start with all providers disabled - frontend will sync the correct list
+        // This prevents the race condition where refresh loop starts before frontend sync
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .map(|descriptor| {
+                let accounts = descriptor
+                    .create_fetcher
+                    .map(|create_fetcher| AccountState {
+                        account_id: DEFAULT_ACCOUNT_ID.to_string(),
+                        label: descriptor.display_name.to_string(),
+                        source: AccountSource::Default,
+                        cached_usage: None,
+                        fetcher: create_fetcher(),
+                    })
+                    .into_iter()
+                    .collect();
+                (
+                    descriptor.id,
+                    ProviderState {
+                        enabled: false,
+                        accounts,
+                    },
+                )
+            })
+            .collect()
+    }
 
-        // Synthetic
-        providers.insert(
-            ProviderId::Synthetic,
-            ProviderState {
-                enabled: false, // Requires API key, not enabled by default
-                cached_usage: None,
-                fetcher: Arc::new(synthetic::SyntheticProvider::new()),
-            },
-        );
+    fn from_providers(
+        providers: HashMap<ProviderId, ProviderState>,
+        data_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            providers: RwLock::new(providers),
+            data_dir,
+            frontend_synced: RwLock::new(false),
+            refresh_intervals: RwLock::new(RefreshIntervals::default()),
+            in_flight: single_flight::SingleFlight::new(),
+            rate_limits: rate_limit::RateLimits::new(),
+            resets: tokio::sync::broadcast::channel(reset::RESET_CHANNEL_CAPACITY).0,
+            sinks: sink::UsageSinks::default(),
+            statuses: RwLock::new(HashMap::new()),
+        }
+    }
 
-        // Gemini
-        providers.insert(
-            ProviderId::Gemini,
-            ProviderState {
-                enabled: false, // Requires Gemini CLI OAuth, not enabled by default
-                cached_usage: None,
-                fetcher: Arc::new(gemini::GeminiProvider::new()),
-            },
-        );
+    /// Register a sink for every fetch result reported from now on
+    pub fn add_sink(&self, sink: Arc<dyn UsageSink>) {
+        self.sinks.add(sink);
+    }
 
-        // Antigravity
-        providers.insert(
-            ProviderId::Antigravity,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(antigravity::AntigravityProvider::new()),
-            },
-        );
+    /// Pass a successful fetch through the sinks
+    pub fn publish_usage(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        self.sinks.usage_updated(key, usage);
+    }
 
-        // Factory (Droid)
-        providers.insert(
-            ProviderId::Factory,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(factory::FactoryProvider::new()),
-            },
-        );
+    /// Pass a reported failure, and the error snapshot shown for it, through the sinks
+    pub fn publish_failure(&self, key: &AccountKey, error: &ProviderError, usage: &UsageSnapshot) {
+        self.sinks.refresh_failed(key, error, usage);
+    }
 
-        // MiniMax
-        providers.insert(
-            ProviderId::Minimax,
-            ProviderState {
-                enabled: false, // Requires browser cookies
-                cached_usage: None,
-                fetcher: Arc::new(minimax::MinimaxProvider::new()),
-            },
-        );
+    /// Pass a failure the caller holds back through the sinks, for those that count
+    /// every fetch
+    pub fn publish_unreported_failure(&self, key: &AccountKey, error: &ProviderError) {
+        self.sinks.fetch_failed(key, error);
+    }
 
-        // Kimi
-        providers.insert(
-            ProviderId::Kimi,
-            ProviderState {
-                enabled: false, // Requires browser cookies
-                cached_usage: None,
-                fetcher: Arc::new(kimi::KimiProvider::new()),
-            },
-        );
+    /// Wait until every result published so far has been through the sinks, e.g.
+    /// before the CLI exits
+    pub async fn flush_sinks(&self) {
+        self.sinks.flush().await;
+    }
 
-        // Kiro
-        providers.insert(
-            ProviderId::Kiro,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(kiro::KiroProvider::new()),
-            },
-        );
+    fn insert_account(
+        providers: &mut HashMap<ProviderId, ProviderState>,
+        mut account: ProviderAccount,
+    ) -> Result<(), anyhow::Error> {
+        account.account_id = account.account_id.trim().to_string();
+        let credentials_path = account.resolve_credentials_path()?;
+        let create_account_fetcher = account
+            .provider_id
+            .descriptor()
+            .create_account_fetcher
+            .ok_or_else(|| anyhow!("Provider does not support multiple accounts"))?;
+        let state = providers
+            .get_mut(&account.provider_id)
+            .ok_or_else(|| anyhow!("Provider {:?} not found", account.provider_id))?;
+        if state.account(&account.account_id).is_some() {
+            return Err(anyhow!("Account {} already exists", account.account_id));
+        }
 
-        // Vertex AI
-        providers.insert(
-            ProviderId::Vertex,
-            Self::placeholder_state("Vertex AI", "Vertex AI provider not implemented"),
-        );
+        state.accounts.push(AccountState {
+            account_id: account.account_id.clone(),
+            label: account.label.clone(),
+            cached_usage: None,
+            fetcher: create_account_fetcher(credentials_path),
+            source: AccountSource::Declared(account),
+        });
+        Ok(())
+    }
 
-        // Augment
-        providers.insert(
-            ProviderId::Augment,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(augment::AugmentProvider::new()),
-            },
-        );
+    /// Replace the plugin accounts with `plugins`, keeping cached usage of plugins that remain
+    fn sync_plugins(state: &mut ProviderState, plugins: Vec<PluginInfo>) {
+        let mut previous = std::mem::take(&mut state.accounts);
+        state.accounts = plugins
+            .into_iter()
+            .map(|info| {
+                let cached_usage = previous
+                    .iter_mut()
+                    .find(|account| {
+                        matches!(&account.source, AccountSource::Plugin(existing) if *existing == info)
+                    })
+                    .and_then(|account| account.cached_usage.take());
+                AccountState {
+                    account_id: info.id.clone(),
+                    label: info.id.clone(),
+                    cached_usage,
+                    fetcher: Arc::new(plugin::PluginProvider::new(info.clone())),
+                    source: AccountSource::Plugin(info),
+                }
+            })
+            .collect();
+    }
 
-        // Amp
-        providers.insert(
-            ProviderId::Amp,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(amp::AmpProvider::new()),
-            },
-        );
+    /// Register a custom provider under its trimmed id. An existing definition with the
+    /// same id is replaced when `replace` is set and rejected otherwise; `replace` also
+    /// requires the definition to exist.
+    fn insert_custom_provider(
+        providers: &mut HashMap<ProviderId, ProviderState>,
+        mut config: CustomProviderConfig,
+        replace: bool,
+    ) -> Result<(), anyhow::Error> {
+        config.id = config.id.trim().to_string();
+        config.validate()?;
+        let state = providers
+            .get_mut(&ProviderId::Custom)
+            .ok_or_else(|| anyhow!("Provider {:?} not found", ProviderId::Custom))?;
+        let existing = state
+            .accounts
+            .iter()
+            .position(|account| account.account_id == config.id);
+        let account = AccountState {
+            account_id: config.id.clone(),
+            label: config.name.clone(),
+            cached_usage: None,
+            fetcher: Arc::new(custom::CustomHttpProvider::new(config.clone())),
+            source: AccountSource::Custom(Box::new(config)),
+        };
+        match (existing, replace) {
+            (Some(index), true) => state.accounts[index] = account,
+            (None, false) => state.accounts.push(account),
+            (Some(_), false) => {
+                return Err(anyhow!(
+                    "Custom provider {} already exists",
+                    account.account_id
+                ))
+            }
+            (None, true) => {
+                return Err(anyhow!("Custom provider {} not found", account.account_id))
+            }
+        }
+        Ok(())
+    }
 
-        // JetBrains
-        providers.insert(
-            ProviderId::Jetbrains,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(jetbrains::JetbrainsProvider::new()),
-            },
-        );
+    /// Clone the fetcher for an account while holding the lock briefly
+    async fn account_fetcher(
+        &self,
+        key: &AccountKey,
+    ) -> Result<Arc<dyn ProviderFetcher>, anyhow::Error> {
+        let providers = self.providers.read().await;
+        let state = providers
+            .get(&key.provider_id)
+            .ok_or_else(|| anyhow!("Provider {:?} not found", key.provider_id))?;
+        state
+            .account(&key.account_id)
+            .map(|account| Arc::clone(&account.fetcher))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Account {} not found for {:?}",
+                    key.account_id,
+                    key.provider_id
+                )
+            })
+    }
 
-        // OpenCode
-        providers.insert(
-            ProviderId::Opencode,
-            ProviderState {
-                enabled: false,
-                cached_usage: None,
-                fetcher: Arc::new(opencode::OpencodeProvider::new()),
-            },
-        );
+    /// Fetch usage, joining a fetch already in flight for the same account
+    pub async fn fetch_usage(&self, key: &AccountKey) -> Result<UsageSnapshot, ProviderError> {
+        self.fetch_usage_shared(key, false).await
+    }
 
-        Self {
-            providers: RwLock::new(providers),
-            frontend_synced: RwLock::new(false),
-        }
+    /// Fetch usage, cancelling a fetch already in flight for the same account
+    pub async fn fetch_usage_forced(
+        &self,
+        key: &AccountKey,
+    ) -> Result<UsageSnapshot, ProviderError> {
+        self.fetch_usage_shared(key, true).await
     }
 
-    fn placeholder_state(name: &'static str, description: &'static str) -> ProviderState {
-        ProviderState {
-            enabled: false,
-            cached_usage: None,
-            fetcher: Arc::new(PlaceholderProvider::new(name, description)),
-        }
+    /// Cancel the usage fetch in flight for an account. Returns whether there was one.
+    pub fn cancel_fetch(&self, key: &AccountKey) -> bool {
+        self.in_flight.cancel(key)
     }
 
-    pub async fn fetch_usage(&self, id: &ProviderId) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch_usage_shared(
+        &self,
+        key: &AccountKey,
+        force: bool,
+    ) -> Result<UsageSnapshot, ProviderError> {
         const FETCH_TIMEOUT_SECS: u64 = 15;
-        
+
+        // Fetching again before the provider's Retry-After only extends the rate limit
+        if let Some(error) = self.rate_limits.check(key, SystemTime::now()) {
+            tracing::debug!("fetch_usage: {:?} is rate limited", key);
+            return Err(error);
+        }
+
         // Clone the Arc<dyn ProviderFetcher> while holding the lock briefly, then drop the lock
         // This prevents lock starvation when one provider's fetch hangs
-        tracing::debug!("fetch_usage: acquiring read lock for {:?}", id);
-        let fetcher = {
-            let providers = self.providers.read().await;
-            tracing::debug!("fetch_usage: got read lock for {:?}", id);
-            match providers.get(id) {
-                Some(state) => Arc::clone(&state.fetcher),
-                None => return Err(anyhow::anyhow!("Provider {:?} not found", id)),
-            }
-        };
+        tracing::debug!("fetch_usage: acquiring read lock for {:?}", key);
+        let fetcher = self.account_fetcher(key).await?;
         // Lock is now dropped, other providers can proceed
-        
-        tracing::debug!("fetch_usage: starting fetch for {:?}", id);
-        // Add timeout to prevent hanging on cookie/network operations
-        let fetch_result = tokio::select! {
-            result = fetcher.fetch() => result,
-            _ = tokio::time::sleep(Duration::from_secs(FETCH_TIMEOUT_SECS)) => {
-                tracing::warn!("Provider {:?} fetch timed out after {}s", id, FETCH_TIMEOUT_SECS);
-                return Err(anyhow!("Fetch timed out after {}s - browser may be blocking cookie access", FETCH_TIMEOUT_SECS));
-            }
+
+        let label = key.clone();
+        let fetch = async move {
+            tracing::debug!("fetch_usage: starting fetch for {:?}", label);
+            // Add timeout to prevent hanging on cookie/network operations
+            let fetch_result = tokio::select! {
+                result = rate_limit::capture(fetcher.fetch()) => result,
+                _ = tokio::time::sleep(Duration::from_secs(FETCH_TIMEOUT_SECS)) => {
+                    tracing::warn!("Provider {:?} fetch timed out after {}s", label, FETCH_TIMEOUT_SECS);
+                    return Err(ProviderError::Timeout(format!("Fetch timed out after {}s - browser may be blocking cookie access", FETCH_TIMEOUT_SECS)));
+                }
+            };
+            tracing::debug!("fetch_usage: fetch completed for {:?}", label);
+            let usage = fetch_result.map(UsageSnapshot::validated)?;
+            // Done here rather than per caller, so joined fetches forecast once
+            Ok(apply_forecasts(label, usage).await)
         };
-        
-        let usage = fetch_result?.validated();
-        tracing::debug!("fetch_usage: fetch completed for {:?}", id);
 
-        // Cache the result
+        if self.in_flight.is_in_flight(key) && !force {
+            tracing::debug!("fetch_usage: joining fetch in flight for {:?}", key);
+        }
+        let result = self.in_flight.run(key.clone(), force, fetch).await;
+        self.rate_limits.record(key, &result, SystemTime::now());
+        let usage = result?;
+
+        // Cache the result. Callers that joined the same fetch find it cached already,
+        // so each reset is reported once.
         let mut providers = self.providers.write().await;
-        if let Some(state) = providers.get_mut(id) {
-            state.cached_usage = Some(usage.clone());
+        let previous = providers
+            .get_mut(&key.provider_id)
+            .and_then(|state| state.account_mut(&key.account_id))
+            .and_then(|account| account.cached_usage.replace(usage.clone()));
+        drop(providers);
+        if let Some(previous) = previous {
+            for reset in reset::detect_resets(key, &previous, &usage, chrono::Utc::now()) {
+                // Nobody listening, e.g. in the CLI
+                let _ = self.resets.send(reset);
+            }
         }
 
         Ok(usage)
     }
 
-    pub async fn fetch_status(&self, id: &ProviderId) -> Result<ProviderStatus, anyhow::Error> {
+    /// Status of the account's provider, read from the feed named by the provider's
+    /// descriptor. Providers whose accounts report status themselves, such as plugins, are
+    /// asked through the account's fetcher. Cached under the account's `status_key`.
+    pub async fn fetch_status(&self, key: &AccountKey) -> Result<ProviderStatus, anyhow::Error> {
         const FETCH_TIMEOUT_SECS: u64 = 10;
-        
-        // Clone the Arc<dyn ProviderFetcher> while holding the lock briefly, then drop the lock
-        // This prevents lock starvation when one provider's fetch hangs
-        tracing::debug!("fetch_status: acquiring read lock for {:?}", id);
-        let fetcher = {
-            let providers = self.providers.read().await;
-            tracing::debug!("fetch_status: got read lock for {:?}", id);
-            match providers.get(id) {
-                Some(state) => Arc::clone(&state.fetcher),
-                None => return Err(anyhow::anyhow!("Provider {:?} not found", id)),
+        let key = key.status_key();
+        let id = &key.provider_id;
+        let descriptor = id.descriptor();
+
+        tracing::debug!("fetch_status: starting fetch for {:?}", key);
+        let request = async {
+            match descriptor.status_source {
+                StatusSource::Fetcher => self.account_fetcher(&key).await?.fetch_status().await,
+                source => status::fetch_feed(source).await,
             }
         };
-        // Lock is now dropped, other providers can proceed
-        
-        tracing::debug!("fetch_status: starting fetch for {:?}", id);
-        let status = match timeout(Duration::from_secs(FETCH_TIMEOUT_SECS), fetcher.fetch_status()).await {
+        let mut status = match timeout(Duration::from_secs(FETCH_TIMEOUT_SECS), request).await {
             Ok(result) => result?.validated(),
             Err(_) => {
-                tracing::warn!("Provider {:?} status fetch timed out after {}s", id, FETCH_TIMEOUT_SECS);
-                return Err(anyhow!("Status fetch timed out after {}s", FETCH_TIMEOUT_SECS));
+                tracing::warn!(
+                    "Provider {:?} status fetch timed out after {}s",
+                    id,
+                    FETCH_TIMEOUT_SECS
+                );
+                return Err(anyhow!(
+                    "Status fetch timed out after {}s",
+                    FETCH_TIMEOUT_SECS
+                ));
             }
         };
-        tracing::debug!("fetch_status: completed for {:?}", id);
+        status.url = descriptor.dashboard_url.map(str::to_string);
+        tracing::debug!("fetch_status: completed for {:?}", key);
+        self.statuses.write().await.insert(key, status.clone());
         Ok(status)
     }
 
-    pub async fn get_cached_usage(&self, id: &ProviderId) -> Option<UsageSnapshot> {
+    pub async fn auth_status(&self, key: &AccountKey) -> AuthStatus {
+        match self.account_fetcher(key).await {
+            Ok(fetcher) => fetcher.auth_status().await,
+            Err(err) => AuthStatus::unauthenticated(Some(err.to_string())),
+        }
+    }
+
+    /// Window resets detected from here on
+    pub fn subscribe_resets(&self) -> tokio::sync::broadcast::Receiver<WindowReset> {
+        self.resets.subscribe()
+    }
+
+    pub async fn get_cached_usage(&self, key: &AccountKey) -> Option<UsageSnapshot> {
         self.providers
             .read()
             .await
-            .get(id)
-            .and_then(|state| state.cached_usage.clone())
+            .get(&key.provider_id)
+            .and_then(|state| state.account(&key.account_id))
+            .and_then(|account| account.cached_usage.clone())
     }
 
-    pub async fn get_all_cached_usage(&self) -> HashMap<ProviderId, UsageSnapshot> {
+    /// Cached usage of every account, including custom providers and plugins
+    pub async fn get_all_cached_usage(&self) -> HashMap<AccountKey, UsageSnapshot> {
         self.providers
             .read()
             .await
             .iter()
-            .filter_map(|(id, state)| state.cached_usage.clone().map(|usage| (*id, usage)))
+            .flat_map(|(id, state)| {
+                state.accounts.iter().filter_map(|account| {
+                    account
+                        .cached_usage
+                        .clone()
+                        .map(|usage| (AccountKey::new(*id, account.account_id.clone()), usage))
+                })
+            })
             .collect()
     }
 
+    /// Last status fetched for each provider, and for each plugin
+    pub async fn get_cached_statuses(&self) -> HashMap<AccountKey, ProviderStatus> {
+        self.statuses.read().await.clone()
+    }
+
     pub async fn get_enabled_providers(&self) -> Vec<ProviderId> {
         self.providers
             .read()
@@ -796,6 +1265,143 @@ impl ProviderRegistry {
             .collect()
     }
 
+    /// Every account of every enabled provider
+    pub async fn get_enabled_accounts(&self) -> Vec<AccountKey> {
+        self.providers
+            .read()
+            .await
+            .iter()
+            .filter(|(_, state)| state.enabled)
+            .flat_map(|(id, state)| {
+                state
+                    .accounts
+                    .iter()
+                    .map(|account| AccountKey::new(*id, account.account_id.clone()))
+            })
+            .collect()
+    }
+
+    /// All registered accounts in descriptor order
+    pub async fn list_accounts(&self) -> Vec<AccountInfo> {
+        let providers = self.providers.read().await;
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .filter_map(|descriptor| {
+                providers
+                    .get(&descriptor.id)
+                    .map(|state| (descriptor.id, state))
+            })
+            .flat_map(|(id, state)| {
+                state.accounts.iter().map(move |account| AccountInfo {
+                    provider_id: id,
+                    account_id: account.account_id.clone(),
+                    label: account.label.clone(),
+                    enabled: state.enabled,
+                    usage: account.cached_usage.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Accounts declared by the user, in the form persisted to `accounts.json`
+    pub async fn declared_accounts(&self) -> Vec<ProviderAccount> {
+        let providers = self.providers.read().await;
+        PROVIDER_DESCRIPTORS
+            .iter()
+            .filter_map(|descriptor| providers.get(&descriptor.id))
+            .flat_map(|state| state.accounts.iter())
+            .filter_map(|account| match &account.source {
+                AccountSource::Declared(declaration) => Some(declaration.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Custom provider definitions, in the form persisted to `custom_providers.json`
+    pub async fn custom_providers(&self) -> Vec<CustomProviderConfig> {
+        self.providers
+            .read()
+            .await
+            .get(&ProviderId::Custom)
+            .map(|state| {
+                state
+                    .accounts
+                    .iter()
+                    .filter_map(|account| match &account.source {
+                        AccountSource::Custom(config) => Some(config.as_ref().clone()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Rescan the plugins directory and return the plugins now registered
+    pub async fn rescan_plugins(&self) -> Vec<PluginInfo> {
+        let plugins = self
+            .data_dir
+            .as_deref()
+            .map(plugin::discover_plugins)
+            .unwrap_or_default();
+        if let Some(state) = self.providers.write().await.get_mut(&ProviderId::Plugin) {
+            Self::sync_plugins(state, plugins.clone());
+        }
+        plugins
+    }
+
+    /// Register a new custom provider; its id must not be taken yet
+    pub async fn add_custom_provider(
+        &self,
+        config: CustomProviderConfig,
+    ) -> Result<(), anyhow::Error> {
+        Self::insert_custom_provider(&mut *self.providers.write().await, config, false)
+    }
+
+    /// Replace the definition of an existing custom provider
+    pub async fn update_custom_provider(
+        &self,
+        config: CustomProviderConfig,
+    ) -> Result<(), anyhow::Error> {
+        Self::insert_custom_provider(&mut *self.providers.write().await, config, true)
+    }
+
+    pub async fn add_account(&self, account: ProviderAccount) -> Result<(), anyhow::Error> {
+        Self::insert_account(&mut *self.providers.write().await, account)
+    }
+
+    /// Persist the declared accounts to `accounts.json` in the data directory
+    pub async fn save_accounts(&self) -> Result<(), anyhow::Error> {
+        accounts::save_accounts_in(self.storage_dir()?, &self.declared_accounts().await)
+    }
+
+    /// Persist the custom provider definitions to `custom_providers.json` in the data
+    /// directory
+    pub async fn save_custom_providers(&self) -> Result<(), anyhow::Error> {
+        custom::save_custom_providers_in(self.storage_dir()?, &self.custom_providers().await)
+    }
+
+    fn storage_dir(&self) -> Result<&std::path::Path, anyhow::Error> {
+        self.data_dir
+            .as_deref()
+            .ok_or_else(|| anyhow!("Could not determine data directory"))
+    }
+
+    /// Remove a user-declared account. The default account cannot be removed.
+    pub async fn remove_account(&self, key: &AccountKey) -> bool {
+        if key.is_default() {
+            return false;
+        }
+        let mut providers = self.providers.write().await;
+        let Some(state) = providers.get_mut(&key.provider_id) else {
+            return false;
+        };
+        let before = state.accounts.len();
+        state
+            .accounts
+            .retain(|account| account.account_id != key.account_id);
+        state.accounts.len() != before
+    }
+
     pub async fn set_enabled(&self, id: &ProviderId, enabled: bool) {
         if let Some(state) = self.providers.write().await.get_mut(id) {
             state.enabled = enabled;
@@ -811,7 +1417,10 @@ impl ProviderRegistry {
         drop(providers);
         // Mark frontend as synced - this allows the refresh loop to start
         *self.frontend_synced.write().await = true;
-        tracing::info!("set_enabled_providers: synced {} providers from frontend, refresh loop can now start", enabled.len());
+        tracing::info!(
+            "set_enabled_providers: synced {} providers from frontend, refresh loop can now start",
+            enabled.len()
+        );
     }
 
     /// Check if the frontend has synced enabled providers
@@ -823,15 +1432,111 @@ impl ProviderRegistry {
     pub async fn mark_frontend_synced(&self) {
         *self.frontend_synced.write().await = true;
     }
+
+    /// Update the background refresh intervals; the loop picks them up on its next tick
+    pub async fn set_refresh_intervals(&self, intervals: RefreshIntervals) {
+        *self.refresh_intervals.write().await = intervals;
+    }
+
+    pub async fn refresh_intervals(&self) -> RefreshIntervals {
+        self.refresh_intervals.read().await.clone()
+    }
 }
 
 #[cfg(test)]
 mod registry_tests {
-    use super::{ProviderId, ProviderRegistry, UsageSnapshot};
+    use super::{
+        AccountKey, CustomProviderConfig, ProviderAccount, ProviderError, ProviderId,
+        ProviderRegistry, StatusIndicator, UsageSink, UsageSnapshot,
+    };
+    use crate::providers::fixtures::ReplayServer;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime};
+
+    struct RecordingSink {
+        name: &'static str,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl UsageSink for RecordingSink {
+        fn usage_updated(&self, key: &AccountKey, _usage: &UsageSnapshot) {
+            let event = format!("{} updated {}", self.name, key.account_id);
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn fetch_failed(&self, key: &AccountKey, _error: &ProviderError) {
+            let event = format!("{} fetch failed {}", self.name, key.account_id);
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn refresh_failed(&self, key: &AccountKey, error: &ProviderError, _usage: &UsageSnapshot) {
+            let event = format!("{} failed {}: {}", self.name, key.account_id, error);
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    /// Registry over an empty data directory, so tests never see the user's accounts
+    fn temp_registry() -> (tempfile::TempDir, ProviderRegistry) {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ProviderRegistry::with_data_dir(dir.path().to_path_buf());
+        (dir, registry)
+    }
+
+    #[tokio::test]
+    async fn published_results_reach_every_sink_in_order() {
+        let registry =
+            ProviderRegistry::from_providers(ProviderRegistry::default_providers(), None);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        for name in ["tray", "history"] {
+            registry.add_sink(Arc::new(RecordingSink {
+                name,
+                events: Arc::clone(&events),
+            }));
+        }
+
+        let key = AccountKey::new(ProviderId::Codex, "work");
+        registry.publish_usage(&key, &UsageSnapshot::error(String::new()));
+        let error = ProviderError::Network("offline".to_string());
+        registry.publish_failure(&key, &error, &UsageSnapshot::from_error(&error));
+        registry.publish_unreported_failure(&key, &error);
+        registry.flush_sinks().await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "tray updated work",
+                "history updated work",
+                "tray fetch failed work",
+                "tray failed work: offline",
+                "history fetch failed work",
+                "history failed work: offline",
+                "tray fetch failed work",
+                "history fetch failed work",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn status_links_to_the_descriptor_dashboard() {
+        let (_dir, registry) = temp_registry();
+
+        // Claude has no status feed, so this never leaves the process
+        let key = AccountKey::new(ProviderId::Claude, "work");
+        let status = registry.fetch_status(&key).await.unwrap();
+        assert!(!status.is_incident());
+        assert_eq!(status.url.as_deref(), Some("https://status.anthropic.com"));
+        // Provider-wide, so kept under the default account whichever account asked
+        let cached = registry.get_cached_statuses().await;
+        assert_eq!(
+            cached[&AccountKey::default_for(ProviderId::Claude)].url,
+            status.url
+        );
+        assert!(!cached.contains_key(&key));
+    }
 
     #[tokio::test]
     async fn registry_starts_with_all_providers_disabled() {
-        let registry = ProviderRegistry::new();
+        let (_dir, registry) = temp_registry();
 
         // All providers start disabled - frontend will sync the correct list
         let enabled_providers = registry.get_enabled_providers().await;
@@ -848,12 +1553,17 @@ mod registry_tests {
         let usage = UsageSnapshot::error("unit test".to_string());
         {
             let mut providers = registry.providers.write().await;
-            if let Some(state) = providers.get_mut(&ProviderId::Claude) {
-                state.cached_usage = Some(usage.clone());
+            if let Some(account) = providers
+                .get_mut(&ProviderId::Claude)
+                .and_then(|state| state.account_mut(super::DEFAULT_ACCOUNT_ID))
+            {
+                account.cached_usage = Some(usage.clone());
             }
         }
 
-        let cached = registry.get_cached_usage(&ProviderId::Claude).await;
+        let cached = registry
+            .get_cached_usage(&AccountKey::default_for(ProviderId::Claude))
+            .await;
         assert_eq!(
             cached.and_then(|snapshot| snapshot.error),
             Some("unit test".to_string())
@@ -862,11 +1572,13 @@ mod registry_tests {
 
     #[tokio::test]
     async fn set_enabled_providers_marks_frontend_synced() {
-        let registry = ProviderRegistry::new();
+        let (_dir, registry) = temp_registry();
 
         assert!(!registry.is_frontend_synced().await);
 
-        registry.set_enabled_providers(&[ProviderId::Cursor, ProviderId::Copilot]).await;
+        registry
+            .set_enabled_providers(&[ProviderId::Cursor, ProviderId::Copilot])
+            .await;
 
         assert!(registry.is_frontend_synced().await);
         let enabled = registry.get_enabled_providers().await;
@@ -875,14 +1587,285 @@ mod registry_tests {
         assert!(!enabled.contains(&ProviderId::Claude));
         assert!(!enabled.contains(&ProviderId::Codex));
     }
+
+    #[tokio::test]
+    async fn auth_status_reports_unknown_accounts() {
+        let (_dir, registry) = temp_registry();
+
+        let status = registry
+            .auth_status(&AccountKey::new(ProviderId::Vertex, "missing"))
+            .await;
+
+        assert!(!status.authenticated);
+        assert!(status.error.unwrap_or_default().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn added_accounts_are_refreshed_alongside_the_default_account() {
+        let (_dir, registry) = temp_registry();
+        let work = ProviderAccount {
+            provider_id: ProviderId::Cursor,
+            account_id: "registry-test-work".to_string(),
+            label: "Work".to_string(),
+            credentials_path: Some(std::env::temp_dir().join("incubar-registry-test-work.json")),
+        };
+
+        registry
+            .add_account(work.clone())
+            .await
+            .expect("add account");
+        assert!(registry.add_account(work.clone()).await.is_err());
+        let padded = ProviderAccount {
+            account_id: format!("  {} ", work.account_id),
+            ..work.clone()
+        };
+        assert!(registry.add_account(padded).await.is_err());
+        let blank = ProviderAccount {
+            account_id: "   ".to_string(),
+            ..work.clone()
+        };
+        assert!(registry.add_account(blank).await.is_err());
+
+        registry.set_enabled(&ProviderId::Cursor, true).await;
+        let accounts = registry.get_enabled_accounts().await;
+        assert!(accounts.contains(&AccountKey::default_for(ProviderId::Cursor)));
+        assert!(accounts.contains(&work.key()));
+        assert!(registry.declared_accounts().await.contains(&work));
+
+        assert!(
+            !registry
+                .remove_account(&AccountKey::default_for(ProviderId::Cursor))
+                .await
+        );
+        assert!(registry.remove_account(&work.key()).await);
+        assert!(!registry.get_enabled_accounts().await.contains(&work.key()));
+    }
+
+    #[tokio::test]
+    async fn declared_accounts_load_from_the_data_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let work = ProviderAccount {
+            provider_id: ProviderId::Claude,
+            account_id: "work".to_string(),
+            label: "Work".to_string(),
+            credentials_path: Some(dir.path().join("work.json")),
+        };
+        std::fs::write(
+            dir.path().join("accounts.json"),
+            serde_json::to_string(&[&work]).unwrap(),
+        )
+        .unwrap();
+
+        let registry = ProviderRegistry::with_data_dir(dir.path().to_path_buf());
+
+        assert_eq!(registry.declared_accounts().await, vec![work]);
+    }
+
+    #[tokio::test]
+    async fn custom_providers_are_accounts_without_a_default() {
+        let (_dir, registry) = temp_registry();
+        let mut gateway = CustomProviderConfig {
+            id: "registry-test-gateway".to_string(),
+            name: "Gateway".to_string(),
+            url: "https://gateway.example.com/quota".to_string(),
+            auth: None,
+            headers: Default::default(),
+            fields: Default::default(),
+        };
+
+        registry
+            .add_custom_provider(gateway.clone())
+            .await
+            .expect("add custom provider");
+        gateway.name = "Renamed gateway".to_string();
+        assert!(registry.add_custom_provider(gateway.clone()).await.is_err());
+        registry
+            .update_custom_provider(gateway.clone())
+            .await
+            .expect("update custom provider");
+        let mut blank = gateway.clone();
+        blank.id = "   ".to_string();
+        assert!(registry.add_custom_provider(blank).await.is_err());
+        let mut padded = gateway.clone();
+        padded.id = format!("  {}  ", gateway.id);
+        assert!(registry.add_custom_provider(padded).await.is_err());
+
+        let key = AccountKey::new(ProviderId::Custom, gateway.id.clone());
+        registry.set_enabled(&ProviderId::Custom, true).await;
+        let accounts = registry.get_enabled_accounts().await;
+        assert!(accounts.contains(&key));
+        assert!(!accounts.contains(&AccountKey::default_for(ProviderId::Custom)));
+        assert!(registry.custom_providers().await.contains(&gateway));
+        assert!(registry.fetch_status(&key).await.is_ok());
+
+        assert!(registry.remove_account(&key).await);
+        assert!(!registry.custom_providers().await.contains(&gateway));
+    }
+
+    #[tokio::test]
+    async fn custom_provider_usage_is_cached_under_its_own_key() {
+        let exchange = serde_json::from_str(
+            r#"{"request":{"method":"GET","path":"/quota"},"response":{"status":200,"body":"{\"quota\":{\"used\":25,\"limit\":100}}"}}"#,
+        )
+        .unwrap();
+        let server = ReplayServer::start(vec![exchange]).await;
+        let (_dir, registry) = temp_registry();
+        let gateway = CustomProviderConfig {
+            id: "cached-gateway".to_string(),
+            name: "Gateway".to_string(),
+            url: format!("{}/quota", server.base_url()),
+            auth: None,
+            headers: Default::default(),
+            fields: Default::default(),
+        };
+        registry.add_custom_provider(gateway).await.unwrap();
+
+        let key = AccountKey::new(ProviderId::Custom, "cached-gateway");
+        let usage = registry.fetch_usage(&key).await.expect("custom usage");
+        assert_eq!(
+            usage.primary.as_ref().map(|window| window.used_percent),
+            Some(25.0)
+        );
+
+        let cached = registry.get_all_cached_usage().await;
+        assert_eq!(
+            cached
+                .get(&key)
+                .and_then(|usage| usage.primary.as_ref())
+                .map(|window| window.used_percent),
+            Some(25.0)
+        );
+        assert!(!cached.contains_key(&AccountKey::default_for(ProviderId::Custom)));
+    }
+
+    #[tokio::test]
+    async fn retry_after_holds_back_the_next_fetch() {
+        let limited = serde_json::from_str(
+            r#"{"request":{"method":"GET","path":"/quota"},"response":{"status":429,"headers":{"retry-after":"120"},"body":"{}"}}"#,
+        )
+        .unwrap();
+        let recovered = serde_json::from_str(
+            r#"{"request":{"method":"GET","path":"/quota"},"response":{"status":200,"body":"{\"quota\":{\"used\":25,\"limit\":100}}"}}"#,
+        )
+        .unwrap();
+        let server = ReplayServer::start(vec![limited, recovered]).await;
+        let (_dir, registry) = temp_registry();
+        let gateway = CustomProviderConfig {
+            id: "limited-gateway".to_string(),
+            name: "Gateway".to_string(),
+            url: format!("{}/quota", server.base_url()),
+            auth: None,
+            headers: Default::default(),
+            fields: Default::default(),
+        };
+        registry.add_custom_provider(gateway).await.unwrap();
+        let key = AccountKey::new(ProviderId::Custom, "limited-gateway");
+
+        let error = registry.fetch_usage(&key).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ProviderError::RateLimited {
+                retry_after: Some(delay),
+                ..
+            } if delay == Duration::from_secs(120)
+        ));
+
+        // Until the delay is over the fetch is turned away without a request
+        let now = SystemTime::now();
+        assert!(matches!(
+            registry.rate_limits.check(&key, now),
+            Some(ProviderError::RateLimited {
+                retry_after: Some(remaining),
+                ..
+            }) if remaining > Duration::from_secs(100)
+        ));
+        assert!(matches!(
+            registry.fetch_usage(&key).await,
+            Err(ProviderError::RateLimited { .. })
+        ));
+        assert_eq!(server.received().len(), 1);
+        assert_eq!(server.pending().len(), 1);
+
+        assert!(registry
+            .rate_limits
+            .check(&key, now + Duration::from_secs(121))
+            .is_none());
+        assert!(registry.fetch_usage(&key).await.is_ok());
+        assert!(server.pending().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn plugin_usage_is_cached_under_its_own_key() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (dir, registry) = temp_registry();
+        let plugins = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins).unwrap();
+        let path = plugins.join("incubar-provider-gateway");
+        std::fs::write(
+            &path,
+            "#!/bin/sh\necho '{\"primary\":{\"usedPercent\":40},\"updatedAt\":\"2025-01-01T00:00:00Z\"}'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(registry.rescan_plugins().await.len(), 1);
+
+        let key = AccountKey::new(ProviderId::Plugin, "gateway");
+        registry.fetch_usage(&key).await.expect("plugin usage");
+
+        let cached = registry.get_all_cached_usage().await;
+        assert_eq!(
+            cached
+                .get(&key)
+                .and_then(|usage| usage.primary.as_ref())
+                .map(|window| window.used_percent),
+            Some(40.0)
+        );
+        assert!(!cached.contains_key(&AccountKey::default_for(ProviderId::Plugin)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn each_plugin_reports_its_own_status() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (dir, registry) = temp_registry();
+        let plugins = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins).unwrap();
+        for (id, indicator) in [("gateway", "major"), ("mirror", "none")] {
+            let path = plugins.join(format!("incubar-provider-{}", id));
+            std::fs::write(
+                &path,
+                format!(
+                    "#!/bin/sh\n[ \"$1\" = status ] && echo '{{\"indicator\":\"{}\",\"description\":\"{} feed\"}}'\n",
+                    indicator, id
+                ),
+            )
+            .unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert_eq!(registry.rescan_plugins().await.len(), 2);
+
+        let gateway = AccountKey::new(ProviderId::Plugin, "gateway");
+        let mirror = AccountKey::new(ProviderId::Plugin, "mirror");
+        let status = registry
+            .fetch_status(&gateway)
+            .await
+            .expect("plugin status");
+        assert_eq!(status.indicator, StatusIndicator::Major);
+        assert_eq!(status.description.as_deref(), Some("gateway feed"));
+        registry.fetch_status(&mirror).await.expect("plugin status");
+
+        let cached = registry.get_cached_statuses().await;
+        assert_eq!(cached[&gateway].indicator, StatusIndicator::Major);
+        assert_eq!(cached[&mirror].indicator, StatusIndicator::None);
+        assert!(!cached.contains_key(&AccountKey::default_for(ProviderId::Plugin)));
+    }
 }
 
 /// Start the background refresh loop
 pub async fn start_refresh_loop(app: AppHandle) {
-    let interval = std::time::Duration::from_secs(300); // 5 minutes
-    let tick_interval = std::time::Duration::from_secs(5);
-    let mut provider_states: HashMap<ProviderId, ProviderRefreshState> = HashMap::new();
-
     // Wait for frontend to sync enabled providers before starting refresh
     // This prevents the refresh loop from using hardcoded defaults
     tracing::info!("start_refresh_loop: waiting for frontend to sync enabled providers...");
@@ -902,81 +1885,230 @@ pub async fn start_refresh_loop(app: AppHandle) {
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
     }
 
+    let Some(registry) = app.try_state::<ProviderRegistry>() else {
+        tracing::error!("start_refresh_loop: no provider registry, not refreshing");
+        return;
+    };
+    tokio::join!(
+        run_refresh_loop(registry.inner(), &app),
+        check_stale_usage(registry.inner(), &app),
+    );
+}
+
+/// Warn about accounts whose usage stopped updating. Runs until the process exits.
+async fn check_stale_usage(registry: &ProviderRegistry, app: &AppHandle) {
+    loop {
+        tokio::time::sleep(REFRESH_TICK).await;
+        let intervals = registry.refresh_intervals().await;
+        for key in registry.get_enabled_accounts().await {
+            if let Some(usage) = registry.get_cached_usage(&key).await {
+                let stale_after =
+                    notifications::stale_after(intervals.for_provider(key.provider_id));
+                notifications::notify_if_stale(app, &key, &usage, stale_after);
+            }
+        }
+    }
+}
+
+/// Refresh every enabled account of `registry` as it falls due, backing off after
+/// failures, and report the results through `emitter`. Runs until the process exits.
+pub async fn run_refresh_loop(registry: &ProviderRegistry, emitter: &impl RefreshEmitter) {
+    let mut provider_states: HashMap<AccountKey, ProviderRefreshState> = HashMap::new();
+
     loop {
-        tokio::time::sleep(tick_interval).await;
+        tokio::time::sleep(REFRESH_TICK).await;
         let now = SystemTime::now();
 
-        if let Some(registry) = app.try_state::<ProviderRegistry>() {
-            let providers = registry.get_enabled_providers().await;
-            for provider_id in &providers {
-                provider_states
-                    .entry(*provider_id)
-                    .or_insert_with(|| ProviderRefreshState::new(now, interval));
-            }
+        let accounts = registry.get_enabled_accounts().await;
+        let intervals = registry.refresh_intervals().await;
 
-            for provider_id in providers {
+        let due: Vec<AccountKey> = accounts
+            .into_iter()
+            .filter(|key| {
+                // Providers set to manual refresh are left to the frontend
+                let Some(interval) = intervals.for_provider(key.provider_id) else {
+                    return false;
+                };
                 let state = provider_states
-                    .entry(provider_id)
-                    .or_insert_with(|| ProviderRefreshState::new(now, interval));
-                let had_cached_data = registry.get_cached_usage(&provider_id).await.is_some();
+                    .entry(key.clone())
+                    .or_insert_with(|| ProviderRefreshState::new(interval));
+                state.set_interval(now, interval);
+                state.is_due(now)
+            })
+            .collect();
+        if due.is_empty() {
+            continue;
+        }
 
-                if !state.is_due(now) {
-                    continue;
-                }
+        // Collected up front so refreshes cut off at the deadline are gated like other failures
+        let mut had_cached_data = std::collections::HashSet::new();
+        for key in &due {
+            if registry.get_cached_usage(key).await.is_some() {
+                had_cached_data.insert(key.clone());
+            }
+        }
 
-                // Skip unauthenticated providers to avoid wasting resources
-                let provider_id_str = serde_json::to_string(&provider_id)
-                    .unwrap_or_default()
-                    .trim_matches('"')
-                    .to_string();
-                let auth_status = crate::login::check_auth_status(&provider_id_str).await;
-                if !auth_status.authenticated {
-                    tracing::debug!("start_refresh_loop: skipping {:?} - not authenticated", provider_id);
-                    continue;
-                }
+        // Only the first account that actually fetches this tick polls each status key,
+        // so provider-wide status is polled once per provider
+        let status_polled = std::sync::Mutex::new(std::collections::HashSet::new());
+        refresh_concurrently(
+            due,
+            REFRESH_CONCURRENCY,
+            REFRESH_DEADLINE,
+            |key| refresh_account(registry, emitter, key, &status_polled),
+            |key| {
+                // The fetch runs on its own single-flight task, which outlives the refresh
+                registry.cancel_fetch(key);
+            },
+            |key, outcome| {
+                let Some(state) = provider_states.get_mut(&key) else {
+                    return;
+                };
+                let had_cached_data = had_cached_data.contains(&key);
+                apply_refresh_outcome(
+                    emitter,
+                    registry,
+                    &key,
+                    state,
+                    outcome,
+                    had_cached_data,
+                    SystemTime::now(),
+                );
+            },
+        )
+        .await;
+    }
+}
 
-                if let Ok(status) = registry.fetch_status(&provider_id).await {
-                    let _ = app.emit(
-                        "status-updated",
-                        serde_json::json!({
-                            "providerId": provider_id,
-                            "status": status,
-                        }),
-                    );
-                }
+/// How often the refresh loop looks for accounts that are due
+const REFRESH_TICK: Duration = Duration::from_secs(5);
+/// Accounts refreshed at the same time
+const REFRESH_CONCURRENCY: usize = 4;
+/// Refreshes still running this long after they start are cancelled and count as failures
+const REFRESH_DEADLINE: Duration = Duration::from_secs(45);
+
+/// Result of refreshing a single account
+#[derive(Debug)]
+enum RefreshOutcome {
+    /// Not authenticated, so nothing was fetched
+    Skipped,
+    Refreshed(Box<UsageSnapshot>),
+    /// A forced manual refresh took over and reports the result itself
+    Cancelled,
+    Failed(ProviderError),
+}
 
-                match registry.fetch_usage(&provider_id).await {
-                    Ok(usage) => {
-                        let _ = app.emit(
-                            "usage-updated",
-                            serde_json::json!({
-                                "providerId": provider_id,
-                                "usage": usage,
-                            }),
-                        );
-                        state.record_success(now);
-                    }
-                    Err(e) => {
-                        tracing::warn!("Refresh failed for {:?}: {}", provider_id, e);
-                        if state.record_failure(now, had_cached_data) {
-                            let usage = UsageSnapshot::error(e.to_string());
-                            let _ = app.emit(
-                                "refresh-failed",
-                                serde_json::json!({
-                                    "providerId": provider_id,
-                                    "usage": usage.clone(),
-                                }),
-                            );
-                            let _ = app.emit(
-                                "usage-updated",
-                                serde_json::json!({
-                                    "providerId": provider_id,
-                                    "usage": usage,
-                                }),
-                            );
-                        }
-                    }
-                }
+/// Run `refresh` for every account with at most `limit` in flight, calling `on_done` as each
+/// one finishes. A refresh still running `deadline` after it got its turn is dropped, handed
+/// to `on_timeout` so work it started elsewhere can be stopped, and reported as a failure.
+/// A refresh that panics is reported as a failure too.
+async fn refresh_concurrently<F, Fut>(
+    accounts: Vec<AccountKey>,
+    limit: usize,
+    deadline: Duration,
+    refresh: F,
+    mut on_timeout: impl FnMut(&AccountKey),
+    mut on_done: impl FnMut(AccountKey, RefreshOutcome),
+) where
+    F: Fn(AccountKey) -> Fut,
+    Fut: std::future::Future<Output = RefreshOutcome>,
+{
+    let refresh = &refresh;
+    let mut refreshes = futures_util::stream::iter(accounts)
+        .map(|key| async move {
+            // Only built once it gets its turn, so waiting does not count against the deadline
+            let outcome = AssertUnwindSafe(tokio::time::timeout(deadline, refresh(key.clone())))
+                .catch_unwind()
+                .await
+                .map(Result::ok);
+            (key, outcome)
+        })
+        .buffer_unordered(limit.max(1));
+
+    while let Some((key, outcome)) = refreshes.next().await {
+        match outcome {
+            Ok(Some(outcome)) => on_done(key, outcome),
+            Ok(None) => {
+                tracing::warn!("Refresh for {:?} missed its deadline", key);
+                on_timeout(&key);
+                on_done(
+                    key,
+                    RefreshOutcome::Failed(ProviderError::Timeout(format!(
+                        "Refresh did not finish within {}s",
+                        deadline.as_secs()
+                    ))),
+                );
+            }
+            Err(_) => {
+                tracing::warn!("Refresh for {:?} panicked", key);
+                on_done(
+                    key,
+                    RefreshOutcome::Failed(ProviderError::Other(
+                        "Refresh failed unexpectedly".to_string(),
+                    )),
+                );
+            }
+        }
+    }
+}
+
+async fn refresh_account(
+    registry: &ProviderRegistry,
+    emitter: &impl RefreshEmitter,
+    key: AccountKey,
+    status_polled: &std::sync::Mutex<std::collections::HashSet<AccountKey>>,
+) -> RefreshOutcome {
+    // Skip unauthenticated providers to avoid wasting resources
+    let auth_status = registry.auth_status(&key).await;
+    if !auth_status.authenticated {
+        tracing::debug!("start_refresh_loop: skipping {:?} - not authenticated", key);
+        return RefreshOutcome::Skipped;
+    }
+
+    let status_key = key.status_key();
+    let poll_status = status_polled
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(status_key.clone());
+    if poll_status {
+        if let Ok(status) = registry.fetch_status(&status_key).await {
+            emitter.emit_event(RefreshEvent::status_updated(&status_key, &status));
+        }
+    }
+
+    match registry.fetch_usage(&key).await {
+        Ok(usage) => RefreshOutcome::Refreshed(Box::new(usage)),
+        Err(ProviderError::Cancelled) => RefreshOutcome::Cancelled,
+        Err(e) => RefreshOutcome::Failed(e),
+    }
+}
+
+fn apply_refresh_outcome(
+    emitter: &impl RefreshEmitter,
+    registry: &ProviderRegistry,
+    key: &AccountKey,
+    state: &mut ProviderRefreshState,
+    outcome: RefreshOutcome,
+    had_cached_data: bool,
+    now: SystemTime,
+) {
+    match outcome {
+        RefreshOutcome::Skipped => {}
+        RefreshOutcome::Refreshed(usage) => {
+            emitter.emit_event(RefreshEvent::usage_updated(key, &usage));
+            registry.publish_usage(key, &usage);
+            state.record_success(now, &usage);
+        }
+        RefreshOutcome::Cancelled => state.record_cancelled(now),
+        RefreshOutcome::Failed(error) => {
+            tracing::warn!("Refresh failed for {:?}: {}", key, error);
+            if state.record_failure(now, had_cached_data, &error) {
+                let usage = UsageSnapshot::from_error(&error);
+                registry.publish_failure(key, &error, &usage);
+                emitter.emit_event(RefreshEvent::refresh_failed(key, &usage, &error));
+                emitter.emit_event(RefreshEvent::usage_updated(key, &usage));
+            } else {
+                registry.publish_unreported_failure(key, &error);
             }
         }
     }
@@ -985,9 +2117,14 @@ pub async fn start_refresh_loop(app: AppHandle) {
 #[cfg(test)]
 mod tests {
     use super::{
-        ConsecutiveFailureGate, Credits, ProviderIdentity, ProviderStatus, RateWindow,
-        RefreshBackoff, RefreshSchedule, StatusIndicator, UsageSnapshot,
+        apply_refresh_outcome, refresh_concurrently, AccountKey, ConsecutiveFailureGate, Credits,
+        ProviderError, ProviderId, ProviderIdentity, ProviderRefreshState, ProviderRegistry,
+        ProviderStatus, RateWindow, RefreshBackoff, RefreshEmitter, RefreshEvent, RefreshIntervals,
+        RefreshOutcome, RefreshSchedule, StatusIndicator, UsageSink, UsageSnapshot, WindowCategory,
+        USAGE_SNAPSHOT_VERSION,
     };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
     use std::time::{Duration, SystemTime};
 
     #[test]
@@ -1006,6 +2143,44 @@ mod tests {
         assert!(!schedule.is_due(after_refresh));
     }
 
+    #[test]
+    fn refresh_schedule_applies_interval_changes() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut schedule = RefreshSchedule::new_at(start, Duration::from_secs(900));
+
+        // Shortening pulls the next refresh forward
+        let changed = start + Duration::from_secs(10);
+        schedule.set_interval(changed, Duration::from_secs(60));
+        assert!(schedule.is_due(changed + Duration::from_secs(60)));
+
+        // Lengthening only applies from the next refresh
+        schedule.set_interval(changed, Duration::from_secs(1_800));
+        assert!(schedule.is_due(changed + Duration::from_secs(60)));
+        schedule.mark_refreshed(changed + Duration::from_secs(60));
+        assert!(!schedule.is_due(changed + Duration::from_secs(1_000)));
+    }
+
+    #[test]
+    fn refresh_intervals_prefer_provider_overrides() {
+        let mut intervals = RefreshIntervals::default();
+        assert_eq!(
+            intervals.for_provider(ProviderId::Claude),
+            Some(Duration::from_secs(300))
+        );
+
+        intervals.default_seconds = 0;
+        intervals.per_provider.insert(ProviderId::Jetbrains, 30);
+        assert_eq!(intervals.for_provider(ProviderId::Claude), None);
+        assert_eq!(
+            intervals.for_provider(ProviderId::Jetbrains),
+            Some(Duration::from_secs(30))
+        );
+
+        intervals.default_seconds = 120;
+        intervals.per_provider.insert(ProviderId::Cursor, 0);
+        assert_eq!(intervals.for_provider(ProviderId::Cursor), None);
+    }
+
     #[test]
     fn refresh_schedule_handles_sleep_gaps() {
         let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
@@ -1038,31 +2213,69 @@ mod tests {
         let interval = Duration::from_secs(300);
         let mut backoff = RefreshBackoff::new(interval);
 
-        let first = backoff.register_failure();
+        let offline = ProviderError::Network("offline".to_string());
+
+        let first = backoff.register_failure(&offline);
         assert_eq!(first, Duration::from_secs(30));
 
         for _ in 0..5 {
-            backoff.register_failure();
+            backoff.register_failure(&offline);
         }
 
-        let capped = backoff.register_failure();
+        let capped = backoff.register_failure(&offline);
         assert_eq!(capped, interval);
     }
 
+    #[test]
+    fn backoff_depends_on_the_error() {
+        let interval = Duration::from_secs(300);
+        let mut backoff = RefreshBackoff::new(interval);
+
+        let expired = ProviderError::TokenExpired("expired".to_string());
+        assert_eq!(backoff.register_failure(&expired), interval);
+
+        let limited = |retry_after| ProviderError::RateLimited {
+            retry_after,
+            message: "429".to_string(),
+        };
+        assert_eq!(
+            backoff.register_failure(&limited(Some(Duration::from_secs(120)))),
+            Duration::from_secs(120)
+        );
+        assert_eq!(backoff.register_failure(&limited(None)), interval);
+
+        backoff.reset();
+        let server = ProviderError::Http {
+            status: 503,
+            message: "unavailable".to_string(),
+        };
+        assert_eq!(backoff.register_failure(&server), Duration::from_secs(30));
+    }
+
     #[test]
     fn failure_gate_suppresses_first_error_with_prior_data() {
         let mut gate = ConsecutiveFailureGate::new();
-        assert!(!gate.should_surface_error(true));
-        assert!(gate.should_surface_error(true));
+        let offline = ProviderError::Network("offline".to_string());
+        assert!(!gate.should_surface_error(true, &offline));
+        assert!(gate.should_surface_error(true, &offline));
+        gate.record_success();
+        assert!(!gate.should_surface_error(true, &offline));
+
         gate.record_success();
-        assert!(!gate.should_surface_error(true));
+        let expired = ProviderError::TokenExpired("expired".to_string());
+        assert!(gate.should_surface_error(true, &expired));
     }
 
     #[test]
     fn usage_snapshot_validation_clamps_and_cleans() {
         let snapshot = UsageSnapshot {
             primary: Some(RateWindow {
+                id: None,
+                category: None,
                 used_percent: 180.0,
+                used: None,
+                limit: None,
+                unit: None,
                 window_minutes: Some(-5),
                 resets_at: Some("bad".to_string()),
                 reset_description: Some("  ".to_string()),
@@ -1084,6 +2297,10 @@ mod tests {
             }),
             updated_at: "invalid".to_string(),
             error: Some(" ".to_string()),
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         };
 
         let validated = snapshot.validated();
@@ -1126,6 +2343,10 @@ mod tests {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         };
 
         let validated = snapshot.validated();
@@ -1133,16 +2354,318 @@ mod tests {
         assert!(validated.identity.is_none());
     }
 
+    #[test]
+    fn legacy_snapshots_get_a_window_list() {
+        let snapshot: UsageSnapshot = serde_json::from_value(serde_json::json!({
+            "primary": { "usedPercent": 40.0, "windowMinutes": 300, "label": "Session" },
+            "secondary": { "usedPercent": 10.0, "windowMinutes": 10080, "label": "Credits" },
+            "tertiary": { "usedPercent": 5.0, "label": "Credits" },
+            "updatedAt": "2025-06-01T12:00:00Z"
+        }))
+        .expect("legacy snapshot");
+        assert_eq!(snapshot.version, 1);
+
+        let validated = snapshot.validated();
+        assert_eq!(validated.version, USAGE_SNAPSHOT_VERSION);
+        let windows: Vec<_> = validated
+            .windows
+            .iter()
+            .map(|window| (window.id.as_deref().unwrap(), window.category))
+            .collect();
+        assert_eq!(
+            windows,
+            [
+                ("session", Some(WindowCategory::Session)),
+                ("credits", Some(WindowCategory::Weekly)),
+                ("credits-2", None),
+            ]
+        );
+        assert_eq!(validated.tertiary.unwrap().id.as_deref(), Some("credits-2"));
+    }
+
+    #[test]
+    fn window_lists_fill_the_legacy_fields() {
+        let window = |label: &str, used_percent| RateWindow {
+            id: None,
+            category: Some(WindowCategory::Model),
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(1440),
+            resets_at: None,
+            reset_description: None,
+            label: Some(label.to_string()),
+        };
+        let mut snapshot = UsageSnapshot::error(String::new());
+        snapshot.error = None;
+        snapshot.windows = (1..=4)
+            .map(|n| window(&format!("Model {}", n), n as f64 * 10.0))
+            .collect();
+
+        let validated = snapshot.validated();
+        assert_eq!(validated.windows.len(), 4);
+        assert_eq!(validated.rate_windows().len(), 4);
+        assert_eq!(validated.windows[3].id.as_deref(), Some("model-4"));
+        assert_eq!(validated.primary.unwrap().id.as_deref(), Some("model-1"));
+        assert_eq!(validated.tertiary.unwrap().used_percent, 30.0);
+    }
+
+    #[test]
+    fn rate_window_counts_are_validated_together() {
+        let window: RateWindow = serde_json::from_value(serde_json::json!({
+            "used": 412.0,
+            "limit": 500.0,
+            "unit": " requests "
+        }))
+        .expect("window");
+        let window = window.validated().expect("percent from counts");
+        assert!((window.used_percent - 82.4).abs() < 1e-9);
+        assert_eq!(window.count_text().as_deref(), Some("412 / 500 requests"));
+
+        let window: RateWindow = serde_json::from_value(serde_json::json!({
+            "usedPercent": 30.0,
+            "used": -1.0,
+            "limit": 10.0,
+            "unit": "tokens"
+        }))
+        .expect("window");
+        let window = window.validated().expect("window");
+        assert_eq!((window.used, window.limit, window.unit), (None, None, None));
+        assert_eq!(window.used_percent, 30.0);
+
+        let window: RateWindow =
+            serde_json::from_value(serde_json::json!({ "used": 5.0, "limit": 0.0 }))
+                .expect("window");
+        assert!(window.validated().is_none());
+    }
+
     #[test]
     fn provider_status_validation_trims_fields() {
         let status = ProviderStatus {
             indicator: StatusIndicator::Major,
             description: Some("  degraded ".to_string()),
             updated_at: Some("bad".to_string()),
+            url: None,
         };
 
         let validated = status.validated();
         assert_eq!(validated.description.as_deref(), Some("degraded"));
         assert!(validated.updated_at.is_none());
     }
+
+    #[tokio::test]
+    async fn concurrent_refresh_respects_the_limit() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let accounts = (0..6)
+            .map(|i| AccountKey::new(ProviderId::Custom, format!("acct-{}", i)))
+            .collect();
+
+        let mut completed = Vec::new();
+        refresh_concurrently(
+            accounts,
+            2,
+            Duration::from_secs(5),
+            |_| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    RefreshOutcome::Skipped
+                }
+            },
+            |_| {},
+            |key, _| completed.push(key),
+        )
+        .await;
+
+        assert_eq!(completed.len(), 6);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_refresh_deadline_starts_with_each_refresh() {
+        let accounts = (0..3)
+            .map(|i| AccountKey::new(ProviderId::Custom, format!("acct-{}", i)))
+            .collect();
+
+        // Together the refreshes take longer than the deadline, one at a time they do not
+        let mut completed = Vec::new();
+        refresh_concurrently(
+            accounts,
+            1,
+            Duration::from_millis(150),
+            |_| async {
+                tokio::time::sleep(Duration::from_millis(80)).await;
+                RefreshOutcome::Skipped
+            },
+            |key| panic!("{:?} timed out", key),
+            |_, outcome| completed.push(outcome),
+        )
+        .await;
+
+        assert_eq!(completed.len(), 3);
+        assert!(completed
+            .iter()
+            .all(|outcome| matches!(outcome, RefreshOutcome::Skipped)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_refresh_reports_fast_results_first_and_cuts_off_slow_ones() {
+        let fast = AccountKey::new(ProviderId::Claude, "default");
+        let slow = AccountKey::new(ProviderId::Kiro, "default");
+        let accounts = vec![slow.clone(), fast.clone()];
+
+        let mut timed_out = Vec::new();
+        let mut completed = Vec::new();
+        refresh_concurrently(
+            accounts,
+            4,
+            Duration::from_millis(200),
+            |key| async move {
+                if key.provider_id == ProviderId::Kiro {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                }
+                RefreshOutcome::Refreshed(Box::new(UsageSnapshot::error(String::new())))
+            },
+            |key| timed_out.push(key.clone()),
+            |key, outcome| completed.push((key, outcome)),
+        )
+        .await;
+
+        assert_eq!(timed_out, vec![slow.clone()]);
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[0].0, fast);
+        assert!(matches!(completed[0].1, RefreshOutcome::Refreshed(_)));
+        assert_eq!(completed[1].0, slow);
+        assert!(matches!(
+            &completed[1].1,
+            RefreshOutcome::Failed(ProviderError::Timeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn concurrent_refresh_reports_a_panicked_refresh_as_failed() {
+        let broken = AccountKey::new(ProviderId::Claude, "default");
+        let healthy = AccountKey::new(ProviderId::Kiro, "default");
+        let accounts = vec![broken.clone(), healthy.clone()];
+
+        let mut completed = Vec::new();
+        refresh_concurrently(
+            accounts,
+            1,
+            Duration::from_secs(5),
+            |key| async move {
+                if key.provider_id == ProviderId::Claude {
+                    panic!("{:?} broke", key);
+                }
+                RefreshOutcome::Skipped
+            },
+            |key| panic!("{:?} timed out", key),
+            |key, outcome| completed.push((key, outcome)),
+        )
+        .await;
+
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[0].0, broken);
+        assert!(matches!(
+            &completed[0].1,
+            RefreshOutcome::Failed(ProviderError::Other(_))
+        ));
+        assert_eq!(completed[1].0, healthy);
+        assert!(matches!(completed[1].1, RefreshOutcome::Skipped));
+    }
+
+    #[derive(Default)]
+    struct RecordingEmitter {
+        events: Mutex<Vec<&'static str>>,
+    }
+
+    impl RefreshEmitter for RecordingEmitter {
+        fn emit_event(&self, event: RefreshEvent) {
+            self.events.lock().unwrap().push(event.event);
+        }
+    }
+
+    #[derive(Default)]
+    struct FailureSink {
+        fetch_failures: AtomicUsize,
+        reported_failures: AtomicUsize,
+    }
+
+    impl UsageSink for FailureSink {
+        fn usage_updated(&self, _key: &AccountKey, _usage: &UsageSnapshot) {}
+
+        fn fetch_failed(&self, _key: &AccountKey, _error: &ProviderError) {
+            self.fetch_failures.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn refresh_failed(
+            &self,
+            _key: &AccountKey,
+            _error: &ProviderError,
+            _usage: &UsageSnapshot,
+        ) {
+            self.reported_failures.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_outcomes_reach_the_emitter_unless_the_failure_gate_holds_them_back() {
+        let registry =
+            ProviderRegistry::from_providers(ProviderRegistry::default_providers(), None);
+        let sink = Arc::new(FailureSink::default());
+        registry.add_sink(sink.clone());
+        let emitter = RecordingEmitter::default();
+        let key = AccountKey::default_for(ProviderId::Claude);
+        let now = SystemTime::now();
+        let mut state = ProviderRefreshState::new(Duration::from_secs(300));
+        let usage = UsageSnapshot::error(String::new());
+        let error = ProviderError::Network("offline".to_string());
+
+        let outcome = RefreshOutcome::Refreshed(Box::new(usage));
+        apply_refresh_outcome(&emitter, &registry, &key, &mut state, outcome, false, now);
+        // The first failure of an account with data on screen is held back
+        let outcome = RefreshOutcome::Failed(error.clone());
+        apply_refresh_outcome(&emitter, &registry, &key, &mut state, outcome, true, now);
+        registry.flush_sinks().await;
+
+        assert_eq!(*emitter.events.lock().unwrap(), vec!["usage-updated"]);
+        assert_eq!(sink.fetch_failures.load(Ordering::SeqCst), 1);
+        assert_eq!(sink.reported_failures.load(Ordering::SeqCst), 0);
+
+        let outcome = RefreshOutcome::Failed(error);
+        apply_refresh_outcome(&emitter, &registry, &key, &mut state, outcome, true, now);
+        registry.flush_sinks().await;
+
+        assert_eq!(
+            *emitter.events.lock().unwrap(),
+            vec!["usage-updated", "refresh-failed", "usage-updated"]
+        );
+        assert_eq!(sink.fetch_failures.load(Ordering::SeqCst), 2);
+        assert_eq!(sink.reported_failures.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_refresh_is_rescheduled_for_the_next_interval() {
+        let registry =
+            ProviderRegistry::from_providers(ProviderRegistry::default_providers(), None);
+        let emitter = RecordingEmitter::default();
+        let key = AccountKey::default_for(ProviderId::Claude);
+        let now = SystemTime::now();
+        let interval = Duration::from_secs(300);
+        let mut state = ProviderRefreshState::new(interval);
+        assert!(state.is_due(now));
+
+        let outcome = RefreshOutcome::Cancelled;
+        apply_refresh_outcome(&emitter, &registry, &key, &mut state, outcome, true, now);
+
+        assert!(emitter.events.lock().unwrap().is_empty());
+        assert!(!state.is_due(now + interval - Duration::from_secs(1)));
+        assert!(state.is_due(now + interval));
+    }
 }
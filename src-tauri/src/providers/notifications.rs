@@ -0,0 +1,785 @@
+//! Native usage alerts
+//!
+//! Session thresholds, low credits, refresh failures and stale data are decided here
+//! rather than in the webview, so alerts still fire while the popup is suspended or
+//! closed. What has already been announced is kept in `notifications.json` together
+//! with the preferences last synced from the frontend and any snoozes, so a restart
+//! neither repeats an alert nor forgets a snooze.
+
+use super::reset::RESET_DROP_PERCENT;
+use super::{
+    format_count, AccountKey, ProviderError, ProviderErrorKind, ProviderId, UsageSink,
+    UsageSnapshot,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const APP_DIR_NAME: &str = "IncuBar";
+const STATE_FILENAME: &str = "notifications.json";
+
+/// Session usage percentages announced once per window
+pub const SESSION_QUOTA_THRESHOLDS: &[f64] = &[80.0, 90.0];
+/// Remaining credit percentages announced once per top-up
+pub const CREDIT_REMAINING_THRESHOLDS: &[f64] = &[20.0, 10.0];
+/// Remaining credits rising by this many points were topped up
+const CREDIT_RESET_RISE: f64 = 5.0;
+/// Usage older than this many refresh intervals is stale
+const STALE_INTERVALS: i64 = 2;
+/// Staleness cutoff for providers refreshed manually, matching the tray
+const STALE_DEFAULT_SECS: i64 = 600;
+
+static NOTIFICATIONS: Lazy<Mutex<Option<NotificationStore>>> = Lazy::new(|| Mutex::new(None));
+
+/// Which alerts to show, synced from the frontend settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationPreferences {
+    pub enabled: bool,
+    pub session_usage: bool,
+    pub credits_low: bool,
+    pub refresh_failure: bool,
+    pub stale_usage: bool,
+    /// Threshold overrides by provider
+    pub provider_thresholds: HashMap<ProviderId, ProviderThresholds>,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            session_usage: true,
+            credits_low: true,
+            refresh_failure: true,
+            stale_usage: true,
+            provider_thresholds: HashMap::new(),
+        }
+    }
+}
+
+impl NotificationPreferences {
+    fn session_thresholds(&self, provider_id: ProviderId) -> &[f64] {
+        self.provider_thresholds
+            .get(&provider_id)
+            .and_then(|thresholds| thresholds.session_usage.as_deref())
+            .unwrap_or(SESSION_QUOTA_THRESHOLDS)
+    }
+
+    fn credit_thresholds(&self, provider_id: ProviderId) -> &[f64] {
+        self.provider_thresholds
+            .get(&provider_id)
+            .and_then(|thresholds| thresholds.credits_remaining.as_deref())
+            .unwrap_or(CREDIT_REMAINING_THRESHOLDS)
+    }
+
+    fn allows(&self, kind: AlertKind) -> bool {
+        self.enabled
+            && match kind {
+                AlertKind::SessionUsage => self.session_usage,
+                AlertKind::CreditsLow => self.credits_low,
+                AlertKind::RefreshFailure => self.refresh_failure,
+                AlertKind::StaleUsage => self.stale_usage,
+            }
+    }
+}
+
+/// A provider's own thresholds. `None` keeps the defaults, an empty list turns the
+/// alert off for that provider.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProviderThresholds {
+    /// Session usage percentages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_usage: Option<Vec<f64>>,
+    /// Remaining credit percentages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credits_remaining: Option<Vec<f64>>,
+}
+
+/// Alerts held back until a point in time. Alerts raised while snoozed are dropped,
+/// not queued.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSnoozes {
+    /// Every provider is snoozed until then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_until: Option<DateTime<Utc>>,
+    pub providers: HashMap<ProviderId, DateTime<Utc>>,
+}
+
+impl NotificationSnoozes {
+    pub fn is_snoozed(&self, provider_id: ProviderId, now: DateTime<Utc>) -> bool {
+        self.all_until.is_some_and(|until| until > now)
+            || self
+                .providers
+                .get(&provider_id)
+                .is_some_and(|until| *until > now)
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        self.all_until = self.all_until.filter(|until| *until > now);
+        self.providers.retain(|_, until| *until > now);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlertKind {
+    SessionUsage,
+    CreditsLow,
+    RefreshFailure,
+    StaleUsage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Alert {
+    kind: AlertKind,
+    title: String,
+    body: String,
+}
+
+/// Thresholds already announced and the reading they were compared against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThresholdState {
+    last_percent: f64,
+    #[serde(default)]
+    triggered: Vec<f64>,
+    /// Session `resets_at`; a new value means a new window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reset_marker: Option<String>,
+    /// Credit total; a new value means a top-up or plan change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_total: Option<f64>,
+}
+
+impl ThresholdState {
+    fn new(percent: f64) -> Self {
+        Self {
+            last_percent: percent,
+            triggered: Vec::new(),
+            reset_marker: None,
+            last_total: None,
+        }
+    }
+
+    fn has_triggered(&self, threshold: f64) -> bool {
+        self.triggered.contains(&threshold)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AccountAlerts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<ThresholdState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credits: Option<ThresholdState>,
+    /// The failure last announced, cleared by the next successful fetch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+    stale_notified: bool,
+}
+
+/// Everything persisted in `notifications.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct NotificationState {
+    preferences: NotificationPreferences,
+    snoozes: NotificationSnoozes,
+    accounts: HashMap<ProviderId, HashMap<String, AccountAlerts>>,
+}
+
+impl NotificationState {
+    fn account(&mut self, key: &AccountKey) -> &mut AccountAlerts {
+        self.accounts
+            .entry(key.provider_id)
+            .or_default()
+            .entry(key.account_id.clone())
+            .or_default()
+    }
+
+    /// Alerts for a successful fetch. Thresholds are tracked even while an alert is
+    /// turned off or snoozed, so turning it back on does not announce old crossings.
+    fn usage_alerts(&mut self, key: &AccountKey, usage: &UsageSnapshot) -> Vec<Alert> {
+        if usage.error.is_some() {
+            return Vec::new();
+        }
+        let name = account_name(key);
+        let session_thresholds = self
+            .preferences
+            .session_thresholds(key.provider_id)
+            .to_vec();
+        let credit_thresholds = self.preferences.credit_thresholds(key.provider_id).to_vec();
+        let account = self.account(key);
+        account.last_error = None;
+        account.stale_notified = false;
+
+        let mut alerts = Vec::new();
+        if let Some(session) = usage
+            .primary
+            .as_ref()
+            .filter(|window| window.used_percent.is_finite())
+        {
+            let percent = session.used_percent.clamp(0.0, 100.0);
+            let marker = session.resets_at.clone();
+            let state = account.session.get_or_insert_with(|| ThresholdState {
+                reset_marker: marker.clone(),
+                ..ThresholdState::new(percent)
+            });
+            let new_window = matches!(
+                (&state.reset_marker, &marker),
+                (Some(before), Some(after)) if before != after
+            );
+            if new_window || state.last_percent - percent >= RESET_DROP_PERCENT {
+                state.triggered.clear();
+            }
+            let label = session.label.as_deref().unwrap_or("Session");
+            for &threshold in &session_thresholds {
+                if percent >= threshold
+                    && state.last_percent < threshold
+                    && !state.has_triggered(threshold)
+                {
+                    alerts.push(Alert {
+                        kind: AlertKind::SessionUsage,
+                        title: format!("{} {} usage", name, label),
+                        body: format!("Reached {}% of {} quota.", threshold, label.to_lowercase()),
+                    });
+                    state.triggered.push(threshold);
+                }
+            }
+            state.last_percent = percent;
+            if marker.is_some() {
+                state.reset_marker = marker;
+            }
+        }
+
+        if let Some(credits) = usage
+            .credits
+            .as_ref()
+            .filter(|credits| credits.remaining.is_finite())
+        {
+            if let Some(total) = credits
+                .total
+                .filter(|total| total.is_finite() && *total > 0.0)
+            {
+                let percent = (credits.remaining / total * 100.0).clamp(0.0, 100.0);
+                let state = account.credits.get_or_insert_with(|| ThresholdState {
+                    last_total: Some(total),
+                    ..ThresholdState::new(percent)
+                });
+                if state.last_total.is_some_and(|last| last != total)
+                    || percent - state.last_percent >= CREDIT_RESET_RISE
+                {
+                    state.triggered.clear();
+                }
+                for &threshold in &credit_thresholds {
+                    if percent <= threshold
+                        && state.last_percent > threshold
+                        && !state.has_triggered(threshold)
+                    {
+                        alerts.push(Alert {
+                            kind: AlertKind::CreditsLow,
+                            title: format!("{} credits low", name),
+                            body: format!(
+                                "Remaining {} is below {}% ({} left).",
+                                credits.unit,
+                                threshold,
+                                format_count(credits.remaining.max(0.0))
+                            ),
+                        });
+                        state.triggered.push(threshold);
+                    }
+                }
+                state.last_percent = percent;
+                state.last_total = Some(total);
+            }
+        }
+        alerts
+    }
+
+    /// An alert for a failed fetch, unless the same failure was already announced
+    fn failure_alerts(
+        &mut self,
+        key: &AccountKey,
+        message: &str,
+        kind: ProviderErrorKind,
+    ) -> Vec<Alert> {
+        let name = account_name(key);
+        let account = self.account(key);
+        if account.last_error.as_deref() == Some(message) {
+            return Vec::new();
+        }
+        account.last_error = Some(message.to_string());
+        vec![Alert {
+            kind: AlertKind::RefreshFailure,
+            title: failure_title(&name, kind),
+            body: message.to_string(),
+        }]
+    }
+
+    /// An alert the first time usage fetched at `updated_at` is found stale
+    fn stale_alerts(
+        &mut self,
+        key: &AccountKey,
+        updated_at: DateTime<Utc>,
+        stale_after: Duration,
+        now: DateTime<Utc>,
+    ) -> Vec<Alert> {
+        let name = account_name(key);
+        let account = self.account(key);
+        let age = now - updated_at;
+        if age <= stale_after {
+            account.stale_notified = false;
+            return Vec::new();
+        }
+        if account.stale_notified {
+            return Vec::new();
+        }
+        account.stale_notified = true;
+        vec![Alert {
+            kind: AlertKind::StaleUsage,
+            title: format!("{} data is stale", name),
+            body: format!("Last updated {}.", describe_age(age)),
+        }]
+    }
+
+    /// Whether an alert for `provider_id` should be shown now
+    fn should_show(&self, alert: &Alert, provider_id: ProviderId, now: DateTime<Utc>) -> bool {
+        self.preferences.allows(alert.kind) && !self.snoozes.is_snoozed(provider_id, now)
+    }
+}
+
+/// Provider name, with the account id for accounts other than the default
+fn account_name(key: &AccountKey) -> String {
+    let name = key.provider_id.display_name();
+    if key.is_default() {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, key.account_id)
+    }
+}
+
+fn failure_title(name: &str, kind: ProviderErrorKind) -> String {
+    match kind {
+        ProviderErrorKind::NotAuthenticated | ProviderErrorKind::TokenExpired => {
+            format!("{} needs you to sign in again", name)
+        }
+        ProviderErrorKind::Forbidden => format!("{} denied access to usage", name),
+        ProviderErrorKind::RateLimited => format!("{} is rate limiting requests", name),
+        ProviderErrorKind::Unavailable => format!("{} is unavailable", name),
+        _ => format!("{} refresh failed", name),
+    }
+}
+
+fn describe_age(age: Duration) -> String {
+    let minutes = age.num_minutes();
+    if minutes < 60 {
+        format!("{} minutes ago", minutes.max(1))
+    } else if minutes < 48 * 60 {
+        format!("{} hours ago", minutes / 60)
+    } else {
+        format!("{} days ago", minutes / (24 * 60))
+    }
+}
+
+/// How old usage may get before it counts as stale, given the account's refresh interval
+pub(crate) fn stale_after(interval: Option<std::time::Duration>) -> Duration {
+    interval
+        .and_then(|interval| Duration::from_std(interval).ok())
+        .map(|interval| interval * STALE_INTERVALS as i32)
+        .unwrap_or_else(|| Duration::seconds(STALE_DEFAULT_SECS))
+}
+
+struct NotificationStore {
+    path: PathBuf,
+    state: NotificationState,
+}
+
+impl NotificationStore {
+    fn open(path: PathBuf) -> Self {
+        let state = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, state }
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context("Failed to create notification state directory")?;
+        }
+        let payload = serde_json::to_string_pretty(&self.state)
+            .context("Failed to serialize notification state")?;
+        write_atomically(&self.path, payload)
+    }
+}
+
+fn write_atomically(path: &Path, payload: String) -> Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, payload).context("Failed to write notification state")?;
+    fs::rename(&temp_path, path).context("Failed to replace notification state")?;
+    Ok(())
+}
+
+fn state_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("Could not determine data directory")?;
+    Ok(data_dir.join(APP_DIR_NAME).join(STATE_FILENAME))
+}
+
+/// Run `f` on the state, saving it when `f` changed anything
+fn with_state<T>(f: impl FnOnce(&mut NotificationState) -> T) -> Result<T> {
+    let mut guard = NOTIFICATIONS
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Notification state lock poisoned"))?;
+    if guard.is_none() {
+        *guard = Some(NotificationStore::open(state_path()?));
+    }
+    let store = guard.as_mut().context("Notification state is not open")?;
+    let before = store.state.clone();
+    let result = f(&mut store.state);
+    if store.state != before {
+        store.save()?;
+    }
+    Ok(result)
+}
+
+/// Evaluate alerts with `f` and show the ones preferences and snoozes allow
+fn dispatch(
+    app: &AppHandle,
+    provider_id: ProviderId,
+    f: impl FnOnce(&mut NotificationState) -> Vec<Alert>,
+) {
+    let now = Utc::now();
+    let alerts = with_state(|state| {
+        let alerts = f(state);
+        alerts
+            .into_iter()
+            .filter(|alert| state.should_show(alert, provider_id, now))
+            .collect::<Vec<_>>()
+    });
+    let alerts = match alerts {
+        Ok(alerts) => alerts,
+        Err(err) => {
+            tracing::warn!("Failed to evaluate notifications: {}", err);
+            return;
+        }
+    };
+    for alert in alerts {
+        tracing::info!("Notifying {:?}: {}", provider_id, alert.title);
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title(&alert.title)
+            .body(&alert.body)
+            .show()
+        {
+            tracing::warn!("Failed to show notification: {}", err);
+        }
+    }
+}
+
+/// Raises session, credit and refresh failure alerts for every reported fetch
+pub struct NotificationSink {
+    app: AppHandle,
+}
+
+impl NotificationSink {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl UsageSink for NotificationSink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        dispatch(&self.app, key.provider_id, |state| {
+            state.usage_alerts(key, usage)
+        });
+    }
+
+    fn refresh_failed(&self, key: &AccountKey, error: &ProviderError, _usage: &UsageSnapshot) {
+        dispatch(&self.app, key.provider_id, |state| {
+            state.failure_alerts(key, &error.to_string(), error.kind())
+        });
+    }
+}
+
+/// A stale data alert once `usage` is older than `stale_after`
+pub(crate) fn notify_if_stale(
+    app: &AppHandle,
+    key: &AccountKey,
+    usage: &UsageSnapshot,
+    stale_after: Duration,
+) {
+    let Ok(updated_at) = DateTime::parse_from_rfc3339(&usage.updated_at) else {
+        return;
+    };
+    let updated_at = updated_at.with_timezone(&Utc);
+    dispatch(app, key.provider_id, |state| {
+        state.stale_alerts(key, updated_at, stale_after, Utc::now())
+    });
+}
+
+/// Whether other native notifications about `provider_id`, e.g. window resets, may be shown
+pub(crate) fn notifications_allowed(provider_id: ProviderId) -> bool {
+    with_state(|state| {
+        state.preferences.enabled && !state.snoozes.is_snoozed(provider_id, Utc::now())
+    })
+    .unwrap_or(true)
+}
+
+pub fn set_notification_preferences(preferences: NotificationPreferences) -> Result<()> {
+    with_state(|state| state.preferences = preferences)
+}
+
+pub fn notification_snoozes() -> Result<NotificationSnoozes> {
+    with_state(|state| {
+        state.snoozes.prune(Utc::now());
+        state.snoozes.clone()
+    })
+}
+
+/// Snooze one provider, or every provider when `provider_id` is `None`, for `minutes`.
+/// Zero minutes lifts the snooze.
+pub fn snooze_notifications(
+    provider_id: Option<ProviderId>,
+    minutes: u32,
+) -> Result<NotificationSnoozes> {
+    with_state(|state| {
+        let now = Utc::now();
+        let until = (minutes > 0).then(|| now + Duration::minutes(i64::from(minutes)));
+        match (provider_id, until) {
+            (Some(provider_id), Some(until)) => {
+                state.snoozes.providers.insert(provider_id, until);
+            }
+            (Some(provider_id), None) => {
+                state.snoozes.providers.remove(&provider_id);
+            }
+            (None, until) => {
+                state.snoozes.all_until = until;
+                if until.is_none() {
+                    state.snoozes.providers.clear();
+                }
+            }
+        }
+        state.snoozes.prune(now);
+        state.snoozes.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Credits, RateWindow, USAGE_SNAPSHOT_VERSION};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn usage(used_percent: f64, resets_at: &str) -> UsageSnapshot {
+        let window = RateWindow {
+            id: Some("session".to_string()),
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(300),
+            resets_at: Some(resets_at.to_string()),
+            reset_description: None,
+            label: Some("Session".to_string()),
+        };
+        UsageSnapshot {
+            primary: Some(window.clone()),
+            secondary: None,
+            tertiary: None,
+            credits: None,
+            cost: None,
+            identity: None,
+            updated_at: now().to_rfc3339(),
+            error: None,
+            retry_at: None,
+            windows: vec![window],
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        }
+    }
+
+    fn credits(remaining: f64, total: f64) -> UsageSnapshot {
+        UsageSnapshot {
+            primary: None,
+            credits: Some(Credits {
+                remaining,
+                total: Some(total),
+                unit: "credits".to_string(),
+            }),
+            ..usage(0.0, "")
+        }
+    }
+
+    fn titles(alerts: &[Alert]) -> Vec<&str> {
+        alerts.iter().map(|alert| alert.title.as_str()).collect()
+    }
+
+    #[test]
+    fn session_thresholds_fire_once_per_window() {
+        let key = AccountKey::default_for(ProviderId::Claude);
+        let mut state = NotificationState::default();
+        let first = "2025-06-01T15:00:00Z";
+
+        assert!(state.usage_alerts(&key, &usage(70.0, first)).is_empty());
+        let alerts = state.usage_alerts(&key, &usage(92.0, first));
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].title, "Claude Session usage");
+        assert_eq!(alerts[1].body, "Reached 90% of session quota.");
+        assert!(state.usage_alerts(&key, &usage(88.0, first)).is_empty());
+        assert!(state.usage_alerts(&key, &usage(91.0, first)).is_empty());
+
+        // A new window re-arms the thresholds
+        let second = "2025-06-01T20:00:00Z";
+        assert!(state.usage_alerts(&key, &usage(10.0, second)).is_empty());
+        assert_eq!(state.usage_alerts(&key, &usage(81.0, second)).len(), 1);
+    }
+
+    #[test]
+    fn provider_thresholds_override_the_defaults() {
+        let key = AccountKey::new(ProviderId::Codex, "work");
+        let mut state = NotificationState::default();
+        state.preferences.provider_thresholds.insert(
+            ProviderId::Codex,
+            ProviderThresholds {
+                session_usage: Some(vec![50.0]),
+                credits_remaining: Some(Vec::new()),
+            },
+        );
+        let resets = "2025-06-01T15:00:00Z";
+
+        state.usage_alerts(&key, &usage(40.0, resets));
+        let alerts = state.usage_alerts(&key, &usage(85.0, resets));
+        assert_eq!(titles(&alerts), vec!["Codex (work) Session usage"]);
+        assert_eq!(alerts[0].body, "Reached 50% of session quota.");
+
+        state.usage_alerts(&key, &credits(50.0, 100.0));
+        assert!(state.usage_alerts(&key, &credits(5.0, 100.0)).is_empty());
+    }
+
+    #[test]
+    fn credits_alert_until_topped_up() {
+        let key = AccountKey::default_for(ProviderId::Factory);
+        let mut state = NotificationState::default();
+
+        assert!(state.usage_alerts(&key, &credits(50.0, 100.0)).is_empty());
+        let alerts = state.usage_alerts(&key, &credits(15.0, 100.0));
+        assert_eq!(titles(&alerts), vec!["Droid credits low"]);
+        assert_eq!(alerts[0].body, "Remaining credits is below 20% (15 left).");
+        assert!(state.usage_alerts(&key, &credits(12.0, 100.0)).is_empty());
+
+        // A larger total is a top-up
+        assert!(state.usage_alerts(&key, &credits(150.0, 200.0)).is_empty());
+        assert_eq!(state.usage_alerts(&key, &credits(30.0, 200.0)).len(), 1);
+    }
+
+    #[test]
+    fn failures_and_stale_data_alert_once_until_recovered() {
+        let key = AccountKey::default_for(ProviderId::Cursor);
+        let mut state = NotificationState::default();
+
+        let alerts = state.failure_alerts(&key, "Token expired", ProviderErrorKind::TokenExpired);
+        assert_eq!(titles(&alerts), vec!["Cursor needs you to sign in again"]);
+        assert!(state
+            .failure_alerts(&key, "Token expired", ProviderErrorKind::TokenExpired)
+            .is_empty());
+        let forbidden = AccountKey::default_for(ProviderId::Copilot);
+        let alerts = state.failure_alerts(&forbidden, "HTTP 403", ProviderErrorKind::Forbidden);
+        assert_eq!(titles(&alerts), vec!["Copilot denied access to usage"]);
+        assert_eq!(
+            state
+                .failure_alerts(&key, "HTTP 500", ProviderErrorKind::Http)
+                .len(),
+            1
+        );
+
+        let stale_after = Duration::minutes(10);
+        let updated_at = now() - Duration::minutes(25);
+        let alerts = state.stale_alerts(&key, updated_at, stale_after, now());
+        assert_eq!(titles(&alerts), vec!["Cursor data is stale"]);
+        assert_eq!(alerts[0].body, "Last updated 25 minutes ago.");
+        assert!(state
+            .stale_alerts(&key, updated_at, stale_after, now())
+            .is_empty());
+
+        // A successful fetch re-arms both
+        state.usage_alerts(&key, &usage(10.0, "2025-06-01T15:00:00Z"));
+        assert_eq!(
+            state
+                .failure_alerts(&key, "HTTP 500", ProviderErrorKind::Http)
+                .len(),
+            1
+        );
+        assert_eq!(
+            state
+                .stale_alerts(&key, updated_at, stale_after, now())
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn preferences_and_snoozes_hold_alerts_back() {
+        let mut state = NotificationState::default();
+        let alert = Alert {
+            kind: AlertKind::CreditsLow,
+            title: String::new(),
+            body: String::new(),
+        };
+        assert!(state.should_show(&alert, ProviderId::Claude, now()));
+
+        state
+            .snoozes
+            .providers
+            .insert(ProviderId::Claude, now() + Duration::minutes(30));
+        assert!(!state.should_show(&alert, ProviderId::Claude, now()));
+        assert!(state.should_show(&alert, ProviderId::Codex, now()));
+        assert!(state.should_show(&alert, ProviderId::Claude, now() + Duration::hours(1)));
+
+        state.snoozes.all_until = Some(now() + Duration::minutes(5));
+        assert!(!state.should_show(&alert, ProviderId::Codex, now()));
+        state.snoozes.prune(now() + Duration::hours(1));
+        assert_eq!(state.snoozes, NotificationSnoozes::default());
+
+        state.preferences.credits_low = false;
+        assert!(!state.should_show(&alert, ProviderId::Codex, now()));
+    }
+
+    #[test]
+    fn state_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILENAME);
+        let key = AccountKey::default_for(ProviderId::Claude);
+        let resets = "2025-06-01T15:00:00Z";
+
+        let mut store = NotificationStore::open(path.clone());
+        store.state.usage_alerts(&key, &usage(70.0, resets));
+        assert_eq!(
+            store.state.usage_alerts(&key, &usage(85.0, resets)).len(),
+            1
+        );
+        store.state.preferences.stale_usage = false;
+        store.save().unwrap();
+
+        let mut reopened = NotificationStore::open(path);
+        assert_eq!(reopened.state, store.state);
+        assert!(reopened
+            .state
+            .usage_alerts(&key, &usage(86.0, resets))
+            .is_empty());
+    }
+}
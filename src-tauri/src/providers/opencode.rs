@@ -3,7 +3,13 @@
 //! Uses cookie-based authentication via browser cookie import.
 //! Endpoint: https://opencode.ai/_server
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    WindowCategory, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde_json::Value;
 
@@ -56,6 +62,8 @@ const RESET_AT_KEYS: &[&str] = &[
 
 pub struct OpencodeProvider {
     client: reqwest::Client,
+    /// Account-specific OpenCode session file; `None` uses the default location
+    session_path: Option<std::path::PathBuf>,
 }
 
 impl OpencodeProvider {
@@ -65,7 +73,18 @@ impl OpencodeProvider {
             .build()
             .unwrap_or_default();
 
-        Self { client }
+        Self {
+            client,
+            session_path: None,
+        }
+    }
+
+    /// Create a provider for an additional account whose OpenCode session file lives at `path`
+    pub fn with_credentials_path(path: std::path::PathBuf) -> Self {
+        Self {
+            session_path: Some(path),
+            ..Self::new()
+        }
     }
 
     async fn fetch_with_cookies(
@@ -221,7 +240,7 @@ impl OpencodeProvider {
         }
 
         let response = request
-            .send()
+            .send_recorded(ProviderId::Opencode)
             .await
             .map_err(|err| OpencodeError::Api(err.to_string()))?;
         let status = response.status();
@@ -321,6 +340,10 @@ impl OpencodeProvider {
     }
 
     fn get_session_path(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        if let Some(path) = &self.session_path {
+            return Ok(path.clone());
+        }
+
         let data_dir = dirs::data_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
         Ok(data_dir.join("IncuBar").join("opencode-session.json"))
@@ -337,7 +360,7 @@ impl ProviderFetcher for OpencodeProvider {
         "OpenCode"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching OpenCode usage");
 
         if let Ok(cookies) = self.load_stored_cookies().await {
@@ -355,20 +378,20 @@ impl ProviderFetcher for OpencodeProvider {
         match crate::browser_cookies::import_opencode_cookies_from_browser().await {
             Ok(result) => {
                 if !cookie_header_has_auth(&result.cookie_header) {
-                    return Err(anyhow::anyhow!(
-                        OpencodeError::InvalidCredentials.to_string()
-                    ));
+                    return Err(OpencodeError::InvalidCredentials.into());
                 }
                 if let Err(err) = self.store_session(&result.cookie_header).await {
                     tracing::debug!("Failed to store OpenCode session: {}", err);
                 }
-                self.fetch_with_cookies(&result.cookie_header)
-                    .await
-                    .map_err(|err| anyhow::anyhow!(err.to_string()))
+                Ok(self.fetch_with_cookies(&result.cookie_header).await?)
             }
-            Err(err) => Err(anyhow::anyhow!("Not authenticated: {}", err)),
+            Err(err) => Err(ProviderError::not_authenticated(err)),
         }
     }
+
+    async fn auth_status(&self) -> AuthStatus {
+        AuthStatus::from_session_file(self.get_session_path())
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -393,14 +416,24 @@ impl OpenCodeUsageSnapshot {
 
         UsageSnapshot {
             primary: Some(RateWindow {
+                id: Some("session".to_string()),
+                category: Some(WindowCategory::Session),
                 used_percent: self.rolling_usage_percent,
+                used: None,
+                limit: None,
+                unit: None,
                 window_minutes: Some(5 * 60),
                 resets_at: Some(rolling_reset.to_rfc3339()),
                 reset_description: None,
                 label: Some("5-hour".to_string()),
             }),
             secondary: Some(RateWindow {
+                id: Some("weekly".to_string()),
+                category: Some(WindowCategory::Weekly),
                 used_percent: self.weekly_usage_percent,
+                used: None,
+                limit: None,
+                unit: None,
                 window_minutes: Some(7 * 24 * 60),
                 resets_at: Some(weekly_reset.to_rfc3339()),
                 reset_description: None,
@@ -417,6 +450,10 @@ impl OpenCodeUsageSnapshot {
             }),
             updated_at: self.updated_at.to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 }
@@ -432,8 +469,9 @@ struct ServerRequest {
 impl ServerRequest {
     fn url(&self) -> String {
         if self.method == "GET" {
-            let mut url =
-                url::Url::parse(SERVER_URL).unwrap_or_else(|_| url::Url::parse(BASE_URL).unwrap());
+            let server_url = endpoint(ProviderId::Opencode, SERVER_URL);
+            let mut url = url::Url::parse(&server_url)
+                .unwrap_or_else(|_| url::Url::parse(SERVER_URL).unwrap());
             url.query_pairs_mut().append_pair("id", self.server_id);
             if let Some(args) = &self.args {
                 if let Ok(encoded) = serde_json::to_string(args) {
@@ -444,7 +482,7 @@ impl ServerRequest {
             }
             url.to_string()
         } else {
-            SERVER_URL.to_string()
+            endpoint(ProviderId::Opencode, SERVER_URL)
         }
     }
 }
@@ -898,6 +936,17 @@ enum OpencodeError {
     Parse(String),
 }
 
+impl From<OpencodeError> for ProviderError {
+    fn from(err: OpencodeError) -> Self {
+        let message = err.to_string();
+        match err {
+            OpencodeError::InvalidCredentials => ProviderError::TokenExpired(message),
+            OpencodeError::Parse(_) => ProviderError::Parse(message),
+            OpencodeError::Api(_) => ProviderError::Other(message),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
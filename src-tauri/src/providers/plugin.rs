@@ -0,0 +1,284 @@
+//! External provider plugins
+//!
+//! Executables named `incubar-provider-<id>` in the plugins directory are run as
+//! providers. `<plugin> usage` must print a `UsageSnapshot` as JSON on stdout;
+//! `<plugin> status` may print a `ProviderStatus`. Anything written to stderr goes
+//! to the debug log.
+
+use super::{ProviderError, ProviderFetcher, ProviderStatus, UsageSnapshot};
+use crate::login::AuthStatus;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+pub const PLUGIN_PREFIX: &str = "incubar-provider-";
+const PLUGINS_DIR_ENV: &str = "INCUBAR_PLUGINS_DIR";
+const PLUGIN_TIMEOUT_SECS: u64 = 10;
+
+/// A plugin executable found in the plugins directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    /// The executable name without the `incubar-provider-` prefix
+    pub id: String,
+    pub path: PathBuf,
+}
+
+/// Directory scanned for plugins, `INCUBAR_PLUGINS_DIR` or `plugins` in `data_dir`
+pub fn plugins_dir(data_dir: &Path) -> PathBuf {
+    if let Ok(dir) = std::env::var(PLUGINS_DIR_ENV) {
+        if !dir.trim().is_empty() {
+            return PathBuf::from(dir.trim());
+        }
+    }
+    data_dir.join("plugins")
+}
+
+/// Find plugin executables. A missing directory means no plugins.
+pub fn discover_plugins(data_dir: &Path) -> Vec<PluginInfo> {
+    discover_plugins_in(&plugins_dir(data_dir))
+}
+
+fn discover_plugins_in(dir: &Path) -> Vec<PluginInfo> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<PluginInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .filter_map(|path| {
+            let id = plugin_id(path.file_name()?.to_str()?)?;
+            Some(PluginInfo { id, path })
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    plugins.dedup_by(|a, b| a.id == b.id);
+    plugins
+}
+
+fn plugin_id(file_name: &str) -> Option<String> {
+    let id = file_name.strip_prefix(PLUGIN_PREFIX)?;
+    let id = id.strip_suffix(".exe").unwrap_or(id);
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return None;
+    }
+    Some(id.to_string())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("exe"))
+}
+
+pub struct PluginProvider {
+    plugin: PluginInfo,
+    timeout: Duration,
+}
+
+impl PluginProvider {
+    pub fn new(plugin: PluginInfo) -> Self {
+        Self {
+            plugin,
+            timeout: Duration::from_secs(PLUGIN_TIMEOUT_SECS),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run the plugin with a single subcommand, logging its stderr
+    async fn run(&self, subcommand: &str) -> Result<std::process::Output, anyhow::Error> {
+        let command = tokio::process::Command::new(&self.plugin.path)
+            .arg(subcommand)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+
+        let output = match tokio::time::timeout(self.timeout, command).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(err)) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to run plugin {}: {}",
+                    self.plugin.id,
+                    err
+                ))
+            }
+            Err(_) => {
+                return Err(anyhow::anyhow!(
+                    "Plugin {} timed out after {}s",
+                    self.plugin.id,
+                    self.timeout.as_secs()
+                ))
+            }
+        };
+
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            if !line.trim().is_empty() {
+                tracing::debug!("plugin {} {}: {}", self.plugin.id, subcommand, line);
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl ProviderFetcher for PluginProvider {
+    fn name(&self) -> &'static str {
+        "Plugin"
+    }
+
+    fn description(&self) -> &'static str {
+        "External provider plugin"
+    }
+
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
+        tracing::debug!("Fetching plugin {} usage", self.plugin.id);
+
+        let output = self.run("usage").await?;
+        if !output.status.success() {
+            return Err(ProviderError::Other(format!(
+                "Plugin {} exited with {}",
+                self.plugin.id, output.status
+            )));
+        }
+
+        let usage: UsageSnapshot = serde_json::from_slice(&output.stdout).map_err(|err| {
+            ProviderError::Parse(format!(
+                "Plugin {} printed invalid usage JSON: {}",
+                self.plugin.id, err
+            ))
+        })?;
+        Ok(usage.validated())
+    }
+
+    async fn fetch_status(&self) -> Result<ProviderStatus, anyhow::Error> {
+        let output = self.run("status").await?;
+        // Status is optional: plugins without it may exit non-zero or print nothing
+        if !output.status.success() || output.stdout.iter().all(u8::is_ascii_whitespace) {
+            return Ok(ProviderStatus::none());
+        }
+
+        let status: ProviderStatus = serde_json::from_slice(&output.stdout).map_err(|err| {
+            anyhow::anyhow!("Plugin {} printed invalid status JSON: {}", self.plugin.id, err)
+        })?;
+        Ok(status.validated())
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        if is_executable(&self.plugin.path) {
+            AuthStatus::authenticated("plugin")
+        } else {
+            AuthStatus::unauthenticated(Some(format!(
+                "Plugin executable not found: {}",
+                self.plugin.path.display()
+            )))
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::providers::StatusIndicator;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("write plugin");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod plugin");
+        path
+    }
+
+    fn provider(path: PathBuf) -> PluginProvider {
+        PluginProvider::new(PluginInfo {
+            id: "test".to_string(),
+            path,
+        })
+    }
+
+    #[test]
+    fn discovers_only_prefixed_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        write_plugin(dir.path(), "incubar-provider-gateway", "exit 0");
+        write_plugin(dir.path(), "other-tool", "exit 0");
+        write_plugin(dir.path(), "incubar-provider-bad.name", "exit 0");
+        std::fs::write(dir.path().join("incubar-provider-notes"), "not executable").unwrap();
+
+        let plugins = discover_plugins_in(dir.path());
+
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].id, "gateway");
+        assert!(discover_plugins_in(&dir.path().join("missing")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn parses_and_validates_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_plugin(
+            dir.path(),
+            "incubar-provider-test",
+            r#"echo "starting" >&2
+if [ "$1" = "usage" ]; then
+  echo '{"primary":{"usedPercent":140,"label":"Requests"},"updatedAt":"2025-01-01T00:00:00Z"}'
+else
+  echo '{"indicator":"minor","description":" Degraded "}'
+fi"#,
+        );
+
+        let usage = provider(path.clone()).fetch().await.expect("usage");
+        let primary = usage.primary.expect("primary");
+        assert_eq!(primary.used_percent, 100.0);
+        assert_eq!(primary.label.as_deref(), Some("Requests"));
+
+        let status = provider(path).fetch_status().await.expect("status");
+        assert_eq!(status.indicator, StatusIndicator::Minor);
+        assert_eq!(status.description.as_deref(), Some("Degraded"));
+    }
+
+    #[tokio::test]
+    async fn reports_failures_and_missing_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_plugin(dir.path(), "incubar-provider-test", "echo nope; exit 3");
+
+        assert!(provider(path.clone()).fetch().await.is_err());
+        let status = provider(path).fetch_status().await.expect("status");
+        assert_eq!(status.indicator, StatusIndicator::None);
+    }
+
+    #[tokio::test]
+    async fn kills_plugins_that_exceed_the_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_plugin(dir.path(), "incubar-provider-test", "sleep 5");
+
+        let err = provider(path)
+            .with_timeout(Duration::from_millis(200))
+            .fetch()
+            .await
+            .expect_err("timeout");
+        assert!(err.to_string().contains("timed out"));
+    }
+}
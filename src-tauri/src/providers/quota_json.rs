@@ -0,0 +1,502 @@
+//! Heuristic parsers for JSON quota payloads
+//!
+//! Quota endpoints rarely agree on field names, so these helpers probe the common
+//! spellings for limits, usage, windows and reset times. Shared by the Synthetic,
+//! Factory and custom HTTP providers.
+
+use super::RateWindow;
+use serde_json::Value;
+
+pub struct QuotaEntry {
+    pub label: Option<String>,
+    pub used_percent: f64,
+    pub used: Option<f64>,
+    pub limit: Option<f64>,
+    pub unit: Option<String>,
+    pub window_minutes: Option<i32>,
+    pub resets_at: Option<String>,
+    pub reset_description: Option<String>,
+}
+
+impl QuotaEntry {
+    pub fn to_rate_window(&self) -> RateWindow {
+        RateWindow {
+            id: None,
+            category: None,
+            used_percent: self.used_percent,
+            used: self.used,
+            limit: self.limit,
+            unit: self.unit.clone(),
+            window_minutes: self.window_minutes,
+            resets_at: self.resets_at.clone(),
+            reset_description: self.reset_description.clone(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+pub fn find_plan_name(json: &Value) -> Option<String> {
+    const PLAN_KEYS: &[&str] = &[
+        "plan",
+        "planName",
+        "plan_name",
+        "subscription",
+        "subscriptionPlan",
+        "tier",
+        "package",
+        "packageName",
+    ];
+
+    // Check root level
+    for key in PLAN_KEYS {
+        if let Some(s) = json.get(*key).and_then(|v| v.as_str()) {
+            let trimmed = s.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed.to_string());
+            }
+        }
+    }
+
+    // Check in data object
+    if let Some(data) = json.get("data") {
+        for key in PLAN_KEYS {
+            if let Some(s) = data.get(*key).and_then(|v| v.as_str()) {
+                let trimmed = s.trim();
+                if !trimmed.is_empty() {
+                    return Some(trimmed.to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_quota_objects(json: &Value) -> Vec<&Value> {
+    const QUOTA_KEYS: &[&str] = &[
+        "quotas",
+        "quota",
+        "limits",
+        "usage",
+        "entries",
+        "subscription",
+        "data",
+    ];
+
+    // Try root level first
+    for key in QUOTA_KEYS {
+        if let Some(arr) = json.get(*key).and_then(|v| v.as_array()) {
+            return arr.iter().collect();
+        }
+        if let Some(obj) = json.get(*key) {
+            if obj.is_object() && is_quota_payload(obj) {
+                return vec![obj];
+            }
+        }
+    }
+
+    // Try in data object
+    if let Some(data) = json.get("data") {
+        for key in QUOTA_KEYS {
+            if let Some(arr) = data.get(*key).and_then(|v| v.as_array()) {
+                return arr.iter().collect();
+            }
+            if let Some(obj) = data.get(*key) {
+                if obj.is_object() && is_quota_payload(obj) {
+                    return vec![obj];
+                }
+            }
+        }
+    }
+
+    // If root is an array, use it directly
+    if let Some(arr) = json.as_array() {
+        return arr.iter().collect();
+    }
+
+    // If root itself is a quota payload
+    if is_quota_payload(json) {
+        return vec![json];
+    }
+
+    vec![]
+}
+
+fn is_quota_payload(json: &Value) -> bool {
+    const CHECK_KEYS: &[&[&str]] = &[
+        &["limit", "quota", "max", "total", "capacity", "allowance"],
+        &[
+            "used",
+            "usage",
+            "requests",
+            "requestCount",
+            "request_count",
+            "consumed",
+            "spent",
+        ],
+        &["remaining", "left", "available", "balance"],
+        &[
+            "percentUsed",
+            "usedPercent",
+            "usagePercent",
+            "usage_percent",
+            "used_percent",
+            "percent_used",
+            "percent",
+        ],
+        &[
+            "percentRemaining",
+            "remainingPercent",
+            "remaining_percent",
+            "percent_remaining",
+        ],
+    ];
+
+    for keys in CHECK_KEYS {
+        for key in *keys {
+            if json.get(*key).and_then(to_double).is_some() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub fn parse_quota(json: &Value) -> Option<QuotaEntry> {
+    let label = find_string(
+        json,
+        &["name", "label", "type", "period", "scope", "title", "id"],
+    );
+
+    // Try to find percent used directly
+    let percent_used = find_double(
+        json,
+        &[
+            "percentUsed",
+            "usedPercent",
+            "usagePercent",
+            "usage_percent",
+            "used_percent",
+            "percent_used",
+            "percent",
+        ],
+    )
+    .map(normalize_percent);
+
+    let percent_remaining = find_double(
+        json,
+        &[
+            "percentRemaining",
+            "remainingPercent",
+            "remaining_percent",
+            "percent_remaining",
+        ],
+    )
+    .map(normalize_percent);
+
+    let mut used_percent = percent_used;
+    if used_percent.is_none() {
+        if let Some(remaining) = percent_remaining {
+            used_percent = Some(100.0 - remaining);
+        }
+    }
+
+    let limit = find_double(
+        json,
+        &["limit", "quota", "max", "total", "capacity", "allowance"],
+    );
+    let used = find_double(
+        json,
+        &[
+            "used",
+            "usage",
+            "requests",
+            "requestCount",
+            "request_count",
+            "consumed",
+            "spent",
+        ],
+    );
+    let remaining = find_double(json, &["remaining", "left", "available", "balance"]);
+
+    let final_limit = limit.or_else(|| match (used, remaining) {
+        (Some(u), Some(r)) => Some(u + r),
+        _ => None,
+    });
+
+    let final_used = used.or_else(|| match (final_limit, remaining) {
+        (Some(l), Some(r)) => Some(l - r),
+        _ => None,
+    });
+
+    // If no percent found, calculate from limit/used/remaining
+    if used_percent.is_none() {
+        if let (Some(l), Some(u)) = (final_limit, final_used) {
+            if l > 0.0 {
+                used_percent = Some((u / l) * 100.0);
+            }
+        }
+    }
+
+    let used_percent = used_percent?.clamp(0.0, 100.0);
+
+    let window_minutes = find_window_minutes(json);
+    let resets_at = find_date(
+        json,
+        &[
+            "resetAt",
+            "reset_at",
+            "resetsAt",
+            "resets_at",
+            "renewAt",
+            "renew_at",
+            "renewsAt",
+            "renews_at",
+            "periodEnd",
+            "period_end",
+            "expiresAt",
+            "expires_at",
+            "endAt",
+            "end_at",
+        ],
+    );
+
+    let reset_description = if resets_at.is_none() {
+        window_description(window_minutes)
+    } else {
+        None
+    };
+
+    Some(QuotaEntry {
+        label,
+        used_percent,
+        used: final_used,
+        limit: final_limit,
+        unit: find_unit(json),
+        window_minutes,
+        resets_at,
+        reset_description,
+    })
+}
+
+/// What a quota counts, when the payload says
+pub fn find_unit(json: &Value) -> Option<String> {
+    find_string(json, &["unit", "units", "unitName", "unit_name"])
+}
+
+pub fn find_string(json: &Value, keys: &[&str]) -> Option<String> {
+    for key in keys {
+        if let Some(s) = json.get(*key).and_then(|v| v.as_str()) {
+            let trimmed = s.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed.to_string());
+            }
+        }
+    }
+    None
+}
+
+pub fn find_double(json: &Value, keys: &[&str]) -> Option<f64> {
+    for key in keys {
+        if let Some(v) = json.get(*key) {
+            if let Some(n) = to_double(v) {
+                return Some(n);
+            }
+        }
+    }
+    None
+}
+
+pub fn find_window_minutes(json: &Value) -> Option<i32> {
+    if let Some(m) = find_double(
+        json,
+        &[
+            "windowMinutes",
+            "window_minutes",
+            "periodMinutes",
+            "period_minutes",
+        ],
+    ) {
+        return Some(m as i32);
+    }
+    if let Some(h) = find_double(
+        json,
+        &["windowHours", "window_hours", "periodHours", "period_hours"],
+    ) {
+        return Some((h * 60.0) as i32);
+    }
+    if let Some(d) = find_double(
+        json,
+        &["windowDays", "window_days", "periodDays", "period_days"],
+    ) {
+        return Some((d * 24.0 * 60.0) as i32);
+    }
+    if let Some(s) = find_double(
+        json,
+        &[
+            "windowSeconds",
+            "window_seconds",
+            "periodSeconds",
+            "period_seconds",
+        ],
+    ) {
+        return Some((s / 60.0) as i32);
+    }
+    None
+}
+
+fn find_date(json: &Value, keys: &[&str]) -> Option<String> {
+    for key in keys {
+        if let Some(v) = json.get(*key) {
+            if let Some(ts) = to_double(v) {
+                // Convert timestamp to ISO string
+                let secs = if ts > 1_000_000_000_000.0 {
+                    ts / 1000.0
+                } else {
+                    ts
+                };
+                if let Some(dt) = chrono::DateTime::from_timestamp(secs as i64, 0) {
+                    return Some(dt.to_rfc3339());
+                }
+            }
+            if let Some(s) = v.as_str() {
+                // Try to parse as timestamp
+                if let Ok(ts) = s.trim().parse::<f64>() {
+                    let secs = if ts > 1_000_000_000_000.0 {
+                        ts / 1000.0
+                    } else {
+                        ts
+                    };
+                    if let Some(dt) = chrono::DateTime::from_timestamp(secs as i64, 0) {
+                        return Some(dt.to_rfc3339());
+                    }
+                }
+                // Assume it's already an ISO string
+                if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+                    return Some(s.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+pub fn to_double(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn normalize_percent(value: f64) -> f64 {
+    if value <= 1.0 {
+        value * 100.0
+    } else {
+        value
+    }
+}
+
+fn window_description(minutes: Option<i32>) -> Option<String> {
+    let minutes = minutes?;
+    if minutes <= 0 {
+        return None;
+    }
+
+    let day_minutes = 24 * 60;
+    if minutes % day_minutes == 0 {
+        let days = minutes / day_minutes;
+        let suffix = if days == 1 { "" } else { "s" };
+        return Some(format!("{} day{} window", days, suffix));
+    }
+    if minutes % 60 == 0 {
+        let hours = minutes / 60;
+        let suffix = if hours == 1 { "" } else { "s" };
+        return Some(format!("{} hour{} window", hours, suffix));
+    }
+    let suffix = if minutes == 1 { "" } else { "s" };
+    Some(format!("{} minute{} window", minutes, suffix))
+}
+
+pub fn find_reset_time(json: &Value) -> Option<String> {
+    let timestamp_keys = [
+        "resetAt",
+        "reset_at",
+        "resetsAt",
+        "resets_at",
+        "resetTime",
+        "reset_time",
+        "nextReset",
+        "next_reset",
+        "renewAt",
+        "renew_at",
+        "periodEnd",
+        "period_end",
+    ];
+
+    for key in &timestamp_keys {
+        if let Some(reset_at) = json.get(*key).and_then(parse_reset_value) {
+            return Some(reset_at);
+        }
+    }
+
+    let duration_keys = [
+        "resetInSeconds",
+        "reset_in_seconds",
+        "resetSeconds",
+        "reset_seconds",
+        "resetIn",
+        "reset_in",
+    ];
+
+    for key in &duration_keys {
+        if let Some(seconds) = json.get(*key).and_then(to_double) {
+            if seconds > 0.0 {
+                let reset_at = chrono::Utc::now() + chrono::Duration::seconds(seconds as i64);
+                return Some(reset_at.to_rfc3339());
+            }
+        }
+    }
+
+    None
+}
+
+/// Interpret a single value as a reset time: a Unix timestamp (seconds or
+/// milliseconds, number or string) or an RFC 3339 string
+pub fn parse_reset_value(value: &Value) -> Option<String> {
+    if let Some(ts) = to_double(value) {
+        return timestamp_to_iso(ts);
+    }
+    let s = value.as_str()?.trim();
+    if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+        return Some(s.to_string());
+    }
+    None
+}
+
+fn timestamp_to_iso(timestamp: f64) -> Option<String> {
+    let seconds = if timestamp > 1_000_000_000_000.0 {
+        (timestamp / 1000.0) as i64
+    } else {
+        timestamp as i64
+    };
+    chrono::DateTime::from_timestamp(seconds, 0).map(|dt| dt.to_rfc3339())
+}
+
+pub fn format_reset_time(iso_time: &str) -> Option<String> {
+    let reset_date = chrono::DateTime::parse_from_rfc3339(iso_time).ok()?;
+    let now = chrono::Utc::now();
+    let duration = reset_date.signed_duration_since(now);
+
+    if duration.num_seconds() <= 0 {
+        return Some("Resets soon".to_string());
+    }
+
+    if duration.num_hours() < 1 {
+        Some(format!("Resets in {} min", duration.num_minutes().max(1)))
+    } else if duration.num_hours() < 24 {
+        Some(format!("Resets in {}h", duration.num_hours()))
+    } else {
+        Some(format!("Resets in {} days", duration.num_days()))
+    }
+}
@@ -0,0 +1,240 @@
+//! Server-requested retry delays
+//!
+//! Requests sent with `send_recorded` report 429 and 503 responses, along with their
+//! `Retry-After` header, to the fetch they belong to. When that fetch fails, the hint
+//! turns its error into [`ProviderError::RateLimited`], and [`RateLimits`] holds the
+//! account back until the server said it may try again, for background and manual
+//! refreshes alike.
+
+use super::{AccountKey, ProviderError};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Longest `Retry-After` honoured, so a bogus header cannot park a provider for days
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A rate-limiting response seen while fetching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RateLimitHint {
+    status: u16,
+    retry_after: Option<Duration>,
+}
+
+tokio::task_local! {
+    static HINT: Cell<Option<RateLimitHint>>;
+}
+
+/// Run a provider fetch, turning its error into a rate limit when a request it sent
+/// was answered with 429, or with 503 and a `Retry-After` header
+pub(crate) async fn capture<T>(
+    fetch: impl Future<Output = Result<T, ProviderError>>,
+) -> Result<T, ProviderError> {
+    HINT.scope(Cell::new(None), async move {
+        let result = fetch.await;
+        let hint = HINT.with(Cell::get);
+        result.map_err(|error| match hint {
+            Some(hint) => apply_hint(error, hint),
+            None => error,
+        })
+    })
+    .await
+}
+
+/// Note a response for the fetch being captured. Called by the HTTP layer for every
+/// provider response; does nothing outside [`capture`].
+pub(crate) fn observe(response: &reqwest::Response) {
+    let status = response.status().as_u16();
+    if status != 429 && status != 503 {
+        return;
+    }
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, SystemTime::now()));
+    let _ = HINT.try_with(|hint| hint.set(Some(RateLimitHint { status, retry_after })));
+}
+
+/// Parse a `Retry-After` value, either delay seconds or an HTTP date
+pub(crate) fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            let at = SystemTime::from(at.with_timezone(&chrono::Utc));
+            at.duration_since(now).unwrap_or_default()
+        }
+    };
+    Some(delay.min(MAX_RETRY_AFTER))
+}
+
+fn apply_hint(error: ProviderError, hint: RateLimitHint) -> ProviderError {
+    match error {
+        // Missing credentials and cancellations say more than the status did
+        ProviderError::NotAuthenticated(_)
+        | ProviderError::TokenExpired(_)
+        | ProviderError::Forbidden(_)
+        | ProviderError::Cancelled => error,
+        ProviderError::RateLimited {
+            retry_after: None,
+            message,
+        } => ProviderError::RateLimited {
+            retry_after: hint.retry_after,
+            message,
+        },
+        ProviderError::RateLimited { .. } => error,
+        // A 503 without a delay is an ordinary outage
+        _ if hint.status == 503 && hint.retry_after.is_none() => error,
+        error => ProviderError::RateLimited {
+            retry_after: hint.retry_after,
+            message: error.to_string(),
+        },
+    }
+}
+
+struct Limit {
+    until: SystemTime,
+    message: String,
+}
+
+/// Accounts waiting out a rate limit
+pub(crate) struct RateLimits {
+    limits: Mutex<HashMap<AccountKey, Limit>>,
+}
+
+impl RateLimits {
+    pub(crate) fn new() -> Self {
+        Self {
+            limits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The error to report instead of fetching, while `key` is still rate limited
+    pub(crate) fn check(&self, key: &AccountKey, now: SystemTime) -> Option<ProviderError> {
+        let mut limits = self.lock();
+        let limit = limits.get(key)?;
+        match limit.until.duration_since(now) {
+            Ok(remaining) if !remaining.is_zero() => Some(ProviderError::RateLimited {
+                retry_after: Some(remaining),
+                message: limit.message.clone(),
+            }),
+            _ => {
+                limits.remove(key);
+                None
+            }
+        }
+    }
+
+    /// Remember a rate limit with a known delay; any other result clears it
+    pub(crate) fn record(
+        &self,
+        key: &AccountKey,
+        result: &Result<impl Sized, ProviderError>,
+        now: SystemTime,
+    ) {
+        let mut limits = self.lock();
+        match result {
+            Err(ProviderError::RateLimited {
+                retry_after: Some(delay),
+                message,
+            }) => {
+                limits.insert(
+                    key.clone(),
+                    Limit {
+                        until: now.checked_add(*delay).unwrap_or(now),
+                        message: message.clone(),
+                    },
+                );
+            }
+            Err(ProviderError::Cancelled) => {}
+            _ => {
+                limits.remove(key);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<AccountKey, Limit>> {
+        self.limits.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ProviderErrorKind, ProviderId};
+
+    #[test]
+    fn parses_seconds_and_http_dates() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_420);
+        assert_eq!(parse_retry_after(" 120 ", now), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("9999999", now), Some(MAX_RETRY_AFTER));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[tokio::test]
+    async fn hints_turn_failures_into_rate_limits() {
+        let limited = |status, retry_after| {
+            capture(async move {
+                let hint = RateLimitHint {
+                    status,
+                    retry_after,
+                };
+                let _ = HINT.try_with(|cell| cell.set(Some(hint)));
+                Err::<(), _>(ProviderError::Other("Augment API error: HTTP 429".to_string()))
+            })
+        };
+
+        let delay = Some(Duration::from_secs(90));
+        assert_eq!(
+            limited(429, delay).await,
+            Err(ProviderError::RateLimited {
+                retry_after: delay,
+                message: "Augment API error: HTTP 429".to_string(),
+            })
+        );
+        assert_eq!(
+            limited(503, None).await.unwrap_err().kind(),
+            ProviderErrorKind::Other
+        );
+
+        let unhinted = capture(async { Err::<(), _>(ProviderError::Cancelled) }).await;
+        assert_eq!(unhinted, Err(ProviderError::Cancelled));
+    }
+
+    #[test]
+    fn holds_accounts_until_the_delay_passes() {
+        let limits = RateLimits::new();
+        let key = AccountKey::new(ProviderId::Cursor, "default");
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let limited: Result<(), _> = Err(ProviderError::RateLimited {
+            retry_after: Some(Duration::from_secs(60)),
+            message: "Too many requests".to_string(),
+        });
+
+        limits.record(&key, &limited, now);
+        assert_eq!(
+            limits.check(&key, now + Duration::from_secs(15)),
+            Some(ProviderError::RateLimited {
+                retry_after: Some(Duration::from_secs(45)),
+                message: "Too many requests".to_string(),
+            })
+        );
+        assert_eq!(limits.check(&key, now + Duration::from_secs(60)), None);
+
+        limits.record(&key, &limited, now);
+        limits.record(&key, &Ok(()), now);
+        assert_eq!(limits.check(&key, now), None);
+    }
+}
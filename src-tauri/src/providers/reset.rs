@@ -0,0 +1,214 @@
+//! Rate window reset detection
+//!
+//! Each fetched snapshot is compared with the one it replaces. A window has reset when
+//! its previous `resets_at` has passed, or when its usage drops sharply. Resets are
+//! broadcast by the registry, and `start_reset_listener` turns them into `window-reset`
+//! events and, for windows that were nearly used up, native notifications.
+
+use super::{
+    notifications_allowed, AccountKey, ProviderId, ProviderRegistry, RateWindow, UsageSnapshot,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::broadcast;
+
+/// A window whose usage drops by at least this many points has reset
+pub(crate) const RESET_DROP_PERCENT: f64 = 5.0;
+/// Resets are only worth a notification when the window was nearly used up
+const NOTIFY_FROM_PERCENT: f64 = 80.0;
+/// Resets buffered for a listener that falls behind
+pub(crate) const RESET_CHANNEL_CAPACITY: usize = 64;
+
+/// Payload of the `window-reset` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowReset {
+    pub provider_id: ProviderId,
+    pub account_id: String,
+    pub window_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub previous_percent: f64,
+    pub used_percent: f64,
+    /// When the new window resets, if the provider says
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+}
+
+/// Windows of `current` that reset since `previous` was fetched
+pub(crate) fn detect_resets(
+    key: &AccountKey,
+    previous: &UsageSnapshot,
+    current: &UsageSnapshot,
+    now: DateTime<Utc>,
+) -> Vec<WindowReset> {
+    if previous.error.is_some() || current.error.is_some() {
+        return Vec::new();
+    }
+    let previous_windows = previous.rate_windows();
+    current
+        .rate_windows()
+        .into_iter()
+        .filter_map(|window| {
+            let id = window.id.as_deref()?;
+            let before = previous_windows
+                .iter()
+                .find(|before| before.id.as_deref() == Some(id))?;
+            if !has_reset(before, window, now) {
+                return None;
+            }
+            Some(WindowReset {
+                provider_id: key.provider_id,
+                account_id: key.account_id.clone(),
+                window_id: id.to_string(),
+                label: window.label.clone(),
+                previous_percent: before.used_percent,
+                used_percent: window.used_percent,
+                resets_at: window.resets_at.clone(),
+            })
+        })
+        .collect()
+}
+
+fn has_reset(before: &RateWindow, after: &RateWindow, now: DateTime<Utc>) -> bool {
+    if before.used_percent - after.used_percent >= RESET_DROP_PERCENT {
+        return true;
+    }
+    // An unused window resetting is not news
+    if before.used_percent <= 0.0 {
+        return false;
+    }
+    match reset_time(before) {
+        Some(reset) if reset <= now => reset_time(after).is_none_or(|next| next > reset),
+        _ => false,
+    }
+}
+
+fn reset_time(window: &RateWindow) -> Option<DateTime<Utc>> {
+    let resets_at = window.resets_at.as_deref()?;
+    DateTime::parse_from_rfc3339(resets_at)
+        .ok()
+        .map(|reset| reset.with_timezone(&Utc))
+}
+
+/// Emit a `window-reset` event for every reset the registry detects, with a native
+/// notification when notifications are on, not snoozed, and the window was nearly used up
+pub async fn start_reset_listener(app: AppHandle) {
+    let mut resets = app.state::<ProviderRegistry>().subscribe_resets();
+    loop {
+        let reset = match resets.recv().await {
+            Ok(reset) => reset,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Reset listener fell behind, skipped {} resets", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        tracing::info!(
+            "{:?}/{} window {} reset: {:.0}% -> {:.0}%",
+            reset.provider_id,
+            reset.account_id,
+            reset.window_id,
+            reset.previous_percent,
+            reset.used_percent
+        );
+        let _ = app.emit("window-reset", &reset);
+
+        if reset.previous_percent < NOTIFY_FROM_PERCENT
+            || !notifications_allowed(reset.provider_id)
+        {
+            continue;
+        }
+        let label = reset.label.as_deref().unwrap_or(&reset.window_id);
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title(format!("{} {} reset", reset.provider_id.display_name(), label))
+            .body(format!(
+                "Usage is back to {:.0}% from {:.0}%",
+                reset.used_percent, reset.previous_percent
+            ))
+            .show()
+        {
+            tracing::warn!("Failed to show reset notification: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::USAGE_SNAPSHOT_VERSION;
+    use chrono::Duration;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn usage(used_percent: f64, resets_at: Option<DateTime<Utc>>) -> UsageSnapshot {
+        let window = RateWindow {
+            id: Some("session".to_string()),
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: Some(300),
+            resets_at: resets_at.map(|reset| reset.to_rfc3339()),
+            reset_description: None,
+            label: Some("Session".to_string()),
+        };
+        UsageSnapshot {
+            primary: Some(window.clone()),
+            secondary: None,
+            tertiary: None,
+            credits: None,
+            cost: None,
+            identity: None,
+            updated_at: now().to_rfc3339(),
+            error: None,
+            retry_at: None,
+            windows: vec![window],
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        }
+    }
+
+    fn resets(previous: &UsageSnapshot, current: &UsageSnapshot) -> Vec<WindowReset> {
+        let key = AccountKey::default_for(ProviderId::Claude);
+        detect_resets(&key, previous, current, now())
+    }
+
+    #[test]
+    fn detects_a_sharp_drop() {
+        let found = resets(&usage(97.0, None), &usage(3.0, None));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].window_id, "session");
+        assert_eq!(found[0].previous_percent, 97.0);
+        assert_eq!(found[0].used_percent, 3.0);
+
+        assert!(resets(&usage(50.0, None), &usage(48.0, None)).is_empty());
+    }
+
+    #[test]
+    fn detects_a_passed_reset_time() {
+        let passed = Some(now() - Duration::minutes(1));
+        let next = Some(now() + Duration::hours(5));
+        assert_eq!(resets(&usage(2.0, passed), &usage(1.0, next)).len(), 1);
+        // Still within the old window, or nothing was used before the reset
+        let upcoming = Some(now() + Duration::minutes(1));
+        assert!(resets(&usage(2.0, upcoming), &usage(2.0, upcoming)).is_empty());
+        assert!(resets(&usage(0.0, passed), &usage(0.0, next)).is_empty());
+    }
+
+    #[test]
+    fn ignores_error_snapshots() {
+        let error = UsageSnapshot::error("boom".to_string());
+        assert!(resets(&usage(90.0, None), &error).is_empty());
+        assert!(resets(&error, &usage(0.0, None)).is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Single-flight fetches
+//!
+//! Manual refreshes, refresh-all, the shortcut and the background loop can all ask for
+//! the same account at once. Each key has at most one fetch in flight; later callers
+//! wait for its result instead of starting their own. A fetch can be cancelled, which
+//! fails it for every waiter, and a forced fetch replaces whatever is in flight.
+
+use super::ProviderError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+
+/// Result shared by every caller waiting on a fetch
+pub(crate) type SharedResult<T> = Result<T, ProviderError>;
+
+struct Flight<T> {
+    id: u64,
+    result: watch::Receiver<Option<SharedResult<T>>>,
+    abort: AbortHandle,
+}
+
+pub(crate) struct SingleFlight<K, T> {
+    flights: Arc<Mutex<HashMap<K, Flight<T>>>>,
+    next_id: AtomicU64,
+}
+
+impl<K, T> SingleFlight<K, T>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            flights: Arc::new(Mutex::new(HashMap::new())),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Wait for the fetch in flight for `key`, starting `fetch` if there is none.
+    /// With `force`, an in-flight fetch is cancelled and `fetch` always runs.
+    pub(crate) async fn run<F>(&self, key: K, force: bool, fetch: F) -> SharedResult<T>
+    where
+        F: Future<Output = SharedResult<T>> + Send + 'static,
+    {
+        let mut result = {
+            let mut flights = self.lock();
+            match flights.get(&key) {
+                Some(flight) if !force => flight.result.clone(),
+                _ => {
+                    if let Some(stale) = flights.remove(&key) {
+                        stale.abort.abort();
+                    }
+                    let flight = self.spawn(key.clone(), fetch);
+                    let result = flight.result.clone();
+                    flights.insert(key, flight);
+                    result
+                }
+            }
+        };
+
+        // The sender is dropped without a value only when the fetch was aborted; a fetch
+        // that panicked reports an error through its guard
+        let shared = match result.wait_for(Option::is_some).await {
+            Ok(value) => value.clone(),
+            Err(_) => None,
+        };
+        shared.unwrap_or(Err(ProviderError::Cancelled))
+    }
+
+    /// Cancel the fetch in flight for `key`. Returns whether there was one.
+    pub(crate) fn cancel(&self, key: &K) -> bool {
+        match self.lock().remove(key) {
+            Some(flight) => {
+                flight.abort.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn is_in_flight(&self, key: &K) -> bool {
+        self.lock().contains_key(key)
+    }
+
+    fn spawn<F>(&self, key: K, fetch: F) -> Flight<T>
+    where
+        F: Future<Output = SharedResult<T>> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = watch::channel(None);
+        let guard = FlightGuard {
+            flights: Arc::clone(&self.flights),
+            key,
+            id,
+            sender,
+        };
+        let task = tokio::spawn(async move {
+            let result = fetch.await;
+            let _ = guard.sender.send(Some(result));
+        });
+        Flight {
+            id,
+            result: receiver,
+            abort: task.abort_handle(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<K, Flight<T>>> {
+        self.flights.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Owned by a fetch's task, so the flight is cleared however the task ends: finished,
+/// aborted or panicked
+struct FlightGuard<K: Hash + Eq, T> {
+    flights: Arc<Mutex<HashMap<K, Flight<T>>>>,
+    key: K,
+    id: u64,
+    sender: watch::Sender<Option<SharedResult<T>>>,
+}
+
+impl<K: Hash + Eq, T> Drop for FlightGuard<K, T> {
+    fn drop(&mut self) {
+        // A forced fetch may already have replaced this one
+        let mut flights = self.flights.lock().unwrap_or_else(|err| err.into_inner());
+        if flights
+            .get(&self.key)
+            .is_some_and(|flight| flight.id == self.id)
+        {
+            flights.remove(&self.key);
+        }
+        drop(flights);
+        if std::thread::panicking() {
+            let _ = self.sender.send(Some(Err(ProviderError::Other(
+                "Fetch failed unexpectedly".to_string(),
+            ))));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    fn counted(
+        calls: &Arc<AtomicUsize>,
+        delay: Duration,
+        value: u32,
+    ) -> impl Future<Output = SharedResult<u32>> + Send + 'static {
+        let calls = Arc::clone(calls);
+        async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(delay).await;
+            Ok(value)
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_callers_share_one_fetch() {
+        let flights = SingleFlight::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let delay = Duration::from_millis(50);
+
+        let (first, second) = tokio::join!(
+            flights.run("claude", false, counted(&calls, delay, 1)),
+            flights.run("claude", false, counted(&calls, delay, 2)),
+        );
+
+        assert_eq!(first, Ok(1));
+        assert_eq!(second, Ok(1));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!flights.is_in_flight(&"claude"));
+
+        // Once finished, the next call fetches again
+        let third = flights
+            .run("claude", false, counted(&calls, delay, 3))
+            .await;
+        assert_eq!(third, Ok(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn cancelling_fails_every_waiter() {
+        let flights = SingleFlight::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let slow = Duration::from_secs(30);
+
+        let (waiter, cancelled) = tokio::join!(
+            flights.run("codex", false, counted(&calls, slow, 1)),
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                flights.cancel(&"codex")
+            },
+        );
+
+        assert!(cancelled);
+        assert_eq!(waiter, Err(ProviderError::Cancelled));
+        assert!(!flights.cancel(&"codex"));
+    }
+
+    #[tokio::test]
+    async fn forced_fetch_replaces_the_one_in_flight() {
+        let flights = SingleFlight::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let (stale, forced) = tokio::join!(
+            flights.run("cursor", false, counted(&calls, Duration::from_secs(30), 1)),
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                flights
+                    .run(
+                        "cursor",
+                        true,
+                        counted(&calls, Duration::from_millis(10), 2),
+                    )
+                    .await
+            },
+        );
+
+        assert_eq!(stale, Err(ProviderError::Cancelled));
+        assert_eq!(forced, Ok(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    async fn panicking() -> SharedResult<u32> {
+        panic!("fetch panicked")
+    }
+
+    #[tokio::test]
+    async fn panicking_fetch_does_not_block_later_ones() {
+        let flights = SingleFlight::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let panicked = flights.run("kiro", false, panicking()).await;
+        assert!(matches!(panicked, Err(ProviderError::Other(_))));
+        assert!(!flights.is_in_flight(&"kiro"));
+
+        let next = flights
+            .run("kiro", false, counted(&calls, Duration::from_millis(10), 1))
+            .await;
+        assert_eq!(next, Ok(1));
+    }
+}
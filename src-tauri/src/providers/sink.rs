@@ -0,0 +1,145 @@
+//! Post-fetch pipeline
+//!
+//! Every fetch result a refresh reports passes through the registry's sinks, whether
+//! the background loop, a manual refresh or refresh-all triggered it. The tray, the
+//! widget snapshot, the usage history and the notifier each register one, so none of
+//! them depends on the webview being alive.
+
+use super::{AccountKey, ProviderError, UsageSnapshot};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use tokio::sync::oneshot;
+
+/// Receives the results of usage fetches
+pub trait UsageSink: Send + Sync {
+    /// A fetch succeeded. Callers that joined the same fetch each report it, so the
+    /// same snapshot can arrive more than once.
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot);
+
+    /// A fetch failed, whether or not its caller reports it. The background refresh
+    /// holds back isolated failures of accounts with data; this still sees them.
+    fn fetch_failed(&self, key: &AccountKey, error: &ProviderError) {
+        let _ = (key, error);
+    }
+
+    /// A fetch failed and its caller chose to report it, right after `fetch_failed`. `usage` is the error snapshot
+    /// shown in place of the account's data.
+    fn refresh_failed(&self, key: &AccountKey, error: &ProviderError, usage: &UsageSnapshot) {
+        let _ = (key, error, usage);
+    }
+}
+
+/// The sinks of a registry, called in the order they were added
+///
+/// Several sinks write files, so results are handed to a thread of their own that
+/// calls the sinks one result at a time, in the order they were published. Async
+/// workers never wait for the disk.
+#[derive(Default)]
+pub(crate) struct UsageSinks {
+    sinks: Arc<RwLock<Vec<Arc<dyn UsageSink>>>>,
+    /// Started with the first result
+    writer: Mutex<Option<mpsc::Sender<SinkMessage>>>,
+}
+
+enum SinkMessage {
+    Updated(AccountKey, UsageSnapshot),
+    Failed(AccountKey, ProviderError, UsageSnapshot),
+    /// Held back by the caller, so only `fetch_failed` sees it
+    Unreported(AccountKey, ProviderError),
+    Flush(oneshot::Sender<()>),
+}
+
+impl UsageSinks {
+    pub(crate) fn add(&self, sink: Arc<dyn UsageSink>) {
+        if let Ok(mut sinks) = self.sinks.write() {
+            sinks.push(sink);
+        }
+    }
+
+    pub(crate) fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        self.send(SinkMessage::Updated(key.clone(), usage.clone()));
+    }
+
+    pub(crate) fn refresh_failed(
+        &self,
+        key: &AccountKey,
+        error: &ProviderError,
+        usage: &UsageSnapshot,
+    ) {
+        self.send(SinkMessage::Failed(
+            key.clone(),
+            error.clone(),
+            usage.clone(),
+        ));
+    }
+
+    pub(crate) fn fetch_failed(&self, key: &AccountKey, error: &ProviderError) {
+        self.send(SinkMessage::Unreported(key.clone(), error.clone()));
+    }
+
+    /// Wait until every result sent so far has been through the sinks
+    pub(crate) async fn flush(&self) {
+        let (done, flushed) = oneshot::channel();
+        self.send(SinkMessage::Flush(done));
+        let _ = flushed.await;
+    }
+
+    fn send(&self, message: SinkMessage) {
+        let mut writer = self.writer.lock().unwrap_or_else(|err| err.into_inner());
+        if writer.is_none() {
+            match self.start_writer() {
+                Ok(sender) => *writer = Some(sender),
+                Err(err) => tracing::warn!("Failed to start the usage sink thread: {}", err),
+            }
+        }
+        let unsent = match writer.as_ref() {
+            Some(sender) => sender.send(message).err().map(|err| err.0),
+            None => Some(message),
+        };
+        if let Some(message) = unsent {
+            // The thread is gone, e.g. after a sink panicked; start a new one next time
+            // and deliver this result here
+            *writer = None;
+            deliver(&self.sinks, message);
+        }
+    }
+
+    fn start_writer(&self) -> std::io::Result<mpsc::Sender<SinkMessage>> {
+        let (sender, receiver) = mpsc::channel();
+        let sinks = Arc::clone(&self.sinks);
+        // Ends once the registry, and with it the sender, is dropped
+        std::thread::Builder::new()
+            .name("usage-sinks".to_string())
+            .spawn(move || {
+                for message in receiver {
+                    deliver(&sinks, message);
+                }
+            })?;
+        Ok(sender)
+    }
+}
+
+fn deliver(sinks: &RwLock<Vec<Arc<dyn UsageSink>>>, message: SinkMessage) {
+    // Copied, so no lock is held while a sink runs
+    let sinks = sinks.read().map(|sinks| sinks.clone()).unwrap_or_default();
+    match message {
+        SinkMessage::Updated(key, usage) => {
+            for sink in sinks {
+                sink.usage_updated(&key, &usage);
+            }
+        }
+        SinkMessage::Failed(key, error, usage) => {
+            for sink in sinks {
+                sink.fetch_failed(&key, &error);
+                sink.refresh_failed(&key, &error, &usage);
+            }
+        }
+        SinkMessage::Unreported(key, error) => {
+            for sink in sinks {
+                sink.fetch_failed(&key, &error);
+            }
+        }
+        SinkMessage::Flush(done) => {
+            let _ = done.send(());
+        }
+    }
+}
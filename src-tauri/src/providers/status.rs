@@ -0,0 +1,52 @@
+//! Shared provider status feeds
+//!
+//! Where a provider's incidents come from is its descriptor's `status_source`. Feeds
+//! shared by several vendors are fetched here; providers that report status themselves
+//! are asked through their fetcher by the registry.
+
+use super::{antigravity, kiro, ProviderStatus, StatusSource};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+const GOOGLE_WORKSPACE_STATUS_URL: &str = "https://www.google.com/appsstatus/json/en";
+const AWS_HEALTH_FEED_URL: &str = "https://status.aws.amazon.com/rss/all.rss";
+const STATUS_TIMEOUT_SECS: u64 = 10;
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(STATUS_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+});
+
+/// Fetch and parse the feed `source` names. Sources without a shared feed report no
+/// incident.
+pub(crate) async fn fetch_feed(source: StatusSource) -> Result<ProviderStatus> {
+    match source {
+        StatusSource::None | StatusSource::Fetcher => Ok(ProviderStatus::none()),
+        StatusSource::GoogleWorkspace { product_id } => {
+            let body = get(GOOGLE_WORKSPACE_STATUS_URL, "application/json").await?;
+            antigravity::parse_google_workspace_status(body.as_bytes(), product_id)
+                .map_err(|err| anyhow!(err.to_string()))
+        }
+        StatusSource::AwsHealth => {
+            let body = get(
+                AWS_HEALTH_FEED_URL,
+                "application/rss+xml, text/xml;q=0.9, */*;q=0.8",
+            )
+            .await?;
+            kiro::parse_aws_status_feed(&body).map_err(|err| anyhow!(err.to_string()))
+        }
+    }
+}
+
+async fn get(url: &str, accept: &str) -> Result<String> {
+    let response = CLIENT.get(url).header("Accept", accept).send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(anyhow!("HTTP {}", status));
+    }
+    Ok(body)
+}
@@ -1,19 +1,23 @@
 //! Provider trait definition
 
-use super::{ProviderStatus, UsageSnapshot};
+use super::{ProviderError, ProviderStatus, UsageSnapshot};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 
 /// Trait for implementing a provider fetcher
 #[async_trait]
 pub trait ProviderFetcher: Send + Sync {
     /// Fetch the current usage data for this provider
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error>;
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError>;
 
     /// Fetch provider incident/status information
     async fn fetch_status(&self) -> Result<ProviderStatus, anyhow::Error> {
         Ok(ProviderStatus::none())
     }
 
+    /// Check whether credentials for this provider are available
+    async fn auth_status(&self) -> AuthStatus;
+
     /// Get the provider name for display
     fn name(&self) -> &'static str;
 
@@ -0,0 +1,950 @@
+//! Vertex AI provider implementation
+//!
+//! Uses gcloud Application Default Credentials (`gcloud auth application-default login`).
+//! The project is `VERTEX_AI_PROJECT`, `GOOGLE_CLOUD_PROJECT` or the ADC quota project;
+//! the region is `VERTEX_AI_REGION`, defaulting to `us-central1`. Quota limits come from
+//! the Cloud Quotas API and current usage from Cloud Monitoring; token counts from Cloud
+//! Monitoring are priced into a rolling 30-day cost snapshot.
+
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    format_count, CostSnapshot, ProviderError, ProviderFetcher, ProviderId, ProviderIdentity,
+    RateWindow, UsageSnapshot, USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const CLOUD_QUOTAS_BASE_URL: &str = "https://cloudquotas.googleapis.com";
+const MONITORING_BASE_URL: &str = "https://monitoring.googleapis.com";
+const AIPLATFORM_SERVICE: &str = "aiplatform.googleapis.com";
+const DEFAULT_REGION: &str = "us-central1";
+const ADC_PATH: &str = ".config/gcloud/application_default_credentials.json";
+/// Refresh the access token this many seconds before it expires
+const TOKEN_EXPIRY_MARGIN_SECS: i64 = 60;
+/// Upper bound on paginated API calls per fetch
+const MAX_PAGES: usize = 10;
+
+/// Per-token prices (USD) for Gemini models served by Vertex AI
+struct ModelPricing {
+    input_cost_per_token: f64,
+    output_cost_per_token: f64,
+}
+
+const VERTEX_PRICING: &[(&str, ModelPricing)] = &[
+    (
+        "gemini-2.5-flash-lite",
+        ModelPricing {
+            input_cost_per_token: 0.10e-6,
+            output_cost_per_token: 0.40e-6,
+        },
+    ),
+    (
+        "gemini-2.5-flash",
+        ModelPricing {
+            input_cost_per_token: 0.30e-6,
+            output_cost_per_token: 2.50e-6,
+        },
+    ),
+    (
+        "gemini-2.5-pro",
+        ModelPricing {
+            input_cost_per_token: 1.25e-6,
+            output_cost_per_token: 10.0e-6,
+        },
+    ),
+    (
+        "gemini-2.0-flash-lite",
+        ModelPricing {
+            input_cost_per_token: 0.075e-6,
+            output_cost_per_token: 0.30e-6,
+        },
+    ),
+    (
+        "gemini-2.0-flash",
+        ModelPricing {
+            input_cost_per_token: 0.15e-6,
+            output_cost_per_token: 0.60e-6,
+        },
+    ),
+];
+
+pub struct VertexAiProvider {
+    client: reqwest::Client,
+    /// Account-specific ADC file; `None` uses the gcloud default location
+    credentials_path: Option<PathBuf>,
+    cached_token: Mutex<Option<CachedToken>>,
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: i64,
+}
+
+impl VertexAiProvider {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            credentials_path: None,
+            cached_token: Mutex::new(None),
+        }
+    }
+
+    /// Create a provider for an additional account whose ADC file lives at `path`
+    pub fn with_credentials_path(path: PathBuf) -> Self {
+        Self {
+            credentials_path: Some(path),
+            ..Self::new()
+        }
+    }
+
+    fn get_credentials_path(&self) -> Result<PathBuf, anyhow::Error> {
+        if let Some(path) = &self.credentials_path {
+            return Ok(path.clone());
+        }
+
+        if let Ok(path) = std::env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            if !path.trim().is_empty() {
+                return Ok(PathBuf::from(path.trim()));
+            }
+        }
+
+        let home = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+        Ok(home.join(ADC_PATH))
+    }
+
+    async fn load_credentials(&self) -> Result<AdcCredentials, anyhow::Error> {
+        let path = self.get_credentials_path()?;
+        if !path.exists() {
+            return Err(anyhow::anyhow!(
+                "Application Default Credentials not found. Run `gcloud auth application-default login`."
+            ));
+        }
+        let content = tokio::fs::read_to_string(&path).await?;
+        let credentials: AdcCredentials = serde_json::from_str(&content)?;
+        if credentials.credential_type.as_deref() != Some("authorized_user") {
+            return Err(anyhow::anyhow!(
+                "Only user Application Default Credentials are supported. Run `gcloud auth application-default login`."
+            ));
+        }
+        Ok(credentials)
+    }
+
+    fn resolve_project(credentials: &AdcCredentials) -> Option<String> {
+        for key in ["VERTEX_AI_PROJECT", "GOOGLE_CLOUD_PROJECT"] {
+            if let Ok(project) = std::env::var(key) {
+                if !project.trim().is_empty() {
+                    return Some(project.trim().to_string());
+                }
+            }
+        }
+        credentials
+            .quota_project_id
+            .clone()
+            .filter(|project| !project.trim().is_empty())
+    }
+
+    fn resolve_region() -> String {
+        std::env::var("VERTEX_AI_REGION")
+            .ok()
+            .map(|region| region.trim().to_string())
+            .filter(|region| !region.is_empty())
+            .unwrap_or_else(|| DEFAULT_REGION.to_string())
+    }
+
+    /// Return a cached access token, refreshing it from the ADC refresh token when needed
+    async fn access_token(&self, credentials: &AdcCredentials) -> Result<String, anyhow::Error> {
+        let mut cached = self.cached_token.lock().await;
+        let now = Utc::now().timestamp();
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at - TOKEN_EXPIRY_MARGIN_SECS > now {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let (client_id, client_secret, refresh_token) = match (
+            credentials.client_id.as_deref(),
+            credentials.client_secret.as_deref(),
+            credentials.refresh_token.as_deref(),
+        ) {
+            (Some(id), Some(secret), Some(token)) => (id, secret, token),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Application Default Credentials are missing a refresh token. Run `gcloud auth application-default login`."
+                ))
+            }
+        };
+
+        tracing::debug!("Refreshing Vertex AI access token");
+        let response = self
+            .client
+            .post(endpoint(ProviderId::Vertex, TOKEN_URL))
+            .form(&[
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+                ("refresh_token", refresh_token),
+                ("grant_type", "refresh_token"),
+            ])
+            .send_recorded(ProviderId::Vertex)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            *cached = None;
+            return Err(ProviderError::TokenExpired(format!(
+                "Token refresh failed ({}). Run `gcloud auth application-default login`.",
+                status
+            ))
+            .into());
+        }
+
+        let token: TokenResponse = response.json().await?;
+        let refreshed = CachedToken {
+            access_token: token.access_token,
+            expires_at: now + token.expires_in.unwrap_or(3600),
+        };
+        *cached = Some(refreshed.clone());
+        Ok(refreshed.access_token)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        access_token: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, anyhow::Error> {
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .query(query)
+            .send_recorded(ProviderId::Vertex)
+            .await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            *self.cached_token.lock().await = None;
+            return Err(ProviderError::TokenExpired(
+                "Vertex AI access token rejected. Run `gcloud auth application-default login`."
+                    .to_string(),
+            )
+            .into());
+        }
+        if status == reqwest::StatusCode::FORBIDDEN {
+            let message =
+                "Permission denied. Enable the Cloud Quotas and Cloud Monitoring APIs for this project."
+                    .to_string();
+            return Err(ProviderError::Http {
+                status: status.as_u16(),
+                message,
+            }
+            .into());
+        }
+        if !status.is_success() {
+            let message = format!("Vertex AI API error: HTTP {}", status);
+            return Err(ProviderError::from_status(status, message).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    async fn fetch_quota_infos(
+        &self,
+        access_token: &str,
+        project: &str,
+    ) -> Result<Vec<QuotaInfo>, anyhow::Error> {
+        let url = format!(
+            "{}/v1/projects/{}/locations/global/services/{}/quotaInfos",
+            endpoint(ProviderId::Vertex, CLOUD_QUOTAS_BASE_URL),
+            project,
+            AIPLATFORM_SERVICE
+        );
+        let mut infos = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![("pageSize", "500".to_string())];
+            if let Some(token) = &page_token {
+                query.push(("pageToken", token.clone()));
+            }
+            let page: QuotaInfosResponse = self.get_json(&url, access_token, &query).await?;
+            infos.extend(page.quota_infos);
+            match page.next_page_token.filter(|token| !token.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(infos)
+    }
+
+    async fn fetch_time_series(
+        &self,
+        access_token: &str,
+        project: &str,
+        filter: String,
+        lookback: chrono::Duration,
+        alignment_secs: i64,
+        group_by: &[&str],
+    ) -> Result<Vec<TimeSeries>, anyhow::Error> {
+        let url = format!(
+            "{}/v3/projects/{}/timeSeries",
+            endpoint(ProviderId::Vertex, MONITORING_BASE_URL),
+            project
+        );
+        let end = Utc::now();
+        let start = end - lookback;
+        let mut series = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("filter", filter.clone()),
+                (
+                    "interval.startTime",
+                    start.to_rfc3339_opts(SecondsFormat::Secs, true),
+                ),
+                (
+                    "interval.endTime",
+                    end.to_rfc3339_opts(SecondsFormat::Secs, true),
+                ),
+                (
+                    "aggregation.alignmentPeriod",
+                    format!("{}s", alignment_secs),
+                ),
+                ("aggregation.perSeriesAligner", "ALIGN_SUM".to_string()),
+            ];
+            if !group_by.is_empty() {
+                query.push(("aggregation.crossSeriesReducer", "REDUCE_SUM".to_string()));
+                for field in group_by {
+                    query.push(("aggregation.groupByFields", field.to_string()));
+                }
+            }
+            if let Some(token) = &page_token {
+                query.push(("pageToken", token.clone()));
+            }
+            let page: TimeSeriesResponse = self.get_json(&url, access_token, &query).await?;
+            series.extend(page.time_series);
+            match page.next_page_token.filter(|token| !token.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(series)
+    }
+
+    async fn fetch_quota_usage(
+        &self,
+        access_token: &str,
+        project: &str,
+    ) -> Result<Vec<TimeSeries>, anyhow::Error> {
+        let filter = format!(
+            "metric.type=\"serviceruntime.googleapis.com/quota/rate/net_usage\" AND resource.type=\"consumer_quota\" AND resource.label.service=\"{}\"",
+            AIPLATFORM_SERVICE
+        );
+        self.fetch_time_series(
+            access_token,
+            project,
+            filter,
+            chrono::Duration::minutes(5),
+            60,
+            &[],
+        )
+        .await
+    }
+
+    async fn fetch_token_counts(
+        &self,
+        access_token: &str,
+        project: &str,
+    ) -> Result<Vec<TimeSeries>, anyhow::Error> {
+        let filter =
+            "metric.type=\"aiplatform.googleapis.com/publisher/online_serving/token_count\""
+                .to_string();
+        self.fetch_time_series(
+            access_token,
+            project,
+            filter,
+            chrono::Duration::days(30),
+            86_400,
+            &["metric.label.type", "resource.label.model_user_id"],
+        )
+        .await
+    }
+
+    async fn fetch_usage(&self) -> Result<UsageSnapshot, anyhow::Error> {
+        let credentials = self.load_credentials().await?;
+        let project = Self::resolve_project(&credentials).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No Google Cloud project configured. Set VERTEX_AI_PROJECT or run `gcloud auth application-default set-quota-project`."
+            )
+        })?;
+        let region = Self::resolve_region();
+        let access_token = self.access_token(&credentials).await?;
+
+        let quota_infos = self.fetch_quota_infos(&access_token, &project).await?;
+        let usage_series = self.fetch_quota_usage(&access_token, &project).await?;
+        let quotas = match_quota_usage(&quota_infos, &usage_series, &region);
+
+        // Token metrics are optional; quotas are still useful without a cost snapshot
+        let cost = match self.fetch_token_counts(&access_token, &project).await {
+            Ok(series) => build_cost_snapshot(&series, Utc::now().date_naive()),
+            Err(err) => {
+                tracing::debug!("Vertex AI token count query failed: {}", err);
+                None
+            }
+        };
+
+        Ok(build_usage_snapshot(quotas, cost, project))
+    }
+}
+
+#[async_trait]
+impl ProviderFetcher for VertexAiProvider {
+    fn name(&self) -> &'static str {
+        "Vertex AI"
+    }
+
+    fn description(&self) -> &'static str {
+        "Google Cloud Vertex AI quotas"
+    }
+
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
+        tracing::debug!("Fetching Vertex AI usage");
+
+        match self.fetch_usage().await {
+            Ok(usage) => Ok(usage),
+            Err(e) => {
+                tracing::warn!("Vertex AI fetch failed: {}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        match self.load_credentials().await {
+            Ok(credentials) if credentials.refresh_token.is_some() => {
+                AuthStatus::authenticated("oauth")
+            }
+            Ok(_) => AuthStatus::unauthenticated(Some(
+                "Application Default Credentials are missing a refresh token".to_string(),
+            )),
+            Err(err) => AuthStatus::unauthenticated(Some(err.to_string())),
+        }
+    }
+}
+
+/// A quota limit joined with its most recent usage
+#[derive(Debug, Clone, PartialEq)]
+struct QuotaUsage {
+    label: String,
+    metric: String,
+    window_minutes: Option<i32>,
+    usage: f64,
+    limit: f64,
+}
+
+impl QuotaUsage {
+    fn used_percent(&self) -> f64 {
+        if self.limit <= 0.0 {
+            0.0
+        } else {
+            self.usage / self.limit * 100.0
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        if self.metric.contains("token") {
+            "tokens"
+        } else {
+            "requests"
+        }
+    }
+
+    fn to_rate_window(&self) -> RateWindow {
+        RateWindow {
+            id: Some(self.metric.clone()),
+            category: None,
+            used_percent: self.used_percent(),
+            used: Some(self.usage),
+            limit: Some(self.limit),
+            unit: Some(self.unit().to_string()),
+            window_minutes: self.window_minutes,
+            resets_at: None,
+            reset_description: Some(format!(
+                "{} / {}",
+                format_count(self.usage),
+                format_count(self.limit)
+            )),
+            label: Some(self.label.clone()),
+        }
+    }
+}
+
+fn refresh_interval_minutes(interval: Option<&str>) -> Option<i32> {
+    match interval? {
+        "minute" => Some(1),
+        "hour" => Some(60),
+        "day" => Some(1440),
+        _ => None,
+    }
+}
+
+/// Join quota limits for `region` with the latest usage point of the matching series
+fn match_quota_usage(infos: &[QuotaInfo], series: &[TimeSeries], region: &str) -> Vec<QuotaUsage> {
+    let mut quotas = Vec::new();
+
+    for info in infos {
+        for dimensions_info in &info.dimensions_infos {
+            let applies_to_region = match dimensions_info.dimensions.get("region") {
+                Some(value) => value == region,
+                None => dimensions_info
+                    .applicable_locations
+                    .iter()
+                    .any(|location| location == region || location == "global"),
+            };
+            if !applies_to_region {
+                continue;
+            }
+            let Some(limit) = dimensions_info
+                .details
+                .as_ref()
+                .and_then(|details| details.value.as_deref())
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|limit| *limit > 0.0)
+            else {
+                continue;
+            };
+
+            let usage: f64 = series
+                .iter()
+                .filter(|series| series.metric.labels.get("quota_metric") == Some(&info.metric))
+                .filter(|series| {
+                    series
+                        .resource
+                        .labels
+                        .get("location")
+                        .is_none_or(|location| location == region || location == "global")
+                })
+                .filter(|series| {
+                    dimensions_info
+                        .dimensions
+                        .iter()
+                        .filter(|(key, _)| key.as_str() != "region")
+                        .all(|(key, value)| series.metric.labels.get(key) == Some(value))
+                })
+                .filter_map(|series| series.points.first().map(Point::numeric_value))
+                .sum();
+
+            let dimension_suffix: Vec<&str> = dimensions_info
+                .dimensions
+                .iter()
+                .filter(|(key, _)| key.as_str() != "region")
+                .map(|(_, value)| value.as_str())
+                .collect();
+            let base_label = info
+                .quota_display_name
+                .clone()
+                .unwrap_or_else(|| info.quota_id.clone());
+            let label = if dimension_suffix.is_empty() {
+                base_label
+            } else {
+                format!("{} ({})", base_label, dimension_suffix.join(", "))
+            };
+
+            quotas.push(QuotaUsage {
+                label,
+                metric: info.metric.clone(),
+                window_minutes: refresh_interval_minutes(info.refresh_interval.as_deref()),
+                usage,
+                limit,
+            });
+        }
+    }
+
+    quotas
+}
+
+fn build_cost_snapshot(series: &[TimeSeries], today: chrono::NaiveDate) -> Option<CostSnapshot> {
+    if series.is_empty() {
+        return None;
+    }
+
+    let mut snapshot = CostSnapshot {
+        today_amount: 0.0,
+        today_tokens: 0,
+        month_amount: 0.0,
+        month_tokens: 0,
+        currency: "USD".to_string(),
+    };
+
+    for entry in series {
+        let model = entry
+            .resource
+            .labels
+            .get("model_user_id")
+            .map(String::as_str)
+            .unwrap_or_default();
+        let is_output = entry.metric.labels.get("type").map(String::as_str) == Some("output");
+        let price = model_pricing(model).map(|pricing| {
+            if is_output {
+                pricing.output_cost_per_token
+            } else {
+                pricing.input_cost_per_token
+            }
+        });
+
+        for point in &entry.points {
+            let tokens = point.numeric_value().max(0.0);
+            let amount = price.map(|price| tokens * price).unwrap_or(0.0);
+            snapshot.month_tokens += tokens as u64;
+            snapshot.month_amount += amount;
+            let is_today = point
+                .interval
+                .end_time
+                .as_deref()
+                .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.with_timezone(&Utc).date_naive() == today)
+                .unwrap_or(false);
+            if is_today {
+                snapshot.today_tokens += tokens as u64;
+                snapshot.today_amount += amount;
+            }
+        }
+    }
+
+    Some(snapshot)
+}
+
+fn model_pricing(model: &str) -> Option<&'static ModelPricing> {
+    let model = model.trim().to_lowercase();
+    let model = model.split('@').next().unwrap_or_default();
+    VERTEX_PRICING
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, pricing)| pricing)
+}
+
+fn build_usage_snapshot(
+    mut quotas: Vec<QuotaUsage>,
+    cost: Option<CostSnapshot>,
+    project: String,
+) -> UsageSnapshot {
+    quotas.sort_by(|a, b| {
+        b.used_percent()
+            .partial_cmp(&a.used_percent())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let is_token_quota = |quota: &QuotaUsage| quota.metric.contains("token");
+    let primary = quotas.iter().find(|quota| !is_token_quota(quota));
+    let secondary = quotas.iter().find(|quota| is_token_quota(quota));
+    let tertiary = quotas
+        .iter()
+        .find(|quota| Some(*quota) != primary && Some(*quota) != secondary);
+
+    UsageSnapshot {
+        primary: primary.map(QuotaUsage::to_rate_window),
+        secondary: secondary.map(QuotaUsage::to_rate_window),
+        tertiary: tertiary.map(QuotaUsage::to_rate_window),
+        credits: None,
+        cost,
+        identity: Some(ProviderIdentity {
+            email: None,
+            name: None,
+            plan: None,
+            organization: Some(project),
+        }),
+        updated_at: Utc::now().to_rfc3339(),
+        error: None,
+        retry_at: None,
+        windows: quotas.iter().map(QuotaUsage::to_rate_window).collect(),
+        forecasts: Vec::new(),
+        version: USAGE_SNAPSHOT_VERSION,
+    }
+}
+
+// ============== API Types ==============
+
+#[derive(Debug, Deserialize)]
+struct AdcCredentials {
+    #[serde(rename = "type")]
+    credential_type: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    refresh_token: Option<String>,
+    quota_project_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuotaInfosResponse {
+    #[serde(default)]
+    quota_infos: Vec<QuotaInfo>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuotaInfo {
+    quota_id: String,
+    metric: String,
+    quota_display_name: Option<String>,
+    refresh_interval: Option<String>,
+    #[serde(default)]
+    dimensions_infos: Vec<DimensionsInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DimensionsInfo {
+    #[serde(default)]
+    dimensions: HashMap<String, String>,
+    details: Option<QuotaDetails>,
+    #[serde(default)]
+    applicable_locations: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QuotaDetails {
+    value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeSeriesResponse {
+    #[serde(default)]
+    time_series: Vec<TimeSeries>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeSeries {
+    #[serde(default)]
+    metric: Labels,
+    #[serde(default)]
+    resource: Labels,
+    /// Newest point first, as returned by Cloud Monitoring
+    #[serde(default)]
+    points: Vec<Point>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Labels {
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Point {
+    interval: PointInterval,
+    value: PointValue,
+}
+
+impl Point {
+    fn numeric_value(&self) -> f64 {
+        self.value
+            .int64_value
+            .as_deref()
+            .and_then(|value| value.parse::<f64>().ok())
+            .or(self.value.double_value)
+            .unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointInterval {
+    end_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointValue {
+    /// Cloud Monitoring encodes int64 values as JSON strings
+    int64_value: Option<String>,
+    double_value: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::fixtures::ReplayServer;
+
+    const QUOTA_INFOS: &str = r#"{
+        "quotaInfos": [
+            {
+                "quotaId": "GenerateContentRequestsPerMinutePerProjectPerRegion",
+                "metric": "aiplatform.googleapis.com/generate_content_requests_per_minute_per_project_per_region",
+                "quotaDisplayName": "Generate content requests per minute",
+                "refreshInterval": "minute",
+                "dimensionsInfos": [
+                    {"dimensions": {"region": "us-central1"}, "details": {"value": "200"}},
+                    {"dimensions": {"region": "europe-west4"}, "details": {"value": "50"}}
+                ]
+            },
+            {
+                "quotaId": "GenerateContentInputTokensPerMinute",
+                "metric": "aiplatform.googleapis.com/generate_content_input_tokens_per_minute_per_base_model",
+                "quotaDisplayName": "Input tokens per minute",
+                "refreshInterval": "minute",
+                "dimensionsInfos": [
+                    {
+                        "dimensions": {"region": "us-central1", "base_model": "gemini-2.5-pro"},
+                        "details": {"value": "1000000"}
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    const QUOTA_USAGE: &str = r#"{
+        "timeSeries": [
+            {
+                "metric": {"labels": {"quota_metric": "aiplatform.googleapis.com/generate_content_requests_per_minute_per_project_per_region"}},
+                "resource": {"labels": {"location": "us-central1"}},
+                "points": [{"interval": {"endTime": "2025-01-15T10:00:00Z"}, "value": {"int64Value": "50"}}]
+            },
+            {
+                "metric": {"labels": {"quota_metric": "aiplatform.googleapis.com/generate_content_input_tokens_per_minute_per_base_model", "base_model": "gemini-2.5-pro"}},
+                "resource": {"labels": {"location": "us-central1"}},
+                "points": [{"interval": {"endTime": "2025-01-15T10:00:00Z"}, "value": {"int64Value": "750000"}}]
+            }
+        ]
+    }"#;
+
+    fn token_counts(today: &str) -> String {
+        format!(
+            r#"{{
+                "timeSeries": [
+                    {{
+                        "metric": {{"labels": {{"type": "input"}}}},
+                        "resource": {{"labels": {{"model_user_id": "gemini-2.5-pro"}}}},
+                        "points": [
+                            {{"interval": {{"endTime": "{today}"}}, "value": {{"int64Value": "1000000"}}}},
+                            {{"interval": {{"endTime": "2000-01-01T00:00:00Z"}}, "value": {{"int64Value": "1000000"}}}}
+                        ]
+                    }},
+                    {{
+                        "metric": {{"labels": {{"type": "output"}}}},
+                        "resource": {{"labels": {{"model_user_id": "unknown-model"}}}},
+                        "points": [
+                            {{"interval": {{"endTime": "{today}"}}, "value": {{"int64Value": "500"}}}}
+                        ]
+                    }}
+                ]
+            }}"#
+        )
+    }
+
+    fn write_adc(dir: &tempfile::TempDir) -> PathBuf {
+        let path = dir.path().join("application_default_credentials.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "type": "authorized_user",
+                "client_id": "client",
+                "client_secret": "secret",
+                "refresh_token": "refresh",
+                "quota_project_id": "adc-project"
+            }"#,
+        )
+        .expect("write adc");
+        path
+    }
+
+    #[test]
+    fn matches_quota_limits_with_usage_for_region() {
+        let infos: QuotaInfosResponse = serde_json::from_str(QUOTA_INFOS).unwrap();
+        let usage: TimeSeriesResponse = serde_json::from_str(QUOTA_USAGE).unwrap();
+
+        let quotas = match_quota_usage(&infos.quota_infos, &usage.time_series, "us-central1");
+
+        assert_eq!(quotas.len(), 2);
+        assert_eq!(quotas[0].label, "Generate content requests per minute");
+        assert_eq!(quotas[0].limit, 200.0);
+        assert_eq!(quotas[0].usage, 50.0);
+        assert_eq!(quotas[0].window_minutes, Some(1));
+        assert_eq!(quotas[1].label, "Input tokens per minute (gemini-2.5-pro)");
+        assert_eq!(quotas[1].used_percent(), 75.0);
+    }
+
+    #[test]
+    fn prices_known_models_and_splits_today() {
+        let today = Utc::now().to_rfc3339();
+        let series: TimeSeriesResponse = serde_json::from_str(&token_counts(&today)).unwrap();
+
+        let cost = build_cost_snapshot(&series.time_series, Utc::now().date_naive()).unwrap();
+
+        assert_eq!(cost.month_tokens, 2_000_500);
+        assert_eq!(cost.today_tokens, 1_000_500);
+        assert!((cost.month_amount - 2.5).abs() < 1e-9);
+        assert!((cost.today_amount - 1.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn model_pricing_prefers_the_most_specific_prefix() {
+        let lite = model_pricing("gemini-2.5-flash-lite@001").unwrap();
+        assert_eq!(lite.input_cost_per_token, 0.10e-6);
+        assert!(model_pricing("claude-sonnet-4").is_none());
+    }
+
+    #[tokio::test]
+    async fn replays_adc_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        // Created before routing: endpoints are resolved for each request
+        let provider = VertexAiProvider::with_credentials_path(write_adc(&dir));
+        let server = ReplayServer::from_fixture("vertexai-adc")
+            .await
+            .route(ProviderId::Vertex);
+
+        let usage = provider.fetch().await.expect("fetch");
+
+        let primary = usage.primary.expect("primary");
+        assert_eq!(primary.used_percent, 25.0);
+        assert_eq!(
+            primary.label.as_deref(),
+            Some("Generate content requests per minute")
+        );
+        let secondary = usage.secondary.expect("secondary");
+        assert_eq!(secondary.used_percent, 75.0);
+        assert!(usage.cost.is_some());
+        let identity = usage.identity.expect("identity");
+        assert_eq!(identity.organization.as_deref(), Some("adc-project"));
+        assert_eq!(identity.plan, None);
+        assert!(server.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn auth_status_requires_adc_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = VertexAiProvider::with_credentials_path(dir.path().join("missing.json"));
+        assert!(!missing.auth_status().await.authenticated);
+
+        let present = VertexAiProvider::with_credentials_path(write_adc(&dir));
+        let status = present.auth_status().await;
+        assert!(status.authenticated);
+        assert_eq!(status.method.as_deref(), Some("oauth"));
+    }
+}
@@ -3,7 +3,13 @@
 //! Uses API token authentication via Z_AI_API_KEY environment variable
 //! or stored in keychain/settings.
 
-use super::{ProviderFetcher, ProviderIdentity, RateWindow, UsageSnapshot};
+use super::endpoints::endpoint;
+use super::fixtures::RecordingSend;
+use super::{
+    ProviderError, ProviderFetcher, ProviderId, ProviderIdentity, RateWindow, UsageSnapshot,
+    USAGE_SNAPSHOT_VERSION,
+};
+use crate::login::AuthStatus;
 use async_trait::async_trait;
 use serde::Deserialize;
 use crate::storage::keyring::KeyringError;
@@ -133,13 +139,14 @@ impl ZaiProvider {
         }
 
         // Use region default
-        self.region.quota_url()
+        endpoint(ProviderId::Zai, &self.region.quota_url())
     }
 
     async fn fetch_usage(&self) -> Result<UsageSnapshot, anyhow::Error> {
         let api_key = self.resolve_api_token().ok_or_else(|| {
-            anyhow::anyhow!(
+            ProviderError::NotAuthenticated(
                 "z.ai API token not found. Set Z_AI_API_KEY or store a keychain token."
+                    .to_string(),
             )
         })?;
 
@@ -151,17 +158,14 @@ impl ZaiProvider {
             .get(&url)
             .header("authorization", format!("Bearer {}", api_key))
             .header("accept", "application/json")
-            .send()
+            .send_recorded(ProviderId::Zai)
             .await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!(
-                "z.ai API error: HTTP {} - {}",
-                status,
-                body
-            ));
+            let message = format!("z.ai API error: HTTP {} - {}", status, body);
+            return Err(ProviderError::from_status(status, message).into());
         }
 
         let api_response: ZaiQuotaResponse = response.json().await?;
@@ -213,6 +217,10 @@ impl ZaiProvider {
             }),
             updated_at: chrono::Utc::now().to_rfc3339(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -226,8 +234,20 @@ impl ZaiProvider {
         });
         let reset_description = self.window_label(limit);
 
+        let counts = self.compute_counts(limit);
+        let unit = if limit.limit_type == "TOKENS_LIMIT" {
+            "tokens"
+        } else {
+            "prompts"
+        };
+
         RateWindow {
+            id: Some(limit.limit_type.to_lowercase().replace('_', "-")),
+            category: None,
             used_percent,
+            used: counts.map(|(used, _)| used),
+            limit: counts.map(|(_, total)| total),
+            unit: counts.map(|_| unit.to_string()),
             window_minutes,
             resets_at: reset_time,
             reset_description,
@@ -241,24 +261,29 @@ impl ZaiProvider {
 
     fn compute_used_percent(&self, limit: &ZaiLimitRaw) -> f64 {
         // If we have usage data, compute from remaining/usage
-        if limit.usage > 0 {
-            let total = limit.usage.max(0);
-            if total > 0 {
-                let used_from_remaining = total - limit.remaining;
-                let used = limit
-                    .current_value
-                    .max(used_from_remaining)
-                    .min(total)
-                    .max(0);
-                let percent = (used as f64 / total as f64) * 100.0;
-                return percent.min(100.0).max(0.0);
-            }
+        if let Some((used, total)) = self.compute_counts(limit) {
+            return (used / total * 100.0).min(100.0).max(0.0);
         }
 
         // Fall back to API-provided percentage
         limit.percentage as f64
     }
 
+    /// Used and total, when the limit reports usage
+    fn compute_counts(&self, limit: &ZaiLimitRaw) -> Option<(f64, f64)> {
+        let total = limit.usage.max(0);
+        if total == 0 {
+            return None;
+        }
+        let used_from_remaining = total - limit.remaining;
+        let used = limit
+            .current_value
+            .max(used_from_remaining)
+            .min(total)
+            .max(0);
+        Some((used as f64, total as f64))
+    }
+
     fn compute_window_minutes(&self, limit: &ZaiLimitRaw) -> Option<i32> {
         if limit.number <= 0 {
             return None;
@@ -362,9 +387,17 @@ impl ProviderFetcher for ZaiProvider {
         "z.ai AI Assistant"
     }
 
-    async fn fetch(&self) -> Result<UsageSnapshot, anyhow::Error> {
+    async fn fetch(&self) -> Result<UsageSnapshot, ProviderError> {
         tracing::debug!("Fetching z.ai usage");
-        self.fetch_usage().await
+        Ok(self.fetch_usage().await?)
+    }
+
+    async fn auth_status(&self) -> AuthStatus {
+        if self.resolve_api_token().is_some() {
+            AuthStatus::authenticated("api_key")
+        } else {
+            AuthStatus::unauthenticated(Some("Set Z_AI_API_KEY environment variable".to_string()))
+        }
     }
 }
 
@@ -0,0 +1,356 @@
+//! Localhost JSON API
+//!
+//! An opt-in server on 127.0.0.1 for editors, scripts and status bars. Every request
+//! must carry `Authorization: Bearer <token>`, where the token is the content of
+//! `api-token` in the data directory.
+//!
+//! - `GET /v1/usage`: cached usage of every account, keyed by provider slug for default
+//!   accounts and `slug:account` for the others, such as custom providers and plugins
+//! - `GET /v1/usage/{provider}`: cached usage of one provider's default account. Other
+//!   accounts are addressed as `/v1/usage/{provider}/{account}` or by their `/v1/usage` key
+//! - `GET /v1/status`: the last status fetched for each provider, keyed like `/v1/usage`
+//!   so plugins, which report their own, appear as `plugin:<id>`
+//! - `POST /v1/refresh/{provider}`: refresh exactly like the `refresh_provider` command,
+//!   taking an account the same way as `/v1/usage/{provider}`
+//! - `GET /v1/events`: `usage-updated` and `refresh-failed` events, as server-sent events
+
+use super::{Request, Response, ServerEvent, ServerSlot};
+use crate::commands;
+use crate::providers::{
+    AccountKey, ProviderError, ProviderId, ProviderRegistry, ProviderStatus, UsageSink,
+    UsageSnapshot,
+};
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use rand::rngs::OsRng;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+
+/// Port the API listens on unless the user picks another
+pub const DEFAULT_API_PORT: u16 = 9465;
+const APP_DIR_NAME: &str = "IncuBar";
+const TOKEN_FILENAME: &str = "api-token";
+/// Events buffered for a client that reads slowly
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+static SERVER: Lazy<ServerSlot> = Lazy::new(ServerSlot::default);
+static EVENTS: Lazy<broadcast::Sender<ServerEvent>> =
+    Lazy::new(|| broadcast::channel(EVENT_CHANNEL_CAPACITY).0);
+
+/// Streams every reported fetch result to `/v1/events` clients, in the same shape as
+/// the webview's `usage-updated` and `refresh-failed` events
+pub struct ApiEventSink;
+
+impl ApiEventSink {
+    fn send(&self, name: &'static str, payload: serde_json::Value) {
+        if EVENTS.receiver_count() == 0 {
+            return;
+        }
+        let _ = EVENTS.send(ServerEvent {
+            name,
+            data: payload.to_string(),
+        });
+    }
+}
+
+impl UsageSink for ApiEventSink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        self.send(
+            "usage-updated",
+            serde_json::json!({
+                "providerId": key.provider_id,
+                "accountId": key.account_id,
+                "usage": usage,
+            }),
+        );
+    }
+
+    fn refresh_failed(&self, key: &AccountKey, error: &ProviderError, usage: &UsageSnapshot) {
+        self.send(
+            "refresh-failed",
+            serde_json::json!({
+                "providerId": key.provider_id,
+                "accountId": key.account_id,
+                "usage": usage,
+                "errorKind": error.kind(),
+            }),
+        );
+    }
+}
+
+/// Start the API on `port`, move it there, or stop it
+pub async fn set_api_server(app: AppHandle, enabled: bool, port: u16) -> Result<()> {
+    let token = if enabled {
+        let path = token_path()?;
+        Arc::new(
+            load_or_create_token(&path)
+                .with_context(|| format!("Failed to prepare {}", path.display()))?,
+        )
+    } else {
+        Arc::default()
+    };
+    SERVER
+        .set("Local API", enabled, port, || {
+            move |request| {
+                let app = app.clone();
+                let token = token.clone();
+                async move { handle(&app, &token, request).await }
+            }
+        })
+        .await
+}
+
+fn token_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("Could not determine data directory")?;
+    Ok(data_dir.join(APP_DIR_NAME).join(TOKEN_FILENAME))
+}
+
+/// The token in `path`, or a new random one written there, readable by the user only
+fn load_or_create_token(path: &Path) -> Result<String> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+    let bytes: [u8; 32] = OsRng.gen();
+    let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    std::io::Write::write_all(&mut file, token.as_bytes())?;
+    Ok(token)
+}
+
+fn authorized(request: &Request, token: &str) -> bool {
+    let Some(given) = request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+    else {
+        return false;
+    };
+    // Compare every byte, so the time taken does not give the token away
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Usage(Option<AccountKey>),
+    Status,
+    Refresh(AccountKey),
+    Events,
+    UnknownProvider(String),
+    MethodNotAllowed,
+    NotFound,
+}
+
+fn route(method: &str, path: &str) -> Route {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let (expected, route) = match segments.as_slice() {
+        ["v1", "usage"] => ("GET", Route::Usage(None)),
+        ["v1", "status"] => ("GET", Route::Status),
+        ["v1", "events"] => ("GET", Route::Events),
+        ["v1", action @ ("usage" | "refresh"), provider, account @ ..] if account.len() <= 1 => {
+            let (provider, account) = match account.first() {
+                Some(account) => (*provider, Some(*account)),
+                None => match provider.split_once(':') {
+                    Some((provider, account)) => (provider, Some(account)),
+                    None => (*provider, None),
+                },
+            };
+            let Some(provider_id) = ProviderId::from_slug(provider) else {
+                return Route::UnknownProvider(provider.to_string());
+            };
+            let key = AccountKey::from_optional(provider_id, account.map(str::to_string));
+            if *action == "usage" {
+                ("GET", Route::Usage(Some(key)))
+            } else {
+                ("POST", Route::Refresh(key))
+            }
+        }
+        _ => return Route::NotFound,
+    };
+    if method == expected {
+        route
+    } else {
+        Route::MethodNotAllowed
+    }
+}
+
+fn error(status: u16, message: impl Into<String>) -> Response {
+    Response::json(status, &serde_json::json!({ "error": message.into() }))
+}
+
+async fn handle(app: &AppHandle, token: &str, request: Request) -> Response {
+    if !authorized(&request, token) {
+        return error(401, "Missing or invalid bearer token");
+    }
+    let registry = app.state::<ProviderRegistry>();
+    match route(&request.method, &request.path) {
+        Route::Usage(None) => {
+            let usage: HashMap<String, UsageSnapshot> = registry
+                .get_all_cached_usage()
+                .await
+                .into_iter()
+                .map(|(key, usage)| (key.to_string(), usage.validated()))
+                .collect();
+            Response::json(200, &usage)
+        }
+        Route::Usage(Some(key)) => match registry.get_cached_usage(&key).await {
+            Some(usage) => Response::json(200, &usage.validated()),
+            None => error(404, format!("No usage cached for {}", key)),
+        },
+        Route::Status => {
+            let statuses: HashMap<String, ProviderStatus> = registry
+                .get_cached_statuses()
+                .await
+                .into_iter()
+                .map(|(key, status)| (key.to_string(), status))
+                .collect();
+            Response::json(200, &statuses)
+        }
+        Route::Refresh(key) => {
+            let account_id = Some(key.account_id);
+            match commands::refresh_provider(
+                key.provider_id,
+                account_id,
+                None,
+                registry,
+                app.clone(),
+            )
+            .await
+            {
+                Ok(usage) => Response::json(200, &usage.validated()),
+                Err(err) => error(502, err.message),
+            }
+        }
+        Route::Events => Response::events(EVENTS.subscribe()),
+        Route::UnknownProvider(provider) => error(404, format!("Unknown provider {}", provider)),
+        Route::MethodNotAllowed => error(405, "Method not allowed"),
+        Route::NotFound => error(404, "Not found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn request(authorization: Option<&str>) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: "/v1/usage".to_string(),
+            headers: authorization
+                .map(|value| ("authorization".to_string(), value.to_string()))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route("GET", "/v1/usage"), Route::Usage(None));
+        assert_eq!(
+            route("GET", "/v1/usage/claude/"),
+            Route::Usage(Some(AccountKey::default_for(ProviderId::Claude)))
+        );
+        assert_eq!(
+            route("GET", "/v1/usage/custom/gateway"),
+            Route::Usage(Some(AccountKey::new(ProviderId::Custom, "gateway")))
+        );
+        assert_eq!(
+            route("GET", "/v1/usage/claude:work"),
+            Route::Usage(Some(AccountKey::new(ProviderId::Claude, "work")))
+        );
+        assert_eq!(route("GET", "/v1/status"), Route::Status);
+        assert_eq!(route("GET", "/v1/events"), Route::Events);
+        assert_eq!(
+            route("POST", "/v1/refresh/codex"),
+            Route::Refresh(AccountKey::default_for(ProviderId::Codex))
+        );
+        assert_eq!(
+            route("POST", "/v1/refresh/plugin:mirror"),
+            Route::Refresh(AccountKey::new(ProviderId::Plugin, "mirror"))
+        );
+        assert_eq!(
+            route("POST", "/v1/refresh/codex/work"),
+            Route::Refresh(AccountKey::new(ProviderId::Codex, "work"))
+        );
+        assert_eq!(route("GET", "/v1/refresh/codex"), Route::MethodNotAllowed);
+        assert_eq!(route("POST", "/v1/usage"), Route::MethodNotAllowed);
+        assert_eq!(
+            route("GET", "/v1/usage/nope"),
+            Route::UnknownProvider("nope".to_string())
+        );
+        assert_eq!(
+            route("GET", "/v1/usage/nope:work"),
+            Route::UnknownProvider("nope".to_string())
+        );
+        assert_eq!(route("GET", "/v1/usage/codex/work/extra"), Route::NotFound);
+        assert_eq!(route("GET", "/v2/usage"), Route::NotFound);
+    }
+
+    #[test]
+    fn requires_the_bearer_token() {
+        assert!(authorized(&request(Some("Bearer secret")), "secret"));
+        assert!(!authorized(&request(Some("Bearer secreT")), "secret"));
+        assert!(!authorized(&request(Some("Bearer secret2")), "secret"));
+        assert!(!authorized(&request(Some("secret")), "secret"));
+        assert!(!authorized(&request(None), "secret"));
+    }
+
+    #[test]
+    fn streams_failures_as_refresh_failed() {
+        let mut events = EVENTS.subscribe();
+        let key = AccountKey::new(ProviderId::Custom, "gateway");
+        let error = ProviderError::Forbidden("denied".to_string());
+        ApiEventSink.refresh_failed(&key, &error, &UsageSnapshot::from_error(&error));
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.name, "refresh-failed");
+        let payload: serde_json::Value = serde_json::from_str(&event.data).unwrap();
+        assert_eq!(payload["accountId"], "gateway");
+        assert_eq!(payload["errorKind"], "forbidden");
+        assert!(payload["usage"]["error"].is_string());
+    }
+
+    #[test]
+    fn creates_the_token_once() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("IncuBar").join(TOKEN_FILENAME);
+        let token = load_or_create_token(&path).unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(load_or_create_token(&path).unwrap(), token);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
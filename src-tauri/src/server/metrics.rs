@@ -0,0 +1,469 @@
+//! OpenMetrics exporter
+//!
+//! An opt-in `GET /metrics` endpoint on 127.0.0.1 for Prometheus to scrape. It renders
+//! what the registry already has cached: each account's usage, the last
+//! provider statuses and how many fetches succeeded or failed. A scrape never fetches.
+
+use super::{Request, Response, ServerSlot};
+use crate::providers::{
+    AccountKey, ProviderError, ProviderId, ProviderRegistry, ProviderStatus, RateWindow,
+    StatusIndicator, UsageSink, UsageSnapshot,
+};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Port the exporter listens on unless the user picks another
+pub const DEFAULT_METRICS_PORT: u16 = 9464;
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+const STATUS_INDICATORS: [StatusIndicator; 6] = [
+    StatusIndicator::None,
+    StatusIndicator::Minor,
+    StatusIndicator::Major,
+    StatusIndicator::Critical,
+    StatusIndicator::Maintenance,
+    StatusIndicator::Unknown,
+];
+
+/// Reported fetch results of one account
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FetchCounts {
+    successes: u64,
+    failures: u64,
+    /// `updated_at` of the last success counted, so joined callers count once
+    last_success: Option<String>,
+}
+
+static FETCH_COUNTS: Lazy<Mutex<HashMap<AccountKey, FetchCounts>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static SERVER: Lazy<ServerSlot> = Lazy::new(ServerSlot::default);
+
+/// Counts the fetch results the registry reports, whether or not the exporter runs
+pub struct MetricsSink;
+
+impl UsageSink for MetricsSink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        if let Ok(mut counts) = FETCH_COUNTS.lock() {
+            let counts = counts.entry(key.clone()).or_default();
+            if counts.last_success.as_deref() != Some(usage.updated_at.as_str()) {
+                counts.successes += 1;
+                counts.last_success = Some(usage.updated_at.clone());
+            }
+        }
+    }
+
+    /// Failures the background refresh holds back count too
+    fn fetch_failed(&self, key: &AccountKey, _error: &ProviderError) {
+        if let Ok(mut counts) = FETCH_COUNTS.lock() {
+            counts.entry(key.clone()).or_default().failures += 1;
+        }
+    }
+}
+
+/// Start the exporter on `port`, move it there, or stop it
+pub async fn set_metrics_exporter(app: AppHandle, enabled: bool, port: u16) -> anyhow::Result<()> {
+    SERVER
+        .set("Metrics exporter", enabled, port, || {
+            move |request| {
+                let app = app.clone();
+                async move { handle(&app, request).await }
+            }
+        })
+        .await
+}
+
+async fn handle(app: &AppHandle, request: Request) -> Response {
+    if request.path != "/metrics" {
+        return Response::not_found();
+    }
+    if request.method != "GET" {
+        return Response::method_not_allowed();
+    }
+    let registry = app.state::<ProviderRegistry>();
+    let usage = registry.get_all_cached_usage().await;
+    let statuses = registry.get_cached_statuses().await;
+    let fetches = FETCH_COUNTS
+        .lock()
+        .map(|counts| counts.clone())
+        .unwrap_or_default();
+    Response::new(
+        200,
+        CONTENT_TYPE,
+        render(&usage, &statuses, &fetches, Utc::now()),
+    )
+}
+
+/// One metric family and its samples, written together as OpenMetrics requires
+struct Family {
+    name: &'static str,
+    kind: &'static str,
+    help: &'static str,
+    samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl Family {
+    fn new(name: &'static str, kind: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            kind,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push((labels, value));
+    }
+
+    fn write(&self, out: &mut String) {
+        let _ = writeln!(out, "# TYPE {} {}", self.name, self.kind);
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let suffix = if self.kind == "counter" { "_total" } else { "" };
+        for (labels, value) in &self.samples {
+            let labels = labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = writeln!(
+                out,
+                "{}{}{{{}}} {}",
+                self.name,
+                suffix,
+                labels,
+                number(*value)
+            );
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn number(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn indicator_name(indicator: StatusIndicator) -> &'static str {
+    match indicator {
+        StatusIndicator::None => "none",
+        StatusIndicator::Minor => "minor",
+        StatusIndicator::Major => "major",
+        StatusIndicator::Critical => "critical",
+        StatusIndicator::Maintenance => "maintenance",
+        StatusIndicator::Unknown => "unknown",
+    }
+}
+
+fn provider_label(id: ProviderId) -> (&'static str, String) {
+    ("provider", id.slug().to_string())
+}
+
+fn account_labels(key: &AccountKey) -> Vec<(&'static str, String)> {
+    vec![
+        provider_label(key.provider_id),
+        ("account", key.account_id.clone()),
+    ]
+}
+
+/// A window's id, else its position. Categories repeat, e.g. one model window per
+/// model, so they would not keep samples apart.
+fn window_label(window: &RateWindow, index: usize) -> String {
+    window
+        .id
+        .clone()
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| index.to_string())
+}
+
+/// Accounts ordered by provider slug, then account id
+fn sorted_by_account<T>(entries: &HashMap<AccountKey, T>) -> Vec<(&AccountKey, &T)> {
+    let mut entries: Vec<_> = entries.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| {
+        (a.provider_id.slug(), &a.account_id).cmp(&(b.provider_id.slug(), &b.account_id))
+    });
+    entries
+}
+
+/// The exposition for one scrape, providers in slug order
+pub(crate) fn render(
+    usage: &HashMap<AccountKey, UsageSnapshot>,
+    statuses: &HashMap<AccountKey, ProviderStatus>,
+    fetches: &HashMap<AccountKey, FetchCounts>,
+    now: DateTime<Utc>,
+) -> String {
+    let mut used = Family::new(
+        "incubar_window_used_percent",
+        "gauge",
+        "Percentage of the rate window used.",
+    );
+    let mut resets = Family::new(
+        "incubar_window_reset_seconds",
+        "gauge",
+        "Seconds until the rate window resets.",
+    );
+    let mut credits_remaining = Family::new(
+        "incubar_credits_remaining",
+        "gauge",
+        "Credits left on the account.",
+    );
+    let mut credits_limit = Family::new(
+        "incubar_credits_limit",
+        "gauge",
+        "Credits the account started with.",
+    );
+    let mut cost_amount = Family::new(
+        "incubar_cost_amount",
+        "gauge",
+        "Local usage cost for the period.",
+    );
+    let mut cost_tokens = Family::new("incubar_cost_tokens", "gauge", "Tokens used in the period.");
+    let mut status = Family::new(
+        "incubar_provider_status",
+        "stateset",
+        "Indicator from the provider's status page.",
+    );
+    let mut fetch_results = Family::new(
+        "incubar_fetches",
+        "counter",
+        "Usage fetches reported since IncuBar started.",
+    );
+
+    for (key, usage) in sorted_by_account(usage) {
+        if usage.error.is_some() {
+            continue;
+        }
+        for (index, window) in usage.rate_windows().into_iter().enumerate() {
+            let mut labels = account_labels(key);
+            labels.push(("window", window_label(window, index)));
+            if let Some(label) = &window.label {
+                labels.push(("label", label.clone()));
+            }
+            used.add(labels.clone(), window.used_percent);
+            let reset = window
+                .resets_at
+                .as_deref()
+                .and_then(|resets_at| DateTime::parse_from_rfc3339(resets_at).ok());
+            if let Some(reset) = reset {
+                let seconds = (reset.with_timezone(&Utc) - now).num_seconds().max(0);
+                resets.add(labels, seconds as f64);
+            }
+        }
+        if let Some(credits) = &usage.credits {
+            let mut labels = account_labels(key);
+            labels.push(("unit", credits.unit.clone()));
+            credits_remaining.add(labels.clone(), credits.remaining);
+            if let Some(total) = credits.total {
+                credits_limit.add(labels, total);
+            }
+        }
+        if let Some(cost) = &usage.cost {
+            for (period, amount, tokens) in [
+                ("today", cost.today_amount, cost.today_tokens),
+                ("month", cost.month_amount, cost.month_tokens),
+            ] {
+                let mut labels = account_labels(key);
+                labels.push(("period", period.to_string()));
+                let mut with_currency = labels.clone();
+                with_currency.push(("currency", cost.currency.clone()));
+                cost_amount.add(with_currency, amount);
+                cost_tokens.add(labels, tokens as f64);
+            }
+        }
+    }
+
+    for (key, provider_status) in sorted_by_account(statuses) {
+        for indicator in STATUS_INDICATORS {
+            // Only accounts that report their own status, such as plugins, get an account label
+            let mut labels = if key.is_default() {
+                vec![provider_label(key.provider_id)]
+            } else {
+                account_labels(key)
+            };
+            labels.push((
+                "incubar_provider_status",
+                indicator_name(indicator).to_string(),
+            ));
+            let active = provider_status.indicator == indicator;
+            status.add(labels, if active { 1.0 } else { 0.0 });
+        }
+    }
+
+    for (key, counts) in sorted_by_account(fetches) {
+        for (result, count) in [("success", counts.successes), ("failure", counts.failures)] {
+            let mut labels = account_labels(key);
+            labels.push(("result", result.to_string()));
+            fetch_results.add(labels, count as f64);
+        }
+    }
+
+    let mut out = String::new();
+    for family in [
+        used,
+        resets,
+        credits_remaining,
+        credits_limit,
+        cost_amount,
+        cost_tokens,
+        status,
+        fetch_results,
+    ] {
+        family.write(&mut out);
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{CostSnapshot, Credits, USAGE_SNAPSHOT_VERSION};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn window(id: &str, label: &str, used_percent: f64, resets_at: Option<&str>) -> RateWindow {
+        RateWindow {
+            id: Some(id.to_string()),
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: None,
+            resets_at: resets_at.map(str::to_string),
+            reset_description: None,
+            label: Some(label.to_string()),
+        }
+    }
+
+    fn usage() -> UsageSnapshot {
+        let windows = vec![
+            window("session", "Session", 42.5, Some("2025-06-01T13:30:00Z")),
+            window("weekly", "Weekly \"all\"", 10.0, None),
+        ];
+        UsageSnapshot {
+            primary: windows.first().cloned(),
+            secondary: windows.get(1).cloned(),
+            tertiary: None,
+            credits: Some(Credits {
+                remaining: 12.5,
+                total: Some(50.0),
+                unit: "USD".to_string(),
+            }),
+            cost: Some(CostSnapshot {
+                today_amount: 1.25,
+                today_tokens: 1000,
+                month_amount: 30.0,
+                month_tokens: 250000,
+                currency: "USD".to_string(),
+            }),
+            identity: None,
+            updated_at: now().to_rfc3339(),
+            error: None,
+            retry_at: None,
+            windows,
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
+        }
+    }
+
+    #[test]
+    fn renders_cached_usage_as_openmetrics() {
+        let usage = HashMap::from([
+            (AccountKey::default_for(ProviderId::Claude), usage()),
+            (
+                AccountKey::default_for(ProviderId::Codex),
+                UsageSnapshot::error("offline".to_string()),
+            ),
+            (AccountKey::new(ProviderId::Custom, "gateway"), usage()),
+        ]);
+        let statuses = HashMap::from([(
+            AccountKey::default_for(ProviderId::Claude),
+            ProviderStatus {
+                indicator: StatusIndicator::Minor,
+                description: None,
+                updated_at: None,
+                url: None,
+            },
+        )]);
+        let fetches = HashMap::from([(
+            AccountKey::default_for(ProviderId::Claude),
+            FetchCounts {
+                successes: 3,
+                failures: 1,
+                last_success: None,
+            },
+        )]);
+        let text = render(&usage, &statuses, &fetches, now());
+
+        for line in [
+            "# TYPE incubar_window_used_percent gauge",
+            "incubar_window_used_percent{provider=\"claude\",account=\"default\",window=\"session\",label=\"Session\"} 42.5",
+            "incubar_window_used_percent{provider=\"claude\",account=\"default\",window=\"weekly\",label=\"Weekly \\\"all\\\"\"} 10",
+            "incubar_window_reset_seconds{provider=\"claude\",account=\"default\",window=\"session\",label=\"Session\"} 5400",
+            "incubar_credits_remaining{provider=\"claude\",account=\"default\",unit=\"USD\"} 12.5",
+            "incubar_credits_limit{provider=\"claude\",account=\"default\",unit=\"USD\"} 50",
+            "incubar_cost_amount{provider=\"claude\",account=\"default\",period=\"month\",currency=\"USD\"} 30",
+            "incubar_cost_tokens{provider=\"claude\",account=\"default\",period=\"today\"} 1000",
+            "incubar_window_used_percent{provider=\"custom\",account=\"gateway\",window=\"session\",label=\"Session\"} 42.5",
+            "# TYPE incubar_provider_status stateset",
+            "incubar_provider_status{provider=\"claude\",incubar_provider_status=\"minor\"} 1",
+            "incubar_provider_status{provider=\"claude\",incubar_provider_status=\"none\"} 0",
+            "# TYPE incubar_fetches counter",
+            "incubar_fetches_total{provider=\"claude\",account=\"default\",result=\"success\"} 3",
+            "incubar_fetches_total{provider=\"claude\",account=\"default\",result=\"failure\"} 1",
+        ] {
+            assert!(text.lines().any(|rendered| rendered == line), "missing {line}\n{text}");
+        }
+        assert!(!text.contains("provider=\"codex\""));
+        // No reset time, no reset gauge
+        assert!(
+            !text.contains("incubar_window_reset_seconds{provider=\"claude\",account=\"default\",window=\"weekly\"")
+        );
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn labels_windows_without_an_id_by_position() {
+        let mut snapshot = usage();
+        snapshot.windows[1].id = None;
+        let usage = HashMap::from([(AccountKey::default_for(ProviderId::Claude), snapshot)]);
+        let text = render(&usage, &HashMap::new(), &HashMap::new(), now());
+
+        assert!(text.contains("account=\"default\",window=\"1\",label=\"Weekly"));
+        assert!(!text.contains("window=\"\""));
+    }
+
+    #[test]
+    fn counts_each_fetch_once() {
+        let key = AccountKey::new(ProviderId::Zai, "metrics-test");
+        let sink = MetricsSink;
+        let snapshot = usage();
+        sink.usage_updated(&key, &snapshot);
+        sink.usage_updated(&key, &snapshot);
+        let error = ProviderError::Network("offline".to_string());
+        sink.fetch_failed(&key, &error);
+
+        let counts = FETCH_COUNTS.lock().unwrap().get(&key).cloned().unwrap();
+        assert_eq!(counts.successes, 1);
+        assert_eq!(counts.failures, 1);
+    }
+}
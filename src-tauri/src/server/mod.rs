@@ -0,0 +1,411 @@
+//! Localhost HTTP servers
+//!
+//! The opt-in endpoints other tools read IncuBar's data from. They only ever bind
+//! 127.0.0.1, answer one request per connection and ignore request bodies, so a few
+//! lines of HTTP/1.1 on a `TcpListener` are all they need.
+
+pub mod api;
+pub mod metrics;
+
+use anyhow::Context;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+/// Longest request head a local client should ever send
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+/// Connections that do not finish their request by then are dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Idle event streams send a comment this often, so clients and proxies keep them open
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A parsed request head
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Header names are lowercase
+    pub headers: HashMap<String, String>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// A server-sent event
+#[derive(Debug, Clone)]
+pub(crate) struct ServerEvent {
+    pub name: &'static str,
+    /// A single line, e.g. compact JSON
+    pub data: String,
+}
+
+pub(crate) enum Body {
+    Full(String),
+    /// Server-sent events, until the channel closes or the client hangs up
+    Events(broadcast::Receiver<ServerEvent>),
+}
+
+pub(crate) struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Body,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: Body::Full(body.into()),
+        }
+    }
+
+    pub fn json(status: u16, body: &impl serde::Serialize) -> Self {
+        match serde_json::to_string(body) {
+            Ok(body) => Self::new(status, "application/json", body),
+            Err(err) => Self::text(500, format!("Failed to encode response: {}\n", err)),
+        }
+    }
+
+    pub fn events(events: broadcast::Receiver<ServerEvent>) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/event-stream",
+            body: Body::Events(events),
+        }
+    }
+
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", body)
+    }
+
+    pub fn not_found() -> Self {
+        Self::text(404, "Not found\n")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self::text(405, "Method not allowed\n")
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        _ => "",
+    }
+}
+
+/// A server task listening on a loopback port. Dropping it stops the server and closes
+/// its connections, event streams included.
+pub(crate) struct LocalServer {
+    pub port: u16,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl LocalServer {
+    /// Bind 127.0.0.1:`port` and answer every request with `handler`. Port 0 picks a
+    /// free port.
+    pub async fn start<F, Fut>(port: u16, handler: F) -> std::io::Result<Self>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response> + Send + 'static,
+    {
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await?;
+        let port = listener.local_addr()?.port();
+        let handler = std::sync::Arc::new(handler);
+        let task = tokio::spawn(async move {
+            // Dropped with this task, which aborts every open connection
+            let mut connections = tokio::task::JoinSet::new();
+            loop {
+                let accepted = tokio::select! {
+                    accepted = listener.accept() => accepted,
+                    Some(_) = connections.join_next() => continue,
+                };
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        tracing::warn!("Local server on port {} failed to accept: {}", port, err);
+                        continue;
+                    }
+                };
+                let handler = handler.clone();
+                connections.spawn(async move {
+                    if let Err(err) = serve(stream, |request| handler(request)).await {
+                        tracing::debug!("Local server connection failed: {}", err);
+                    }
+                });
+            }
+        });
+        Ok(Self { port, task })
+    }
+}
+
+/// A server that settings turn on, move to another port, or off
+#[derive(Default)]
+pub(crate) struct ServerSlot {
+    server: tokio::sync::Mutex<Option<LocalServer>>,
+}
+
+impl ServerSlot {
+    /// Run a server on `port` while `enabled`. `handler` is only built when one starts.
+    pub async fn set<F, Fut>(
+        &self,
+        name: &str,
+        enabled: bool,
+        port: u16,
+        handler: impl FnOnce() -> F,
+    ) -> anyhow::Result<()>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response> + Send + 'static,
+    {
+        let mut server = self.server.lock().await;
+        if !enabled {
+            if server.take().is_some() {
+                tracing::info!("{} stopped", name);
+            }
+            return Ok(());
+        }
+        if server.as_ref().is_some_and(|server| server.port == port) {
+            return Ok(());
+        }
+        // Free the old port before binding the new one
+        *server = None;
+        let started = LocalServer::start(port, handler())
+            .await
+            .with_context(|| format!("Failed to listen on 127.0.0.1:{}", port))?;
+        tracing::info!("{} listening on 127.0.0.1:{}", name, started.port);
+        *server = Some(started);
+        Ok(())
+    }
+}
+
+async fn serve<F, Fut>(mut stream: TcpStream, handler: F) -> std::io::Result<()>
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(Some(request))) => handler(request).await,
+        Ok(Ok(None)) => return Ok(()),
+        Ok(Err(err)) if err.kind() == std::io::ErrorKind::InvalidData => {
+            Response::text(400, "Bad request\n")
+        }
+        Ok(Err(err)) => return Err(err),
+        Err(_) => return Ok(()),
+    };
+    write_response(&mut stream, response).await
+}
+
+/// Read a request head. `None` when the client hangs up before sending one.
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(index) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break index;
+        }
+        if buffer.len() > MAX_REQUEST_HEAD {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+    };
+    parse_request(&String::from_utf8_lossy(&buffer[..header_end]))
+        .map(Some)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed request"))
+}
+
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_ascii_uppercase();
+    let target = request_line.next()?;
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    Some(Request {
+        method,
+        path,
+        headers,
+    })
+}
+
+async fn write_response(stream: &mut TcpStream, response: Response) -> std::io::Result<()> {
+    let length = match &response.body {
+        Body::Full(body) => format!("Content-Length: {}\r\n", body.len()),
+        Body::Events(_) => String::new(),
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\n{}Cache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        length
+    );
+    stream.write_all(head.as_bytes()).await?;
+    match response.body {
+        Body::Full(body) => stream.write_all(body.as_bytes()).await?,
+        Body::Events(events) => stream_events(stream, events).await?,
+    }
+    stream.shutdown().await
+}
+
+async fn stream_events(
+    stream: &mut TcpStream,
+    mut events: broadcast::Receiver<ServerEvent>,
+) -> std::io::Result<()> {
+    stream.write_all(b": connected\n\n").await?;
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+    loop {
+        let message = tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => format!("event: {}\ndata: {}\n\n", event.name, event.data),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Event stream fell behind, skipped {} events", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+        stream.write_all(message.as_bytes()).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_heads() {
+        let request = parse_request("get /metrics?x=1 HTTP/1.1\r\nHost: 127.0.0.1:9464").unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/metrics");
+        assert_eq!(request.header("host"), Some("127.0.0.1:9464"));
+
+        assert!(parse_request("GET /metrics").is_none());
+        assert!(parse_request("").is_none());
+    }
+
+    #[tokio::test]
+    async fn serves_loopback_requests() {
+        let server = LocalServer::start(0, |request: Request| async move {
+            Response::text(200, format!("{} {}", request.method, request.path))
+        })
+        .await
+        .unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET /hello HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Length: 10\r\n"));
+        assert!(response.ends_with("\r\n\r\nGET /hello"));
+    }
+
+    #[tokio::test]
+    async fn streams_events_until_the_channel_closes() {
+        let (sender, receiver) = broadcast::channel(4);
+        let receiver = std::sync::Mutex::new(Some(receiver));
+        let server = LocalServer::start(0, move |_: Request| {
+            let events = receiver.lock().unwrap().take().unwrap();
+            async move { Response::events(events) }
+        })
+        .await
+        .unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET /events HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        sender
+            .send(ServerEvent {
+                name: "usage-updated",
+                data: "{\"providerId\":\"claude\"}".to_string(),
+            })
+            .unwrap();
+        drop(sender);
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.contains("Content-Type: text/event-stream\r\n"));
+        assert!(!response.contains("Content-Length"));
+        assert!(response.ends_with("event: usage-updated\ndata: {\"providerId\":\"claude\"}\n\n"));
+    }
+
+    #[tokio::test]
+    async fn stopping_the_server_closes_open_streams() {
+        let (sender, _) = broadcast::channel::<ServerEvent>(4);
+        let events = sender.clone();
+        let server = LocalServer::start(0, move |_: Request| {
+            let events = events.subscribe();
+            async move { Response::events(events) }
+        })
+        .await
+        .unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET /events HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut head = [0u8; 12];
+        stream.read_exact(&mut head).await.unwrap();
+        assert_eq!(&head, b"HTTP/1.1 200");
+        drop(server);
+
+        // The channel is still open, so only the server going away ends the stream
+        let mut rest = Vec::new();
+        let closed =
+            tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest)).await;
+        assert!(
+            closed.is_ok(),
+            "stream stayed open after the server stopped"
+        );
+        drop(sender);
+    }
+}
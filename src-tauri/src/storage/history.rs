@@ -0,0 +1,562 @@
+//! Usage history: every validated snapshot, appended to `usage-history.jsonl`
+//!
+//! One JSON record per line keeps appends cheap. Old records are thinned out by
+//! `compact`, which runs on the first append of each process and every
+//! `COMPACT_EVERY` appends after that. The usage records of the last
+//! `FORECAST_LOOKBACK_HOURS` are also kept in memory per account, so forecasting a
+//! fetch does not read the whole file.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::providers::{
+    AccountKey, CostSnapshot, ProviderId, RateWindow, UsageSink, UsageSnapshot,
+    FORECAST_LOOKBACK_HOURS,
+};
+
+const APP_DIR_NAME: &str = "IncuBar";
+const HISTORY_FILENAME: &str = "usage-history.jsonl";
+
+/// Records older than this are dropped
+const RETENTION_DAYS: i64 = 365;
+/// Records younger than this are kept exactly as fetched
+const FULL_RESOLUTION_HOURS: i64 = 48;
+/// Until this age one record per account and hour is kept, then one per day
+const HOURLY_RESOLUTION_DAYS: i64 = 30;
+/// Appends between compactions
+const COMPACT_EVERY: usize = 500;
+
+static HISTORY_STORE: Lazy<Mutex<Option<HistoryStore>>> = Lazy::new(|| Mutex::new(None));
+
+/// A single history entry. Usage records keep their cost inside the snapshot;
+/// `cost` is only set for cost scans recorded on their own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRecord {
+    pub recorded_at: String,
+    pub provider_id: ProviderId,
+    pub account_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<UsageSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostSnapshot>,
+}
+
+impl HistoryRecord {
+    pub fn recorded_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.recorded_at)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    fn key(&self) -> AccountKey {
+        AccountKey::new(self.provider_id, self.account_id.clone())
+    }
+
+    /// Narrow a usage record to a single window. Returns false when it does not have it.
+    fn retain_window(&mut self, window_id: &str) -> bool {
+        let Some(usage) = self.usage.as_mut() else {
+            return false;
+        };
+        let matches = |window: &RateWindow| window.id.as_deref() == Some(window_id);
+        usage.windows.retain(matches);
+        for slot in [&mut usage.primary, &mut usage.secondary, &mut usage.tertiary] {
+            if !slot.as_ref().is_some_and(matches) {
+                *slot = None;
+            }
+        }
+        !usage.windows.is_empty()
+    }
+}
+
+/// Filter for `query_history`. Every field is optional; an empty query returns everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    pub provider_id: Option<ProviderId>,
+    pub account_id: Option<String>,
+    /// Keep only this rate window, and only records that have it
+    pub window_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl HistoryQuery {
+    fn apply(&self, mut record: HistoryRecord) -> Option<HistoryRecord> {
+        if self.provider_id.is_some_and(|id| id != record.provider_id) {
+            return None;
+        }
+        if self
+            .account_id
+            .as_deref()
+            .is_some_and(|id| id != record.account_id)
+        {
+            return None;
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let recorded_at = record.recorded_at()?;
+            if self.since.is_some_and(|since| recorded_at < since)
+                || self.until.is_some_and(|until| recorded_at > until)
+            {
+                return None;
+            }
+        }
+        if let Some(window_id) = self.window_id.as_deref() {
+            if !record.retain_window(window_id) {
+                return None;
+            }
+        }
+        Some(record)
+    }
+}
+
+/// The history file and the bookkeeping for when to compact it
+pub struct HistoryStore {
+    path: PathBuf,
+    appends: usize,
+    /// Recent usage records per account, oldest first; loaded on first use
+    recent: Option<HashMap<AccountKey, VecDeque<HistoryRecord>>>,
+}
+
+impl HistoryStore {
+    pub fn open(path: PathBuf) -> Self {
+        Self {
+            path,
+            appends: 0,
+            recent: None,
+        }
+    }
+
+    pub fn append(&mut self, record: &HistoryRecord) -> Result<()> {
+        // Forecasts are derived from the history, so they are not stored in it
+        let record = &strip_forecasts(record);
+        if self.appends.is_multiple_of(COMPACT_EVERY) {
+            if let Err(err) = self.compact(Utc::now()) {
+                tracing::warn!("Failed to compact usage history: {}", err);
+            }
+        }
+        self.appends += 1;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context("Failed to create usage history directory")?;
+        }
+        let line = serde_json::to_string(record).context("Failed to serialize history record")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open usage history")?;
+        writeln!(file, "{}", line).context("Failed to append to usage history")?;
+        self.remember(record);
+        Ok(())
+    }
+
+    /// Usage records of `key` since `since`, oldest first. Only the first call reads
+    /// the file; after that the records come from memory.
+    pub fn recent_usage(
+        &mut self,
+        key: &AccountKey,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<HistoryRecord>> {
+        if self.recent.is_none() {
+            let cutoff = since.min(Utc::now() - Duration::hours(FORECAST_LOOKBACK_HOURS));
+            let mut recent: HashMap<AccountKey, VecDeque<HistoryRecord>> = HashMap::new();
+            for record in read_records(&self.path)? {
+                if record.usage.is_some() && record.recorded_at().is_some_and(|at| at >= cutoff) {
+                    recent.entry(record.key()).or_default().push_back(record);
+                }
+            }
+            self.recent = Some(recent);
+        }
+        Ok(self
+            .recent
+            .as_ref()
+            .and_then(|recent| recent.get(key))
+            .into_iter()
+            .flatten()
+            .filter(|record| record.recorded_at().is_some_and(|at| at >= since))
+            .cloned()
+            .collect())
+    }
+
+    /// Add an appended usage record to the account's recent ones, dropping those that
+    /// fell out of the lookback
+    fn remember(&mut self, record: &HistoryRecord) {
+        let (Some(recent), Some(recorded_at)) = (self.recent.as_mut(), record.recorded_at()) else {
+            return;
+        };
+        if record.usage.is_none() {
+            return;
+        }
+        let cutoff = recorded_at - Duration::hours(FORECAST_LOOKBACK_HOURS);
+        let records = recent.entry(record.key()).or_default();
+        records.push_back(record.clone());
+        while records
+            .front()
+            .and_then(HistoryRecord::recorded_at)
+            .is_some_and(|at| at < cutoff)
+        {
+            records.pop_front();
+        }
+    }
+
+    /// Matching records, oldest first
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryRecord>> {
+        Ok(read_records(&self.path)?
+            .into_iter()
+            .filter_map(|record| query.apply(record))
+            .collect())
+    }
+
+    /// Drop records past retention and thin out older ones to one per account and
+    /// hour, then per day. Returns how many records were removed.
+    pub fn compact(&mut self, now: DateTime<Utc>) -> Result<usize> {
+        let records = read_records(&self.path)?;
+        let total = records.len();
+        let kept = compact_records(records, now);
+        let removed = total - kept.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        let mut payload = String::new();
+        for record in &kept {
+            payload.push_str(
+                &serde_json::to_string(record).context("Failed to serialize history record")?,
+            );
+            payload.push('\n');
+        }
+        // Written next to the history and renamed over it, so a crash cannot truncate it
+        let temp_path = self.path.with_extension("jsonl.tmp");
+        fs::write(&temp_path, payload).context("Failed to write compacted usage history")?;
+        fs::rename(&temp_path, &self.path).context("Failed to replace usage history")?;
+        Ok(removed)
+    }
+}
+
+fn history_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("Could not determine data directory")?;
+    Ok(data_dir.join(APP_DIR_NAME).join(HISTORY_FILENAME))
+}
+
+/// Every readable record in the file. Lines that fail to parse, e.g. one cut short
+/// by a crash, are skipped.
+fn read_records(path: &Path) -> Result<Vec<HistoryRecord>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("Failed to open usage history"),
+    };
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.context("Failed to read usage history")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(record) = serde_json::from_str(&line) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+fn compact_records(records: Vec<HistoryRecord>, now: DateTime<Utc>) -> Vec<HistoryRecord> {
+    let retention_cutoff = now - Duration::days(RETENTION_DAYS);
+    let full_cutoff = now - Duration::hours(FULL_RESOLUTION_HOURS);
+    let hourly_cutoff = now - Duration::days(HOURLY_RESOLUTION_DAYS);
+
+    // Walk newest first so the latest record of each bucket is the one kept
+    let mut buckets = HashSet::new();
+    let mut kept: Vec<HistoryRecord> = records
+        .into_iter()
+        .rev()
+        .filter(|record| {
+            let Some(recorded_at) = record.recorded_at() else {
+                return false;
+            };
+            if recorded_at < retention_cutoff {
+                return false;
+            }
+            if recorded_at >= full_cutoff {
+                return true;
+            }
+            let resolution = if recorded_at >= hourly_cutoff {
+                Duration::hours(1)
+            } else {
+                Duration::days(1)
+            };
+            let Ok(bucket) = recorded_at.duration_trunc(resolution) else {
+                return true;
+            };
+            buckets.insert((
+                record.provider_id,
+                record.account_id.clone(),
+                record.usage.is_some(),
+                bucket,
+            ))
+        })
+        .collect();
+    kept.reverse();
+    kept
+}
+
+fn strip_forecasts(record: &HistoryRecord) -> HistoryRecord {
+    let mut record = record.clone();
+    if let Some(usage) = record.usage.as_mut() {
+        usage.forecasts.clear();
+    }
+    record
+}
+
+fn with_store<T>(f: impl FnOnce(&mut HistoryStore) -> Result<T>) -> Result<T> {
+    let mut guard = HISTORY_STORE
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Usage history lock poisoned"))?;
+    if guard.is_none() {
+        *guard = Some(HistoryStore::open(history_path()?));
+    }
+    let store = guard.as_mut().context("Usage history is not open")?;
+    f(store)
+}
+
+/// Append a fetched snapshot. Error snapshots carry no usage and are skipped.
+pub fn record_usage(key: &AccountKey, usage: &UsageSnapshot) -> Result<()> {
+    if usage.error.is_some() {
+        return Ok(());
+    }
+    let record = HistoryRecord {
+        recorded_at: Utc::now().to_rfc3339(),
+        provider_id: key.provider_id,
+        account_id: key.account_id.clone(),
+        usage: Some(usage.clone()),
+        cost: None,
+    };
+    with_store(|store| store.append(&record))
+}
+
+/// Append a cost scan made outside a usage fetch
+pub fn record_cost(key: &AccountKey, cost: &CostSnapshot) -> Result<()> {
+    let record = HistoryRecord {
+        recorded_at: Utc::now().to_rfc3339(),
+        provider_id: key.provider_id,
+        account_id: key.account_id.clone(),
+        usage: None,
+        cost: Some(cost.clone()),
+    };
+    with_store(|store| store.append(&record))
+}
+
+pub fn query_history(query: &HistoryQuery) -> Result<Vec<HistoryRecord>> {
+    with_store(|store| store.query(query))
+}
+
+/// Usage records of `key` since `since`, at most `FORECAST_LOOKBACK_HOURS` back
+pub fn recent_usage(key: &AccountKey, since: DateTime<Utc>) -> Result<Vec<HistoryRecord>> {
+    with_store(|store| store.recent_usage(key, since))
+}
+
+/// Appends every reported snapshot to the history. Callers that joined the same fetch
+/// report the same snapshot, so one already recorded for the account is skipped.
+#[derive(Default)]
+pub struct HistorySink {
+    last_recorded: Mutex<HashMap<AccountKey, String>>,
+}
+
+impl UsageSink for HistorySink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        if let Ok(mut last_recorded) = self.last_recorded.lock() {
+            if last_recorded.get(key) == Some(&usage.updated_at) {
+                return;
+            }
+            last_recorded.insert(key.clone(), usage.updated_at.clone());
+        }
+        if let Err(err) = record_usage(key, usage) {
+            tracing::warn!("Failed to record usage history for {:?}: {}", key, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Forecast, USAGE_SNAPSHOT_VERSION};
+    use tempfile::tempdir;
+
+    fn window(id: &str, used_percent: f64) -> RateWindow {
+        RateWindow {
+            id: Some(id.to_string()),
+            category: None,
+            used_percent,
+            used: None,
+            limit: None,
+            unit: None,
+            window_minutes: None,
+            resets_at: None,
+            reset_description: None,
+            label: None,
+        }
+    }
+
+    fn record(provider_id: ProviderId, recorded_at: DateTime<Utc>) -> HistoryRecord {
+        let windows = vec![window("session", 40.0), window("weekly", 10.0)];
+        HistoryRecord {
+            recorded_at: recorded_at.to_rfc3339(),
+            provider_id,
+            account_id: "default".to_string(),
+            usage: Some(UsageSnapshot {
+                primary: windows.first().cloned(),
+                secondary: windows.get(1).cloned(),
+                tertiary: None,
+                credits: None,
+                cost: None,
+                identity: None,
+                updated_at: recorded_at.to_rfc3339(),
+                error: None,
+                retry_at: None,
+                windows,
+                forecasts: Vec::new(),
+                version: USAGE_SNAPSHOT_VERSION,
+            }),
+            cost: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn query_filters_by_provider_window_and_time() {
+        let dir = tempdir().expect("tempdir");
+        let mut store = HistoryStore::open(dir.path().join(HISTORY_FILENAME));
+        store.appends = 1;
+        for hours in [3, 2, 1] {
+            store
+                .append(&record(ProviderId::Claude, now() - Duration::hours(hours)))
+                .expect("append");
+        }
+        store.append(&record(ProviderId::Codex, now())).expect("append");
+
+        let records = store
+            .query(&HistoryQuery {
+                provider_id: Some(ProviderId::Claude),
+                window_id: Some("weekly".to_string()),
+                since: Some(now() - Duration::minutes(150)),
+                ..HistoryQuery::default()
+            })
+            .expect("query");
+        assert_eq!(records.len(), 2);
+        for record in &records {
+            let usage = record.usage.as_ref().expect("usage");
+            assert_eq!(usage.windows.len(), 1);
+            assert!(usage.primary.is_none());
+            assert_eq!(usage.secondary.as_ref().unwrap().used_percent, 10.0);
+        }
+
+        let missing = store
+            .query(&HistoryQuery {
+                window_id: Some("monthly".to_string()),
+                ..HistoryQuery::default()
+            })
+            .expect("query");
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn recent_usage_is_kept_in_memory_and_stored_without_forecasts() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(HISTORY_FILENAME);
+        let mut store = HistoryStore::open(path.clone());
+        store.appends = 1;
+        let key = AccountKey::default_for(ProviderId::Claude);
+        let since = now() - Duration::hours(FORECAST_LOOKBACK_HOURS);
+        store
+            .append(&record(ProviderId::Claude, since - Duration::hours(1)))
+            .expect("append");
+        store
+            .append(&record(ProviderId::Claude, now() - Duration::hours(2)))
+            .expect("append");
+        store
+            .append(&record(ProviderId::Codex, now() - Duration::hours(1)))
+            .expect("append");
+        assert_eq!(store.recent_usage(&key, since).expect("recent").len(), 1);
+
+        let mut forecast = record(ProviderId::Claude, now() - Duration::hours(1));
+        forecast.usage.as_mut().unwrap().forecasts = vec![Forecast {
+            id: "session".to_string(),
+            rate_per_hour: 5.0,
+            exhausts_at: None,
+            resets_first: false,
+            confidence: 0.5,
+            description: "Session limit hit soon".to_string(),
+        }];
+        store.append(&forecast).expect("append");
+        assert!(fs::read_to_string(&path)
+            .expect("history")
+            .lines()
+            .all(|line| !line.contains("Session limit hit soon")));
+
+        // Later lookups do not go back to the file
+        fs::remove_file(&path).expect("remove history");
+        let recent = store.recent_usage(&key, since).expect("recent");
+        assert_eq!(recent.len(), 2);
+        assert!(recent
+            .iter()
+            .all(|record| record.usage.as_ref().unwrap().forecasts.is_empty()));
+    }
+
+    #[test]
+    fn compaction_thins_old_records_and_drops_expired_ones() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(HISTORY_FILENAME);
+        let mut store = HistoryStore::open(path.clone());
+        store.appends = 1;
+        let recent = now() - Duration::hours(1);
+        let last_week = now() - Duration::days(7);
+        let last_quarter = now() - Duration::days(90);
+        let records = [
+            record(ProviderId::Claude, recent),
+            record(ProviderId::Claude, recent + Duration::minutes(5)),
+            record(ProviderId::Claude, last_week),
+            record(ProviderId::Claude, last_week + Duration::minutes(5)),
+            record(ProviderId::Codex, last_week + Duration::minutes(5)),
+            record(ProviderId::Claude, last_quarter),
+            record(ProviderId::Claude, last_quarter + Duration::hours(3)),
+            record(ProviderId::Claude, now() - Duration::days(400)),
+        ];
+        for record in &records {
+            store.append(record).expect("append");
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{{\"truncated"))
+            .expect("write partial line");
+
+        assert_eq!(store.compact(now()).expect("compact"), 3);
+        let kept = store.query(&HistoryQuery::default()).expect("query");
+        let times: Vec<_> = kept
+            .iter()
+            .map(|record| (record.provider_id, record.recorded_at().unwrap()))
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                (ProviderId::Claude, recent),
+                (ProviderId::Claude, recent + Duration::minutes(5)),
+                (ProviderId::Claude, last_week + Duration::minutes(5)),
+                (ProviderId::Codex, last_week + Duration::minutes(5)),
+                (ProviderId::Claude, last_quarter + Duration::hours(3)),
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Storage utilities for credentials and settings
 
+pub mod history;
 pub mod install_origin;
 pub mod keyring;
 pub mod secure_delete;
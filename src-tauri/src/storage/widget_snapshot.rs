@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::providers::{ProviderId, UsageSnapshot};
+use crate::providers::{AccountKey, ProviderError, ProviderId, UsageSink, UsageSnapshot};
 
 const APP_DIR_NAME: &str = "IncuBar";
 const WIDGET_SNAPSHOT_FILENAME: &str = "widget-snapshot.json";
@@ -51,3 +51,21 @@ pub fn write_widget_snapshot(provider_id: ProviderId, usage: &UsageSnapshot) ->
     fs::write(&path, payload).context("Failed to write widget snapshot")?;
     Ok(())
 }
+
+/// Keeps the widget file current. The widget only tracks each provider's default account.
+pub struct WidgetSnapshotSink;
+
+impl UsageSink for WidgetSnapshotSink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        if !key.is_default() {
+            return;
+        }
+        if let Err(err) = write_widget_snapshot(key.provider_id, usage) {
+            tracing::warn!("Failed to write widget snapshot: {}", err);
+        }
+    }
+
+    fn refresh_failed(&self, key: &AccountKey, _error: &ProviderError, usage: &UsageSnapshot) {
+        self.usage_updated(key, usage);
+    }
+}
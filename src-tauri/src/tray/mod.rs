@@ -21,10 +21,16 @@ use tokio::time::{self, Instant, MissedTickBehavior};
 use url::Url;
 
 use crate::debug_settings;
-use crate::providers::{ProviderId, UsageSnapshot};
+use crate::providers::{
+    self, AccountKey, ProviderError, ProviderId, RateWindow, UsageSink, UsageSnapshot,
+    WindowCategory,
+};
 
 const TRAY_ICON_ID: &str = "main";
 const TRAY_REFRESH_MENU_ID: &str = "tray-refresh";
+const TRAY_SNOOZE_MENU_ID: &str = "tray-snooze";
+/// How long the tray's snooze item holds alerts back
+const TRAY_SNOOZE_MINUTES: u32 = 60;
 const TRAY_TOOLTIP_BASE: &str = "IncuBar - AI Usage Tracker";
 const ICON_SIZE: u32 = 32;
 const RING_THICKNESS: f64 = 3.0;
@@ -57,7 +63,7 @@ static TRAY_ICON_TEMPLATE: Lazy<Image<'static>> = Lazy::new(|| {
             render_tray_icon(TrayRenderState {
                 usage_rings: Vec::new(),
                 status: TrayStatus::Disabled,
-                primary_provider: None,
+                primary: None,
                 animation_phase: 0,
                 blink_enabled: false,
                 theme: Theme::Light,
@@ -201,7 +207,7 @@ fn start_tray_animation_thread(app: &AppHandle) {
 }
 
 struct TrayUsageState {
-    provider_usage: HashMap<ProviderId, UsageSnapshot>,
+    provider_usage: HashMap<AccountKey, UsageSnapshot>,
     disabled_providers: HashSet<ProviderId>,
     loading_count: usize,
     animation_phase: u8,
@@ -270,18 +276,18 @@ enum TrayStatus {
     Error,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 struct UsageRing {
     percent: f64,
     color: [u8; 4],
-    provider_id: ProviderId,
+    key: AccountKey,
 }
 
 #[derive(Clone)]
 struct TrayRenderState {
     usage_rings: Vec<UsageRing>,
     status: TrayStatus,
-    primary_provider: Option<ProviderId>,
+    primary: Option<AccountKey>,
     animation_phase: u8,
     blink_enabled: bool,
     theme: Theme,
@@ -539,18 +545,42 @@ impl Canvas {
     }
 }
 
-pub fn handle_usage_update(
-    app: &AppHandle,
-    provider_id: ProviderId,
-    usage: UsageSnapshot,
-) -> Result<()> {
+pub fn handle_usage_update(app: &AppHandle, key: AccountKey, usage: UsageSnapshot) -> Result<()> {
     {
         let mut state = write_tray_usage_state();
-        state.provider_usage.insert(provider_id, usage);
+        state.provider_usage.insert(key, usage);
     }
     update_tray_icon(app)
 }
 
+/// Keeps the tray icon current. The tray tracks each provider's default account, and
+/// every account of providers that only have user-defined ones (custom providers and
+/// plugins).
+pub struct TraySink {
+    app: AppHandle,
+}
+
+impl TraySink {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl UsageSink for TraySink {
+    fn usage_updated(&self, key: &AccountKey, usage: &UsageSnapshot) {
+        if !key.is_default() && key.provider_id.descriptor().has_default_account() {
+            return;
+        }
+        if let Err(e) = handle_usage_update(&self.app, key.clone(), usage.clone()) {
+            tracing::warn!("Failed to update tray icon: {}", e);
+        }
+    }
+
+    fn refresh_failed(&self, key: &AccountKey, _error: &ProviderError, usage: &UsageSnapshot) {
+        self.usage_updated(key, usage);
+    }
+}
+
 pub fn set_loading_state(app: &AppHandle, is_loading: bool) -> Result<()> {
     {
         let mut state = write_tray_usage_state();
@@ -689,7 +719,7 @@ fn compute_render_state() -> TrayRenderState {
     let blinking = state.blinking;
     let has_disabled = !state.disabled_providers.is_empty();
     let theme = state.theme;
-    for (provider_id, usage) in state.provider_usage.iter() {
+    for (key, usage) in state.provider_usage.iter() {
         if usage.error.is_some() {
             has_error = true;
         }
@@ -700,14 +730,14 @@ fn compute_render_state() -> TrayRenderState {
             rings.push(UsageRing {
                 percent,
                 color: usage_color(percent, theme),
-                provider_id: *provider_id,
+                key: key.clone(),
             });
         }
     }
 
     sort_usage_rings(&mut rings);
     rings.truncate(MAX_RINGS);
-    let primary_provider = rings.first().map(|ring| ring.provider_id);
+    let primary = rings.first().map(|ring| ring.key.clone());
 
     let status = if loading_count > 0 {
         TrayStatus::Loading
@@ -726,7 +756,7 @@ fn compute_render_state() -> TrayRenderState {
     TrayRenderState {
         usage_rings: rings,
         status,
-        primary_provider,
+        primary,
         animation_phase,
         blink_enabled: blinking,
         theme,
@@ -745,9 +775,9 @@ fn format_tray_display_text(
     state: &TrayRenderState,
     display_state: TrayDisplayTextState,
 ) -> Option<String> {
-    let primary_provider = state.primary_provider?;
+    let primary = state.primary.as_ref()?;
     let usage_state = read_tray_usage_state();
-    let usage = usage_state.provider_usage.get(&primary_provider)?;
+    let usage = usage_state.provider_usage.get(primary)?;
     let percent_text = resolve_percent_window(display_state, usage).and_then(|value| {
         if !value.is_finite() {
             return None;
@@ -761,7 +791,7 @@ fn format_tray_display_text(
         Some(format!("{:.0}%", shown))
     });
 
-    let pace_text = format_tray_pace_text(primary_provider, usage);
+    let pace_text = format_tray_pace_text(primary.provider_id, usage);
 
     match display_state.mode {
         TrayDisplayTextMode::Percent => percent_text,
@@ -778,17 +808,23 @@ fn resolve_percent_window(
     display_state: TrayDisplayTextState,
     usage: &UsageSnapshot,
 ) -> Option<f64> {
+    let category_or = |category, fallback: &Option<RateWindow>| {
+        usage
+            .rate_windows()
+            .into_iter()
+            .find(|window| window.category == Some(category))
+            .or(fallback.as_ref())
+            .map(|window| window.used_percent)
+    };
     match display_state.percent_window_mode {
-        TrayPercentWindowMode::Session => usage.primary.as_ref().map(|window| window.used_percent),
-        TrayPercentWindowMode::Weekly => usage.secondary.as_ref().map(|window| window.used_percent),
+        TrayPercentWindowMode::Session => category_or(WindowCategory::Session, &usage.primary),
+        TrayPercentWindowMode::Weekly => category_or(WindowCategory::Weekly, &usage.secondary),
         TrayPercentWindowMode::Highest => {
             let mut best: Option<f64> = None;
-            for window in [&usage.primary, &usage.secondary, &usage.tertiary] {
-                if let Some(window) = window.as_ref() {
-                    let percent = window.used_percent;
-                    if percent.is_finite() {
-                        best = Some(best.map_or(percent, |current| current.max(percent)));
-                    }
+            for window in usage.rate_windows() {
+                let percent = window.used_percent;
+                if percent.is_finite() {
+                    best = Some(best.map_or(percent, |current| current.max(percent)));
                 }
             }
             best
@@ -847,17 +883,15 @@ fn is_snapshot_stale(usage: &UsageSnapshot) -> bool {
 }
 
 fn usage_percent_from_snapshot(usage: &UsageSnapshot) -> Option<f64> {
-    let mut best: Option<f64> = None;
-    for window in [&usage.primary, &usage.secondary, &usage.tertiary] {
-        if let Some(window) = window {
-            let percent = window.used_percent;
-            if percent.is_finite() {
-                let clamped = percent.clamp(0.0, 100.0);
-                best = Some(best.map_or(clamped, |current| current.max(clamped)));
-            }
-        }
-    }
-    best
+    busiest_window(usage).map(|window| window.used_percent.clamp(0.0, 100.0))
+}
+
+fn busiest_window(usage: &UsageSnapshot) -> Option<&RateWindow> {
+    usage
+        .rate_windows()
+        .into_iter()
+        .filter(|window| window.used_percent.is_finite())
+        .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
 }
 
 fn render_tray_icon(state: TrayRenderState) -> Image<'static> {
@@ -901,7 +935,8 @@ fn render_tray_icon(state: TrayRenderState) -> Image<'static> {
     }
 
     if !matches!(state.status, TrayStatus::Disabled) {
-        draw_provider_icon(&mut canvas, state.primary_provider, center, palette);
+        let primary_provider = state.primary.as_ref().map(|key| key.provider_id);
+        draw_provider_icon(&mut canvas, primary_provider, center, palette);
     } else {
         draw_generic_ring(&mut canvas, center, palette);
     }
@@ -1027,26 +1062,12 @@ fn usage_color(percent: f64, theme: Theme) -> [u8; 4] {
     }
 }
 
-fn provider_display_name(provider_id: ProviderId) -> &'static str {
-    match provider_id {
-        ProviderId::Claude => "Claude",
-        ProviderId::Codex => "Codex",
-        ProviderId::Cursor => "Cursor",
-        ProviderId::Copilot => "Copilot",
-        ProviderId::Gemini => "Gemini",
-        ProviderId::Antigravity => "Antigravity",
-        ProviderId::Factory => "Droid",
-        ProviderId::Zai => "z.ai",
-        ProviderId::Minimax => "MiniMax",
-        ProviderId::Kimi => "Kimi",
-        ProviderId::KimiK2 => "Kimi K2",
-        ProviderId::Kiro => "Kiro",
-        ProviderId::Vertex => "Vertex AI",
-        ProviderId::Augment => "Augment",
-        ProviderId::Amp => "Amp",
-        ProviderId::Jetbrains => "JetBrains AI",
-        ProviderId::Opencode => "OpenCode",
-        ProviderId::Synthetic => "Synthetic",
+/// The provider's name for a default account, the account id otherwise
+fn account_display_name(key: &AccountKey) -> String {
+    if key.is_default() {
+        key.provider_id.display_name().to_string()
+    } else {
+        key.account_id.clone()
     }
 }
 
@@ -1056,32 +1077,34 @@ fn build_tray_tooltip() -> String {
 }
 
 fn format_tray_tooltip(state: &TrayUsageState) -> String {
-    let mut entries: Vec<(f64, ProviderId)> = Vec::new();
-    let mut error_entries: Vec<ProviderId> = Vec::new();
+    let mut entries: Vec<(f64, &AccountKey, Option<String>)> = Vec::new();
+    let mut error_entries: Vec<&AccountKey> = Vec::new();
 
-    for (provider_id, usage) in state.provider_usage.iter() {
+    for (key, usage) in state.provider_usage.iter() {
         if usage.error.is_some() {
-            error_entries.push(*provider_id);
+            error_entries.push(key);
             continue;
         }
-        if let Some(percent) = usage_percent_from_snapshot(usage) {
-            entries.push((percent, *provider_id));
+        if let Some(window) = busiest_window(usage) {
+            let percent = window.used_percent.clamp(0.0, 100.0);
+            entries.push((percent, key, window.count_text()));
         }
     }
 
     entries.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
 
     let mut summary_parts: Vec<String> = Vec::new();
-    for (percent, provider_id) in entries.iter().take(MAX_RINGS) {
-        summary_parts.push(format!(
-            "{} {:.0}%",
-            provider_display_name(*provider_id),
-            percent
-        ));
+    for (percent, key, counts) in entries.iter().take(MAX_RINGS) {
+        // Real counts read better than a percentage when the provider reports them
+        let amount = match counts {
+            Some(counts) => counts.clone(),
+            None => format!("{:.0}%", percent),
+        };
+        summary_parts.push(format!("{} {}", account_display_name(key), amount));
     }
 
-    for provider_id in error_entries {
-        summary_parts.push(format!("{} error", provider_display_name(provider_id)));
+    for key in error_entries {
+        summary_parts.push(format!("{} error", account_display_name(key)));
     }
 
     if summary_parts.is_empty() {
@@ -1101,7 +1124,7 @@ pub fn setup_tray(app: &AppHandle) -> Result<()> {
     let initial_icon = render_tray_icon(TrayRenderState {
         usage_rings: Vec::new(),
         status: TrayStatus::Ok,
-        primary_provider: None,
+        primary: None,
         animation_phase: 0,
         blink_enabled: false,
         theme: Theme::Dark,
@@ -1117,7 +1140,10 @@ pub fn setup_tray(app: &AppHandle) -> Result<()> {
     let refresh_item = MenuItemBuilder::new("Refresh")
         .id(TRAY_REFRESH_MENU_ID)
         .build(app)?;
-    let tray_menu = Menu::with_items(app, &[&refresh_item])?;
+    let snooze_item = MenuItemBuilder::new("Snooze Alerts for 1 Hour")
+        .id(TRAY_SNOOZE_MENU_ID)
+        .build(app)?;
+    let tray_menu = Menu::with_items(app, &[&refresh_item, &snooze_item])?;
 
     // In dev mode, disable template mode to show colored icon
     #[cfg(debug_assertions)]
@@ -1134,6 +1160,10 @@ pub fn setup_tray(app: &AppHandle) -> Result<()> {
         .on_menu_event(|app, event| {
             if event.id().as_ref() == TRAY_REFRESH_MENU_ID {
                 let _ = app.emit("refresh-requested", ());
+            } else if event.id().as_ref() == TRAY_SNOOZE_MENU_ID {
+                if let Err(e) = providers::snooze_notifications(None, TRAY_SNOOZE_MINUTES) {
+                    tracing::warn!("Failed to snooze notifications: {}", e);
+                }
             }
         })
         .on_tray_icon_event(|tray, event| {
@@ -1680,7 +1710,9 @@ mod tests {
         sort_usage_rings, write_tray_usage_state, TrayRenderState, TrayStatus, UsageRing,
         BLINKING_ANIMATION_TICK_MS, ICON_SIZE, LOADING_ANIMATION_TICK_MS, STALE_THRESHOLD_SECS,
     };
-    use crate::providers::{ProviderId, RateWindow, UsageSnapshot};
+    use crate::providers::{
+        AccountKey, ProviderId, RateWindow, UsageSnapshot, USAGE_SNAPSHOT_VERSION,
+    };
     use std::collections::HashMap;
     use std::time::Duration;
     use tauri::Theme;
@@ -1693,7 +1725,12 @@ mod tests {
     fn sample_usage_with_time(percent: f64, updated_at: &str) -> UsageSnapshot {
         UsageSnapshot {
             primary: Some(RateWindow {
+                id: None,
+                category: None,
                 used_percent: percent,
+                used: None,
+                limit: None,
+                unit: None,
                 window_minutes: None,
                 resets_at: None,
                 reset_description: None,
@@ -1706,6 +1743,10 @@ mod tests {
             identity: None,
             updated_at: updated_at.to_string(),
             error: None,
+            retry_at: None,
+            windows: Vec::new(),
+            forecasts: Vec::new(),
+            version: USAGE_SNAPSHOT_VERSION,
         }
     }
 
@@ -1716,10 +1757,10 @@ mod tests {
             usage_rings: vec![UsageRing {
                 percent: 42.0,
                 color: [73, 177, 108, 255],
-                provider_id: ProviderId::Claude,
+                key: AccountKey::default_for(ProviderId::Claude),
             }],
             status: TrayStatus::Ok,
-            primary_provider: Some(ProviderId::Claude),
+            primary: Some(AccountKey::default_for(ProviderId::Claude)),
             animation_phase: 0,
             blink_enabled: false,
             theme: Theme::Light,
@@ -1734,8 +1775,8 @@ mod tests {
         reset_tray_usage_state();
         let mut guard = write_tray_usage_state();
         guard.provider_usage = HashMap::from([
-            (ProviderId::Claude, sample_usage(33.0)),
-            (ProviderId::Codex, sample_usage(81.0)),
+            (AccountKey::default_for(ProviderId::Claude), sample_usage(33.0)),
+            (AccountKey::default_for(ProviderId::Codex), sample_usage(81.0)),
         ]);
         drop(guard);
 
@@ -1748,7 +1789,7 @@ mod tests {
             state.usage_rings.first().map(|ring| ring.percent),
             Some(81.0)
         );
-        assert_eq!(state.primary_provider, Some(ProviderId::Codex));
+        assert_eq!(state.primary, Some(AccountKey::default_for(ProviderId::Codex)));
     }
 
     #[test]
@@ -1757,12 +1798,12 @@ mod tests {
             UsageRing {
                 percent: f64::NAN,
                 color: [0, 0, 0, 0],
-                provider_id: ProviderId::Claude,
+                key: AccountKey::default_for(ProviderId::Claude),
             },
             UsageRing {
                 percent: 42.0,
                 color: [0, 0, 0, 0],
-                provider_id: ProviderId::Codex,
+                key: AccountKey::default_for(ProviderId::Codex),
             },
         ];
 
@@ -1770,7 +1811,7 @@ mod tests {
 
         assert_eq!(rings.len(), 1);
         assert_eq!(rings[0].percent, 42.0);
-        assert_eq!(rings[0].provider_id, ProviderId::Codex);
+        assert_eq!(rings[0].key.provider_id, ProviderId::Codex);
     }
 
     #[test]
@@ -1779,7 +1820,7 @@ mod tests {
         let mut guard = write_tray_usage_state();
         let mut usage = sample_usage(10.0);
         usage.error = Some("failed".to_string());
-        guard.provider_usage = HashMap::from([(ProviderId::Claude, usage)]);
+        guard.provider_usage = HashMap::from([(AccountKey::default_for(ProviderId::Claude), usage)]);
         drop(guard);
 
         let state = compute_render_state();
@@ -1795,7 +1836,7 @@ mod tests {
             .to_rfc3339();
         let mut guard = write_tray_usage_state();
         guard.provider_usage = HashMap::from([(
-            ProviderId::Claude,
+            AccountKey::default_for(ProviderId::Claude),
             sample_usage_with_time(55.0, &stale_time),
         )]);
         drop(guard);
@@ -1829,7 +1870,7 @@ mod tests {
         let steady_icon = render_tray_icon(TrayRenderState {
             usage_rings: Vec::new(),
             status: TrayStatus::Disabled,
-            primary_provider: None,
+            primary: None,
             animation_phase: 0,
             blink_enabled: false,
             theme: Theme::Light,
@@ -1837,7 +1878,7 @@ mod tests {
         let blinking_icon = render_tray_icon(TrayRenderState {
             usage_rings: Vec::new(),
             status: TrayStatus::Disabled,
-            primary_provider: None,
+            primary: None,
             animation_phase: 1,
             blink_enabled: true,
             theme: Theme::Light,
@@ -1856,7 +1897,7 @@ mod tests {
         let icon = render_tray_icon(TrayRenderState {
             usage_rings: Vec::new(),
             status: TrayStatus::Disabled,
-            primary_provider: None,
+            primary: None,
             animation_phase: 0,
             blink_enabled: false,
             theme: Theme::Dark,
@@ -1873,12 +1914,23 @@ mod tests {
         reset_tray_usage_state();
         let mut guard = write_tray_usage_state();
         guard.provider_usage = HashMap::from([
-            (ProviderId::Codex, sample_usage(72.0)),
-            (ProviderId::Claude, sample_usage(12.0)),
+            (AccountKey::default_for(ProviderId::Codex), sample_usage(72.0)),
+            (AccountKey::default_for(ProviderId::Claude), sample_usage(12.0)),
         ]);
         let mut error_usage = sample_usage(40.0);
         error_usage.error = Some("broken".to_string());
-        guard.provider_usage.insert(ProviderId::Cursor, error_usage);
+        guard.provider_usage.insert(AccountKey::default_for(ProviderId::Cursor), error_usage);
+        let mut counted_usage = sample_usage(82.4);
+        if let Some(window) = counted_usage.primary.as_mut() {
+            window.used = Some(412.0);
+            window.limit = Some(500.0);
+            window.unit = Some("requests".to_string());
+        }
+        guard.provider_usage.insert(AccountKey::default_for(ProviderId::Copilot), counted_usage);
+        guard.provider_usage.insert(
+            AccountKey::new(ProviderId::Custom, "gateway"),
+            UsageSnapshot::error("offline".to_string()),
+        );
         drop(guard);
 
         let state = read_tray_usage_state();
@@ -1887,6 +1939,8 @@ mod tests {
         assert!(tooltip.contains("Codex 72%"));
         assert!(tooltip.contains("Claude 12%"));
         assert!(tooltip.contains("Cursor error"));
+        assert!(tooltip.contains("Copilot 412 / 500 requests"));
+        assert!(tooltip.contains("gateway error"));
     }
 
     #[test]
@@ -1944,7 +1998,7 @@ mod tests {
         let state = TrayRenderState {
             usage_rings: Vec::new(),
             status: TrayStatus::Loading,
-            primary_provider: None,
+            primary: None,
             animation_phase: 0,
             blink_enabled: false,
             theme: Theme::Light,
@@ -3,26 +3,14 @@ import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
 import { check } from '@tauri-apps/plugin-updater';
 import { relaunch } from '@tauri-apps/plugin-process';
-import { sendNotification } from '@tauri-apps/plugin-notification';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { PopupWindow } from './components/PopupWindow';
 import { SettingsPanel } from './components/SettingsPanel';
 import { useUsageStore } from './stores/usageStore';
 import { useSettingsStore } from './stores/settingsStore';
-import type { ProviderId, ProviderIncident, RefreshingEvent, UpdateChannel, UsageUpdateEvent } from './lib/types';
-import { parseUsageUpdateEvent } from './lib/eventValidation';
-import type {
-  CreditsNotificationState,
-  RefreshFailureNotificationState,
-  SessionNotificationState,
-  StaleUsageNotificationState,
-} from './lib/notifications';
-import {
-  evaluateCreditsNotifications,
-  evaluateRefreshFailureNotifications,
-  evaluateSessionNotifications,
-  evaluateStaleUsageNotifications,
-} from './lib/notifications';
+import { DEFAULT_ACCOUNT_ID } from './lib/types';
+import type { AccountInfo, ProviderId, ProviderIncident, RefreshingEvent, UpdateChannel, UsageUpdateEvent } from './lib/types';
+import { parseRefreshFailedEvent, parseUsageUpdateEvent } from './lib/eventValidation';
 import { PROVIDERS } from './lib/providers';
 import { getStaleAfterMs, isTimestampStale } from './lib/staleness';
 import { restoreSafeStateAfterCrash } from './lib/crashRecovery';
@@ -42,6 +30,7 @@ function App() {
   console.log('[App] Rendering, isSettingsWindow:', isSettingsWindow, 'location.search:', window.location.search);
   const setProviderUsage = useUsageStore((s) => s.setProviderUsage);
   const setProviderStatus = useUsageStore((s) => s.setProviderStatus);
+  const setProviderError = useUsageStore((s) => s.setProviderError);
   const initializeProviders = useUsageStore((s) => s.initializeProviders);
   const enabledProviders = useSettingsStore((s) => s.enabledProviders);
   const hasHydrated = useSettingsStore((s) => s.hasHydrated);
@@ -53,23 +42,25 @@ function App() {
   const notifyCreditsLow = useSettingsStore((s) => s.notifyCreditsLow);
   const notifyRefreshFailure = useSettingsStore((s) => s.notifyRefreshFailure);
   const notifyStaleUsage = useSettingsStore((s) => s.notifyStaleUsage);
+  const notificationThresholds = useSettingsStore((s) => s.notificationThresholds);
   const pollProviderStatus = useSettingsStore((s) => s.pollProviderStatus);
   const debugFileLogging = useSettingsStore((s) => s.debugFileLogging);
   const debugKeepCliSessionsAlive = useSettingsStore(
     (s) => s.debugKeepCliSessionsAlive
   );
   const debugRandomBlink = useSettingsStore((s) => s.debugRandomBlink);
+  const debugRecordFixtures = useSettingsStore((s) => s.debugRecordFixtures);
   const redactPersonalInfo = useSettingsStore((s) => s.redactPersonalInfo);
+  const providerBaseUrls = useSettingsStore((s) => s.providerBaseUrls);
+  const providerHostBaseUrls = useSettingsStore((s) => s.providerHostBaseUrls);
+  const metricsExporterEnabled = useSettingsStore((s) => s.metricsExporterEnabled);
+  const metricsExporterPort = useSettingsStore((s) => s.metricsExporterPort);
+  const apiServerEnabled = useSettingsStore((s) => s.apiServerEnabled);
+  const apiServerPort = useSettingsStore((s) => s.apiServerPort);
   const initAutostart = useSettingsStore((s) => s.initAutostart);
   const setInstallOrigin = useSettingsStore((s) => s.setInstallOrigin);
   const initializedRef = useRef(false);
   const enabledProvidersRef = useRef<ProviderId[]>([]);
-  const notificationStateRef = useRef(new Map<ProviderId, SessionNotificationState>());
-  const creditsNotificationStateRef = useRef(new Map<ProviderId, CreditsNotificationState>());
-  const refreshFailureNotificationRef = useRef(
-    new Map<ProviderId, RefreshFailureNotificationState>()
-  );
-  const staleUsageNotificationRef = useRef(new Map<ProviderId, StaleUsageNotificationState>());
   const lastUpdateCheckChannelRef = useRef<UpdateChannel | null>(null);
 
   // Initialize enabled providers from settings (only once after hydration)
@@ -182,10 +173,55 @@ function App() {
     invoke('set_debug_random_blink', { enabled: debugRandomBlink }).catch(console.error);
   }, [debugRandomBlink]);
 
+  useEffect(() => {
+    invoke('set_debug_record_fixtures', { enabled: debugRecordFixtures }).catch(console.error);
+  }, [debugRecordFixtures]);
+
   useEffect(() => {
     invoke('set_redact_personal_info', { enabled: redactPersonalInfo }).catch(console.error);
   }, [redactPersonalInfo]);
 
+  useEffect(() => {
+    invoke('set_provider_base_urls', {
+      baseUrls: providerBaseUrls ?? {},
+      hostBaseUrls: providerHostBaseUrls ?? {},
+    }).catch(console.error);
+  }, [providerBaseUrls, providerHostBaseUrls]);
+
+  useEffect(() => {
+    invoke('set_metrics_exporter', {
+      enabled: metricsExporterEnabled,
+      port: metricsExporterPort,
+    }).catch(console.error);
+  }, [metricsExporterEnabled, metricsExporterPort]);
+
+  useEffect(() => {
+    invoke('set_api_server', { enabled: apiServerEnabled, port: apiServerPort }).catch(
+      console.error
+    );
+  }, [apiServerEnabled, apiServerPort]);
+
+  // Alerts are decided in Rust so they still fire while this window is closed
+  useEffect(() => {
+    invoke('set_notification_preferences', {
+      preferences: {
+        enabled: showNotifications,
+        sessionUsage: notifySessionUsage,
+        creditsLow: notifyCreditsLow,
+        refreshFailure: notifyRefreshFailure,
+        staleUsage: notifyStaleUsage,
+        providerThresholds: notificationThresholds ?? {},
+      },
+    }).catch(console.error);
+  }, [
+    showNotifications,
+    notifySessionUsage,
+    notifyCreditsLow,
+    notifyRefreshFailure,
+    notifyStaleUsage,
+    notificationThresholds,
+  ]);
+
   // Sync enabled providers when settings change (only after hydration)
   useEffect(() => {
     if (initializedRef.current && hasHydrated) {
@@ -251,41 +287,35 @@ function App() {
         console.log('[App] Failed to parse usage-updated event');
         return;
       }
-      const { providerId, usage } = parsedUsageUpdate;
-      console.log('[App] Setting provider usage:', providerId, usage);
+      const { providerId, accountId, usage } = parsedUsageUpdate;
+      console.log('[App] Setting provider usage:', providerId, accountId, usage);
+      if (accountId && accountId !== DEFAULT_ACCOUNT_ID) {
+        useUsageStore.getState().setAccountState(providerId, accountId, {
+          usage,
+          isLoading: false,
+          lastError: usage.error,
+          retryAt: usage.retryAt,
+        });
+        return;
+      }
       setProviderUsage(providerId, usage);
-      const metadata = PROVIDERS[providerId];
-      evaluateSessionNotifications({
-        providerId,
-        providerName: metadata.name,
-        sessionLabel: metadata.sessionLabel,
-        usage,
-        showNotifications: showNotifications && notifySessionUsage,
-        stateMap: notificationStateRef.current,
-        notify: (title, body) => void sendNotification({ title, body }),
-      });
-      evaluateCreditsNotifications({
-        providerId,
-        providerName: metadata.name,
-        usage,
-        showNotifications: showNotifications && notifyCreditsLow,
-        stateMap: creditsNotificationStateRef.current,
-        notify: (title, body) => void sendNotification({ title, body }),
-      });
-      evaluateRefreshFailureNotifications({
-        providerId,
-        providerName: metadata.name,
-        error: usage.error,
-        showNotifications: showNotifications && notifyRefreshFailure,
-        stateMap: refreshFailureNotificationRef.current,
-        notify: (title, body) => void sendNotification({ title, body }),
-      });
     });
 
     return () => {
       void unlisten.then((fn) => fn()).catch(console.error);
     };
-  }, [setProviderUsage, showNotifications, notifySessionUsage, notifyCreditsLow, notifyRefreshFailure]);
+  }, [setProviderUsage]);
+
+  useEffect(() => {
+    void useUsageStore.getState().loadAccounts();
+    const unlistenAccounts = listen<AccountInfo[]>('accounts-updated', (event) => {
+      useUsageStore.getState().setAccounts(event.payload);
+    });
+
+    return () => {
+      void unlistenAccounts.then((fn) => fn()).catch(console.error);
+    };
+  }, []);
 
   useEffect(() => {
     const unlistenRefresh = listen('refresh-requested', () => {
@@ -299,6 +329,13 @@ function App() {
 
   useEffect(() => {
     const unlistenRefreshing = listen<RefreshingEvent>('refreshing-provider', (event) => {
+      const { accountId } = event.payload;
+      if (accountId && accountId !== DEFAULT_ACCOUNT_ID) {
+        useUsageStore.getState().setAccountState(event.payload.providerId, accountId, {
+          isLoading: event.payload.isRefreshing,
+        });
+        return;
+      }
       useUsageStore.getState().setProviderLoading(
         event.payload.providerId,
         event.payload.isRefreshing
@@ -312,52 +349,24 @@ function App() {
 
   useEffect(() => {
     const unlistenRefreshFailure = listen<UsageUpdateEvent>('refresh-failed', (event) => {
-      const parsedUsageUpdate = parseUsageUpdateEvent(event.payload);
-      if (!parsedUsageUpdate) return;
-      const { providerId, usage } = parsedUsageUpdate;
+      const parsedRefreshFailure = parseRefreshFailedEvent(event.payload);
+      if (!parsedRefreshFailure) return;
+      const { providerId, accountId, usage } = parsedRefreshFailure;
       if (!usage?.error) return;
-      const metadata = PROVIDERS[providerId];
-      evaluateRefreshFailureNotifications({
-        providerId,
-        providerName: metadata.name,
-        error: usage.error,
-        showNotifications: showNotifications && notifyRefreshFailure,
-        stateMap: refreshFailureNotificationRef.current,
-        notify: (title, body) => void sendNotification({ title, body }),
-      });
+      if (accountId && accountId !== DEFAULT_ACCOUNT_ID) {
+        useUsageStore.getState().setAccountState(providerId, accountId, {
+          lastError: usage.error,
+          isLoading: false,
+        });
+        return;
+      }
+      setProviderError(providerId, usage.error);
     });
 
     return () => {
       unlistenRefreshFailure.then((fn) => fn());
     };
-  }, [showNotifications, notifyRefreshFailure]);
-
-  useEffect(() => {
-    if (refreshIntervalSeconds <= 0) return undefined;
-
-    const intervalMs = refreshIntervalSeconds * 1000;
-    const intervalId = window.setInterval(() => {
-      const { providers } = useUsageStore.getState();
-      Object.values(providers).forEach((provider) => {
-        if (!provider.enabled || !provider.usage?.updatedAt) return;
-        const metadata = PROVIDERS[provider.id];
-        const staleAfterMs = getStaleAfterMs(refreshIntervalSeconds);
-        evaluateStaleUsageNotifications({
-          providerId: provider.id,
-          providerName: metadata.name,
-          updatedAt: provider.usage.updatedAt,
-          showNotifications: showNotifications && notifyStaleUsage,
-          staleAfterMs,
-          stateMap: staleUsageNotificationRef.current,
-          notify: (title, body) => void sendNotification({ title, body }),
-        });
-      });
-    }, intervalMs);
-
-    return () => {
-      window.clearInterval(intervalId);
-    };
-  }, [refreshIntervalSeconds, showNotifications, notifyStaleUsage]);
+  }, [setProviderError]);
 
 
   useEffect(() => {
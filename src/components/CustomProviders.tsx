@@ -0,0 +1,137 @@
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Plus, Trash2 } from 'lucide-react';
+import type { CustomProviderConfig } from '../lib/types';
+import { useProviderAccounts, useUsageStore } from '../stores/usageStore';
+
+const inputClass =
+  'flex-1 min-w-0 px-2 py-1 text-[11px] bg-[var(--bg-base)] rounded-md border border-[var(--border-default)] text-[var(--text-secondary)] placeholder:text-[var(--text-quaternary)] focus:outline-none focus:border-[var(--accent-primary)]';
+
+// User-defined JSON quota endpoints, each shown as its own card
+export function CustomProviders() {
+  const entries = useProviderAccounts('custom');
+  const [id, setId] = useState('');
+  const [name, setName] = useState('');
+  const [url, setUrl] = useState('');
+  const [secret, setSecret] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const handleAdd = async () => {
+    const trimmedId = id.trim();
+    if (!trimmedId || !url.trim()) {
+      setError('Enter an id and a URL');
+      return;
+    }
+    const config: CustomProviderConfig = {
+      id: trimmedId,
+      name: name.trim() || trimmedId,
+      url: url.trim(),
+    };
+    setIsSaving(true);
+    try {
+      await invoke('save_custom_provider', { config, secret: secret.trim() || null });
+      setId('');
+      setName('');
+      setUrl('');
+      setSecret('');
+      setError(null);
+      void useUsageStore.getState().refreshProvider('custom', false, trimmedId);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleRemove = async (removedId: string) => {
+    try {
+      await invoke('remove_custom_provider', { id: removedId });
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-1.5" data-testid="custom-providers">
+      <span className="text-[11px] text-[var(--text-quaternary)]">Endpoints</span>
+      {entries.map((entry) => (
+        <div
+          key={entry.accountId}
+          className="flex items-center gap-2 px-2 py-1 rounded-md bg-[var(--bg-surface)]"
+        >
+          <span className="flex-1 min-w-0 truncate text-[12px] text-[var(--text-secondary)]">
+            {entry.name}
+            <span className="ml-1.5 text-[11px] text-[var(--text-quaternary)]">{entry.accountId}</span>
+          </span>
+          <button
+            type="button"
+            onClick={() => handleRemove(entry.accountId!)}
+            className="btn btn-icon focus-ring"
+            aria-label={`Remove ${entry.name}`}
+          >
+            <Trash2 className="w-3 h-3" aria-hidden="true" />
+          </button>
+        </div>
+      ))}
+      <div className="flex items-center gap-1.5">
+        <input
+          type="text"
+          value={id}
+          onChange={(event) => setId(event.target.value)}
+          placeholder="Id"
+          aria-label="New custom provider id"
+          className={inputClass}
+          autoComplete="off"
+          spellCheck={false}
+        />
+        <input
+          type="text"
+          value={name}
+          onChange={(event) => setName(event.target.value)}
+          placeholder="Name"
+          aria-label="New custom provider name"
+          className={inputClass}
+          autoComplete="off"
+        />
+      </div>
+      <input
+        type="url"
+        value={url}
+        onChange={(event) => setUrl(event.target.value)}
+        placeholder="https://example.com/quota"
+        aria-label="New custom provider URL"
+        className={`${inputClass} w-full`}
+        autoComplete="off"
+        spellCheck={false}
+      />
+      <div className="flex items-center gap-1.5">
+        <input
+          type="password"
+          value={secret}
+          onChange={(event) => setSecret(event.target.value)}
+          placeholder="API key (optional)"
+          aria-label="New custom provider API key"
+          className={inputClass}
+          autoComplete="off"
+        />
+        <button
+          type="button"
+          onClick={handleAdd}
+          disabled={isSaving}
+          className="btn btn-sm btn-ghost focus-ring text-[11px]"
+          data-testid="custom-provider-add"
+        >
+          <Plus className="w-3 h-3" aria-hidden="true" />
+          <span>Add</span>
+        </button>
+      </div>
+      {error && (
+        <p className="text-[11px] text-[var(--accent-warning)]" role="alert">
+          {error}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -2,6 +2,7 @@ import { useCallback, useMemo } from 'react';
 import { formatDistanceToNow } from 'date-fns';
 import { RefreshCw, AlertCircle, Loader2, ExternalLink } from 'lucide-react';
 import { ProgressBar } from './ProgressBar';
+import { RetryCountdown, useRetryCountdown } from './RetryCountdown';
 import { ProviderIcon, ProviderIconWithOverlay } from './ProviderIcons';
 import type { MenuBarDisplayTextPayload, ProviderState } from '../lib/types';
 import { PROVIDERS } from '../lib/providers';
@@ -28,11 +29,19 @@ export function MenuCard({ provider }: MenuCardProps) {
   const metadata = PROVIDERS[provider.id];
   const { usage, isLoading, lastError, status } = provider;
   const usageHistory = provider.usageHistory ?? [];
-  const statusPageUrl = metadata.statusPageUrl;
+  const statusPageUrl = status?.url;
+  // Refreshing before the provider's Retry-After would only be turned away
+  const isRateLimited = useRetryCountdown(provider.retryAt) !== null;
 
+  // While a refresh is running the button cancels it instead
   const handleRefresh = useCallback(() => {
-    useUsageStore.getState().refreshProvider(provider.id);
-  }, [provider.id]);
+    const store = useUsageStore.getState();
+    if (isLoading) {
+      void store.cancelRefresh(provider.id, provider.accountId);
+    } else {
+      void store.refreshProvider(provider.id, false, provider.accountId);
+    }
+  }, [isLoading, provider.id, provider.accountId]);
 
   const lastUpdatedText = usage?.updatedAt
     ? formatDistanceToNow(new Date(usage.updatedAt), { addSuffix: true })
@@ -181,6 +190,14 @@ export function MenuCard({ provider }: MenuCardProps) {
   }, [highestWindow, menuBarDisplayMode, sessionLabel, weeklyLabel]);
 
   const showSecondary = menuBarDisplayMode === 'session' && !!usage?.secondary;
+  // Windows past the three legacy slots, e.g. one per Gemini or Antigravity model
+  const extraWindows = useMemo(() => {
+    if (!usage?.windows?.length) return [];
+    const shownIds = new Set(
+      [usage.primary?.id, usage.secondary?.id, usage.tertiary?.id].filter(Boolean)
+    );
+    return usage.windows.filter((window) => !window.id || !shownIds.has(window.id));
+  }, [usage]);
   const usageBreakdown = useMemo(() => {
     if (!usage) return [];
     return [
@@ -239,8 +256,8 @@ export function MenuCard({ provider }: MenuCardProps) {
     <div 
       className="p-4 animate-fade-in"
       role="tabpanel"
-      id={`panel-${provider.id}`}
-      aria-label={`${metadata.name} usage`}
+      id={provider.accountId ? `panel-${provider.id}-${provider.accountId}` : `panel-${provider.id}`}
+      aria-label={`${provider.name} usage`}
     >
       {/* Header */}
       <div className="flex items-start justify-between mb-4">
@@ -255,7 +272,7 @@ export function MenuCard({ provider }: MenuCardProps) {
               <ProviderIconWithOverlay indicator={status?.indicator} />
             </span>
             <h2 className="text-[15px] font-semibold text-[var(--text-primary)]">
-              {metadata.name}
+              {provider.name}
             </h2>
             {usage?.identity?.plan && !hidePersonalInfo && (
               <span className="badge">
@@ -272,9 +289,9 @@ export function MenuCard({ provider }: MenuCardProps) {
 
         <button
           onClick={handleRefresh}
-          disabled={isLoading}
+          disabled={isRateLimited && !isLoading}
           className="btn btn-icon focus-ring"
-          aria-label={`Refresh ${metadata.name}`}
+          aria-label={isLoading ? `Cancel refreshing ${provider.name}` : `Refresh ${provider.name}`}
         >
           {isLoading ? (
             <Loader2 className="w-4 h-4 animate-spin" aria-hidden="true" />
@@ -284,6 +301,8 @@ export function MenuCard({ provider }: MenuCardProps) {
         </button>
       </div>
 
+      <RetryCountdown retryAt={provider.retryAt} className="mb-3" />
+
       {/* Error State */}
       {lastError && !usage && (
         <div 
@@ -344,6 +363,19 @@ export function MenuCard({ provider }: MenuCardProps) {
             />
           )}
 
+          {showExtraUsage && extraWindows.map((window, index) => (
+            <ProgressBar
+              key={window.id ?? index}
+              percent={window.usedPercent}
+              label={window.label || window.id || 'Extra'}
+              resetDescription={window.resetDescription}
+              resetsAt={window.resetsAt}
+              resetTimeDisplayMode={resetTimeDisplayMode}
+              size="sm"
+              displayMode={usageBarDisplayMode}
+            />
+          ))}
+
           {menuBarDisplayMode === 'pace' && usage.secondary && (
             <div className="rounded-lg border border-[var(--border-subtle)] bg-[var(--bg-surface)] px-3 py-2.5">
               <div className="flex items-center justify-between">
@@ -458,7 +490,7 @@ export function MenuCard({ provider }: MenuCardProps) {
             </div>
           </div>
           <div className="flex items-baseline justify-between">
-            <span className="text-[13px] text-[var(--text-tertiary)]">This Month</span>
+            <span className="text-[13px] text-[var(--text-tertiary)]">Last 30 Days</span>
             <span className="text-[13px] text-[var(--text-secondary)] tabular-nums">
               {usage.cost.currency}{usage.cost.monthAmount.toFixed(2)}
             </span>
@@ -3,10 +3,11 @@ import { Settings, RefreshCw, Plug, AlertCircle } from 'lucide-react';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { MenuCard } from './MenuCard';
 import { ProviderTabs, ProviderSwitcherButtons } from './ProviderTabs';
-import { useUsageStore, useEnabledProviders } from '../stores/usageStore';
+import { useUsageStore, useEnabledProviders, useProviderAccounts } from '../stores/usageStore';
 import { useSettingsStore } from '../stores/settingsStore';
 import { ProviderIcon } from './ProviderIcons';
-import { PROVIDERS } from '../lib/providers';
+import { RetryCountdown, useRetryCountdown } from './RetryCountdown';
+import { PROVIDERS, ACCOUNT_ONLY_PROVIDERS } from '../lib/providers';
 import type { ProviderId } from '../lib/types';
 
 interface PopupWindowProps {
@@ -21,12 +22,20 @@ export function PopupWindow({ onOpenSettings }: PopupWindowProps) {
   const hasHydrated = useSettingsStore((s) => s.hasHydrated);
   const displayMode = useSettingsStore((s) => s.displayMode);
   const selectedProvider = useUsageStore((s) => s.providers[s.activeProvider]);
+  const activeProvider = useUsageStore((s) => s.activeProvider);
+  const selectedAccounts = useProviderAccounts(activeProvider);
+  // Custom providers and plugins only have their own entries, shown as account cards
+  const selectedIsAccountOnly = ACCOUNT_ONLY_PROVIDERS.includes(activeProvider);
+  const hasAnyAccountResult = useUsageStore((s) =>
+    Object.values(s.accounts).some((account) => account.enabled && (account.usage || account.lastError))
+  );
   const lastRefreshKeyRef = useRef<string | null>(null);
   const hasEnabledProvidersInSettings = settingsEnabledProviders.length > 0;
   
   // Get the currently selected provider (might be an error provider)
   const selectedHasError = selectedProvider?.lastError && !selectedProvider?.usage;
   const selectedIsLoading = selectedProvider?.isLoading;
+  const selectedIsRateLimited = useRetryCountdown(selectedProvider?.retryAt) !== null;
   const selectedHasNoData = selectedProvider && !selectedProvider.usage && !selectedProvider.lastError && !selectedProvider.isLoading;
   
   // Check if usage store is synced with settings store
@@ -35,7 +44,7 @@ export function PopupWindow({ onOpenSettings }: PopupWindowProps) {
   const isProvidersSynced = usageEnabledIds === settingsEnabledIds;
 
   // Check if any enabled provider already has results (usage or error)
-  const hasAnyResult = enabledProviders.some((p) => p.usage || p.lastError);
+  const hasAnyResult = enabledProviders.some((p) => p.usage || p.lastError) || hasAnyAccountResult;
 
   // Timeout for initial loading - don't show spinner forever
   const [loadingTimedOut, setLoadingTimedOut] = useState(false);
@@ -161,6 +170,31 @@ export function PopupWindow({ onOpenSettings }: PopupWindowProps) {
               Loading…
             </p>
           </div>
+        ) : selectedIsAccountOnly ? (
+          selectedAccounts.length === 0 && (
+            <div
+              className="flex flex-col items-center justify-center py-10 px-6 text-center animate-slide-up"
+              data-testid="provider-accounts-empty"
+            >
+              <div className="w-11 h-11 rounded-full bg-[var(--bg-subtle)] flex items-center justify-center mb-4">
+                <ProviderIcon providerId={activeProvider} className="w-5 h-5 text-[var(--text-quaternary)]" aria-hidden="true" />
+              </div>
+              <h2 className="text-[15px] font-semibold text-[var(--text-primary)] mb-1.5 text-balance">
+                {PROVIDERS[activeProvider].name}
+              </h2>
+              <p className="text-sm text-[var(--text-tertiary)] mb-5 max-w-[220px]">
+                {activeProvider === 'plugin'
+                  ? 'Add plugins to the plugins folder, then rescan in Settings'
+                  : 'Add a quota endpoint in Settings to track it here'}
+              </p>
+              <button
+                onClick={onOpenSettings}
+                className="btn btn-primary focus-ring"
+              >
+                Settings
+              </button>
+            </div>
+          )
         ) : selectedIsLoading && selectedProvider ? (
           // Show loading for selected provider
           <div className="flex flex-col items-center justify-center py-12 animate-fade-in">
@@ -194,10 +228,11 @@ export function PopupWindow({ onOpenSettings }: PopupWindowProps) {
             <div className="w-full max-w-[240px] rounded-lg border border-[var(--accent-warning)]/20 bg-[var(--accent-warning)]/5 px-3 py-2 text-[12px] text-[var(--accent-warning)]/90 mb-4">
               {selectedProvider.lastError}
             </div>
+            <RetryCountdown retryAt={selectedProvider.retryAt} className="-mt-2 mb-4" />
             <div className="flex gap-2">
               <button
                 onClick={() => handleRefreshProvider(selectedProvider.id)}
-                disabled={selectedProvider.isLoading}
+                disabled={selectedProvider.isLoading || selectedIsRateLimited}
                 className="btn btn-primary focus-ring"
               >
                 {selectedProvider.isLoading ? 'Refreshing…' : 'Try Again'}
@@ -285,6 +320,17 @@ export function PopupWindow({ onOpenSettings }: PopupWindowProps) {
             </button>
           </div>
         )}
+
+        {/* Additional accounts of the selected provider */}
+        {!isInitialLoading && selectedAccounts.length > 0 && (
+          <div data-testid="provider-account-cards">
+            {selectedAccounts.map((account) => (
+              <div key={account.accountId} className="border-t border-[var(--border-subtle)]">
+                <MenuCard provider={account} />
+              </div>
+            ))}
+          </div>
+        )}
       </div>
 
       {/* Footer */}
@@ -0,0 +1,133 @@
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Plus, Trash2 } from 'lucide-react';
+import type { ProviderAccount, ProviderId } from '../lib/types';
+import { PROVIDERS } from '../lib/providers';
+import { useProviderAccounts, useUsageStore } from '../stores/usageStore';
+
+interface ProviderAccountsProps {
+  providerId: ProviderId;
+}
+
+const inputClass =
+  'flex-1 min-w-0 px-2 py-1 text-[11px] bg-[var(--bg-base)] rounded-md border border-[var(--border-default)] text-[var(--text-secondary)] placeholder:text-[var(--text-quaternary)] focus:outline-none focus:border-[var(--accent-primary)]';
+
+// Additional accounts of a provider, shown next to its default account
+export function ProviderAccounts({ providerId }: ProviderAccountsProps) {
+  const accounts = useProviderAccounts(providerId);
+  const [accountId, setAccountId] = useState('');
+  const [label, setLabel] = useState('');
+  const [credentialsPath, setCredentialsPath] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+  const usesCookies = PROVIDERS[providerId].authMethod === 'cookies';
+
+  const handleAdd = async () => {
+    const trimmedId = accountId.trim();
+    if (!trimmedId) {
+      setError('Enter an account id');
+      return;
+    }
+    const account: ProviderAccount = {
+      providerId,
+      accountId: trimmedId,
+      label: label.trim() || trimmedId,
+      credentialsPath: credentialsPath.trim() || undefined,
+    };
+    setIsSaving(true);
+    try {
+      await invoke('add_account', { account });
+      setAccountId('');
+      setLabel('');
+      setCredentialsPath('');
+      setError(null);
+      void useUsageStore.getState().refreshProvider(providerId, false, trimmedId);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleRemove = async (removedId: string) => {
+    try {
+      await invoke('remove_account', { providerId, accountId: removedId });
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-1.5" data-testid={`provider-accounts-${providerId}`}>
+      <span className="text-[11px] text-[var(--text-quaternary)]">Additional accounts</span>
+      {accounts.map((account) => (
+        <div
+          key={account.accountId}
+          className="flex items-center gap-2 px-2 py-1 rounded-md bg-[var(--bg-surface)]"
+        >
+          <span className="flex-1 min-w-0 truncate text-[12px] text-[var(--text-secondary)]">
+            {account.name}
+            <span className="ml-1.5 text-[11px] text-[var(--text-quaternary)]">{account.accountId}</span>
+          </span>
+          <button
+            type="button"
+            onClick={() => handleRemove(account.accountId!)}
+            className="btn btn-icon focus-ring"
+            aria-label={`Remove ${account.name}`}
+          >
+            <Trash2 className="w-3 h-3" aria-hidden="true" />
+          </button>
+        </div>
+      ))}
+      <div className="flex items-center gap-1.5">
+        <input
+          type="text"
+          value={accountId}
+          onChange={(event) => setAccountId(event.target.value)}
+          placeholder="Account id"
+          aria-label={`New ${PROVIDERS[providerId].name} account id`}
+          className={inputClass}
+          autoComplete="off"
+          spellCheck={false}
+        />
+        <input
+          type="text"
+          value={label}
+          onChange={(event) => setLabel(event.target.value)}
+          placeholder="Label"
+          aria-label={`New ${PROVIDERS[providerId].name} account label`}
+          className={inputClass}
+          autoComplete="off"
+        />
+      </div>
+      <div className="flex items-center gap-1.5">
+        <input
+          type="text"
+          value={credentialsPath}
+          onChange={(event) => setCredentialsPath(event.target.value)}
+          placeholder={usesCookies ? 'Credentials file (optional)' : 'Credentials file'}
+          aria-label={`New ${PROVIDERS[providerId].name} account credentials file`}
+          className={inputClass}
+          autoComplete="off"
+          spellCheck={false}
+        />
+        <button
+          type="button"
+          onClick={handleAdd}
+          disabled={isSaving}
+          className="btn btn-sm btn-ghost focus-ring text-[11px]"
+          data-testid={`provider-account-add-${providerId}`}
+        >
+          <Plus className="w-3 h-3" aria-hidden="true" />
+          <span>Add</span>
+        </button>
+      </div>
+      {error && (
+        <p className="text-[11px] text-[var(--accent-warning)]" role="alert">
+          {error}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -174,6 +174,25 @@ export function SyntheticIcon({ className, 'aria-hidden': ariaHidden = true }: I
   );
 }
 
+// Custom HTTP providers - gauge icon
+export function CustomIcon({ className, 'aria-hidden': ariaHidden = true }: IconProps) {
+  return (
+    <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" className={className} aria-hidden={ariaHidden}>
+      <path d="m12 14 4-4"/>
+      <path d="M3.34 19a10 10 0 1 1 17.32 0"/>
+    </svg>
+  );
+}
+
+// External plugins - puzzle piece icon
+export function PluginIcon({ className, 'aria-hidden': ariaHidden = true }: IconProps) {
+  return (
+    <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" className={className} aria-hidden={ariaHidden}>
+      <path d="M19.44 7.85c-.14.46.02.95.37 1.3l1.57 1.57a2.41 2.41 0 0 1 0 3.41l-1.57 1.57a1.3 1.3 0 0 1-2.21-.72 2.5 2.5 0 1 0-2.79 2.79 1.3 1.3 0 0 1 .72 2.21l-1.57 1.57a2.41 2.41 0 0 1-3.41 0l-1.57-1.57a1.3 1.3 0 0 0-2.21.72 2.5 2.5 0 1 1-2.79-2.79 1.3 1.3 0 0 0 .72-2.21l-1.57-1.57a2.41 2.41 0 0 1 0-3.41l1.57-1.57a1.3 1.3 0 0 1 2.21.72 2.5 2.5 0 1 0 2.79-2.79 1.3 1.3 0 0 1-.72-2.21l1.57-1.57a2.41 2.41 0 0 1 3.41 0l1.57 1.57c.35.35.84.51 1.3.37"/>
+    </svg>
+  );
+}
+
 // Map provider IDs to their icon components
 export const ProviderIconMap: Record<ProviderId, React.ComponentType<IconProps>> = {
   claude: ClaudeIcon,
@@ -194,6 +213,8 @@ export const ProviderIconMap: Record<ProviderId, React.ComponentType<IconProps>>
   kimi_k2: KimiIcon,
   antigravity: AntigravityIcon,
   synthetic: SyntheticIcon,
+  custom: CustomIcon,
+  plugin: PluginIcon,
 };
 
 // Helper component that renders the appropriate icon for a provider
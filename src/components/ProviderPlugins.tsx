@@ -0,0 +1,60 @@
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { RefreshCw } from 'lucide-react';
+import { useProviderAccounts, useUsageStore } from '../stores/usageStore';
+
+// Plugin executables found in the plugins folder, each shown as its own card
+export function ProviderPlugins() {
+  const plugins = useProviderAccounts('plugin');
+  const [error, setError] = useState<string | null>(null);
+  const [isScanning, setIsScanning] = useState(false);
+
+  const handleRescan = async () => {
+    setIsScanning(true);
+    try {
+      await invoke('rescan_plugins');
+      const store = useUsageStore.getState();
+      await store.loadAccounts();
+      void store.refreshProvider('plugin', false);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsScanning(false);
+    }
+  };
+
+  return (
+    <div className="space-y-1.5" data-testid="provider-plugins">
+      <span className="text-[11px] text-[var(--text-quaternary)]">Installed plugins</span>
+      {plugins.length === 0 && (
+        <p className="text-[11px] text-[var(--text-tertiary)]">
+          No plugins found. Add <code>incubar-provider-&lt;id&gt;</code> executables to the plugins folder.
+        </p>
+      )}
+      {plugins.map((plugin) => (
+        <div
+          key={plugin.accountId}
+          className="px-2 py-1 rounded-md bg-[var(--bg-surface)] text-[12px] text-[var(--text-secondary)] truncate"
+        >
+          {plugin.name}
+        </div>
+      ))}
+      <button
+        type="button"
+        onClick={handleRescan}
+        disabled={isScanning}
+        className="btn btn-sm btn-ghost focus-ring text-[11px]"
+        data-testid="provider-plugins-rescan"
+      >
+        <RefreshCw className={`w-3 h-3 ${isScanning ? 'animate-spin' : ''}`} aria-hidden="true" />
+        <span>Rescan</span>
+      </button>
+      {error && (
+        <p className="text-[11px] text-[var(--accent-warning)]" role="alert">
+          {error}
+        </p>
+      )}
+    </div>
+  );
+}
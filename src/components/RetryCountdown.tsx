@@ -0,0 +1,57 @@
+import { useEffect, useState } from 'react';
+import { Clock } from 'lucide-react';
+
+const secondsUntil = (retryAt?: string) => {
+  if (!retryAt) return null;
+  const retryAtMs = Date.parse(retryAt);
+  if (Number.isNaN(retryAtMs)) return null;
+  const seconds = Math.ceil((retryAtMs - Date.now()) / 1000);
+  return seconds > 0 ? seconds : null;
+};
+
+export const formatRetryCountdown = (seconds: number) => {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  const rest = seconds % 60;
+  if (hours > 0) return `${hours}h ${minutes}m`;
+  if (minutes > 0) return `${minutes}m ${rest}s`;
+  return `${rest}s`;
+};
+
+// Seconds until a rate-limited provider is tried again, or null once it may be refreshed
+export function useRetryCountdown(retryAt?: string) {
+  const [seconds, setSeconds] = useState(() => secondsUntil(retryAt));
+
+  useEffect(() => {
+    setSeconds(secondsUntil(retryAt));
+    if (secondsUntil(retryAt) === null) return undefined;
+    const intervalId = window.setInterval(() => {
+      const remaining = secondsUntil(retryAt);
+      setSeconds(remaining);
+      if (remaining === null) window.clearInterval(intervalId);
+    }, 1000);
+    return () => window.clearInterval(intervalId);
+  }, [retryAt]);
+
+  return seconds;
+}
+
+interface RetryCountdownProps {
+  retryAt?: string;         // ISO date string
+  className?: string;
+}
+
+export function RetryCountdown({ retryAt, className = '' }: RetryCountdownProps) {
+  const seconds = useRetryCountdown(retryAt);
+  if (seconds === null) return null;
+
+  return (
+    <p
+      className={`flex items-center gap-1.5 text-[12px] text-[var(--accent-warning)]/90 ${className}`}
+      role="status"
+    >
+      <Clock className="w-3.5 h-3.5 flex-shrink-0" aria-hidden="true" />
+      Rate limited · retrying in {formatRetryCountdown(seconds)}
+    </p>
+  );
+}
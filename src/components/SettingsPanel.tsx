@@ -18,6 +18,12 @@ import { COOKIE_SOURCES, COOKIE_SOURCE_LABELS } from '../lib/cookieSources';
 import { useSettingsStore } from '../stores/settingsStore';
 import { useUsageStore } from '../stores/usageStore';
 import { ProviderIcon } from './ProviderIcons';
+import { ProviderAccounts } from './ProviderAccounts';
+import { CustomProviders } from './CustomProviders';
+import { ProviderPlugins } from './ProviderPlugins';
+
+// Providers that never call a remote API, so a base URL override does not apply
+const LOCAL_ONLY_PROVIDERS: ProviderId[] = ['antigravity', 'jetbrains', 'kiro', 'custom', 'plugin'];
 
 interface AuthStatus {
   authenticated: boolean;
@@ -76,6 +82,10 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
   const storeUsageHistory = useSettingsStore((s) => s.storeUsageHistory);
   const pollProviderStatus = useSettingsStore((s) => s.pollProviderStatus);
   const redactPersonalInfo = useSettingsStore((s) => s.redactPersonalInfo);
+  const metricsExporterEnabled = useSettingsStore((s) => s.metricsExporterEnabled);
+  const metricsExporterPort = useSettingsStore((s) => s.metricsExporterPort);
+  const apiServerEnabled = useSettingsStore((s) => s.apiServerEnabled);
+  const apiServerPort = useSettingsStore((s) => s.apiServerPort);
   const autoUpdateEnabled = useSettingsStore((s) => s.autoUpdateEnabled);
   const updateChannel = useSettingsStore((s) => s.updateChannel);
   const showNotifications = useSettingsStore((s) => s.showNotifications);
@@ -90,6 +100,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
     (s) => s.debugKeepCliSessionsAlive
   );
   const debugRandomBlink = useSettingsStore((s) => s.debugRandomBlink);
+  const debugRecordFixtures = useSettingsStore((s) => s.debugRecordFixtures);
   const hidePersonalInfo = useSettingsStore((s) => s.hidePersonalInfo);
   const setHidePersonalInfo = useSettingsStore((s) => s.setHidePersonalInfo);
   const setRedactPersonalInfo = useSettingsStore((s) => s.setRedactPersonalInfo);
@@ -111,6 +122,9 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
   );
   const [updateMessage, setUpdateMessage] = useState<string | null>(null);
   const cookieSources = useSettingsStore((s) => s.cookieSources);
+  const providerBaseUrls = useSettingsStore((s) => s.providerBaseUrls);
+  const providerHostBaseUrls = useSettingsStore((s) => s.providerHostBaseUrls);
+  const providerRefreshIntervals = useSettingsStore((s) => s.providerRefreshIntervals);
   const [draggingProviderId, setDraggingProviderId] = useState<ProviderId | null>(null);
   const [dragOverProviderId, setDragOverProviderId] = useState<ProviderId | null>(null);
   const [activeTab, setActiveTab] = useState<SettingsTab>('providers');
@@ -257,7 +271,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
           settingsStore.enableProvider('cursor');
           void settingsStore.syncProviderEnabled('cursor', true);
           useUsageStore.getState().setProviderEnabled('cursor', true);
-          useUsageStore.getState().refreshProvider('cursor');
+          useUsageStore.getState().refreshProvider('cursor', true);
           // Auto-collapse after success
           const timeoutId = setTimeout(() => {
             if (active) clearProviderLoginState('cursor');
@@ -292,7 +306,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         settingsStore.enableProvider(providerId);
         void settingsStore.syncProviderEnabled(providerId, true);
         useUsageStore.getState().setProviderEnabled(providerId, true);
-        useUsageStore.getState().refreshProvider(providerId);
+        useUsageStore.getState().refreshProvider(providerId, true);
         // Auto-collapse after success
         const timeoutId = setTimeout(() => {
           if (active) clearProviderLoginState(providerId);
@@ -408,6 +422,28 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
     useSettingsStore.getState().setPollProviderStatus(enabled);
   }, []);
 
+  const handleSetMetricsExporterEnabled = useCallback((enabled: boolean) => {
+    useSettingsStore.getState().setMetricsExporterEnabled(enabled);
+  }, []);
+
+  const handleSetMetricsExporterPort = useCallback((value: string) => {
+    const port = Number(value);
+    if (Number.isInteger(port) && port >= 1024 && port <= 65535) {
+      useSettingsStore.getState().setMetricsExporterPort(port);
+    }
+  }, []);
+
+  const handleSetApiServerEnabled = useCallback((enabled: boolean) => {
+    useSettingsStore.getState().setApiServerEnabled(enabled);
+  }, []);
+
+  const handleSetApiServerPort = useCallback((value: string) => {
+    const port = Number(value);
+    if (Number.isInteger(port) && port >= 1024 && port <= 65535) {
+      useSettingsStore.getState().setApiServerPort(port);
+    }
+  }, []);
+
   const handleSetShowNotifications = useCallback((show: boolean) => {
     useSettingsStore.getState().setShowNotifications(show);
   }, []);
@@ -563,6 +599,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
       store.setDebugFileLogging(false);
       store.setDebugKeepCliSessionsAlive(false);
       store.setDebugRandomBlink(false);
+      store.setDebugRecordFixtures(false);
     }
   }, []);
 
@@ -578,6 +615,10 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
     useSettingsStore.getState().setDebugRandomBlink(enabled);
   }, []);
 
+  const handleSetDebugRecordFixtures = useCallback((enabled: boolean) => {
+    useSettingsStore.getState().setDebugRecordFixtures(enabled);
+  }, []);
+
   const handleSetDebugDisableKeychainAccess = useCallback((enabled: boolean) => {
     useSettingsStore.getState().setDebugDisableKeychainAccess(enabled);
   }, []);
@@ -632,7 +673,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
           settingsStore.enableProvider(providerId);
           void settingsStore.syncProviderEnabled(providerId, true);
           useUsageStore.getState().setProviderEnabled(providerId, true);
-          useUsageStore.getState().refreshProvider(providerId);
+          useUsageStore.getState().refreshProvider(providerId, true);
           setTimeout(() => clearProviderLoginState(providerId), 2000);
           return;
         }
@@ -664,6 +705,22 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         return;
       }
 
+      if (providerId === 'custom') {
+        updateProviderLoginState(providerId, { 
+          message: 'Add a JSON quota endpoint below. Each one gets its own card.',
+          isLoggingIn: false,
+        });
+        return;
+      }
+
+      if (providerId === 'plugin') {
+        updateProviderLoginState(providerId, { 
+          message: 'Plugins are found in the plugins folder. Each one gets its own card.',
+          isLoggingIn: false,
+        });
+        return;
+      }
+
       if (providerId === 'jetbrains') {
         updateProviderLoginState(providerId, { 
           message: 'Open a JetBrains IDE with AI Assistant enabled to connect automatically.',
@@ -708,7 +765,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         settingsStore.enableProvider(providerId);
         void settingsStore.syncProviderEnabled(providerId, true);
         useUsageStore.getState().setProviderEnabled(providerId, true);
-        useUsageStore.getState().refreshProvider(providerId);
+        useUsageStore.getState().refreshProvider(providerId, true);
         setTimeout(() => clearProviderLoginState(providerId), 2000);
       } else {
         updateProviderLoginState(providerId, { 
@@ -765,7 +822,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         settingsStore.enableProvider(providerId);
         void settingsStore.syncProviderEnabled(providerId, true);
         useUsageStore.getState().setProviderEnabled(providerId, true);
-        useUsageStore.getState().refreshProvider(providerId);
+        useUsageStore.getState().refreshProvider(providerId, true);
         setTimeout(() => clearProviderLoginState(providerId), 2000);
       } else {
         updateProviderLoginState(providerId, { 
@@ -837,7 +894,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         settingsStore.enableProvider(providerId);
         void settingsStore.syncProviderEnabled(providerId, true);
         useUsageStore.getState().setProviderEnabled(providerId, true);
-        useUsageStore.getState().refreshProvider(providerId);
+        useUsageStore.getState().refreshProvider(providerId, true);
         setTimeout(() => clearProviderLoginState(providerId), 2000);
       } else {
         updateProviderLoginState(providerId, { 
@@ -910,7 +967,7 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
         settingsStore.enableProvider('copilot');
         void settingsStore.syncProviderEnabled('copilot', true);
         useUsageStore.getState().setProviderEnabled('copilot', true);
-        useUsageStore.getState().refreshProvider('copilot');
+        useUsageStore.getState().refreshProvider('copilot', true);
         setTimeout(() => clearProviderLoginState(providerId), 2000);
       } else {
         updateProviderLoginState(providerId, { 
@@ -1024,6 +1081,12 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
     { label: '15m', value: 900 },
   ];
 
+  const providerRefreshIntervalOptions = [
+    { label: '30s', value: 30 },
+    ...refreshIntervals,
+    { label: '30m', value: 1800 },
+  ];
+
   const keychainPromptSources: CookieSource[] = ['chrome', 'arc', 'edge', 'brave', 'opera'];
 
   const getAuthMethodLabel = (method: string) => {
@@ -1377,6 +1440,99 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
                         </div>
                       )}
 
+                      {/* Base URL override, e.g. a corporate reverse proxy */}
+                      {!LOCAL_ONLY_PROVIDERS.includes(id) && !provider.baseUrlHosts && !loginState?.deviceCode && (
+                        <div className="flex items-center gap-2">
+                          <label
+                            htmlFor={`base-url-${id}`}
+                            className="text-[11px] text-[var(--text-quaternary)]"
+                          >
+                            Base URL
+                          </label>
+                          <input
+                            id={`base-url-${id}`}
+                            type="url"
+                            defaultValue={providerBaseUrls?.[id] ?? ''}
+                            onBlur={(event) => useSettingsStore.getState().setProviderBaseUrl(id, event.target.value)}
+                            placeholder="Default"
+                            className="flex-1 px-2 py-1 text-[11px] bg-[var(--bg-base)] rounded-md border border-[var(--border-default)] text-[var(--text-secondary)] placeholder:text-[var(--text-quaternary)] focus:outline-none focus:border-[var(--accent-primary)]"
+                            autoComplete="off"
+                            spellCheck={false}
+                          />
+                        </div>
+                      )}
+
+                      {/* One base URL per host for providers that talk to several */}
+                      {provider.baseUrlHosts && !loginState?.deviceCode && (
+                        <div className="space-y-1.5" data-testid={`host-base-urls-${id}`}>
+                          {provider.baseUrlHosts.map((host) => (
+                            <div key={host} className="flex items-center gap-2">
+                              <label
+                                htmlFor={`base-url-${id}-${host}`}
+                                className="text-[11px] text-[var(--text-quaternary)] truncate max-w-[45%]"
+                              >
+                                {host}
+                              </label>
+                              <input
+                                id={`base-url-${id}-${host}`}
+                                type="url"
+                                defaultValue={providerHostBaseUrls?.[id]?.[host] ?? ''}
+                                onBlur={(event) =>
+                                  useSettingsStore.getState().setProviderHostBaseUrl(id, host, event.target.value)
+                                }
+                                placeholder="Default"
+                                className="flex-1 px-2 py-1 text-[11px] bg-[var(--bg-base)] rounded-md border border-[var(--border-default)] text-[var(--text-secondary)] placeholder:text-[var(--text-quaternary)] focus:outline-none focus:border-[var(--accent-primary)]"
+                                autoComplete="off"
+                                spellCheck={false}
+                              />
+                            </div>
+                          ))}
+                        </div>
+                      )}
+
+                      {/* Per-provider refresh interval, overriding the global one */}
+                      {!loginState?.deviceCode && (
+                        <div className="flex items-center gap-2">
+                          <label
+                            htmlFor={`refresh-interval-${id}`}
+                            className="text-[11px] text-[var(--text-quaternary)]"
+                          >
+                            Refresh
+                          </label>
+                          <select
+                            id={`refresh-interval-${id}`}
+                            value={providerRefreshIntervals?.[id] ?? 'default'}
+                            onChange={(event) =>
+                              useSettingsStore
+                                .getState()
+                                .setProviderRefreshInterval(
+                                  id,
+                                  event.target.value === 'default' ? null : Number(event.target.value)
+                                )
+                            }
+                            className="bg-[var(--bg-base)] text-[11px] text-[var(--text-secondary)] border border-[var(--border-default)] rounded-md px-2 py-1 focus:outline-none focus:border-[var(--accent-primary)]"
+                          >
+                            <option value="default">Default</option>
+                            {providerRefreshIntervalOptions.map(({ label, value }) => (
+                              <option key={value} value={value}>
+                                {label}
+                              </option>
+                            ))}
+                          </select>
+                        </div>
+                      )}
+
+                      {/* Additional accounts, e.g. a work and a personal login */}
+                      {provider.supportsAccounts && !loginState?.deviceCode && (
+                        <ProviderAccounts providerId={id} />
+                      )}
+
+                      {/* User-defined quota endpoints */}
+                      {id === 'custom' && <CustomProviders />}
+
+                      {/* Plugin executables from the plugins folder */}
+                      {id === 'plugin' && <ProviderPlugins />}
+
                       {/* Cancel/Close button for non-device-code flows */}
                       {!loginState?.deviceCode && (
                         <button
@@ -1751,6 +1907,26 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
                 <p className="mt-2 text-[11px] text-[var(--text-quaternary)]">
                   Status polling checks provider health pages and never sends your usage data.
                 </p>
+                <LocalServerToggle
+                  testId="metrics-exporter"
+                  portId="metrics-exporter-port"
+                  label="Prometheus metrics endpoint"
+                  enabled={metricsExporterEnabled}
+                  port={metricsExporterPort}
+                  onToggle={handleSetMetricsExporterEnabled}
+                  onPortChange={handleSetMetricsExporterPort}
+                  description={`Serves cached usage in OpenMetrics format at http://127.0.0.1:${metricsExporterPort}/metrics, reachable only from this machine.`}
+                />
+                <LocalServerToggle
+                  testId="local-api"
+                  portId="api-server-port"
+                  label="Local JSON API"
+                  enabled={apiServerEnabled}
+                  port={apiServerPort}
+                  onToggle={handleSetApiServerEnabled}
+                  onPortChange={handleSetApiServerPort}
+                  description={`Lets local tools read usage from http://127.0.0.1:${apiServerPort}/v1. Requests need the token saved as api-token in the IncuBar data folder.`}
+                />
               </div>
               <ToggleOption label="Notifications" enabled={showNotifications} onChange={handleSetShowNotifications} />
               {showNotifications && (
@@ -1890,6 +2066,11 @@ export function SettingsPanel({ showTabs = true }: SettingsPanelProps) {
                   enabled={debugRandomBlink}
                   onChange={handleSetDebugRandomBlink}
                 />
+                <ToggleOption
+                  label="Record HTTP Fixtures"
+                  enabled={debugRecordFixtures}
+                  onChange={handleSetDebugRecordFixtures}
+                />
                 <div className="mt-2 space-y-2 rounded-md bg-[var(--bg-surface)] border border-[var(--border-subtle)] px-3 py-2">
                   <div className="text-[13px] text-[var(--text-secondary)]">Support Bundle</div>
                   <p className="text-[11px] text-[var(--text-quaternary)]">
@@ -2016,3 +2197,51 @@ function ToggleOption({ label, enabled, onChange }: ToggleOptionProps) {
     </button>
   );
 }
+
+interface LocalServerToggleProps {
+  testId: string;
+  portId: string;
+  label: string;
+  enabled: boolean;
+  port: number;
+  onToggle: (enabled: boolean) => void;
+  onPortChange: (value: string) => void;
+  description: string;
+}
+
+// An opt-in server on 127.0.0.1 and the port it listens on
+function LocalServerToggle({
+  testId,
+  portId,
+  label,
+  enabled,
+  port,
+  onToggle,
+  onPortChange,
+  description,
+}: LocalServerToggleProps) {
+  return (
+    <>
+      <div className="mt-2 space-y-1" data-testid={testId}>
+        <ToggleOption label={label} enabled={enabled} onChange={onToggle} />
+        {enabled && (
+          <div className="flex items-center gap-2 pl-2">
+            <label htmlFor={portId} className="text-[11px] text-[var(--text-quaternary)]">
+              Port
+            </label>
+            <input
+              id={portId}
+              type="number"
+              min={1024}
+              max={65535}
+              defaultValue={port}
+              onBlur={(event) => onPortChange(event.target.value)}
+              className="w-24 px-2 py-1 text-[11px] bg-[var(--bg-base)] rounded-md border border-[var(--border-default)] text-[var(--text-secondary)] focus:outline-none focus:border-[var(--accent-primary)]"
+            />
+          </div>
+        )}
+      </div>
+      <p className="mt-2 text-[11px] text-[var(--text-quaternary)]">{description}</p>
+    </>
+  );
+}
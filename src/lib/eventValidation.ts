@@ -19,16 +19,32 @@ const providerIdSchema = z.enum([
   'jetbrains',
   'opencode',
   'synthetic',
+  'custom',
+  'plugin',
 ]);
 
 const rateWindowSchema = z.object({
+  id: z.string().optional(),
+  category: z.enum(['session', 'weekly', 'monthly', 'model']).optional(),
   usedPercent: z.number(),
+  used: z.number().optional(),
+  limit: z.number().optional(),
+  unit: z.string().optional(),
   windowMinutes: z.number().optional(),
   resetsAt: z.string().optional(),
   resetDescription: z.string().optional(),
   label: z.string().optional(),
 });
 
+const forecastSchema = z.object({
+  id: z.string(),
+  ratePerHour: z.number(),
+  exhaustsAt: z.string().optional(),
+  resetsFirst: z.boolean(),
+  confidence: z.number(),
+  description: z.string(),
+});
+
 const costSnapshotSchema = z.object({
   todayAmount: z.number(),
   todayTokens: z.number(),
@@ -59,15 +75,40 @@ const usageSnapshotSchema = z.object({
   identity: providerIdentitySchema.optional(),
   updatedAt: z.string(),
   error: z.string().optional(),
+  retryAt: z.string().optional(),
+  windows: z.array(rateWindowSchema).optional(),
+  forecasts: z.array(forecastSchema).optional(),
+  version: z.number().optional(),
 });
 
 const usageUpdateEventSchema = z.object({
   providerId: providerIdSchema,
+  accountId: z.string().optional(),
   usage: usageSnapshotSchema,
 });
 
 export type UsageUpdatePayload = z.infer<typeof usageUpdateEventSchema>;
 
+const refreshFailedEventSchema = usageUpdateEventSchema.extend({
+  errorKind: z
+    .enum([
+      'notAuthenticated',
+      'tokenExpired',
+      'forbidden',
+      'rateLimited',
+      'network',
+      'timeout',
+      'http',
+      'parse',
+      'unavailable',
+      'cancelled',
+      'other',
+    ])
+    .optional(),
+});
+
+export type RefreshFailedPayload = z.infer<typeof refreshFailedEventSchema>;
+
 export const parseUsageUpdateEvent = (payload: unknown): UsageUpdatePayload | null => {
   const result = usageUpdateEventSchema.safeParse(payload);
   if (!result.success) {
@@ -76,3 +117,12 @@ export const parseUsageUpdateEvent = (payload: unknown): UsageUpdatePayload | nu
   }
   return result.data;
 };
+
+export const parseRefreshFailedEvent = (payload: unknown): RefreshFailedPayload | null => {
+  const result = refreshFailedEventSchema.safeParse(payload);
+  if (!result.success) {
+    console.warn('Invalid refresh failure payload received', result.error);
+    return null;
+  }
+  return result.data;
+};
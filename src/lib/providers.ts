@@ -1,4 +1,4 @@
-import type { ProviderId, ProviderMetadata, CookieSource } from './types';
+import type { ProviderId, ProviderMetadata, CookieSource, NotificationThresholds } from './types';
 
 export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
   claude: {
@@ -12,9 +12,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: 'Sonnet',
     supportsOpus: true,
     supportsCredits: false,
+    supportsAccounts: true,
     implemented: true,
     available: true,
-    statusPageUrl: 'https://status.anthropic.com',
   },
   codex: {
     id: 'codex',
@@ -27,9 +27,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: true,
+    supportsAccounts: true,
     implemented: true,
     available: true,
-    statusPageUrl: 'https://status.openai.com',
   },
   cursor: {
     id: 'cursor',
@@ -42,9 +42,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: true,
+    supportsAccounts: true,
     implemented: true,
     available: true,
-    statusPageUrl: 'https://status.cursor.sh',
   },
   copilot: {
     id: 'copilot',
@@ -57,9 +57,10 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
+    baseUrlHosts: ['api.github.com', 'github.com'],
     implemented: true,
     available: true,
-    statusPageUrl: 'https://www.githubstatus.com',
   },
   gemini: {
     id: 'gemini',
@@ -72,6 +73,8 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
+    baseUrlHosts: ['cloudcode-pa.googleapis.com', 'oauth2.googleapis.com'],
     implemented: true,
     available: false,
   },
@@ -86,9 +89,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: 'Gemini Flash',
     supportsOpus: true,
     supportsCredits: false,
+    supportsAccounts: false,
     implemented: true,
     available: true,
-    statusPageUrl: 'https://www.google.com/appsstatus/dashboard/products/npdyhgECDJ6tB66MxXyo',
   },
   factory: {
     id: 'factory',
@@ -101,6 +104,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -115,6 +119,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: false,
     implemented: true,
     available: false,
   },
@@ -129,6 +134,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: true,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -143,6 +149,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -157,6 +164,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: false,
     implemented: true,
     available: false,
   },
@@ -171,9 +179,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: false,
     implemented: true,
     available: false,
-    statusPageUrl: 'https://status.aws.amazon.com/rss/all.rss',
   },
   vertexai: {
     id: 'vertexai',
@@ -186,7 +194,9 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
-    implemented: false,
+    supportsAccounts: true,
+    baseUrlHosts: ['cloudquotas.googleapis.com', 'monitoring.googleapis.com', 'oauth2.googleapis.com'],
+    implemented: true,
     available: false,
   },
   augment: {
@@ -200,6 +210,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: true,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -214,6 +225,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -228,6 +240,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: true,
+    supportsAccounts: false,
     implemented: true,
     available: false,
   },
@@ -242,6 +255,7 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: true,
     implemented: true,
     available: false,
   },
@@ -256,13 +270,47 @@ export const PROVIDERS: Record<ProviderId, ProviderMetadata> = {
     opusLabel: null,
     supportsOpus: false,
     supportsCredits: false,
+    supportsAccounts: false,
     implemented: true,
     available: false,
   },
+  custom: {
+    id: 'custom',
+    name: 'Custom',
+    icon: 'Gauge',
+    accentColor: 'slate',
+    authMethod: 'api_key',
+    sessionLabel: 'Quota',
+    weeklyLabel: 'Usage',
+    opusLabel: null,
+    supportsOpus: false,
+    supportsCredits: false,
+    supportsAccounts: false,
+    implemented: true,
+    available: true,
+  },
+  plugin: {
+    id: 'plugin',
+    name: 'Plugins',
+    icon: 'Puzzle',
+    accentColor: 'zinc',
+    authMethod: 'local_config',
+    sessionLabel: 'Session',
+    weeklyLabel: 'Weekly',
+    opusLabel: null,
+    supportsOpus: false,
+    supportsCredits: false,
+    supportsAccounts: false,
+    implemented: true,
+    available: true,
+  },
 };
 
 export const DEFAULT_ENABLED_PROVIDERS: ProviderId[] = ['codex'];
 
+// Providers without a default account; each user-defined entry gets its own card
+export const ACCOUNT_ONLY_PROVIDERS: ProviderId[] = ['custom', 'plugin'];
+
 export const DEFAULT_PROVIDER_ORDER: ProviderId[] = [
   'codex',
   'claude',
@@ -282,6 +330,8 @@ export const DEFAULT_PROVIDER_ORDER: ProviderId[] = [
   'kimi_k2',
   'amp',
   'synthetic',
+  'custom',
+  'plugin',
 ];
 
 export const DEFAULT_SETTINGS = {
@@ -310,11 +360,20 @@ export const DEFAULT_SETTINGS = {
   storeUsageHistory: true,
   pollProviderStatus: true,
   redactPersonalInfo: false,
+  metricsExporterEnabled: false,
+  metricsExporterPort: 9464,
+  apiServerEnabled: false,
+  apiServerPort: 9465,
   cookieSources: {} as Partial<Record<ProviderId, CookieSource>>,
+  providerBaseUrls: {} as Partial<Record<ProviderId, string>>,
+  providerHostBaseUrls: {} as Partial<Record<ProviderId, Record<string, string>>>,
+  providerRefreshIntervals: {} as Partial<Record<ProviderId, number>>,
+  notificationThresholds: {} as Partial<Record<ProviderId, NotificationThresholds>>,
   debugMenuEnabled: false,
   debugFileLogging: false,
   debugKeepCliSessionsAlive: false,
   debugRandomBlink: false,
+  debugRecordFixtures: false,
   hidePersonalInfo: false,
   debugDisableKeychainAccess: false,
 };
@@ -17,11 +17,20 @@ export type ProviderId =
   | 'amp'
   | 'jetbrains'
   | 'opencode'
-  | 'synthetic';
+  | 'synthetic'
+  | 'custom'
+  | 'plugin';
 
 // Rate window represents a usage period (session, weekly, etc.)
+export type WindowCategory = 'session' | 'weekly' | 'monthly' | 'model';
+
 export interface RateWindow {
+  id?: string;                // Stable within a provider, e.g. "session" or a model id
+  category?: WindowCategory;
   usedPercent: number;        // 0-100
+  used?: number;              // Absolute usage, when the provider reports counts
+  limit?: number;
+  unit?: string;              // "requests", "tokens", "USD", etc.
   windowMinutes?: number;     // Duration of the window
   resetsAt?: string;          // ISO date string
   resetDescription?: string;  // "Resets in 4h"
@@ -51,6 +60,18 @@ export interface ProviderIncident {
   indicator: StatusIndicator;
   description?: string;
   updatedAt?: string;
+  // Status page from the backend provider descriptor
+  url?: string;
+}
+
+// Projected time-to-exhaustion of a rate window or credit balance
+export interface Forecast {
+  id: string;                 // RateWindow id, or "credits"
+  ratePerHour: number;        // Percentage points (windows) or credit units per hour
+  exhaustsAt?: string;        // ISO date; missing while usage is flat
+  resetsFirst: boolean;       // The window resets before exhaustsAt
+  confidence: number;         // 0-1
+  description: string;        // "Weekly limit hit Thu 14:00 at current pace"
 }
 
 // Full usage snapshot for a provider
@@ -67,6 +88,10 @@ export interface UsageSnapshot {
   identity?: ProviderIdentity;
   updatedAt: string;          // ISO date string
   error?: string;             // Error message if fetch failed
+  retryAt?: string;           // ISO date a rate-limited provider is tried again
+  windows?: RateWindow[];     // Every window; primary/secondary/tertiary are the first three
+  forecasts?: Forecast[];     // Projected exhaustion per window id, plus "credits"
+  version?: number;           // Snapshot shape, 2 once windows is sent
 }
 
 export interface UsageHistoryPoint {
@@ -75,9 +100,28 @@ export interface UsageHistoryPoint {
   credits?: number;
 }
 
+// A record from the persistent usage history (get_usage_history)
+export interface UsageHistoryRecord {
+  recordedAt: string;         // ISO date string
+  providerId: ProviderId;
+  accountId: string;
+  usage?: UsageSnapshot;
+  cost?: CostSnapshot;        // Only for cost scans recorded on their own
+}
+
+export interface UsageHistoryQuery {
+  providerId?: ProviderId;
+  accountId?: string;
+  windowId?: string;          // Narrows each record to this window
+  since?: string;             // ISO date string
+  until?: string;
+}
+
 // Provider state in the store
 export interface ProviderState {
   id: ProviderId;
+  // Set for additional accounts; the provider's default account leaves it unset
+  accountId?: string;
   name: string;
   enabled: boolean;
   usage?: UsageSnapshot;
@@ -85,6 +129,7 @@ export interface ProviderState {
   status?: ProviderIncident;
   isLoading: boolean;
   lastError?: string;
+  retryAt?: string;
 }
 
 // Provider metadata (static info)
@@ -99,9 +144,12 @@ export interface ProviderMetadata {
   opusLabel?: string | null;
   supportsOpus: boolean;
   supportsCredits: boolean;
+  // Whether additional accounts can be registered next to the default one
+  supportsAccounts: boolean;
+  // Hosts that can each get their own base URL; one base covers the provider otherwise
+  baseUrlHosts?: string[];
   implemented: boolean;
   available: boolean;         // false = grayed out "coming soon"
-  statusPageUrl?: string;
 }
 
 export type CookieSource = 'chrome' | 'safari' | 'firefox' | 'arc' | 'edge' | 'brave' | 'opera' | 'manual';
@@ -121,6 +169,18 @@ export type ResetTimeDisplayMode = 'relative' | 'absolute';
 
 export type UpdateChannel = 'stable' | 'beta';
 
+// Per-provider alert thresholds. Missing keeps the defaults, an empty list turns the alert off.
+export interface NotificationThresholds {
+  sessionUsage?: number[];      // Session usage percentages, default [80, 90]
+  creditsRemaining?: number[];  // Remaining credit percentages, default [20, 10]
+}
+
+// Alerts held back by snooze_notifications
+export interface NotificationSnoozes {
+  allUntil?: string;          // ISO date every provider is snoozed until
+  providers: Partial<Record<ProviderId, string>>;
+}
+
 // Settings
 export interface AppSettings {
   refreshIntervalSeconds: number;
@@ -148,29 +208,101 @@ export interface AppSettings {
   storeUsageHistory: boolean;
   pollProviderStatus: boolean;
   redactPersonalInfo: boolean;
+  metricsExporterEnabled: boolean;
+  metricsExporterPort: number;  // 127.0.0.1 port serving /metrics
+  apiServerEnabled: boolean;
+  apiServerPort: number;        // 127.0.0.1 port serving the /v1 JSON API
   cookieSources: Partial<Record<ProviderId, CookieSource>>;
+  providerBaseUrls: Partial<Record<ProviderId, string>>;
+  // Keyed by the host each base replaces, for providers with `baseUrlHosts`
+  providerHostBaseUrls: Partial<Record<ProviderId, Record<string, string>>>;
+  providerRefreshIntervals: Partial<Record<ProviderId, number>>;
+  notificationThresholds: Partial<Record<ProviderId, NotificationThresholds>>;
   crashRecoveryAt?: string;
   debugMenuEnabled: boolean;
   debugFileLogging: boolean;
   debugKeepCliSessionsAlive: boolean;
   debugRandomBlink: boolean;
+  debugRecordFixtures: boolean;
   hidePersonalInfo: boolean;
   debugDisableKeychainAccess: boolean;
   installOrigin?: string;
 }
 
 // Event payloads from Rust
+export const DEFAULT_ACCOUNT_ID = 'default';
+
+// A registered account, as returned by `list_accounts`
+export interface AccountInfo {
+  providerId: ProviderId;
+  accountId: string;
+  label: string;
+  enabled: boolean;
+  usage?: UsageSnapshot;
+}
+
+// An additional account to register through `add_account`
+export interface ProviderAccount {
+  providerId: ProviderId;
+  accountId: string;
+  label: string;
+  credentialsPath?: string;
+}
+
+// A user-defined JSON endpoint, as saved through `save_custom_provider`
+export interface CustomProviderConfig {
+  id: string;
+  name: string;
+  url: string;
+  headers?: Record<string, string>;
+  fields?: Record<string, string>;
+}
+
 export interface UsageUpdateEvent {
   providerId: ProviderId;
+  accountId?: string;
   usage: UsageSnapshot;
 }
 
+export type ProviderErrorKind =
+  | 'notAuthenticated'
+  | 'tokenExpired'
+  | 'forbidden'
+  | 'rateLimited'
+  | 'network'
+  | 'timeout'
+  | 'http'
+  | 'parse'
+  | 'unavailable'
+  | 'cancelled'
+  | 'other';
+
+/** Rejection value of the `refresh_provider` command */
+export interface RefreshError {
+  message: string;
+  errorKind: ProviderErrorKind;
+}
+
 export interface StatusUpdateEvent {
   providerId: ProviderId;
+  /** `default` unless each account reports its own status, as plugins do */
+  accountId: string;
   status: ProviderIncident | null;
 }
 
+// Emitted as `window-reset` when a rate window resets
+export interface WindowResetEvent {
+  providerId: ProviderId;
+  accountId: string;
+  windowId: string;
+  label?: string;
+  previousPercent: number;
+  usedPercent: number;
+  resetsAt?: string;          // When the new window resets
+}
+
 export interface RefreshingEvent {
   providerId: ProviderId;
+  accountId?: string;
   isRefreshing: boolean;
 }
@@ -5,6 +5,7 @@ import type {
   ProviderId,
   AppSettings,
   CookieSource,
+  NotificationThresholds,
   MenuBarDisplayMode,
   MenuBarDisplayTextMode,
   UsageBarDisplayMode,
@@ -238,13 +239,25 @@ interface SettingsStore extends AppSettings {
   setStoreUsageHistory: (enabled: boolean) => void;
   setPollProviderStatus: (enabled: boolean) => void;
   setRedactPersonalInfo: (enabled: boolean) => void;
+  setMetricsExporterEnabled: (enabled: boolean) => void;
+  setMetricsExporterPort: (port: number) => void;
+  setApiServerEnabled: (enabled: boolean) => void;
+  setApiServerPort: (port: number) => void;
   setCookieSource: (providerId: ProviderId, source: CookieSource) => void;
   getCookieSource: (providerId: ProviderId) => CookieSource;
+  setProviderBaseUrl: (providerId: ProviderId, baseUrl: string) => void;
+  setProviderHostBaseUrl: (providerId: ProviderId, host: string, baseUrl: string) => void;
+  setProviderRefreshInterval: (providerId: ProviderId, seconds: number | null) => void;
+  setNotificationThresholds: (
+    providerId: ProviderId,
+    thresholds: NotificationThresholds | null
+  ) => void;
   resetToDefaults: () => void;
   setDebugMenuEnabled: (enabled: boolean) => void;
   setDebugFileLogging: (enabled: boolean) => void;
   setDebugKeepCliSessionsAlive: (enabled: boolean) => void;
   setDebugRandomBlink: (enabled: boolean) => void;
+  setDebugRecordFixtures: (enabled: boolean) => void;
   setHidePersonalInfo: (enabled: boolean) => void;
   setDebugDisableKeychainAccess: (enabled: boolean) => void;
   setInstallOrigin: (origin: string | null) => void;
@@ -336,6 +349,14 @@ export const useSettingsStore = create<SettingsStore>()(
 
       setRedactPersonalInfo: (enabled) => set({ redactPersonalInfo: enabled }),
 
+      setMetricsExporterEnabled: (enabled) => set({ metricsExporterEnabled: enabled }),
+
+      setMetricsExporterPort: (port) => set({ metricsExporterPort: port }),
+
+      setApiServerEnabled: (enabled) => set({ apiServerEnabled: enabled }),
+
+      setApiServerPort: (port) => set({ apiServerPort: port }),
+
       setDebugMenuEnabled: (enabled) => set({ debugMenuEnabled: enabled }),
 
       setDebugFileLogging: (enabled) => set({ debugFileLogging: enabled }),
@@ -344,6 +365,7 @@ export const useSettingsStore = create<SettingsStore>()(
         set({ debugKeepCliSessionsAlive: enabled }),
 
       setDebugRandomBlink: (enabled) => set({ debugRandomBlink: enabled }),
+      setDebugRecordFixtures: (enabled) => set({ debugRecordFixtures: enabled }),
 
       setHidePersonalInfo: (enabled) => set({ hidePersonalInfo: enabled }),
 
@@ -370,6 +392,56 @@ export const useSettingsStore = create<SettingsStore>()(
       getCookieSource: (providerId) =>
         get().cookieSources[providerId] ?? DEFAULT_COOKIE_SOURCE,
 
+      setProviderBaseUrl: (providerId, baseUrl) =>
+        set((state) => {
+          const providerBaseUrls = { ...state.providerBaseUrls };
+          if (baseUrl.trim()) {
+            providerBaseUrls[providerId] = baseUrl.trim();
+          } else {
+            delete providerBaseUrls[providerId];
+          }
+          return { providerBaseUrls };
+        }),
+
+      setProviderHostBaseUrl: (providerId, host, baseUrl) =>
+        set((state) => {
+          const hostBaseUrls = { ...state.providerHostBaseUrls?.[providerId] };
+          if (baseUrl.trim()) {
+            hostBaseUrls[host] = baseUrl.trim();
+          } else {
+            delete hostBaseUrls[host];
+          }
+          const providerHostBaseUrls = { ...state.providerHostBaseUrls };
+          if (Object.keys(hostBaseUrls).length > 0) {
+            providerHostBaseUrls[providerId] = hostBaseUrls;
+          } else {
+            delete providerHostBaseUrls[providerId];
+          }
+          return { providerHostBaseUrls };
+        }),
+
+      setProviderRefreshInterval: (providerId, seconds) =>
+        set((state) => {
+          const providerRefreshIntervals = { ...state.providerRefreshIntervals };
+          if (seconds === null) {
+            delete providerRefreshIntervals[providerId];
+          } else {
+            providerRefreshIntervals[providerId] = seconds;
+          }
+          return { providerRefreshIntervals };
+        }),
+
+      setNotificationThresholds: (providerId, thresholds) =>
+        set((state) => {
+          const notificationThresholds = { ...state.notificationThresholds };
+          if (thresholds === null) {
+            delete notificationThresholds[providerId];
+          } else {
+            notificationThresholds[providerId] = thresholds;
+          }
+          return { notificationThresholds };
+        }),
+
       resetToDefaults: () => set(DEFAULT_SETTINGS),
 
       setCrashRecoveryAt: (timestamp) => set({ crashRecoveryAt: timestamp }),
@@ -459,9 +531,16 @@ useSettingsStore.persist.onFinishHydration(() => {
   console.log('[settingsStore] onFinishHydration callback - setting hasHydrated to true');
   useSettingsStore.setState({ hasHydrated: true });
   // Ensure backend registry matches current settings on startup
-  const enabledProviders = useSettingsStore.getState().enabledProviders;
+  const { enabledProviders, refreshIntervalSeconds, providerRefreshIntervals } =
+    useSettingsStore.getState();
   import('@tauri-apps/api/core')
-    .then(({ invoke }) => invoke('set_enabled_providers', { providerIds: enabledProviders }))
+    .then(({ invoke }) =>
+      invoke('set_enabled_providers', {
+        providerIds: enabledProviders,
+        refreshIntervalSeconds,
+        providerRefreshIntervals,
+      })
+    )
     .catch((error) => {
       console.warn('[settingsStore] Failed to sync enabled providers on hydration', error);
     });
@@ -472,24 +551,41 @@ if (useSettingsStore.persist.hasHydrated()) {
   console.log('[settingsStore] Already hydrated on module load - setting hasHydrated to true');
   useSettingsStore.setState({ hasHydrated: true });
   // Also sync providers since we missed the onFinishHydration callback
-  const enabledProviders = useSettingsStore.getState().enabledProviders;
+  const { enabledProviders, refreshIntervalSeconds, providerRefreshIntervals } =
+    useSettingsStore.getState();
   import('@tauri-apps/api/core')
-    .then(({ invoke }) => invoke('set_enabled_providers', { providerIds: enabledProviders }))
+    .then(({ invoke }) =>
+      invoke('set_enabled_providers', {
+        providerIds: enabledProviders,
+        refreshIntervalSeconds,
+        providerRefreshIntervals,
+      })
+    )
     .catch((error) => {
       console.warn('[settingsStore] Failed to sync enabled providers on module load hydration', error);
     });
 }
 // Note: We don't unsubscribe because we only need this to fire once on app start
 
-// Cross-window sync: emit settings-updated event when enabledProviders or providerOrder changes
-// This is done via subscription so it works regardless of which action modified the state
+// Cross-window sync: emit settings-updated event when enabledProviders, providerOrder or the
+// refresh intervals change. This is done via subscription so it works regardless of which
+// action modified the state
 let lastEnabledProviders: string | null = null;
 let lastProviderOrder: string | null = null;
+let lastRefreshIntervals: string | null = null;
+
+const refreshIntervalsKey = (state: AppSettings) =>
+  JSON.stringify([state.refreshIntervalSeconds, state.providerRefreshIntervals]);
 
-const emitSettingsUpdated = async (enabledProviders: ProviderId[], providerOrder: ProviderId[]) => {
+const emitSettingsUpdated = async (state: AppSettings) => {
   const { invoke } = await import('@tauri-apps/api/core');
   try {
-    await invoke('broadcast_settings_updated', { enabledProviders, providerOrder });
+    await invoke('broadcast_settings_updated', {
+      enabledProviders: state.enabledProviders,
+      providerOrder: state.providerOrder,
+      refreshIntervalSeconds: state.refreshIntervalSeconds,
+      providerRefreshIntervals: state.providerRefreshIntervals,
+    });
   } catch (error) {
     console.warn('[settingsStore] Failed to broadcast settings update', error);
   }
@@ -505,26 +601,38 @@ useSettingsStore.subscribe((state, prevState) => {
   const currentOrder = state.providerOrder.join('|');
   const prevEnabled = prevState.enabledProviders.join('|');
   const prevOrder = prevState.providerOrder.join('|');
+  const currentIntervals = refreshIntervalsKey(state);
+  const prevIntervals = refreshIntervalsKey(prevState);
   
   // Skip if nothing changed (also handles initial subscription call)
-  if (currentEnabled === prevEnabled && currentOrder === prevOrder) {
+  if (
+    currentEnabled === prevEnabled &&
+    currentOrder === prevOrder &&
+    currentIntervals === prevIntervals
+  ) {
     return;
   }
   
   if (lastEnabledProviders === null) {
     lastEnabledProviders = prevEnabled;
     lastProviderOrder = prevOrder;
+    lastRefreshIntervals = prevIntervals;
   }
 
   // Check if values actually changed from our tracked state
-  if (currentEnabled === lastEnabledProviders && currentOrder === lastProviderOrder) {
+  if (
+    currentEnabled === lastEnabledProviders &&
+    currentOrder === lastProviderOrder &&
+    currentIntervals === lastRefreshIntervals
+  ) {
     return;
   }
 
   lastEnabledProviders = currentEnabled;
   lastProviderOrder = currentOrder;
+  lastRefreshIntervals = currentIntervals;
 
-  void emitSettingsUpdated(state.enabledProviders, state.providerOrder);
+  void emitSettingsUpdated(state);
 });
 
 // Selectors
@@ -2,12 +2,15 @@ import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 import { useShallow } from 'zustand/shallow';
 import type {
+  AccountInfo,
   ProviderId,
   ProviderState,
   UsageSnapshot,
   ProviderIncident,
+  RefreshError,
 } from '../lib/types';
-import { PROVIDERS, DEFAULT_ENABLED_PROVIDERS } from '../lib/providers';
+import { DEFAULT_ACCOUNT_ID } from '../lib/types';
+import { PROVIDERS, DEFAULT_ENABLED_PROVIDERS, ACCOUNT_ONLY_PROVIDERS } from '../lib/providers';
 import { useSettingsStore } from './settingsStore';
 
 const MAX_HISTORY_POINTS = 30;
@@ -64,6 +67,14 @@ const persistUsageHistory = (providers: Record<ProviderId, ProviderState>) => {
   saveUsageHistoryStorage(nextStorage);
 };
 
+// Additional accounts are kept apart from the per-provider default account state
+export const accountKey = (providerId: ProviderId, accountId: string) => `${providerId}/${accountId}`;
+
+const isDefaultAccount = (accountId?: string) => !accountId || accountId === DEFAULT_ACCOUNT_ID;
+
+const isRefreshError = (error: unknown): error is RefreshError =>
+  typeof error === 'object' && error !== null && 'errorKind' in error && 'message' in error;
+
 let storedUsageHistory = loadUsageHistoryStorage();
 if (!shouldStoreUsageHistory()) {
   clearUsageHistoryStorage();
@@ -73,6 +84,7 @@ if (!shouldStoreUsageHistory()) {
 interface UsageStore {
   // State
   providers: Record<ProviderId, ProviderState>;
+  accounts: Record<string, ProviderState>;
   activeProvider: ProviderId;
   isRefreshing: boolean;
   lastGlobalRefresh: Date | null;
@@ -84,7 +96,11 @@ interface UsageStore {
   setProviderLoading: (id: ProviderId, isLoading: boolean) => void;
   setProviderError: (id: ProviderId, error: string | undefined) => void;
   setProviderEnabled: (id: ProviderId, enabled: boolean) => void;
-  refreshProvider: (id: ProviderId) => Promise<void>;
+  setAccounts: (accounts: AccountInfo[]) => void;
+  setAccountState: (id: ProviderId, accountId: string, update: Partial<ProviderState>) => void;
+  loadAccounts: () => Promise<void>;
+  refreshProvider: (id: ProviderId, force?: boolean, accountId?: string) => Promise<void>;
+  cancelRefresh: (id: ProviderId, accountId?: string) => Promise<void>;
   refreshAllProviders: () => Promise<void>;
   initializeProviders: (enabledIds: ProviderId[]) => void;
   clearUsageHistory: () => void;
@@ -122,6 +138,7 @@ const orderProviderStates = (
 
 export const useUsageStore = create<UsageStore>((set, get) => ({
   providers: initialProviders,
+  accounts: {},
   activeProvider: DEFAULT_ENABLED_PROVIDERS[0] || 'claude',
   isRefreshing: false,
   lastGlobalRefresh: null,
@@ -143,6 +160,7 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
               usageHistory: [] as ProviderState['usageHistory'],
               isLoading: false,
               lastError: usage.error,
+              retryAt: usage.retryAt,
             },
           },
         };
@@ -167,6 +185,7 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
             usageHistory: nextHistory,
             isLoading: false,
             lastError: usage.error,
+            retryAt: usage.retryAt,
           },
         },
       };
@@ -221,10 +240,77 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
       },
     })),
 
-  refreshProvider: async (id) => {
-    const { setProviderLoading, setProviderUsage, setProviderError } = get();
-    console.log('[usageStore] refreshProvider - starting:', id);
-    setProviderLoading(id, true);
+  setAccounts: (accounts) =>
+    set((state) => ({
+      accounts: Object.fromEntries(
+        accounts
+          .filter((account) => !isDefaultAccount(account.accountId))
+          .map((account) => {
+            const key = accountKey(account.providerId, account.accountId);
+            const previous = state.accounts[key];
+            const usage = previous?.usage ?? account.usage ?? undefined;
+            return [
+              key,
+              {
+                isLoading: false,
+                lastError: usage?.error,
+                retryAt: usage?.retryAt,
+                ...previous,
+                id: account.providerId,
+                accountId: account.accountId,
+                name: account.label,
+                enabled: account.enabled,
+                usage,
+              },
+            ];
+          })
+      ),
+    })),
+
+  setAccountState: (id, accountId, update) =>
+    set((state) => {
+      const key = accountKey(id, accountId);
+      const previous = state.accounts[key] ?? {
+        id,
+        accountId,
+        name: accountId,
+        enabled: state.providers[id]?.enabled ?? false,
+        isLoading: false,
+      };
+      return {
+        accounts: {
+          ...state.accounts,
+          [key]: { ...previous, ...update },
+        },
+      };
+    }),
+
+  loadAccounts: async () => {
+    try {
+      const accounts = await invoke<AccountInfo[]>('list_accounts');
+      get().setAccounts(accounts);
+    } catch (error) {
+      console.warn('[usageStore] loadAccounts - failed:', error);
+    }
+  },
+
+  refreshProvider: async (id, force = false, accountId) => {
+    const { setProviderLoading, setProviderUsage, setProviderError, setAccountState } = get();
+    const isDefault = isDefaultAccount(accountId);
+    if (isDefault && ACCOUNT_ONLY_PROVIDERS.includes(id)) {
+      // Nothing to fetch for the provider itself, only for its entries
+      const entries = Object.values(get().accounts).filter((account) => account.id === id);
+      await Promise.allSettled(
+        entries.map((account) => get().refreshProvider(id, force, account.accountId))
+      );
+      return;
+    }
+    console.log('[usageStore] refreshProvider - starting:', id, accountId ?? DEFAULT_ACCOUNT_ID);
+    if (isDefault) {
+      setProviderLoading(id, true);
+    } else {
+      setAccountState(id, accountId!, { isLoading: true });
+    }
 
     // Frontend timeout to ensure we don't hang indefinitely waiting for backend
     const REFRESH_TIMEOUT_MS = 30_000; // 30 seconds (backend has 10s status + 15s usage timeouts)
@@ -235,28 +321,68 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
 
     try {
       const usage = await Promise.race([
-        invoke<UsageSnapshot>('refresh_provider', { providerId: id }),
+        invoke<UsageSnapshot>('refresh_provider', { providerId: id, accountId, force }),
         timeoutPromise,
       ]);
       console.log('[usageStore] refreshProvider - success:', id, usage);
-      setProviderUsage(id, usage);
+      if (isDefault) {
+        setProviderUsage(id, usage);
+      } else {
+        setAccountState(id, accountId!, {
+          usage,
+          isLoading: false,
+          lastError: usage.error,
+          retryAt: usage.retryAt,
+        });
+      }
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
+      // Replaced by a forced refresh, which reports the result, or cancelled via cancelRefresh
+      if (isRefreshError(error) && error.errorKind === 'cancelled') {
+        console.log('[usageStore] refreshProvider - cancelled:', id);
+        return;
+      }
+      const message = isRefreshError(error)
+        ? error.message
+        : error instanceof Error
+          ? error.message
+          : String(error);
       console.log('[usageStore] refreshProvider - error:', id, message);
-      setProviderError(id, message);
+      if (isDefault) {
+        setProviderError(id, message);
+      } else {
+        setAccountState(id, accountId!, { lastError: message, isLoading: false });
+      }
+    }
+  },
+
+  cancelRefresh: async (id, accountId) => {
+    try {
+      await invoke<boolean>('cancel_refresh', { providerId: id, accountId });
+    } catch (error) {
+      console.warn('[usageStore] cancelRefresh - failed:', id, error);
+    }
+    if (isDefaultAccount(accountId)) {
+      get().setProviderLoading(id, false);
+    } else {
+      get().setAccountState(id, accountId!, { isLoading: false });
     }
   },
 
   refreshAllProviders: async () => {
-    const { providers, refreshProvider } = get();
+    const { providers, accounts, refreshProvider } = get();
     set({ isRefreshing: true });
 
-    const enabledProviders = Object.values(providers).filter((p) => p.enabled);
+    // Entries of account-only providers are refreshed with the other accounts
+    const enabledProviders = Object.values(providers).filter(
+      (p) => p.enabled && !ACCOUNT_ONLY_PROVIDERS.includes(p.id)
+    );
+    const enabledAccounts = Object.values(accounts).filter((account) => providers[account.id]?.enabled);
     console.log('[usageStore] refreshAllProviders - enabled providers:', enabledProviders.map(p => p.id));
     
-    const results = await Promise.allSettled(
-      enabledProviders.map((p) => refreshProvider(p.id))
-    );
+    const results = await Promise.allSettled([
+      ...enabledProviders.map((p) => refreshProvider(p.id)),
+      ...enabledAccounts.map((account) => refreshProvider(account.id, false, account.accountId)),
+    ]);
     console.log('[usageStore] refreshAllProviders - completed, results:', results);
 
     set({ isRefreshing: false, lastGlobalRefresh: new Date() });
@@ -314,6 +440,7 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
 
   resetState: () => set({
     providers: initialProviders,
+    accounts: {},
     activeProvider: DEFAULT_ENABLED_PROVIDERS[0] || 'claude',
     isRefreshing: false,
     lastGlobalRefresh: null,
@@ -359,5 +486,11 @@ export const useAuthenticatedProviders = () => {
   );
 };
 
+// Additional accounts of a provider, in the order the backend lists them
+export const useProviderAccounts = (id: ProviderId) =>
+  useUsageStore(
+    useShallow((state) => Object.values(state.accounts).filter((account) => account.id === id))
+  );
+
 export const useProviderById = (id: ProviderId) =>
   useUsageStore((state) => state.providers[id]);
@@ -0,0 +1,42 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const providersFile = read('src', 'lib', 'providers.ts');
+const usageStoreFile = read('src', 'stores', 'usageStore.ts');
+const popupFile = read('src', 'components', 'PopupWindow.tsx');
+const customProvidersFile = read('src', 'components', 'CustomProviders.tsx');
+const providerPluginsFile = read('src', 'components', 'ProviderPlugins.tsx');
+const settingsPanelFile = read('src', 'components', 'SettingsPanel.tsx');
+
+const requiredMarkers = [
+  { name: 'ACCOUNT_ONLY_PROVIDERS', sources: [providersFile, usageStoreFile, popupFile] },
+  { name: 'data-testid="provider-accounts-empty"', sources: [popupFile] },
+  { name: "'save_custom_provider'", sources: [customProvidersFile] },
+  { name: "'remove_custom_provider'", sources: [customProvidersFile] },
+  { name: '<CustomProviders', sources: [settingsPanelFile] },
+  { name: "'rescan_plugins'", sources: [providerPluginsFile] },
+  { name: "useProviderAccounts('plugin')", sources: [providerPluginsFile] },
+  { name: '<ProviderPlugins', sources: [settingsPanelFile] },
+];
+
+requiredMarkers.forEach(({ name, sources }) => {
+  if (!sources.every((source) => source.includes(name))) {
+    throw new Error(`Custom provider marker missing: ${name}`);
+  }
+});
+
+// Custom providers and plugins must be selectable rather than listed as coming soon
+const blockOf = (id) => {
+  const start = providersFile.indexOf(`  ${id}: {`);
+  return providersFile.slice(start, providersFile.indexOf('  },', start));
+};
+['custom', 'plugin'].forEach((id) => {
+  if (!/available: true/.test(blockOf(id))) {
+    throw new Error(`${id} providers are not available.`);
+  }
+});
+
+console.log('custom provider and plugin checks passed');
@@ -18,10 +18,12 @@ const requiredMarkers = [
   { name: 'debugFileLogging', sources: [settingsStoreFile, appFile, commandsFile] },
   { name: 'debugKeepCliSessionsAlive', sources: [settingsStoreFile, appFile, commandsFile] },
   { name: 'debugRandomBlink', sources: [settingsStoreFile, appFile, commandsFile] },
+  { name: 'debugRecordFixtures', sources: [settingsStoreFile, appFile] },
   { name: 'data-testid="debug-settings"', sources: [settingsPanelFile] },
   { name: 'set_debug_file_logging', sources: [commandsFile, libFile, appFile] },
   { name: 'set_debug_keep_cli_sessions_alive', sources: [commandsFile, libFile, appFile] },
   { name: 'set_debug_random_blink', sources: [commandsFile, libFile, appFile] },
+  { name: 'set_debug_record_fixtures', sources: [commandsFile, libFile, appFile] },
 ];
 
 requiredMarkers.forEach(({ name, sources }) => {
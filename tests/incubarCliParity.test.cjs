@@ -12,6 +12,14 @@ const cliFile = fs.readFileSync(cliPath, 'utf-8');
 const requiredMarkers = [
   'run_status',
   'run_cost',
+  'run_usage',
+  'UsagePayload',
+  'fetch_usage',
+  'auth_status',
+  'run_watch',
+  'WatchEmitter',
+  'impl RefreshEmitter for WatchEmitter',
+  'run_refresh_loop',
   'ProviderRegistry::new',
   'load_cost_snapshot',
   'status',
@@ -23,6 +31,10 @@ const requiredMarkers = [
   'status_page_url',
 ];
 
+if (cliFile.includes('usage is not supported in the bundled CLI')) {
+  throw new Error('incubar CLI still rejects the usage command.');
+}
+
 requiredMarkers.forEach((marker) => {
   if (!cliFile.includes(marker)) {
     throw new Error(`incubar CLI missing marker: ${marker}`);
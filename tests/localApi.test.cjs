@@ -0,0 +1,41 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const settingsStoreFile = read('src', 'stores', 'settingsStore.ts');
+const settingsPanelFile = read('src', 'components', 'SettingsPanel.tsx');
+const appFile = read('src', 'App.tsx');
+const commandsFile = read('src-tauri', 'src', 'commands', 'mod.rs');
+const libFile = read('src-tauri', 'src', 'lib.rs');
+const apiFile = read('src-tauri', 'src', 'server', 'api.rs');
+const serverFile = read('src-tauri', 'src', 'server', 'mod.rs');
+
+const requiredMarkers = [
+  { name: 'apiServerEnabled', sources: [settingsStoreFile, appFile, settingsPanelFile] },
+  { name: 'apiServerPort', sources: [settingsStoreFile, appFile, settingsPanelFile] },
+  { name: 'testId="local-api"', sources: [settingsPanelFile] },
+  { name: 'function LocalServerToggle', sources: [settingsPanelFile] },
+  { name: 'set_api_server', sources: [commandsFile, libFile, appFile] },
+  { name: 'ApiEventSink', sources: [libFile, apiFile] },
+  { name: 'commands::refresh_provider', sources: [apiFile] },
+  { name: 'api-token', sources: [apiFile] },
+  { name: 'text/event-stream', sources: [serverFile] },
+];
+
+requiredMarkers.forEach(({ name, sources }) => {
+  if (!sources.every((source) => source.includes(name))) {
+    throw new Error(`Local API marker missing: ${name}`);
+  }
+});
+
+['["v1", "usage"]', '["v1", "status"]', '["v1", "events"]', '"refresh"', 'Bearer '].forEach(
+  (marker) => {
+    if (!apiFile.includes(marker)) {
+      throw new Error(`Local API missing ${marker}.`);
+    }
+  }
+);
+
+console.log('local api checks passed');
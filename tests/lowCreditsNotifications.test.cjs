@@ -2,26 +2,20 @@ const fs = require('node:fs');
 const path = require('node:path');
 
 const root = path.resolve(__dirname, '..');
-const appPath = path.join(root, 'src', 'App.tsx');
-const notificationsPath = path.join(root, 'src', 'lib', 'notifications.ts');
+const notificationsPath = path.join(root, 'src-tauri', 'src', 'providers', 'notifications.rs');
 
-const appFile = fs.readFileSync(appPath, 'utf-8');
 const notificationsFile = fs.readFileSync(notificationsPath, 'utf-8');
 
 if (!notificationsFile.includes('CREDIT_REMAINING_THRESHOLDS')) {
   throw new Error('Credit remaining thresholds are missing.');
 }
 
-if (!notificationsFile.includes('evaluateCreditsNotifications')) {
+if (!notificationsFile.includes('credits low')) {
   throw new Error('Credits notification evaluator missing.');
 }
 
-if (!appFile.includes('evaluateCreditsNotifications')) {
-  throw new Error('App missing credits notification evaluation.');
-}
-
-if (!appFile.includes('sendNotification')) {
-  throw new Error('App missing notification dispatch wiring.');
+if (!notificationsFile.includes('fn credit_thresholds')) {
+  throw new Error('Credits notifications missing per-provider thresholds.');
 }
 
 console.log('Low credits notification checks passed.');
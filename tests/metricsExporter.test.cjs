@@ -0,0 +1,48 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const settingsStoreFile = read('src', 'stores', 'settingsStore.ts');
+const settingsPanelFile = read('src', 'components', 'SettingsPanel.tsx');
+const appFile = read('src', 'App.tsx');
+const commandsFile = read('src-tauri', 'src', 'commands', 'mod.rs');
+const libFile = read('src-tauri', 'src', 'lib.rs');
+const metricsFile = read('src-tauri', 'src', 'server', 'metrics.rs');
+const serverFile = read('src-tauri', 'src', 'server', 'mod.rs');
+
+const requiredMarkers = [
+  { name: 'metricsExporterEnabled', sources: [settingsStoreFile, appFile, settingsPanelFile] },
+  { name: 'metricsExporterPort', sources: [settingsStoreFile, appFile, settingsPanelFile] },
+  { name: 'testId="metrics-exporter"', sources: [settingsPanelFile] },
+  { name: 'function LocalServerToggle', sources: [settingsPanelFile] },
+  { name: 'set_metrics_exporter', sources: [commandsFile, libFile, appFile] },
+  { name: 'MetricsSink', sources: [libFile, metricsFile] },
+  { name: 'get_all_cached_usage', sources: [metricsFile] },
+  { name: 'Ipv4Addr::LOCALHOST', sources: [serverFile] },
+];
+
+requiredMarkers.forEach(({ name, sources }) => {
+  if (!sources.every((source) => source.includes(name))) {
+    throw new Error(`Metrics exporter marker missing: ${name}`);
+  }
+});
+
+[
+  'incubar_window_used_percent',
+  'incubar_window_reset_seconds',
+  'incubar_credits_remaining',
+  'incubar_credits_limit',
+  'incubar_cost_amount',
+  'incubar_cost_tokens',
+  'incubar_provider_status',
+  'incubar_fetches',
+  '# EOF',
+].forEach((metric) => {
+  if (!metricsFile.includes(metric)) {
+    throw new Error(`Metrics exporter missing ${metric}.`);
+  }
+});
+
+console.log('metrics exporter checks passed');
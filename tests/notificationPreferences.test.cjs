@@ -22,6 +22,8 @@ const requiredMarkers = [
   { name: 'data-testid="notification-preferences"', sources: [settingsPanelFile] },
   { name: 'data-testid="notification-test-button"', sources: [settingsPanelFile] },
   { name: 'send_test_notification', sources: [commandsFile, libFile, settingsPanelFile] },
+  { name: 'set_notification_preferences', sources: [commandsFile, libFile, appFile] },
+  { name: 'snooze_notifications', sources: [commandsFile, libFile] },
 ];
 
 requiredMarkers.forEach(({ name, sources }) => {
@@ -0,0 +1,36 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const appFile = read('src', 'App.tsx');
+const usageStoreFile = read('src', 'stores', 'usageStore.ts');
+const popupFile = read('src', 'components', 'PopupWindow.tsx');
+const accountsFile = read('src', 'components', 'ProviderAccounts.tsx');
+const settingsPanelFile = read('src', 'components', 'SettingsPanel.tsx');
+const commandsFile = read('src-tauri', 'src', 'commands', 'mod.rs');
+
+const requiredMarkers = [
+  { name: "'list_accounts'", sources: [usageStoreFile] },
+  { name: "'add_account'", sources: [accountsFile] },
+  { name: "'remove_account'", sources: [accountsFile] },
+  { name: 'accounts-updated', sources: [appFile, commandsFile] },
+  { name: 'setAccountState', sources: [appFile, usageStoreFile] },
+  { name: 'useProviderAccounts', sources: [usageStoreFile, popupFile, accountsFile] },
+  { name: 'data-testid="provider-account-cards"', sources: [popupFile] },
+  { name: '<ProviderAccounts', sources: [settingsPanelFile] },
+];
+
+requiredMarkers.forEach(({ name, sources }) => {
+  if (!sources.every((source) => source.includes(name))) {
+    throw new Error(`Provider accounts marker missing: ${name}`);
+  }
+});
+
+// Events for additional accounts must reach their own card rather than being dropped
+if (/accountId !== DEFAULT_ACCOUNT_ID\) return;/.test(appFile)) {
+  throw new Error('App drops events for additional accounts.');
+}
+
+console.log('provider accounts checks passed');
@@ -0,0 +1,37 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const countdownFile = read('src', 'components', 'RetryCountdown.tsx');
+const menuCardFile = read('src', 'components', 'MenuCard.tsx');
+const popupFile = read('src', 'components', 'PopupWindow.tsx');
+const storeFile = read('src', 'stores', 'usageStore.ts');
+const validationFile = read('src', 'lib', 'eventValidation.ts');
+
+if (!countdownFile.includes('export function useRetryCountdown')) {
+  throw new Error('RetryCountdown missing countdown hook.');
+}
+
+if (!storeFile.includes('retryAt: usage.retryAt')) {
+  throw new Error('Usage store does not keep the retry time from usage updates.');
+}
+
+if (!validationFile.includes('retryAt: z.string().optional()')) {
+  throw new Error('Usage update schema missing retryAt.');
+}
+
+[
+  ['MenuCard', menuCardFile],
+  ['PopupWindow', popupFile],
+].forEach(([name, file]) => {
+  if (!file.includes('<RetryCountdown retryAt=')) {
+    throw new Error(`${name} does not show the rate limit countdown.`);
+  }
+  if (!file.includes('useRetryCountdown(')) {
+    throw new Error(`${name} does not hold refresh back while rate limited.`);
+  }
+});
+
+console.log('Rate limit countdown checks passed.');
@@ -0,0 +1,33 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const typesFile = read('src', 'lib', 'types.ts');
+const validationFile = read('src', 'lib', 'eventValidation.ts');
+const menuCardFile = read('src', 'components', 'MenuCard.tsx');
+const providersFile = read('src-tauri', 'src', 'providers', 'mod.rs');
+const trayFile = read('src-tauri', 'src', 'tray', 'mod.rs');
+
+if (!typesFile.includes('windows?: RateWindow[]')) {
+  throw new Error('UsageSnapshot type missing the window list.');
+}
+
+if (!validationFile.includes('windows: z.array(rateWindowSchema).optional()')) {
+  throw new Error('Usage update schema drops the window list.');
+}
+
+if (!menuCardFile.includes('extraWindows.map(')) {
+  throw new Error('MenuCard does not render windows past the legacy three.');
+}
+
+if (!providersFile.includes('pub windows: Vec<RateWindow>')) {
+  throw new Error('Rust UsageSnapshot missing the window list.');
+}
+
+if (!trayFile.includes('usage.rate_windows()')) {
+  throw new Error('Tray still reads only the legacy windows.');
+}
+
+console.log('Rate window list checks passed.');
@@ -0,0 +1,35 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const read = (...segments) => fs.readFileSync(path.join(root, ...segments), 'utf-8');
+
+const usageStoreFile = read('src', 'stores', 'usageStore.ts');
+const menuCardFile = read('src', 'components', 'MenuCard.tsx');
+const popupFile = read('src', 'components', 'PopupWindow.tsx');
+const commandsFile = read('src-tauri', 'src', 'commands', 'mod.rs');
+
+const requiredMarkers = [
+  { name: 'RefreshError', sources: [usageStoreFile, commandsFile] },
+  { name: "errorKind === 'cancelled'", sources: [usageStoreFile] },
+  { name: "'cancel_refresh'", sources: [usageStoreFile] },
+  { name: 'cancelRefresh(provider.id, provider.accountId)', sources: [menuCardFile] },
+];
+
+requiredMarkers.forEach(({ name, sources }) => {
+  if (!sources.every((source) => source.includes(name))) {
+    throw new Error(`Refresh cancellation marker missing: ${name}`);
+  }
+});
+
+// Cancellations are recognised by kind, not by the backend's message
+if (usageStoreFile.includes('Fetch was cancelled')) {
+  throw new Error('usageStore still matches the cancellation message.');
+}
+
+// Clicking refresh joins a fetch in flight; only credential changes force a new one
+[menuCardFile, popupFile].forEach((source) => {
+  if (/refreshProvider\([^)]*\btrue\b/.test(source)) {
+    throw new Error('A refresh button forces its refresh.');
+  }
+});
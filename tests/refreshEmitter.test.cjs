@@ -0,0 +1,47 @@
+const fs = require('node:fs');
+const path = require('node:path');
+
+const root = path.resolve(__dirname, '..');
+const providersPath = path.join(root, 'src-tauri', 'src', 'providers', 'mod.rs');
+const emitterPath = path.join(root, 'src-tauri', 'src', 'providers', 'emitter.rs');
+
+const providersFile = fs.readFileSync(providersPath, 'utf-8');
+const emitterFile = fs.readFileSync(emitterPath, 'utf-8');
+
+if (!emitterFile.includes('pub trait RefreshEmitter')) {
+  throw new Error('Refresh loop event emitter trait missing.');
+}
+
+if (!emitterFile.includes('impl RefreshEmitter for AppHandle')) {
+  throw new Error('The app does not implement the refresh loop event emitter.');
+}
+
+if (/fn registry\(|fn check_stale\(/.test(emitterFile)) {
+  throw new Error('The refresh loop event emitter should only emit events.');
+}
+
+['usage-updated', 'status-updated', 'refresh-failed'].forEach((event) => {
+  if (!emitterFile.includes(`"${event}"`)) {
+    throw new Error(`Refresh loop event missing: ${event}`);
+  }
+});
+
+if (
+  !providersFile.includes(
+    'pub async fn run_refresh_loop(registry: &ProviderRegistry, emitter: &impl RefreshEmitter)',
+  )
+) {
+  throw new Error('Refresh loop does not take its registry and event emitter.');
+}
+
+if (!providersFile.includes('run_refresh_loop(registry.inner(), &app)')) {
+  throw new Error('start_refresh_loop does not drive the shared refresh loop.');
+}
+
+const loopStart = providersFile.indexOf('pub async fn run_refresh_loop');
+const loopEnd = providersFile.indexOf('#[cfg(test)]', loopStart);
+if (/app\.emit\(|AppHandle/.test(providersFile.slice(loopStart, loopEnd))) {
+  throw new Error('Refresh loop still depends on the app handle.');
+}
+
+console.log('Refresh emitter checks passed.');
@@ -3,29 +3,33 @@ const path = require('node:path');
 
 const root = path.resolve(__dirname, '..');
 const appPath = path.join(root, 'src', 'App.tsx');
-const notificationsPath = path.join(root, 'src', 'lib', 'notifications.ts');
+const notificationsPath = path.join(root, 'src-tauri', 'src', 'providers', 'notifications.rs');
+const providersPath = path.join(root, 'src-tauri', 'src', 'providers', 'mod.rs');
+const commandsPath = path.join(root, 'src-tauri', 'src', 'commands', 'mod.rs');
 
 const appFile = fs.readFileSync(appPath, 'utf-8');
 const notificationsFile = fs.readFileSync(notificationsPath, 'utf-8');
+const providersFile = fs.readFileSync(providersPath, 'utf-8');
+const commandsFile = fs.readFileSync(commandsPath, 'utf-8');
 
-if (!notificationsFile.includes('evaluateRefreshFailureNotifications')) {
+if (!notificationsFile.includes('fn failure_alerts')) {
   throw new Error('Refresh failure notification evaluator missing.');
 }
 
-if (!appFile.includes('refresh-failed')) {
-  throw new Error('App missing refresh failure event listener.');
+if (!notificationsFile.includes('needs you to sign in again')) {
+  throw new Error('Refresh failure notifications missing error kind titles.');
 }
 
-if (!appFile.includes('evaluateRefreshFailureNotifications')) {
-  throw new Error('App missing refresh failure notification evaluation.');
+if (!appFile.includes('refresh-failed')) {
+  throw new Error('App missing refresh failure event listener.');
 }
 
-if (!appFile.includes('refreshIntervalSeconds')) {
-  throw new Error('App missing refresh interval wiring for failure resets.');
+if (!providersFile.includes('.publish_failure(key, &error, &usage)')) {
+  throw new Error('Background refresh loop does not report failures to the usage pipeline.');
 }
 
-if (!appFile.includes('sendNotification')) {
-  throw new Error('App missing notification dispatch wiring.');
+if (!commandsFile.includes('registry.publish_failure(&key, &e, &usage)')) {
+  throw new Error('Manual refresh does not report failures to the usage pipeline.');
 }
 
 console.log('Refresh failure notification checks passed.');
@@ -2,26 +2,30 @@ const fs = require('node:fs');
 const path = require('node:path');
 
 const root = path.resolve(__dirname, '..');
-const appPath = path.join(root, 'src', 'App.tsx');
-const notificationsPath = path.join(root, 'src', 'lib', 'notifications.ts');
+const notificationsPath = path.join(root, 'src-tauri', 'src', 'providers', 'notifications.rs');
+const libPath = path.join(root, 'src-tauri', 'src', 'lib.rs');
 
-const appFile = fs.readFileSync(appPath, 'utf-8');
 const notificationsFile = fs.readFileSync(notificationsPath, 'utf-8');
+const libFile = fs.readFileSync(libPath, 'utf-8');
 
 if (!notificationsFile.includes('SESSION_QUOTA_THRESHOLDS')) {
   throw new Error('Session quota thresholds are missing.');
 }
 
-if (!notificationsFile.includes('evaluateSessionNotifications')) {
+if (!notificationsFile.includes('fn usage_alerts')) {
   throw new Error('Session notification evaluator missing.');
 }
 
-if (!appFile.includes('evaluateSessionNotifications')) {
-  throw new Error('App missing session notification evaluation.');
+if (!notificationsFile.includes('impl UsageSink for NotificationSink')) {
+  throw new Error('Session notifications are not fed by the usage pipeline.');
 }
 
-if (!appFile.includes('sendNotification')) {
-  throw new Error('App missing notification dispatch wiring.');
+if (!libFile.includes('NotificationSink')) {
+  throw new Error('Notification sink not registered with the provider registry.');
+}
+
+if (!notificationsFile.includes('.notification()')) {
+  throw new Error('Notifier missing notification dispatch wiring.');
 }
 
 console.log('Session quota notification checks passed.');
@@ -2,26 +2,25 @@ const fs = require('node:fs');
 const path = require('node:path');
 
 const root = path.resolve(__dirname, '..');
-const appPath = path.join(root, 'src', 'App.tsx');
-const notificationsPath = path.join(root, 'src', 'lib', 'notifications.ts');
+const notificationsPath = path.join(root, 'src-tauri', 'src', 'providers', 'notifications.rs');
+const providersPath = path.join(root, 'src-tauri', 'src', 'providers', 'mod.rs');
 
-const appFile = fs.readFileSync(appPath, 'utf-8');
 const notificationsFile = fs.readFileSync(notificationsPath, 'utf-8');
+const providersFile = fs.readFileSync(providersPath, 'utf-8');
 
-if (!notificationsFile.includes('evaluateStaleUsageNotifications')) {
+if (!notificationsFile.includes('fn stale_alerts')) {
   throw new Error('Stale usage notification evaluator missing.');
 }
 
-if (!notificationsFile.includes('formatDistanceToNow')) {
+if (!notificationsFile.includes('fn describe_age')) {
   throw new Error('Stale usage notifications need relative time formatting.');
 }
 
-if (!appFile.includes('evaluateStaleUsageNotifications')) {
-  throw new Error('App missing stale usage notification evaluation.');
-}
-
-if (!appFile.includes('sendNotification')) {
-  throw new Error('App missing notification dispatch wiring.');
+if (
+  !providersFile.includes('check_stale_usage(registry.inner(), &app)') ||
+  !providersFile.includes('notifications::notify_if_stale(app')
+) {
+  throw new Error('Background refresh loop missing stale usage notification evaluation.');
 }
 
 console.log('Stale usage notification checks passed.');
@@ -4,12 +4,12 @@ const path = require('node:path');
 const root = path.resolve(__dirname, '..');
 const storagePath = path.join(root, 'src-tauri', 'src', 'storage', 'widget_snapshot.rs');
 const storageModPath = path.join(root, 'src-tauri', 'src', 'storage', 'mod.rs');
-const commandsPath = path.join(root, 'src-tauri', 'src', 'commands', 'mod.rs');
+const libPath = path.join(root, 'src-tauri', 'src', 'lib.rs');
 const tasksPath = path.join(root, 'tasks.yaml');
 
 const storageFile = fs.readFileSync(storagePath, 'utf-8');
 const storageModFile = fs.readFileSync(storageModPath, 'utf-8');
-const commandsFile = fs.readFileSync(commandsPath, 'utf-8');
+const libFile = fs.readFileSync(libPath, 'utf-8');
 const tasksFile = fs.readFileSync(tasksPath, 'utf-8');
 
 if (!storageFile.includes('WIDGET_SNAPSHOT_FILENAME')) {
@@ -24,8 +24,12 @@ if (!storageModFile.includes('widget_snapshot')) {
   throw new Error('Widget snapshot module not exported.');
 }
 
-if (!commandsFile.includes('write_widget_snapshot')) {
-  throw new Error('Widget snapshot writer not wired into commands.');
+if (!storageFile.includes('impl UsageSink for WidgetSnapshotSink')) {
+  throw new Error('Widget snapshot writer is not a usage sink.');
+}
+
+if (!libFile.includes('WidgetSnapshotSink')) {
+  throw new Error('Widget snapshot sink not registered with the provider registry.');
 }
 
 if (!tasksFile.includes('widget snapshot')) {